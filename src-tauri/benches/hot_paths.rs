@@ -0,0 +1,89 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use specops_lib::bench;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+// Regression thresholds for a single optimised run. They are loose on
+// purpose: the point is to fail on accidental quadratic behaviour before
+// criterion spends minutes sampling it, not to pin exact speeds.
+fn within_budget<T>(name: &str, budget: Duration, run: impl FnOnce() -> T) -> T {
+	let started = Instant::now();
+	let result = run();
+	let elapsed = started.elapsed();
+	assert!(
+		elapsed <= budget,
+		"{name} took {elapsed:?}, budget {budget:?}"
+	);
+	result
+}
+
+fn spec_parsing(c: &mut Criterion) {
+	let spec = bench::synthetic_spec(2_000);
+	within_budget(
+		"parse_spec_2000_requirements",
+		Duration::from_millis(500),
+		|| bench::parse_spec(&spec),
+	);
+	within_budget(
+		"outline_2000_requirements",
+		Duration::from_millis(500),
+		|| bench::build_outline(&spec),
+	);
+	c.bench_function("parse_spec_2000_requirements", |b| {
+		b.iter(|| bench::parse_spec(black_box(&spec)))
+	});
+	c.bench_function("outline_2000_requirements", |b| {
+		b.iter(|| bench::build_outline(black_box(&spec)))
+	});
+}
+
+fn tree_building(c: &mut Criterion) {
+	let project = TempDir::new().expect("create project dir");
+	bench::synthetic_project(project.path(), 500, 20);
+	let entries = within_budget("tree_500_capabilities", Duration::from_secs(1), || {
+		bench::build_tree(project.path())
+	});
+	assert_eq!(entries, 500);
+	c.bench_function("tree_500_capabilities", |b| {
+		b.iter(|| bench::build_tree(black_box(project.path())))
+	});
+}
+
+fn delta_diffing(c: &mut Criterion) {
+	let old = bench::synthetic_spec(500);
+	let new = old.replace("case 250.", "case 250, including retries.");
+	within_budget("diff_500_requirements", Duration::from_millis(200), || {
+		bench::diff_specs(&old, &new)
+	});
+	c.bench_function("diff_500_requirements", |b| {
+		b.iter(|| bench::diff_specs(black_box(&old), black_box(&new)))
+	});
+}
+
+fn search_indexing(c: &mut Criterion) {
+	let project = TempDir::new().expect("create project dir");
+	bench::synthetic_project(project.path(), 500, 20);
+	let index = within_budget("index_500_capabilities", Duration::from_secs(2), || {
+		bench::index_project(project.path())
+	});
+	within_budget(
+		"search_500_capabilities",
+		Duration::from_millis(500),
+		|| bench::search_index(&index, "behaviour 7"),
+	);
+	c.bench_function("index_500_capabilities", |b| {
+		b.iter(|| bench::index_project(black_box(project.path())))
+	});
+	c.bench_function("search_500_capabilities", |b| {
+		b.iter(|| bench::search_index(&index, black_box("behaviour 7")))
+	});
+}
+
+criterion_group!(
+	hot_paths,
+	spec_parsing,
+	tree_building,
+	delta_diffing,
+	search_indexing
+);
+criterion_main!(hot_paths);
@@ -0,0 +1,170 @@
+use crate::spec::{existing_openspec_root, SpecError, SpecErrorPayload};
+use serde::Serialize;
+use std::{
+	fs::{self, OpenOptions},
+	io::Write,
+	path::Path,
+};
+
+const PROJECT_TEMPLATE: &str = "# Project Context
+
+## Purpose
+Describe what this project does and who it is for.
+
+## Tech Stack
+- List the primary languages, frameworks and services.
+
+## Project Conventions
+Describe code style, architecture patterns, testing and git workflow.
+
+## Domain Context
+Capture the vocabulary and constraints contributors need to know.
+";
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum EntryKind {
+	File,
+	Directory,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum EntryStatus {
+	Present,
+	Missing,
+	Created,
+	Conflict,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructureEntry {
+	path: &'static str,
+	kind: EntryKind,
+	status: EntryStatus,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdoptionReport {
+	project: String,
+	entries: Vec<StructureEntry>,
+	complete: bool,
+}
+
+const REQUIRED: &[(&str, EntryKind)] = &[
+	("project.md", EntryKind::File),
+	("specs", EntryKind::Directory),
+	("changes", EntryKind::Directory),
+];
+
+fn entry_status(path: &Path, kind: EntryKind) -> EntryStatus {
+	match fs::metadata(path) {
+		Ok(metadata) if metadata.is_dir() == (kind == EntryKind::Directory) => EntryStatus::Present,
+		Ok(_) => EntryStatus::Conflict,
+		Err(_) => EntryStatus::Missing,
+	}
+}
+
+fn create_entry(path: &Path, kind: EntryKind) -> Result<(), SpecError> {
+	match kind {
+		EntryKind::Directory => fs::create_dir(path)?,
+		EntryKind::File => OpenOptions::new()
+			.write(true)
+			.create_new(true)
+			.open(path)?
+			.write_all(PROJECT_TEMPLATE.as_bytes())?,
+	}
+	Ok(())
+}
+
+fn adopt(project: &Path, dry_run: bool) -> Result<AdoptionReport, SpecError> {
+	let root = existing_openspec_root(project)?;
+	let mut entries = Vec::new();
+	for (name, kind) in REQUIRED {
+		let path = root.join(name);
+		let mut status = entry_status(&path, *kind);
+		if status == EntryStatus::Missing && !dry_run {
+			create_entry(&path, *kind)?;
+			status = EntryStatus::Created;
+		}
+		entries.push(StructureEntry {
+			path: name,
+			kind: *kind,
+			status,
+		});
+	}
+	Ok(AdoptionReport {
+		project: project.to_string_lossy().to_string(),
+		complete: entries
+			.iter()
+			.all(|entry| matches!(entry.status, EntryStatus::Present | EntryStatus::Created)),
+		entries,
+	})
+}
+
+#[tauri::command]
+pub fn adopt_project(
+	project: String,
+	dry_run: Option<bool>,
+) -> Result<AdoptionReport, SpecErrorPayload> {
+	adopt(Path::new(&project), dry_run.unwrap_or(false)).map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	fn statuses(report: &AdoptionReport) -> Vec<EntryStatus> {
+		report.entries.iter().map(|entry| entry.status).collect()
+	}
+
+	#[test]
+	fn reports_missing_structure_without_writing_in_dry_run() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		fs::create_dir_all(temp_dir.path().join("openspec/specs")).expect("create specs");
+
+		let report = adopt(temp_dir.path(), true).expect("analyze");
+		assert_eq!(
+			statuses(&report),
+			vec![
+				EntryStatus::Missing,
+				EntryStatus::Present,
+				EntryStatus::Missing
+			]
+		);
+		assert!(!report.complete);
+		assert!(!temp_dir.path().join("openspec/changes").exists());
+	}
+
+	#[test]
+	fn creates_missing_pieces_and_leaves_existing_files_alone() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let root = temp_dir.path().join("openspec");
+		fs::create_dir_all(&root).expect("create openspec");
+		fs::write(root.join("project.md"), "# Mine\n").expect("write project");
+		fs::write(root.join("changes"), "not a dir").expect("write changes file");
+
+		let report = adopt(temp_dir.path(), false).expect("adopt");
+		assert_eq!(
+			statuses(&report),
+			vec![
+				EntryStatus::Present,
+				EntryStatus::Created,
+				EntryStatus::Conflict
+			]
+		);
+		assert!(!report.complete);
+		assert_eq!(
+			fs::read_to_string(root.join("project.md")).expect("read project"),
+			"# Mine\n"
+		);
+		assert!(root.join("specs").is_dir());
+		assert!(matches!(
+			adopt(&temp_dir.path().join("missing"), false),
+			Err(SpecError::MissingOpenSpec)
+		));
+	}
+}
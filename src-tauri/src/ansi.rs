@@ -0,0 +1,187 @@
+use serde::Serialize;
+
+const NAMED_COLORS: [&str; 8] = [
+	"black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+];
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+#[derive(Clone, Debug, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Style {
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub fg: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub bg: Option<String>,
+	#[serde(skip_serializing_if = "std::ops::Not::not")]
+	pub bold: bool,
+	#[serde(skip_serializing_if = "std::ops::Not::not")]
+	pub dim: bool,
+	#[serde(skip_serializing_if = "std::ops::Not::not")]
+	pub italic: bool,
+	#[serde(skip_serializing_if = "std::ops::Not::not")]
+	pub underline: bool,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct StyledSegment {
+	pub text: String,
+	#[serde(flatten)]
+	pub style: Style,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StyledLine {
+	pub plain: String,
+	pub segments: Vec<StyledSegment>,
+}
+
+fn named(index: u8) -> String {
+	let name = NAMED_COLORS[usize::from(index % 8)];
+	if index < 8 {
+		name.to_string()
+	} else {
+		let mut chars = name.chars();
+		let first = chars.next().map(|c| c.to_ascii_uppercase());
+		format!("bright{}{}", first.unwrap_or_default(), chars.as_str())
+	}
+}
+
+fn indexed(index: u8) -> String {
+	match index {
+		0..=15 => named(index),
+		16..=231 => {
+			let index = index - 16;
+			let level = |value: u8| CUBE_LEVELS[usize::from(value)];
+			format!(
+				"#{:02x}{:02x}{:02x}",
+				level(index / 36),
+				level(index / 6 % 6),
+				level(index % 6)
+			)
+		}
+		_ => {
+			let gray = 8 + (index - 232) * 10;
+			format!("#{gray:02x}{gray:02x}{gray:02x}")
+		}
+	}
+}
+
+fn extended_color(params: &mut impl Iterator<Item = u16>) -> Option<String> {
+	match params.next()? {
+		5 => params.next().map(|index| indexed(index.min(255) as u8)),
+		2 => {
+			let mut channel = || params.next().map(|value| value.min(255) as u8);
+			let (r, g, b) = (channel()?, channel()?, channel()?);
+			Some(format!("#{r:02x}{g:02x}{b:02x}"))
+		}
+		_ => None,
+	}
+}
+
+fn apply_sgr(style: &mut Style, params: &str) {
+	let mut params = params
+		.split([';', ':'])
+		.map(|param| param.parse::<u16>().unwrap_or(0))
+		.collect::<Vec<_>>()
+		.into_iter();
+	if params.len() == 0 {
+		*style = Style::default();
+		return;
+	}
+	while let Some(code) = params.next() {
+		match code {
+			0 => *style = Style::default(),
+			1 => style.bold = true,
+			2 => style.dim = true,
+			3 => style.italic = true,
+			4 => style.underline = true,
+			22 => {
+				style.bold = false;
+				style.dim = false;
+			}
+			23 => style.italic = false,
+			24 => style.underline = false,
+			30..=37 => style.fg = Some(named((code - 30) as u8)),
+			38 => style.fg = extended_color(&mut params),
+			39 => style.fg = None,
+			40..=47 => style.bg = Some(named((code - 40) as u8)),
+			48 => style.bg = extended_color(&mut params),
+			49 => style.bg = None,
+			90..=97 => style.fg = Some(named((code - 90 + 8) as u8)),
+			100..=107 => style.bg = Some(named((code - 100 + 8) as u8)),
+			_ => {}
+		}
+	}
+}
+
+fn push_text(line: &mut StyledLine, text: &str, style: &Style) {
+	if text.is_empty() {
+		return;
+	}
+	line.plain.push_str(text);
+	match line.segments.last_mut() {
+		Some(last) if last.style == *style => last.text.push_str(text),
+		_ => line.segments.push(StyledSegment {
+			text: text.to_string(),
+			style: style.clone(),
+		}),
+	}
+}
+
+pub fn parse(input: &str, style: &mut Style) -> StyledLine {
+	let mut line = StyledLine::default();
+	let mut rest = input;
+	while let Some(index) = rest.find('\u{1b}') {
+		push_text(&mut line, &rest[..index], style);
+		rest = &rest[index + 1..];
+		if let Some(sequence) = rest.strip_prefix('[') {
+			let end = sequence
+				.find(|c: char| ('@'..='~').contains(&c))
+				.unwrap_or(sequence.len());
+			if sequence[end..].starts_with('m') {
+				apply_sgr(style, &sequence[..end]);
+			}
+			rest = sequence.get(end + 1..).unwrap_or_default();
+		} else if let Some(sequence) = rest.strip_prefix(']') {
+			let end = sequence.find(['\u{7}', '\u{1b}']).unwrap_or(sequence.len());
+			rest = &sequence[end..];
+			rest = rest
+				.strip_prefix('\u{7}')
+				.or_else(|| rest.strip_prefix("\u{1b}\\"))
+				.unwrap_or(rest);
+		} else {
+			let mut chars = rest.chars();
+			chars.next();
+			rest = chars.as_str();
+		}
+	}
+	push_text(&mut line, rest, style);
+	line
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn splits_styled_segments_and_strips_escapes() {
+		let mut style = Style::default();
+		let line = parse(
+			"\u{1b}[1;32m✔\u{1b}[0m Done \u{1b}[38;5;196merr\u{1b}[39m \u{1b}]8;;https://x\u{7}link\u{1b}]8;;\u{7}\u{1b}[2K",
+			&mut style,
+		);
+		assert_eq!(line.plain, "✔ Done err link");
+		assert_eq!(line.segments.len(), 4);
+		assert_eq!(line.segments[0].style.fg.as_deref(), Some("green"));
+		assert!(line.segments[0].style.bold);
+		assert_eq!(line.segments[1].text, " Done ");
+		assert_eq!(line.segments[2].style.fg.as_deref(), Some("#ff0000"));
+		assert_eq!(line.segments[3].style, Style::default());
+
+		let mut style = Style::default();
+		parse("\u{1b}[91mwarn", &mut style);
+		let next = parse("still red", &mut style);
+		assert_eq!(next.segments[0].style.fg.as_deref(), Some("brightRed"));
+	}
+}
@@ -0,0 +1,204 @@
+use crate::{
+	changes::{change_dir, read_metadata, update_metadata, METADATA_FILE},
+	git::git_output,
+	settings::SettingsStore,
+	spec::{SpecError, SpecErrorPayload},
+	storage,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+	fs::{self, File},
+	io,
+	path::Path,
+};
+use tauri::State;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Approval {
+	pub approver: String,
+	pub note: Option<String>,
+	pub approved_at: u64,
+	pub git_name: Option<String>,
+	pub git_email: Option<String>,
+	pub commit: Option<String>,
+	pub digest: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalStatus {
+	pub required: usize,
+	pub valid: usize,
+	pub satisfied: bool,
+	pub digest: String,
+	approvals: Vec<Approval>,
+}
+
+fn hash_dir(hasher: &mut Sha256, root: &Path, dir: &Path) -> Result<(), SpecError> {
+	let mut entries = fs::read_dir(dir)?
+		.filter_map(Result::ok)
+		.map(|entry| entry.path())
+		.collect::<Vec<_>>();
+	entries.sort();
+	for path in entries {
+		if path.file_name().and_then(|name| name.to_str()) == Some(METADATA_FILE) {
+			continue;
+		}
+		if path.is_dir() {
+			hash_dir(hasher, root, &path)?;
+			continue;
+		}
+		let relative = path.strip_prefix(root).unwrap_or(&path);
+		hasher.update(relative.to_string_lossy().replace('\\', "/").as_bytes());
+		hasher.update([0]);
+		// Streamed, so a large asset in the change is never held in memory.
+		io::copy(&mut File::open(&path)?, hasher)?;
+		hasher.update([0]);
+	}
+	Ok(())
+}
+
+fn change_digest(dir: &Path) -> Result<String, SpecError> {
+	let mut hasher = Sha256::new();
+	hash_dir(&mut hasher, dir, dir)?;
+	Ok(hasher
+		.finalize()
+		.iter()
+		.map(|byte| format!("{byte:02x}"))
+		.collect())
+}
+
+fn git_value(project: &Path, args: &[&str]) -> Option<String> {
+	git_output(project, args)
+		.ok()
+		.map(|value| value.trim().to_string())
+		.filter(|value| !value.is_empty())
+}
+
+fn record_approval(
+	project: &Path,
+	change_id: &str,
+	approver: &str,
+	note: Option<String>,
+) -> Result<Approval, SpecError> {
+	let approver = approver.trim();
+	if approver.is_empty() {
+		return Err(SpecError::InvalidApprover);
+	}
+	let approval = Approval {
+		approver: approver.to_string(),
+		note: note.filter(|note| !note.trim().is_empty()),
+		approved_at: storage::now_secs(),
+		git_name: git_value(project, &["config", "user.name"]),
+		git_email: git_value(project, &["config", "user.email"]),
+		commit: git_value(project, &["rev-parse", "HEAD"]),
+		digest: change_digest(&change_dir(project, change_id)?)?,
+	};
+	update_metadata(project, change_id, |metadata| {
+		metadata
+			.approvals
+			.retain(|existing| existing.approver != approval.approver);
+		metadata.approvals.push(approval.clone());
+	})?;
+	Ok(approval)
+}
+
+pub fn approval_status_for(
+	project: &Path,
+	change_id: &str,
+	required: usize,
+) -> Result<ApprovalStatus, SpecError> {
+	let digest = change_digest(&change_dir(project, change_id)?)?;
+	let approvals = read_metadata(project, change_id)?.approvals;
+	let valid = approvals
+		.iter()
+		.filter(|approval| approval.digest == digest)
+		.count();
+	Ok(ApprovalStatus {
+		required,
+		valid,
+		satisfied: valid >= required,
+		digest,
+		approvals,
+	})
+}
+
+#[tauri::command]
+pub fn approve_change(
+	settings: State<'_, SettingsStore>,
+	project: String,
+	change_id: String,
+	approver: String,
+	note: Option<String>,
+) -> Result<ApprovalStatus, SpecErrorPayload> {
+	let project = Path::new(&project);
+	record_approval(project, &change_id, &approver, note)?;
+	Ok(approval_status_for(
+		project,
+		&change_id,
+		settings.get().required_approvals,
+	)?)
+}
+
+#[tauri::command]
+pub fn approval_status(
+	settings: State<'_, SettingsStore>,
+	project: String,
+	change_id: String,
+) -> Result<ApprovalStatus, SpecErrorPayload> {
+	approval_status_for(
+		Path::new(&project),
+		&change_id,
+		settings.get().required_approvals,
+	)
+	.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn set_required_approvals(
+	settings: State<'_, SettingsStore>,
+	count: usize,
+) -> Result<usize, SpecErrorPayload> {
+	settings
+		.update(|settings| settings.required_approvals = count)
+		.map_err(|error| SpecErrorPayload::from(SpecError::from(error)))?;
+	Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn approvals_go_stale_when_the_change_is_edited() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let change = temp_dir.path().join("openspec/changes/add-login");
+		fs::create_dir_all(&change).expect("create change");
+		fs::write(change.join("proposal.md"), "# Add login\n").expect("write proposal");
+
+		record_approval(temp_dir.path(), "add-login", "alice", None).expect("approve");
+		record_approval(
+			temp_dir.path(),
+			"add-login",
+			"bob",
+			Some("LGTM".to_string()),
+		)
+		.expect("approve");
+		record_approval(temp_dir.path(), "add-login", "alice", None).expect("re-approve");
+		let status = approval_status_for(temp_dir.path(), "add-login", 2).expect("status");
+		assert_eq!(status.approvals.len(), 2);
+		assert!(status.satisfied);
+
+		fs::write(change.join("tasks.md"), "- [ ] 1.1 Build\n").expect("write tasks");
+		let status = approval_status_for(temp_dir.path(), "add-login", 2).expect("status");
+		assert_eq!(status.valid, 0);
+		assert!(!status.satisfied);
+		assert!(matches!(
+			record_approval(temp_dir.path(), "add-login", "  ", None),
+			Err(SpecError::InvalidApprover)
+		));
+	}
+}
@@ -0,0 +1,372 @@
+use crate::{
+	approvals::approval_status_for,
+	changes::change_dir,
+	editing::EditSessions,
+	run_journaled_command,
+	settings::SettingsStore,
+	spec::{markdown_files, openspec_root},
+	webhook::{emit_webhook, WebhookEvent},
+	CommandRunOutput, OpenSpecCommandError, OpenSpecCommandErrorPayload,
+};
+use serde::Serialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	fs::{self, File},
+	io,
+	path::{Path, PathBuf},
+};
+use tauri::{State, Window};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveResult {
+	#[serde(flatten)]
+	output: CommandRunOutput,
+	change_id: String,
+	archived_to: Option<String>,
+	updated_specs: Vec<String>,
+	created_specs: Vec<String>,
+}
+
+struct TreeState {
+	specs: BTreeMap<PathBuf, Vec<u8>>,
+	archived: BTreeSet<PathBuf>,
+}
+
+fn capture(project: &Path) -> TreeState {
+	let root = openspec_root(project);
+	let specs = markdown_files(&root.join("specs"))
+		.unwrap_or_default()
+		.into_iter()
+		.filter_map(|path| {
+			let mut hasher = Sha256::new();
+			io::copy(&mut File::open(&path).ok()?, &mut hasher).ok()?;
+			Some((path, hasher.finalize().to_vec()))
+		})
+		.collect();
+	let archived = fs::read_dir(root.join("changes").join("archive"))
+		.map(|entries| {
+			entries
+				.filter_map(Result::ok)
+				.map(|entry| entry.path())
+				.filter(|path| path.is_dir())
+				.collect()
+		})
+		.unwrap_or_default();
+	TreeState { specs, archived }
+}
+
+fn relative(project: &Path, path: &Path) -> String {
+	path.strip_prefix(project)
+		.unwrap_or(path)
+		.to_string_lossy()
+		.replace('\\', "/")
+}
+
+fn archive_outcome(
+	project: &Path,
+	change_id: &str,
+	before: &TreeState,
+	after: &TreeState,
+) -> (Option<String>, Vec<String>, Vec<String>) {
+	let archived_to = after
+		.archived
+		.difference(&before.archived)
+		.find(|path| {
+			path.file_name()
+				.map(|name| name.to_string_lossy())
+				.is_some_and(|name| name == change_id || name.ends_with(&format!("-{change_id}")))
+		})
+		.map(|path| relative(project, path));
+	let mut updated = Vec::new();
+	let mut created = Vec::new();
+	for (path, digest) in &after.specs {
+		match before.specs.get(path) {
+			Some(previous) if previous == digest => {}
+			Some(_) => updated.push(relative(project, path)),
+			None => created.push(relative(project, path)),
+		}
+	}
+	(archived_to, updated, created)
+}
+
+fn announce_archive(
+	settings: &SettingsStore,
+	project: &Path,
+	change_id: &str,
+	archived_to: Option<&str>,
+	updated_specs: &[String],
+	created_specs: &[String],
+) {
+	emit_webhook(
+		settings,
+		WebhookEvent::ArchiveCompleted,
+		json!({
+			"project": project,
+			"changeId": change_id,
+			"archivedTo": archived_to,
+			"updatedSpecs": updated_specs,
+			"createdSpecs": created_specs,
+		}),
+	);
+}
+
+// Archiving moves the change folder and rewrites specs, which would
+// silently discard what an open editor saves next.
+fn ensure_not_editing(
+	sessions: &EditSessions,
+	root: &Path,
+	change_dir: &Path,
+) -> Result<(), OpenSpecCommandError> {
+	if sessions.is_locked_within(change_dir) || sessions.is_locked_within(&root.join("specs")) {
+		Err(OpenSpecCommandError::FileLocked)
+	} else {
+		Ok(())
+	}
+}
+
+// Approvals are checked against the change's current digest, so an edit
+// after sign-off blocks the archive until it is approved again.
+fn ensure_approved(
+	project: &Path,
+	change_id: &str,
+	required: usize,
+) -> Result<(), OpenSpecCommandError> {
+	let status = approval_status_for(project, change_id, required)?;
+	if status.satisfied {
+		Ok(())
+	} else {
+		Err(OpenSpecCommandError::ApprovalsMissing {
+			required: status.required,
+			present: status.valid,
+		})
+	}
+}
+
+#[tauri::command(async)]
+pub fn openspec_archive(
+	window: Window,
+	settings: State<'_, SettingsStore>,
+	sessions: State<'_, EditSessions>,
+	project: String,
+	change_id: String,
+	yes: Option<bool>,
+	env: Option<BTreeMap<String, String>>,
+) -> Result<ArchiveResult, OpenSpecCommandErrorPayload> {
+	let project = Path::new(&project);
+	let dir = change_dir(project, &change_id).map_err(|_| OpenSpecCommandError::ChangeNotFound)?;
+	let root = openspec_root(project);
+	ensure_not_editing(&sessions, &root, &dir)?;
+	ensure_approved(project, &change_id, settings.get().required_approvals)?;
+	let backup_root = relative(project, &root);
+	let mut args = vec!["archive".to_string(), change_id.clone()];
+	if yes.unwrap_or(false) {
+		args.push("--yes".to_string());
+	}
+
+	let before = capture(project);
+	let output = run_journaled_command(
+		&window,
+		&env.unwrap_or_default(),
+		"archive",
+		Some(project),
+		&[backup_root.as_str()],
+		"openspec",
+		&args,
+	)?;
+	let (archived_to, updated_specs, created_specs) =
+		archive_outcome(project, &change_id, &before, &capture(project));
+	announce_archive(
+		&settings,
+		project,
+		&change_id,
+		archived_to.as_deref(),
+		&updated_specs,
+		&created_specs,
+	);
+	Ok(ArchiveResult {
+		output,
+		change_id,
+		archived_to,
+		updated_specs,
+		created_specs,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{approvals::Approval, changes::update_metadata, webhook::WebhookSettings};
+	use serde_json::Value;
+	use std::{
+		io::{Read, Write},
+		net::TcpListener,
+		time::Duration,
+	};
+	use tempfile::TempDir;
+
+	#[test]
+	fn announces_a_completed_archive_to_the_webhook() {
+		let receiver = TcpListener::bind("127.0.0.1:0").expect("bind webhook receiver");
+		let settings = SettingsStore::load(None);
+		settings
+			.update(|settings| {
+				settings.webhook = WebhookSettings {
+					enabled: true,
+					url: format!("http://{}/hook", receiver.local_addr().expect("address")),
+					events: vec![WebhookEvent::ArchiveCompleted],
+				}
+			})
+			.expect("configure webhook");
+
+		announce_archive(
+			&settings,
+			Path::new("/repo"),
+			"add-login",
+			Some("openspec/changes/archive/2026-10-16-add-login"),
+			&["openspec/specs/auth/spec.md".to_string()],
+			&[],
+		);
+
+		let (mut stream, _) = receiver.accept().expect("webhook delivery");
+		stream
+			.set_read_timeout(Some(Duration::from_secs(5)))
+			.expect("set timeout");
+		let mut request = Vec::new();
+		let mut buffer = [0; 4096];
+		let body = loop {
+			let read = stream.read(&mut buffer).expect("read request");
+			assert!(read > 0, "request ended before its body");
+			request.extend_from_slice(&buffer[..read]);
+			let text = String::from_utf8_lossy(&request);
+			let Some((head, body)) = text.split_once("\r\n\r\n") else {
+				continue;
+			};
+			let length = head
+				.lines()
+				.find_map(|line| {
+					let (name, value) = line.split_once(':')?;
+					name.eq_ignore_ascii_case("content-length")
+						.then(|| value.trim().parse::<usize>().ok())?
+				})
+				.expect("content length");
+			if body.len() >= length {
+				assert!(head.contains("archive-completed"));
+				break body.to_string();
+			}
+		};
+		stream
+			.write_all(b"HTTP/1.1 204 No Content\r\n\r\n")
+			.expect("respond");
+
+		let envelope: Value = serde_json::from_str(&body).expect("parse body");
+		assert_eq!(envelope["event"], "archive-completed");
+		assert_eq!(envelope["payload"]["changeId"], "add-login");
+		assert_eq!(
+			envelope["payload"]["archivedTo"],
+			"openspec/changes/archive/2026-10-16-add-login"
+		);
+		assert_eq!(
+			envelope["payload"]["updatedSpecs"][0],
+			"openspec/specs/auth/spec.md"
+		);
+	}
+
+	#[test]
+	fn refuses_to_archive_while_the_change_or_specs_are_being_edited() {
+		let project = TempDir::new().expect("create project dir");
+		let root = project.path().join("openspec");
+		let change = root.join("changes/add-login");
+		fs::create_dir_all(&change).expect("create change");
+		fs::create_dir_all(root.join("specs/auth")).expect("create specs");
+		fs::write(change.join("proposal.md"), "# Add login\n").expect("write proposal");
+		fs::write(root.join("specs/auth/spec.md"), "# Auth\n").expect("write spec");
+		let sessions = EditSessions::default();
+		ensure_not_editing(&sessions, &root, &change).expect("nothing open");
+
+		let proposal = sessions.open(&change.join("proposal.md")).expect("open");
+		assert!(matches!(
+			ensure_not_editing(&sessions, &root, &change),
+			Err(OpenSpecCommandError::FileLocked)
+		));
+		sessions.close(&proposal.id);
+		sessions
+			.open(&root.join("specs/auth/spec.md"))
+			.expect("open spec");
+		assert!(matches!(
+			ensure_not_editing(&sessions, &root, &change),
+			Err(OpenSpecCommandError::FileLocked)
+		));
+	}
+
+	#[test]
+	fn refuses_to_archive_without_current_approvals() {
+		let project = TempDir::new().expect("create project dir");
+		let change = project.path().join("openspec/changes/add-login");
+		fs::create_dir_all(&change).expect("create change");
+		fs::write(change.join("proposal.md"), "# Add login\n").expect("write proposal");
+
+		ensure_approved(project.path(), "add-login", 0).expect("no approvals required");
+		assert!(matches!(
+			ensure_approved(project.path(), "add-login", 1),
+			Err(OpenSpecCommandError::ApprovalsMissing {
+				required: 1,
+				present: 0
+			})
+		));
+
+		let digest = approval_status_for(project.path(), "add-login", 1)
+			.expect("status")
+			.digest;
+		update_metadata(project.path(), "add-login", |metadata| {
+			metadata.approvals.push(Approval {
+				approver: "alice".to_string(),
+				note: None,
+				approved_at: 0,
+				git_name: None,
+				git_email: None,
+				commit: None,
+				digest,
+			});
+		})
+		.expect("approve");
+		ensure_approved(project.path(), "add-login", 1).expect("approved");
+
+		fs::write(change.join("tasks.md"), "- [ ] 1.1 Build\n").expect("edit change");
+		let payload = OpenSpecCommandErrorPayload::from(
+			ensure_approved(project.path(), "add-login", 1).expect_err("stale approval"),
+		);
+		assert_eq!(payload.code, "approvals_missing");
+	}
+
+	#[test]
+	fn reports_moved_change_and_touched_specs() {
+		let project = TempDir::new().expect("create project dir");
+		let openspec = project.path().join("openspec");
+		let auth = openspec.join("specs/auth");
+		fs::create_dir_all(&auth).expect("create auth spec");
+		fs::create_dir_all(openspec.join("specs/billing")).expect("create billing spec");
+		fs::create_dir_all(openspec.join("changes/add-login")).expect("create change");
+		fs::write(auth.join("spec.md"), "# Auth\n").expect("write auth");
+		fs::write(openspec.join("specs/billing/spec.md"), "# Billing\n").expect("write billing");
+		let before = capture(project.path());
+
+		fs::write(auth.join("spec.md"), "# Auth\n\n### Requirement: Login\n").expect("update auth");
+		fs::create_dir_all(openspec.join("specs/session")).expect("create session spec");
+		fs::write(openspec.join("specs/session/spec.md"), "# Session\n").expect("write session");
+		fs::create_dir_all(openspec.join("changes/archive/2026-10-16-add-login"))
+			.expect("archive change");
+		let after = capture(project.path());
+
+		let (archived_to, updated, created) =
+			archive_outcome(project.path(), "add-login", &before, &after);
+		assert_eq!(
+			archived_to.as_deref(),
+			Some("openspec/changes/archive/2026-10-16-add-login")
+		);
+		assert_eq!(updated, ["openspec/specs/auth/spec.md"]);
+		assert_eq!(created, ["openspec/specs/session/spec.md"]);
+	}
+}
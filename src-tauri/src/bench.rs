@@ -0,0 +1,90 @@
+use crate::{
+	cache::ProjectCache,
+	diff::unified_diff,
+	ignore::IgnoreRules,
+	lint::lint_source,
+	outline::outline_source,
+	search::{ProjectIndex, SearchFilters},
+	spec::{parse_headings, SourceText},
+	tree::SpecTree,
+};
+use std::{fmt::Write, fs, path::Path};
+
+pub fn synthetic_spec(requirements: usize) -> String {
+	let mut spec = String::from(
+		"# Synthetic Specification\n\n## Purpose\nGenerated fixture.\n\n## Requirements\n",
+	);
+	for index in 0..requirements {
+		let _ = write!(
+			spec,
+			"\n### Requirement: Behaviour {index}\nThe system SHALL handle case {index}.\n\n#### Scenario: Success {index}\n- **WHEN** input {index} arrives\n- **THEN** output {index} is produced\n\n#### Scenario: Failure {index}\n- **WHEN** input {index} is invalid\n- **THEN** an error is reported\n",
+		);
+	}
+	spec
+}
+
+pub fn synthetic_project(project: &Path, capabilities: usize, requirements: usize) {
+	let spec = synthetic_spec(requirements);
+	for index in 0..capabilities {
+		let dir = project.join(format!("openspec/specs/capability-{index}"));
+		fs::create_dir_all(&dir).expect("create capability dir");
+		fs::write(dir.join("spec.md"), &spec).expect("write spec");
+	}
+}
+
+pub fn parse_spec(text: &str) -> usize {
+	let source = SourceText::parse(text);
+	parse_headings(&source.lines).len() + lint_source(Path::new("spec.md"), &source).len()
+}
+
+pub fn build_outline(text: &str) -> usize {
+	outline_source(
+		Path::new("openspec/specs/capability/spec.md"),
+		&SourceText::parse(text),
+	)
+	.len()
+}
+
+pub fn build_tree(project: &Path) -> usize {
+	SpecTree::refresh(None, project, &IgnoreRules::default())
+		.expect("build spec tree")
+		.entries
+		.len()
+}
+
+pub fn diff_specs(old: &str, new: &str) -> usize {
+	unified_diff(old, new, "spec.md").len()
+}
+
+pub fn index_project(project: &Path) -> ProjectIndex {
+	ProjectIndex::refresh(None, project, &IgnoreRules::default()).expect("build search index")
+}
+
+pub fn search_index(index: &ProjectIndex, query: &str) -> usize {
+	index.search(query, &SearchFilters::default()).len()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	// Timing budgets live in `benches/hot_paths.rs`; these only check that the
+	// fixtures exercise what the benchmarks claim to measure.
+	#[test]
+	fn fixtures_exercise_every_hot_path() {
+		let spec = synthetic_spec(20);
+		assert!(parse_spec(&spec) >= 60);
+		assert!(build_outline(&spec) >= 60);
+		let new = spec.replace("case 10.", "case 10, including retries.");
+		assert!(diff_specs(&spec, &new) > 0);
+
+		let project = TempDir::new().expect("create project dir");
+		synthetic_project(project.path(), 5, 10);
+		assert_eq!(build_tree(project.path()), 5);
+		assert_eq!(
+			search_index(&index_project(project.path()), "behaviour 7"),
+			5
+		);
+	}
+}
@@ -0,0 +1,3 @@
+fn main() -> std::process::ExitCode {
+	specops_lib::headless::main()
+}
@@ -0,0 +1,415 @@
+use crate::{
+	crypto::project_cipher,
+	diff::unified_diff,
+	editing::EditSessions,
+	operations::{self, CancellationToken},
+	settings::SettingsStore,
+	snapshots::{create_snapshot_in, snapshots_root, SnapshotKind},
+	spec::{existing_openspec_root, openspec_root, SpecError, SpecErrorPayload, MAX_PARSE_BYTES},
+	storage,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+	collections::BTreeSet,
+	fs,
+	io::{Read, Write},
+	path::{Path, PathBuf},
+};
+use tauri::{State, Window};
+use zip::{write::SimpleFileOptions, ZipArchive, ZipWriter};
+
+const MANIFEST: &str = "specops-bundle.json";
+const BUNDLE_ROOT: &str = "openspec/";
+// Caps what a bundle may expand to, so a crafted archive cannot exhaust
+// memory or disk before anything is written.
+const MAX_BUNDLE_BYTES: u64 = 64 * MAX_PARSE_BYTES;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportStrategy {
+	Replace,
+	Merge,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleExport {
+	path: String,
+	file_count: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleConflict {
+	path: String,
+	diff: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+	added: Vec<String>,
+	updated: Vec<String>,
+	unchanged: Vec<String>,
+	removed: Vec<String>,
+	conflicts: Vec<BundleConflict>,
+	snapshot_id: Option<String>,
+}
+
+fn relative_files(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+	for entry in fs::read_dir(dir)? {
+		let path = entry?.path();
+		if path.is_dir() {
+			relative_files(root, &path, files)?;
+		} else if let Ok(relative) = path.strip_prefix(root) {
+			files.push(relative.to_path_buf());
+		}
+	}
+	Ok(())
+}
+
+fn bundle_name(relative: &Path) -> String {
+	format!(
+		"{BUNDLE_ROOT}{}",
+		relative.to_string_lossy().replace('\\', "/")
+	)
+}
+
+fn export_bundle(
+	project: &Path,
+	dest: &Path,
+	cancel: &CancellationToken,
+) -> Result<BundleExport, SpecError> {
+	let root = existing_openspec_root(project)?;
+	let mut files = Vec::new();
+	relative_files(&root, &root, &mut files)?;
+	files.sort();
+
+	let mut writer = ZipWriter::new(std::io::Cursor::new(Vec::new()));
+	let options = SimpleFileOptions::default();
+	let zip_error = |_| SpecError::InvalidBundle;
+	writer.start_file(MANIFEST, options).map_err(zip_error)?;
+	writer.write_all(
+		json!({
+			"formatVersion": 1,
+			"project": project.file_name().map(|name| name.to_string_lossy().to_string()),
+			"createdAt": storage::now_secs(),
+			"fileCount": files.len(),
+		})
+		.to_string()
+		.as_bytes(),
+	)?;
+	for file in &files {
+		cancel.check()?;
+		writer
+			.start_file(bundle_name(file), options)
+			.map_err(zip_error)?;
+		writer.write_all(&fs::read(root.join(file))?)?;
+	}
+	let bytes = writer.finish().map_err(zip_error)?.into_inner();
+	storage::write_atomic(dest, &bytes)?;
+	Ok(BundleExport {
+		path: dest.to_string_lossy().to_string(),
+		file_count: files.len(),
+	})
+}
+
+fn read_bundle(
+	bundle: &Path,
+	cancel: &CancellationToken,
+) -> Result<Vec<(PathBuf, Vec<u8>)>, SpecError> {
+	let mut archive =
+		ZipArchive::new(fs::File::open(bundle)?).map_err(|_| SpecError::InvalidBundle)?;
+	if archive.index_for_name(MANIFEST).is_none() {
+		return Err(SpecError::InvalidBundle);
+	}
+	let mut entries = Vec::new();
+	let mut total = 0;
+	for index in 0..archive.len() {
+		cancel.check()?;
+		let file = archive
+			.by_index(index)
+			.map_err(|_| SpecError::InvalidBundle)?;
+		if file.is_dir() || file.name() == MANIFEST {
+			continue;
+		}
+		let relative = file
+			.enclosed_name()
+			.and_then(|path| path.strip_prefix("openspec").ok().map(Path::to_path_buf))
+			.filter(|path| !path.as_os_str().is_empty())
+			.ok_or(SpecError::InvalidBundle)?;
+		// The declared size can lie, so the read itself is capped too.
+		if file.size() > MAX_PARSE_BYTES {
+			return Err(SpecError::InvalidBundle);
+		}
+		let mut contents = Vec::new();
+		file.take(MAX_PARSE_BYTES + 1).read_to_end(&mut contents)?;
+		total += contents.len() as u64;
+		if contents.len() as u64 > MAX_PARSE_BYTES || total > MAX_BUNDLE_BYTES {
+			return Err(SpecError::InvalidBundle);
+		}
+		entries.push((relative, contents));
+	}
+	entries.sort_by(|a, b| a.0.cmp(&b.0));
+	Ok(entries)
+}
+
+fn apply_bundle(
+	root: &Path,
+	entries: Vec<(PathBuf, Vec<u8>)>,
+	strategy: ImportStrategy,
+) -> Result<ImportReport, SpecError> {
+	let mut report = ImportReport::default();
+	let mut existing = Vec::new();
+	if root.is_dir() {
+		relative_files(root, root, &mut existing)?;
+	}
+	let incoming = entries
+		.iter()
+		.map(|(path, _)| path.clone())
+		.collect::<BTreeSet<_>>();
+
+	for (relative, contents) in entries {
+		let target = root.join(&relative);
+		let name = bundle_name(&relative);
+		match fs::read(&target) {
+			Ok(current) if current == contents => report.unchanged.push(name),
+			Ok(current) => match strategy {
+				ImportStrategy::Replace => {
+					storage::write_atomic(&target, &contents)?;
+					report.updated.push(name);
+				}
+				ImportStrategy::Merge => report.conflicts.push(BundleConflict {
+					diff: unified_diff(
+						&String::from_utf8_lossy(&current),
+						&String::from_utf8_lossy(&contents),
+						&name,
+					),
+					path: name,
+				}),
+			},
+			Err(_) => {
+				storage::write_atomic(&target, &contents)?;
+				report.added.push(name);
+			}
+		}
+	}
+
+	if strategy == ImportStrategy::Replace {
+		existing.sort();
+		for relative in existing {
+			if !incoming.contains(&relative) {
+				fs::remove_file(root.join(&relative))?;
+				report.removed.push(bundle_name(&relative));
+			}
+		}
+	}
+	Ok(report)
+}
+
+#[tauri::command(async)]
+pub fn export_openspec_bundle(
+	window: Window,
+	project: String,
+	dest: String,
+) -> Result<BundleExport, SpecErrorPayload> {
+	operations::run_native(
+		&window,
+		"bundle-export",
+		format!("export {project}"),
+		|cancel| export_bundle(Path::new(&project), Path::new(&dest), cancel),
+	)
+	.map_err(SpecErrorPayload::from)
+}
+
+fn import_bundle(
+	settings: &SettingsStore,
+	project: &Path,
+	bundle: &Path,
+	strategy: ImportStrategy,
+	cancel: &CancellationToken,
+) -> Result<ImportReport, SpecError> {
+	let entries = read_bundle(bundle, cancel)?;
+	cancel.check()?;
+	let snapshot_id = match existing_openspec_root(project) {
+		Ok(_) => {
+			let cipher = project_cipher(settings, project)?;
+			let snapshot = create_snapshot_in(
+				&snapshots_root()?,
+				project,
+				SnapshotKind::Manual,
+				Some("Before bundle import".to_string()),
+				cipher.as_ref(),
+			)?;
+			Some(snapshot.id)
+		}
+		Err(_) => None,
+	};
+	let mut report = apply_bundle(&openspec_root(project), entries, strategy)?;
+	report.snapshot_id = snapshot_id;
+	Ok(report)
+}
+
+#[tauri::command(async)]
+pub fn import_openspec_bundle(
+	window: Window,
+	settings: State<'_, SettingsStore>,
+	sessions: State<'_, EditSessions>,
+	project: String,
+	bundle_path: String,
+	strategy: ImportStrategy,
+) -> Result<ImportReport, SpecErrorPayload> {
+	let project = Path::new(&project);
+	if sessions.is_locked_within(&openspec_root(project)) {
+		return Err(SpecError::FileLocked.into());
+	}
+	operations::run_native(
+		&window,
+		"bundle-import",
+		format!("import {bundle_path}"),
+		|cancel| {
+			import_bundle(
+				&settings,
+				project,
+				Path::new(&bundle_path),
+				strategy,
+				cancel,
+			)
+		},
+	)
+	.map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	fn write(root: &Path, relative: &str, contents: &str) {
+		let path = root.join(relative);
+		fs::create_dir_all(path.parent().expect("parent")).expect("create dirs");
+		fs::write(path, contents).expect("write");
+	}
+
+	#[test]
+	fn round_trips_bundle_into_empty_project() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let source = temp_dir.path().join("source");
+		write(&source, "openspec/project.md", "# Project\n");
+		write(&source, "openspec/specs/auth/spec.md", "# Auth\n");
+		let bundle = temp_dir.path().join("export.zip");
+		assert_eq!(
+			export_bundle(&source, &bundle, &CancellationToken::default())
+				.expect("export")
+				.file_count,
+			2
+		);
+
+		let target = temp_dir.path().join("target");
+		let report = apply_bundle(
+			&openspec_root(&target),
+			read_bundle(&bundle, &CancellationToken::default()).expect("read"),
+			ImportStrategy::Merge,
+		)
+		.expect("import");
+		assert_eq!(
+			report.added,
+			vec!["openspec/project.md", "openspec/specs/auth/spec.md"]
+		);
+		assert_eq!(
+			fs::read_to_string(target.join("openspec/specs/auth/spec.md")).expect("read"),
+			"# Auth\n"
+		);
+	}
+
+	#[test]
+	fn merge_reports_conflicts_and_replace_overwrites() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let source = temp_dir.path().join("source");
+		write(&source, "openspec/project.md", "# Bundle\n");
+		let bundle = temp_dir.path().join("export.zip");
+		export_bundle(&source, &bundle, &CancellationToken::default()).expect("export");
+
+		let target = temp_dir.path().join("target");
+		write(&target, "openspec/project.md", "# Local\n");
+		write(&target, "openspec/extra.md", "extra\n");
+		let root = openspec_root(&target);
+
+		let merged = apply_bundle(
+			&root,
+			read_bundle(&bundle, &CancellationToken::default()).expect("read"),
+			ImportStrategy::Merge,
+		)
+		.expect("merge");
+		assert_eq!(merged.conflicts.len(), 1);
+		assert!(merged.conflicts[0].diff.contains("+# Bundle"));
+		assert_eq!(
+			fs::read_to_string(root.join("project.md")).expect("read"),
+			"# Local\n"
+		);
+
+		let replaced = apply_bundle(
+			&root,
+			read_bundle(&bundle, &CancellationToken::default()).expect("read"),
+			ImportStrategy::Replace,
+		)
+		.expect("replace");
+		assert_eq!(replaced.updated, vec!["openspec/project.md"]);
+		assert_eq!(replaced.removed, vec!["openspec/extra.md"]);
+		assert!(!root.join("extra.md").exists());
+	}
+
+	#[test]
+	fn rejects_oversized_entries_and_stops_when_cancelled() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let bomb = temp_dir.path().join("bomb.zip");
+		let mut writer = ZipWriter::new(fs::File::create(&bomb).expect("create bundle"));
+		let options = SimpleFileOptions::default();
+		writer
+			.start_file(MANIFEST, options)
+			.expect("start manifest");
+		writer
+			.start_file("openspec/huge.md", options)
+			.expect("start entry");
+		writer
+			.write_all(&vec![b'a'; MAX_PARSE_BYTES as usize + 1])
+			.expect("write entry");
+		writer.finish().expect("finish bundle");
+		assert!(matches!(
+			read_bundle(&bomb, &CancellationToken::default()),
+			Err(SpecError::InvalidBundle)
+		));
+
+		let source = temp_dir.path().join("source");
+		write(&source, "openspec/project.md", "# Project\n");
+		let bundle = temp_dir.path().join("export.zip");
+		export_bundle(&source, &bundle, &CancellationToken::default()).expect("export");
+		let cancel = CancellationToken::default();
+		cancel.cancel();
+		assert!(matches!(
+			read_bundle(&bundle, &cancel),
+			Err(SpecError::OperationCancelled)
+		));
+	}
+
+	#[test]
+	fn cancelled_export_leaves_no_bundle() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let source = temp_dir.path().join("source");
+		write(
+			&source,
+			"openspec/project.md",
+			"# Project
+",
+		);
+		let bundle = temp_dir.path().join("export.zip");
+		let cancel = CancellationToken::default();
+		cancel.cancel();
+		assert!(matches!(
+			export_bundle(&source, &bundle, &cancel),
+			Err(SpecError::OperationCancelled)
+		));
+		assert!(!bundle.exists());
+	}
+}
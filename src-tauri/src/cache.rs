@@ -0,0 +1,224 @@
+use crate::{
+	crypto::{project_cipher, ArtifactCipher},
+	git::git_output,
+	ignore::IgnoreRules,
+	settings::SettingsStore,
+	spec::SpecError,
+	storage::{self, StorageError},
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+	collections::HashMap,
+	fs,
+	path::{Path, PathBuf},
+	time::SystemTime,
+};
+
+const CACHE_VERSION: u32 = 2;
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct CacheHeader {
+	version: u32,
+	git_head: Option<String>,
+	checksum: String,
+}
+
+pub fn cache_root() -> Option<PathBuf> {
+	storage::specops_home().map(|home| home.join("cache"))
+}
+
+fn cache_path(root: &Path, project: &Path, name: &str) -> PathBuf {
+	root.join(storage::project_key(project))
+		.join(format!("{name}.cache"))
+}
+
+fn git_head(project: &Path) -> Option<String> {
+	git_output(project, &["rev-parse", "HEAD"])
+		.ok()
+		.map(|head| head.trim().to_string())
+}
+
+fn checksum(payload: &[u8]) -> String {
+	format!("{:016x}", storage::fnv1a(payload))
+}
+
+pub fn store<T: Serialize>(
+	root: &Path,
+	project: &Path,
+	name: &str,
+	payload: &T,
+	cipher: Option<&ArtifactCipher>,
+) -> Result<(), SpecError> {
+	let payload = serde_json::to_vec(payload).map_err(StorageError::from)?;
+	let header = CacheHeader {
+		version: CACHE_VERSION,
+		git_head: git_head(project),
+		checksum: checksum(&payload),
+	};
+	let mut bytes = serde_json::to_vec(&header).map_err(StorageError::from)?;
+	bytes.push(b'\n');
+	bytes.extend(payload);
+	if let Some(cipher) = cipher {
+		bytes = cipher.seal(&bytes)?;
+	}
+	storage::write_atomic(&cache_path(root, project, name), &bytes)?;
+	Ok(())
+}
+
+pub fn load<T: DeserializeOwned>(
+	root: &Path,
+	project: &Path,
+	name: &str,
+	cipher: Option<&ArtifactCipher>,
+) -> Option<T> {
+	let bytes = fs::read(cache_path(root, project, name)).ok()?;
+	let bytes = match cipher {
+		Some(cipher) => cipher.open(&bytes).ok()?,
+		None => bytes,
+	};
+	let split = bytes.iter().position(|byte| *byte == b'\n')?;
+	let (header, payload) = (&bytes[..split], &bytes[split + 1..]);
+	let header = serde_json::from_slice::<CacheHeader>(header).ok()?;
+	if header.version != CACHE_VERSION
+		|| header.checksum != checksum(payload)
+		|| header.git_head != git_head(project)
+	{
+		return None;
+	}
+	serde_json::from_slice(payload).ok()
+}
+
+/// What a cached entry remembers about the file it was read from. The size
+/// catches rewrites that land within the filesystem's timestamp granularity.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FileStamp {
+	pub len: u64,
+	pub modified: Option<SystemTime>,
+}
+
+impl FileStamp {
+	pub fn of(path: &Path) -> Self {
+		fs::metadata(path)
+			.map(|meta| Self {
+				len: meta.len(),
+				modified: meta.modified().ok(),
+			})
+			.unwrap_or_default()
+	}
+}
+
+/// Data derived file by file from a project, kept on disk between launches
+/// and checked against the files before it is trusted again.
+pub trait ProjectCache: Serialize + DeserializeOwned {
+	const NAME: &'static str;
+
+	fn is_stale(&self, project: &Path, ignore: &IgnoreRules) -> bool;
+
+	/// Brings `previous` up to date, reading only files whose stamp changed.
+	fn refresh(
+		previous: Option<&Self>,
+		project: &Path,
+		ignore: &IgnoreRules,
+	) -> Result<Self, SpecError>;
+}
+
+/// Whether `stamps` list different files, or the same files in another
+/// state, than the entries were read from.
+pub fn entries_changed<T>(
+	stamps: &[(PathBuf, FileStamp)],
+	entries: &[T],
+	key: impl Fn(&T) -> (&Path, &FileStamp),
+) -> bool {
+	stamps.len() != entries.len()
+		|| stamps.iter().zip(entries).any(|((path, stamp), entry)| {
+			let (entry_path, entry_stamp) = key(entry);
+			path != entry_path || stamp != entry_stamp
+		})
+}
+
+/// Rebuilds the entries for `stamps`, reusing previous entries whose file is
+/// unchanged and calling `read` for the rest.
+pub fn refresh_entries<T: Clone>(
+	stamps: Vec<(PathBuf, FileStamp)>,
+	previous: &[T],
+	key: impl Fn(&T) -> (&Path, &FileStamp),
+	mut read: impl FnMut(PathBuf, FileStamp) -> Result<T, SpecError>,
+) -> Result<Vec<T>, SpecError> {
+	let previous = previous
+		.iter()
+		.map(|entry| {
+			let (path, stamp) = key(entry);
+			(path, (stamp, entry))
+		})
+		.collect::<HashMap<_, _>>();
+	stamps
+		.into_iter()
+		.map(|(path, stamp)| match previous.get(path.as_path()) {
+			Some((cached, entry)) if **cached == stamp => Ok((*entry).clone()),
+			_ => read(path, stamp),
+		})
+		.collect()
+}
+
+/// Returns an up-to-date `T` for `project`, starting from `previous` or the
+/// copy on disk, and writes it back when anything had to be re-read.
+pub fn warm<T: ProjectCache>(
+	root: Option<&Path>,
+	project: &Path,
+	settings: &SettingsStore,
+	ignore: &IgnoreRules,
+	previous: Option<&T>,
+) -> Result<T, SpecError> {
+	let stored = root.zip(project_cipher(settings, project).ok());
+	let loaded = match (previous, &stored) {
+		(None, Some((root, cipher))) => load::<T>(root, project, T::NAME, cipher.as_ref()),
+		_ => None,
+	};
+	match loaded {
+		Some(cached) if !cached.is_stale(project, ignore) => Ok(cached),
+		loaded => {
+			let fresh = T::refresh(previous.or(loaded.as_ref()), project, ignore)?;
+			if let Some((root, cipher)) = &stored {
+				let _ = store(root, project, T::NAME, &fresh, cipher.as_ref());
+			}
+			Ok(fresh)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn round_trips_and_rejects_corrupt_entries() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let root = temp_dir.path().join("cache");
+		let project = temp_dir.path().join("project");
+		let payload = vec!["spec".to_string(), "tree".to_string()];
+
+		store(&root, &project, "tree", &payload, None).expect("store");
+		assert_eq!(
+			load::<Vec<String>>(&root, &project, "tree", None),
+			Some(payload.clone())
+		);
+
+		let path = cache_path(&root, &project, "tree");
+		let corrupted = fs::read_to_string(&path)
+			.expect("read")
+			.replace("tree\"", "trie\"");
+		fs::write(&path, corrupted).expect("write");
+		assert_eq!(load::<Vec<String>>(&root, &project, "tree", None), None);
+
+		let cipher = ArtifactCipher::from_key(&[3u8; 32]).expect("cipher");
+		store(&root, &project, "tree", &payload, Some(&cipher)).expect("store sealed");
+		assert_eq!(load::<Vec<String>>(&root, &project, "tree", None), None);
+		assert_eq!(
+			load::<Vec<String>>(&root, &project, "tree", Some(&cipher)),
+			Some(payload)
+		);
+	}
+}
@@ -0,0 +1,763 @@
+use crate::{
+	approvals::Approval,
+	checklist::ChecklistRecord,
+	ordering,
+	review::ReviewComment,
+	secrets::read_secret,
+	settings::SettingsStore,
+	spec::{
+		existing_openspec_root, markdown_files, parse_heading, SourceText, SpecError,
+		SpecErrorPayload,
+	},
+	storage::{self, StorageError},
+	tasks::parse_tasks,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+	cmp::Ordering,
+	collections::BTreeSet,
+	fs,
+	path::{Path, PathBuf},
+	process::Command,
+	time::{Duration, UNIX_EPOCH},
+};
+use tauri::State;
+
+pub const METADATA_FILE: &str = ".specops.json";
+const MAX_CHANGE_ID_LEN: usize = 64;
+const GITHUB_TOKEN_SECRET: &str = "github.token";
+const GITHUB_HOST: &str = "github.com";
+const ISSUE_LOOKUP_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChangeMetadata {
+	pub issue: Option<IssueLink>,
+	pub comments: Vec<ReviewComment>,
+	pub approvals: Vec<Approval>,
+	pub checklist: ChecklistRecord,
+	pub target_date: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueLink {
+	pub url: String,
+	pub host: String,
+	pub owner: String,
+	pub repo: String,
+	pub number: u64,
+	pub last_status: Option<IssueStatus>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum IssueState {
+	Open,
+	Closed,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueStatus {
+	pub state: IssueState,
+	pub title: String,
+	pub fetched_at: u64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NewChange {
+	pub change_id: String,
+	pub title: Option<String>,
+	pub why: Option<String>,
+	pub design: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreatedChange {
+	pub change_id: String,
+	pub path: String,
+	pub files: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeState {
+	Draft,
+	InProgress,
+	Complete,
+	Archived,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ChangeSortKey {
+	#[default]
+	LastActivity,
+	Completion,
+	DeltaSize,
+	State,
+	Name,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChangeListQuery {
+	pub sort_by: ChangeSortKey,
+	pub ascending: bool,
+	pub states: Vec<ChangeState>,
+	pub search: Option<String>,
+	pub include_archived: bool,
+	pub offset: usize,
+	pub limit: Option<usize>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeSummary {
+	pub id: String,
+	pub path: String,
+	pub state: ChangeState,
+	pub last_activity: u64,
+	pub tasks_total: usize,
+	pub tasks_done: usize,
+	pub completion: u8,
+	pub delta_size: usize,
+	pub issue: Option<IssueLink>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeList {
+	total: usize,
+	changes: Vec<ChangeSummary>,
+}
+
+pub fn change_dir(project: &Path, change_id: &str) -> Result<PathBuf, SpecError> {
+	if change_id.is_empty()
+		|| change_id == "archive"
+		|| change_id.starts_with('.')
+		|| change_id.contains(['/', '\\'])
+	{
+		return Err(SpecError::ChangeNotFound);
+	}
+	let dir = existing_openspec_root(project)?
+		.join("changes")
+		.join(change_id);
+	if dir.is_dir() {
+		Ok(dir)
+	} else {
+		Err(SpecError::ChangeNotFound)
+	}
+}
+
+// Matches the ids the OpenSpec CLI scaffolds: verb-led kebab-case such as
+// `add-two-factor`, which also keeps the directory name portable.
+pub fn validate_change_id(change_id: &str) -> Result<(), SpecError> {
+	let valid = change_id.len() <= MAX_CHANGE_ID_LEN
+		&& change_id != "archive"
+		&& change_id.starts_with(|c: char| c.is_ascii_lowercase())
+		&& !change_id.ends_with('-')
+		&& !change_id.contains("--")
+		&& change_id
+			.chars()
+			.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+	if valid {
+		Ok(())
+	} else {
+		Err(SpecError::InvalidChangeId)
+	}
+}
+
+fn default_title(change_id: &str) -> String {
+	let words = change_id.replace('-', " ");
+	let mut chars = words.chars();
+	chars
+		.next()
+		.map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+		.unwrap_or_default()
+}
+
+fn proposal_template(title: &str, why: Option<&str>) -> String {
+	let why = why
+		.map(str::trim)
+		.filter(|why| !why.is_empty())
+		.unwrap_or("Describe the problem or opportunity this change addresses.");
+	format!(
+		"# Change: {title}\n\n## Why\n{why}\n\n## What Changes\n- \n\n## Impact\n- Affected specs: \n- Affected code: \n"
+	)
+}
+
+const TASKS_TEMPLATE: &str = "## 1. Implementation\n- [ ] 1.1 \n";
+
+const DESIGN_TEMPLATE: &str = "## Context\n\n## Goals / Non-Goals\n- Goals: \n- Non-Goals: \n\n## Decisions\n\n## Risks / Trade-offs\n";
+
+pub fn create_change(project: &Path, change: &NewChange) -> Result<CreatedChange, SpecError> {
+	validate_change_id(&change.change_id)?;
+	let changes_root = existing_openspec_root(project)?.join("changes");
+	fs::create_dir_all(&changes_root)?;
+	let dir = changes_root.join(&change.change_id);
+	match fs::create_dir(&dir) {
+		Ok(()) => {}
+		Err(error) if error.kind() == std::io::ErrorKind::AlreadyExists => {
+			return Err(SpecError::ChangeExists)
+		}
+		Err(error) => return Err(error.into()),
+	}
+
+	let title = change
+		.title
+		.as_deref()
+		.map(str::trim)
+		.filter(|title| !title.is_empty())
+		.map(str::to_string)
+		.unwrap_or_else(|| default_title(&change.change_id));
+	let mut files = vec![
+		(
+			"proposal.md",
+			proposal_template(&title, change.why.as_deref()),
+		),
+		("tasks.md", TASKS_TEMPLATE.to_string()),
+	];
+	if change.design {
+		files.push(("design.md", DESIGN_TEMPLATE.to_string()));
+	}
+	let relative = |path: &Path| {
+		path.strip_prefix(project)
+			.unwrap_or(path)
+			.to_string_lossy()
+			.replace('\\', "/")
+	};
+	let mut created = Vec::with_capacity(files.len());
+	for (name, contents) in files {
+		let path = dir.join(name);
+		storage::write_atomic(&path, contents.as_bytes())?;
+		created.push(relative(&path));
+	}
+	Ok(CreatedChange {
+		change_id: change.change_id.clone(),
+		path: relative(&dir),
+		files: created,
+	})
+}
+
+pub fn read_metadata(project: &Path, change_id: &str) -> Result<ChangeMetadata, SpecError> {
+	let path = change_dir(project, change_id)?.join(METADATA_FILE);
+	match fs::read(&path) {
+		// Falling back to defaults here would let `update_metadata` overwrite
+		// a newer or hand-edited file with an empty one.
+		Ok(bytes) => serde_json::from_slice(&bytes).map_err(|_| SpecError::InvalidChangeMetadata),
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(ChangeMetadata::default()),
+		Err(error) => Err(error.into()),
+	}
+}
+
+pub fn update_metadata<R>(
+	project: &Path,
+	change_id: &str,
+	mutate: impl FnOnce(&mut ChangeMetadata) -> R,
+) -> Result<R, SpecError> {
+	let mut metadata = read_metadata(project, change_id)?;
+	let result = mutate(&mut metadata);
+	let bytes = serde_json::to_vec_pretty(&metadata).map_err(StorageError::from)?;
+	storage::write_atomic(&change_dir(project, change_id)?.join(METADATA_FILE), &bytes)?;
+	Ok(result)
+}
+
+pub fn task_counts(tasks: &str) -> (usize, usize) {
+	let tasks = parse_tasks(tasks);
+	(tasks.len(), tasks.iter().filter(|task| task.done).count())
+}
+
+fn last_modified(dir: &Path) -> u64 {
+	fs::read_dir(dir)
+		.map(|entries| {
+			entries
+				.filter_map(Result::ok)
+				.map(|entry| match entry.metadata() {
+					Ok(metadata) if metadata.is_dir() => last_modified(&entry.path()),
+					Ok(metadata) => metadata
+						.modified()
+						.ok()
+						.and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+						.map(|duration| duration.as_secs())
+						.unwrap_or_default(),
+					Err(_) => 0,
+				})
+				.max()
+				.unwrap_or_default()
+		})
+		.unwrap_or_default()
+}
+
+fn delta_size(change: &Path) -> usize {
+	let specs = change.join("specs");
+	if !specs.is_dir() {
+		return 0;
+	}
+	markdown_files(&specs)
+		.unwrap_or_default()
+		.iter()
+		.filter_map(|path| SourceText::read_complete(path).ok())
+		.map(|source| {
+			source
+				.lines
+				.iter()
+				.filter_map(|line| parse_heading(line))
+				.filter(|(level, title)| *level == 3 && title.starts_with("Requirement:"))
+				.count()
+		})
+		.sum()
+}
+
+fn summarize_change(dir: &Path, archived: bool) -> ChangeSummary {
+	let (tasks_total, tasks_done) = SourceText::read_complete(&dir.join("tasks.md"))
+		.map(|tasks| task_counts(&tasks.render()))
+		.unwrap_or_default();
+	let state = if archived {
+		ChangeState::Archived
+	} else if tasks_total > 0 && tasks_done == tasks_total {
+		ChangeState::Complete
+	} else if tasks_done > 0 {
+		ChangeState::InProgress
+	} else {
+		ChangeState::Draft
+	};
+	let issue = fs::read(dir.join(METADATA_FILE))
+		.ok()
+		.and_then(|bytes| serde_json::from_slice::<ChangeMetadata>(&bytes).ok())
+		.and_then(|metadata| metadata.issue);
+	ChangeSummary {
+		id: dir
+			.file_name()
+			.map(|name| name.to_string_lossy().to_string())
+			.unwrap_or_default(),
+		path: dir.to_string_lossy().to_string(),
+		state,
+		last_activity: last_modified(dir),
+		tasks_total,
+		tasks_done,
+		completion: (tasks_done * 100).checked_div(tasks_total).unwrap_or(0) as u8,
+		delta_size: delta_size(dir),
+		issue,
+	}
+}
+
+fn change_dirs(dir: &Path) -> Vec<PathBuf> {
+	fs::read_dir(dir)
+		.map(|entries| {
+			entries
+				.filter_map(Result::ok)
+				.map(|entry| entry.path())
+				.filter(|path| {
+					path.is_dir()
+						&& path
+							.file_name()
+							.and_then(|name| name.to_str())
+							.is_some_and(|name| name != "archive" && !name.starts_with('.'))
+				})
+				.collect()
+		})
+		.unwrap_or_default()
+}
+
+pub fn list_changes(
+	project: &Path,
+	include_archived: bool,
+) -> Result<Vec<ChangeSummary>, SpecError> {
+	let changes_root = existing_openspec_root(project)?.join("changes");
+	let by_id = |a: &ChangeSummary, b: &ChangeSummary| ordering::compare(&a.id, &b.id);
+	let mut changes = change_dirs(&changes_root)
+		.iter()
+		.map(|dir| summarize_change(dir, false))
+		.collect::<Vec<_>>();
+	changes.sort_by(by_id);
+	if include_archived {
+		let mut archived = change_dirs(&changes_root.join("archive"))
+			.iter()
+			.map(|dir| summarize_change(dir, true))
+			.collect::<Vec<_>>();
+		archived.sort_by(by_id);
+		changes.extend(archived);
+	}
+	Ok(changes)
+}
+
+fn compare_changes(a: &ChangeSummary, b: &ChangeSummary, key: ChangeSortKey) -> Ordering {
+	let primary = match key {
+		ChangeSortKey::LastActivity => a.last_activity.cmp(&b.last_activity),
+		ChangeSortKey::Completion => a.completion.cmp(&b.completion),
+		ChangeSortKey::DeltaSize => a.delta_size.cmp(&b.delta_size),
+		ChangeSortKey::State => a.state.cmp(&b.state),
+		ChangeSortKey::Name => ordering::compare(&a.id, &b.id),
+	};
+	primary.then_with(|| ordering::compare(&a.id, &b.id))
+}
+
+fn query_changes(mut changes: Vec<ChangeSummary>, query: &ChangeListQuery) -> ChangeList {
+	let search = query
+		.search
+		.as_deref()
+		.map(str::trim)
+		.filter(|search| !search.is_empty())
+		.map(str::to_lowercase);
+	changes.retain(|change| {
+		(query.states.is_empty() || query.states.contains(&change.state))
+			&& search
+				.as_ref()
+				.is_none_or(|search| change.id.to_lowercase().contains(search))
+	});
+	changes.sort_by(|a, b| {
+		let ordering = compare_changes(a, b, query.sort_by);
+		if query.ascending {
+			ordering
+		} else {
+			ordering.reverse()
+		}
+	});
+	ChangeList {
+		total: changes.len(),
+		changes: changes
+			.into_iter()
+			.skip(query.offset)
+			.take(query.limit.unwrap_or(usize::MAX))
+			.collect(),
+	}
+}
+
+fn valid_host(host: &str) -> bool {
+	!host.is_empty()
+		&& host
+			.chars()
+			.all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '-' | ':'))
+}
+
+fn parse_issue_url(url: &str) -> Option<IssueLink> {
+	let rest = url
+		.trim()
+		.strip_prefix("https://")
+		.or_else(|| url.trim().strip_prefix("http://"))?;
+	let parts = rest.trim_end_matches('/').split('/').collect::<Vec<_>>();
+	match parts.as_slice() {
+		[host, owner, repo, "issues", number]
+			if valid_host(host) && !owner.is_empty() && !repo.is_empty() =>
+		{
+			Some(IssueLink {
+				url: format!("https://{host}/{owner}/{repo}/issues/{number}"),
+				host: host.to_ascii_lowercase(),
+				owner: owner.to_string(),
+				repo: repo.to_string(),
+				number: number.parse().ok()?,
+				last_status: None,
+			})
+		}
+		_ => None,
+	}
+}
+
+fn parse_issue_json(value: &Value) -> Option<(IssueState, String)> {
+	let state = match value["state"].as_str()?.to_ascii_lowercase().as_str() {
+		"open" => IssueState::Open,
+		"closed" => IssueState::Closed,
+		_ => return None,
+	};
+	Some((
+		state,
+		value["title"].as_str().unwrap_or_default().to_string(),
+	))
+}
+
+fn fetch_with_gh(issue: &IssueLink) -> Option<(IssueState, String)> {
+	let output = Command::new("gh")
+		.args(["issue", "view", &issue.url, "--json", "state,title"])
+		.output()
+		.ok()?;
+	if !output.status.success() {
+		return None;
+	}
+	parse_issue_json(&serde_json::from_slice(&output.stdout).ok()?)
+}
+
+fn api_base(host: &str) -> String {
+	if host == GITHUB_HOST {
+		"https://api.github.com".to_string()
+	} else {
+		format!("https://{host}/api/v3")
+	}
+}
+
+// The host is read from `.specops.json`, which is committed with the
+// repository, so the token only goes to GitHub or a host the user trusts.
+fn sends_token_to(host: &str, trusted_hosts: &BTreeSet<String>) -> bool {
+	host == GITHUB_HOST
+		|| trusted_hosts
+			.iter()
+			.any(|trusted| trusted.eq_ignore_ascii_case(host))
+}
+
+fn fetch_with_api(
+	issue: &IssueLink,
+	trusted_hosts: &BTreeSet<String>,
+) -> Result<(IssueState, String), SpecError> {
+	let url = format!(
+		"{}/repos/{}/{}/issues/{}",
+		api_base(&issue.host),
+		issue.owner,
+		issue.repo,
+		issue.number
+	);
+	let agent = ureq::AgentBuilder::new()
+		.timeout(ISSUE_LOOKUP_TIMEOUT)
+		.build();
+	let mut request = agent
+		.get(&url)
+		.set("Accept", "application/vnd.github+json")
+		.set("User-Agent", "SpecOps");
+	if sends_token_to(&issue.host, trusted_hosts) {
+		if let Some(token) = read_secret(GITHUB_TOKEN_SECRET)? {
+			request = request.set("Authorization", &format!("Bearer {token}"));
+		}
+	}
+	let value = request
+		.call()
+		.map_err(|_| SpecError::IssueLookupFailed)?
+		.into_json::<Value>()
+		.map_err(|_| SpecError::IssueLookupFailed)?;
+	parse_issue_json(&value).ok_or(SpecError::IssueLookupFailed)
+}
+
+#[tauri::command]
+pub fn openspec_list(
+	project: String,
+	query: Option<ChangeListQuery>,
+) -> Result<ChangeList, SpecErrorPayload> {
+	let query = query.unwrap_or_default();
+	let changes = list_changes(Path::new(&project), query.include_archived)?;
+	Ok(query_changes(changes, &query))
+}
+
+#[tauri::command]
+pub fn openspec_create_change(
+	project: String,
+	change: NewChange,
+) -> Result<CreatedChange, SpecErrorPayload> {
+	create_change(Path::new(&project), &change).map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn link_change_to_issue(
+	project: String,
+	change_id: String,
+	issue_url: String,
+) -> Result<IssueLink, SpecErrorPayload> {
+	let issue = parse_issue_url(&issue_url).ok_or(SpecError::InvalidIssueUrl)?;
+	update_metadata(Path::new(&project), &change_id, |metadata| {
+		metadata.issue = Some(issue.clone());
+	})?;
+	Ok(issue)
+}
+
+#[tauri::command]
+pub fn unlink_change_issue(project: String, change_id: String) -> Result<(), SpecErrorPayload> {
+	update_metadata(Path::new(&project), &change_id, |metadata| {
+		metadata.issue = None;
+	})
+	.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command(async)]
+pub fn fetch_issue_status(
+	settings: State<'_, SettingsStore>,
+	project: String,
+	change_id: String,
+) -> Result<IssueStatus, SpecErrorPayload> {
+	let project = Path::new(&project);
+	let issue = read_metadata(project, &change_id)?
+		.issue
+		.ok_or(SpecError::IssueNotLinked)?;
+	let (state, title) = match fetch_with_gh(&issue) {
+		Some(result) => result,
+		None => fetch_with_api(&issue, &settings.get().trusted_issue_hosts)?,
+	};
+	let status = IssueStatus {
+		state,
+		title,
+		fetched_at: storage::now_secs(),
+	};
+	update_metadata(project, &change_id, |metadata| {
+		if let Some(issue) = metadata.issue.as_mut() {
+			issue.last_status = Some(status.clone());
+		}
+	})?;
+	Ok(status)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+	use tempfile::TempDir;
+
+	#[test]
+	fn parses_github_issue_urls() {
+		let issue =
+			parse_issue_url("https://github.com/chainyo/specops/issues/42/").expect("issue");
+		assert_eq!(
+			(issue.owner.as_str(), issue.repo.as_str(), issue.number),
+			("chainyo", "specops", 42)
+		);
+		assert_eq!(api_base(&issue.host), "https://api.github.com");
+		assert!(parse_issue_url("https://github.com/chainyo/specops/pull/42").is_none());
+		assert!(parse_issue_url("github.com/chainyo/specops/issues/x").is_none());
+		assert!(parse_issue_url("https://github.com@evil.test/o/r/issues/1").is_none());
+
+		let enterprise =
+			parse_issue_url("https://GHE.example.com/o/r/issues/1").expect("enterprise issue");
+		assert!(sends_token_to(&issue.host, &BTreeSet::new()));
+		assert!(!sends_token_to(&enterprise.host, &BTreeSet::new()));
+		assert!(sends_token_to(
+			&enterprise.host,
+			&BTreeSet::from(["ghe.example.com".to_string()])
+		));
+		assert_eq!(
+			parse_issue_json(&json!({ "state": "CLOSED", "title": "Ship it" })),
+			Some((IssueState::Closed, "Ship it".to_string()))
+		);
+	}
+
+	#[test]
+	fn scaffolds_new_changes_with_validated_ids() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		fs::create_dir_all(temp_dir.path().join("openspec/specs")).expect("create openspec");
+		for id in [
+			"",
+			"Add-login",
+			"add--login",
+			"add-login-",
+			"1-add",
+			"archive",
+			"../x",
+		] {
+			assert!(matches!(
+				validate_change_id(id),
+				Err(SpecError::InvalidChangeId)
+			));
+		}
+
+		let created = create_change(
+			temp_dir.path(),
+			&NewChange {
+				change_id: "add-two-factor".to_string(),
+				design: true,
+				..NewChange::default()
+			},
+		)
+		.expect("create change");
+		assert_eq!(created.path, "openspec/changes/add-two-factor");
+		assert_eq!(
+			created.files,
+			[
+				"openspec/changes/add-two-factor/proposal.md",
+				"openspec/changes/add-two-factor/tasks.md",
+				"openspec/changes/add-two-factor/design.md",
+			]
+		);
+		let proposal = fs::read_to_string(temp_dir.path().join(&created.files[0])).expect("read");
+		assert!(proposal.starts_with("# Change: Add two factor\n\n## Why\n"));
+		let listed = list_changes(temp_dir.path(), false).expect("list changes");
+		assert_eq!(listed.len(), 1);
+
+		assert!(matches!(
+			create_change(
+				temp_dir.path(),
+				&NewChange {
+					change_id: "add-two-factor".to_string(),
+					..NewChange::default()
+				},
+			),
+			Err(SpecError::ChangeExists)
+		));
+	}
+
+	#[test]
+	fn persists_metadata_next_to_change() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		fs::create_dir_all(temp_dir.path().join("openspec/changes/add-login")).expect("create");
+		let issue = parse_issue_url("https://github.com/o/r/issues/1").expect("issue");
+		update_metadata(temp_dir.path(), "add-login", |metadata| {
+			metadata.issue = Some(issue.clone());
+		})
+		.expect("update");
+		assert_eq!(
+			read_metadata(temp_dir.path(), "add-login")
+				.expect("read")
+				.issue,
+			Some(issue)
+		);
+		assert!(temp_dir
+			.path()
+			.join("openspec/changes/add-login/.specops.json")
+			.is_file());
+		assert!(matches!(
+			read_metadata(temp_dir.path(), "../add-login"),
+			Err(SpecError::ChangeNotFound)
+		));
+
+		let path = temp_dir
+			.path()
+			.join("openspec/changes/add-login/.specops.json");
+		fs::write(&path, "{\"comments\": 3}").expect("corrupt metadata");
+		assert!(matches!(
+			update_metadata(temp_dir.path(), "add-login", |metadata| {
+				metadata.target_date = None;
+			}),
+			Err(SpecError::InvalidChangeMetadata)
+		));
+		assert_eq!(
+			fs::read_to_string(&path).expect("read metadata"),
+			"{\"comments\": 3}"
+		);
+	}
+
+	#[test]
+	fn summarizes_and_sorts_changes() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let changes = temp_dir.path().join("openspec/changes");
+		fs::create_dir_all(changes.join("add-login/specs/auth")).expect("create");
+		fs::write(
+			changes.join("add-login/tasks.md"),
+			"## 1. Build\n- [x] 1.1 Form\n- [ ] 1.2 Session\n",
+		)
+		.expect("write tasks");
+		fs::write(
+			changes.join("add-login/specs/auth/spec.md"),
+			"## ADDED Requirements\n### Requirement: Login\n### Requirement: Logout\n",
+		)
+		.expect("write delta");
+		fs::create_dir_all(changes.join("fix-typo")).expect("create");
+		fs::write(changes.join("fix-typo/tasks.md"), "- [X] 1.1 Fix\n").expect("write tasks");
+		fs::create_dir_all(changes.join("archive/2024-01-01-old")).expect("create archive");
+
+		let all = list_changes(temp_dir.path(), true).expect("list");
+		assert_eq!(all.len(), 3);
+
+		let list = query_changes(
+			all,
+			&ChangeListQuery {
+				sort_by: ChangeSortKey::Completion,
+				states: vec![ChangeState::InProgress, ChangeState::Complete],
+				..ChangeListQuery::default()
+			},
+		);
+		assert_eq!(list.total, 2);
+		let ids = list
+			.changes
+			.iter()
+			.map(|change| (change.id.as_str(), change.completion, change.delta_size))
+			.collect::<Vec<_>>();
+		assert_eq!(ids, vec![("fix-typo", 100, 0), ("add-login", 50, 2)]);
+	}
+}
@@ -0,0 +1,361 @@
+use crate::{
+	changes::{change_dir, read_metadata, update_metadata},
+	lint::{lint_config, lint_file, LintRule, Severity},
+	ordering,
+	spec::{markdown_files, SourceText, SpecError, SpecErrorPayload, MAX_PARSE_BYTES},
+	storage,
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, path::Path};
+
+pub const CHECKLIST_CONFIG: &str = ".specops-review.json";
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AutomaticCheck {
+	ValidationPasses,
+	RequirementsHaveScenarios,
+	TasksReferenceDeltas,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum ChecklistRule {
+	Automatic { check: AutomaticCheck },
+	Manual,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistItem {
+	id: String,
+	title: String,
+	#[serde(flatten)]
+	rule: ChecklistRule,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChecklistConfig {
+	items: Vec<ChecklistItem>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckStatus {
+	Passed,
+	Failed,
+	Pending,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistTick {
+	reviewer: String,
+	checked_at: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistItemResult {
+	id: String,
+	title: String,
+	#[serde(flatten)]
+	rule: ChecklistRule,
+	status: CheckStatus,
+	detail: Option<String>,
+	tick: Option<ChecklistTick>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChecklistResult {
+	complete: bool,
+	evaluated_at: u64,
+	items: Vec<ChecklistItemResult>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ChecklistRecord {
+	ticks: BTreeMap<String, ChecklistTick>,
+	last_result: Option<ChecklistResult>,
+}
+
+fn automatic(id: &str, title: &str, check: AutomaticCheck) -> ChecklistItem {
+	ChecklistItem {
+		id: id.to_string(),
+		title: title.to_string(),
+		rule: ChecklistRule::Automatic { check },
+	}
+}
+
+fn default_items() -> Vec<ChecklistItem> {
+	vec![
+		automatic(
+			"validation",
+			"Validation passes",
+			AutomaticCheck::ValidationPasses,
+		),
+		automatic(
+			"scenarios",
+			"Every requirement has a scenario",
+			AutomaticCheck::RequirementsHaveScenarios,
+		),
+		automatic(
+			"tasks",
+			"Tasks reference every spec delta",
+			AutomaticCheck::TasksReferenceDeltas,
+		),
+	]
+}
+
+pub fn checklist_items(project: &Path) -> Result<Vec<ChecklistItem>, SpecError> {
+	match fs::read(project.join(CHECKLIST_CONFIG)) {
+		Ok(bytes) => serde_json::from_slice::<ChecklistConfig>(&bytes)
+			.map(|config| config.items)
+			.map_err(|_| SpecError::InvalidChecklistConfig),
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(default_items()),
+		Err(error) => Err(error.into()),
+	}
+}
+
+fn outcome(failures: Vec<String>) -> (CheckStatus, Option<String>) {
+	if failures.is_empty() {
+		(CheckStatus::Passed, None)
+	} else {
+		(CheckStatus::Failed, Some(failures.join(", ")))
+	}
+}
+
+pub fn delta_capabilities(dir: &Path) -> Vec<String> {
+	let mut capabilities = fs::read_dir(dir.join("specs"))
+		.map(|entries| {
+			entries
+				.filter_map(Result::ok)
+				.filter(|entry| entry.path().is_dir())
+				.map(|entry| entry.file_name().to_string_lossy().to_string())
+				.collect::<Vec<_>>()
+		})
+		.unwrap_or_default();
+	capabilities.sort_by(|a, b| ordering::compare(a, b));
+	capabilities
+}
+
+fn run_check(
+	project: &Path,
+	dir: &Path,
+	check: AutomaticCheck,
+) -> Result<(CheckStatus, Option<String>), SpecError> {
+	let relative = |path: &Path| {
+		path.strip_prefix(dir)
+			.unwrap_or(path)
+			.to_string_lossy()
+			.replace('\\', "/")
+	};
+	let mut diagnostics = Vec::new();
+	if check != AutomaticCheck::TasksReferenceDeltas {
+		for file in markdown_files(dir)? {
+			diagnostics.extend(lint_file(&file)?);
+		}
+	}
+	Ok(match check {
+		AutomaticCheck::ValidationPasses => outcome(
+			lint_config(project)?
+				.apply(diagnostics)
+				.into_iter()
+				.filter(|diagnostic| diagnostic.severity == Severity::Error)
+				.map(|diagnostic| {
+					format!(
+						"{}:{} {}",
+						relative(Path::new(&diagnostic.path)),
+						diagnostic.line,
+						diagnostic.rule.id()
+					)
+				})
+				.collect(),
+		),
+		AutomaticCheck::RequirementsHaveScenarios => outcome(
+			diagnostics
+				.into_iter()
+				.filter(|diagnostic| diagnostic.rule == LintRule::RequirementMissingScenario)
+				.map(|diagnostic| {
+					format!(
+						"{}:{}",
+						relative(Path::new(&diagnostic.path)),
+						diagnostic.line
+					)
+				})
+				.collect(),
+		),
+		AutomaticCheck::TasksReferenceDeltas => {
+			let tasks = SourceText::read_limited(&dir.join("tasks.md"), MAX_PARSE_BYTES)
+				.map(|source| source.render())
+				.unwrap_or_default();
+			outcome(
+				delta_capabilities(dir)
+					.into_iter()
+					.filter(|capability| !tasks.contains(capability.as_str()))
+					.collect(),
+			)
+		}
+	})
+}
+
+fn evaluate(
+	project: &Path,
+	change_id: &str,
+	ticks: &BTreeMap<String, ChecklistTick>,
+) -> Result<ChecklistResult, SpecError> {
+	let dir = change_dir(project, change_id)?;
+	let mut items = Vec::new();
+	for item in checklist_items(project)? {
+		let tick = ticks.get(&item.id).cloned();
+		let (status, detail) = match item.rule {
+			ChecklistRule::Automatic { check } => run_check(project, &dir, check)?,
+			ChecklistRule::Manual if tick.is_some() => (CheckStatus::Passed, None),
+			ChecklistRule::Manual => (CheckStatus::Pending, None),
+		};
+		items.push(ChecklistItemResult {
+			id: item.id,
+			title: item.title,
+			rule: item.rule,
+			status,
+			detail,
+			tick,
+		});
+	}
+	Ok(ChecklistResult {
+		complete: items.iter().all(|item| item.status == CheckStatus::Passed),
+		evaluated_at: storage::now_secs(),
+		items,
+	})
+}
+
+fn evaluate_and_store(project: &Path, change_id: &str) -> Result<ChecklistResult, SpecError> {
+	let ticks = read_metadata(project, change_id)?.checklist.ticks;
+	let result = evaluate(project, change_id, &ticks)?;
+	update_metadata(project, change_id, |metadata| {
+		metadata.checklist.last_result = Some(result.clone());
+	})?;
+	Ok(result)
+}
+
+fn tick_item(
+	project: &Path,
+	change_id: &str,
+	item_id: &str,
+	reviewer: &str,
+	checked: bool,
+) -> Result<ChecklistResult, SpecError> {
+	let manual = checklist_items(project)?
+		.iter()
+		.any(|item| item.id == item_id && item.rule == ChecklistRule::Manual);
+	if !manual {
+		return Err(SpecError::ChecklistItemNotFound);
+	}
+	let reviewer = reviewer.trim();
+	if checked && reviewer.is_empty() {
+		return Err(SpecError::InvalidApprover);
+	}
+	update_metadata(project, change_id, |metadata| {
+		if checked {
+			metadata.checklist.ticks.insert(
+				item_id.to_string(),
+				ChecklistTick {
+					reviewer: reviewer.to_string(),
+					checked_at: storage::now_secs(),
+				},
+			);
+		} else {
+			metadata.checklist.ticks.remove(item_id);
+		}
+	})?;
+	evaluate_and_store(project, change_id)
+}
+
+#[tauri::command]
+pub fn review_checklist(
+	project: String,
+	change_id: String,
+) -> Result<ChecklistResult, SpecErrorPayload> {
+	evaluate_and_store(Path::new(&project), &change_id).map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn tick_checklist_item(
+	project: String,
+	change_id: String,
+	item_id: String,
+	reviewer: String,
+	checked: bool,
+) -> Result<ChecklistResult, SpecErrorPayload> {
+	tick_item(
+		Path::new(&project),
+		&change_id,
+		&item_id,
+		&reviewer,
+		checked,
+	)
+	.map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn mixes_automatic_and_manual_checks() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let project = temp_dir.path();
+		let change = project.join("openspec/changes/add-login");
+		fs::create_dir_all(change.join("specs/auth")).expect("create change");
+		fs::create_dir_all(change.join("specs/billing")).expect("create change");
+		fs::write(
+			change.join("specs/auth/spec.md"),
+			"## ADDED Requirements\n\n### Requirement: Login\nThe system SHALL log in.\n",
+		)
+		.expect("write spec");
+		fs::write(change.join("tasks.md"), "- [ ] 1.1 Implement auth flow\n").expect("write tasks");
+		fs::write(
+			project.join(CHECKLIST_CONFIG),
+			r#"{"items":[
+				{"id":"scenarios","title":"Scenarios","kind":"automatic","check":"requirementsHaveScenarios"},
+				{"id":"tasks","title":"Tasks","kind":"automatic","check":"tasksReferenceDeltas"},
+				{"id":"security","title":"Security reviewed","kind":"manual"}
+			]}"#,
+		)
+		.expect("write config");
+
+		let result = evaluate_and_store(project, "add-login").expect("evaluate");
+		let statuses = result
+			.items
+			.iter()
+			.map(|item| (item.id.as_str(), item.status, item.detail.as_deref()))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			statuses,
+			vec![
+				(
+					"scenarios",
+					CheckStatus::Failed,
+					Some("specs/auth/spec.md:3")
+				),
+				("tasks", CheckStatus::Failed, Some("billing")),
+				("security", CheckStatus::Pending, None),
+			]
+		);
+		assert!(!result.complete);
+
+		assert!(matches!(
+			tick_item(project, "add-login", "tasks", "ana", true),
+			Err(SpecError::ChecklistItemNotFound)
+		));
+		let result = tick_item(project, "add-login", "security", "ana", true).expect("tick");
+		assert_eq!(result.items[2].status, CheckStatus::Passed);
+		let stored = read_metadata(project, "add-login").expect("metadata");
+		assert!(stored.checklist.ticks.contains_key("security"));
+		assert!(stored.checklist.last_result.is_some());
+	}
+}
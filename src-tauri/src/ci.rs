@@ -0,0 +1,248 @@
+use crate::{
+	diff::unified_diff,
+	global_install_args, launcher, openspec_package,
+	spec::{SpecError, SpecErrorPayload},
+	storage,
+};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+const GITHUB_WORKFLOW: &str = ".github/workflows/openspec-validate.yml";
+const GITLAB_ROOT: &str = ".gitlab-ci.yml";
+const GITLAB_FRAGMENT: &str = ".gitlab/ci/openspec-validate.yml";
+const VALIDATE_COMMAND: &str = "openspec validate --all --strict --no-interactive";
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum CiProvider {
+	GithubActions,
+	GitlabCi,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CiConfig {
+	provider: CiProvider,
+	path: String,
+	relative_path: String,
+	package_manager: String,
+	cli_version: String,
+	content: String,
+	exists: bool,
+	diff: String,
+}
+
+pub fn detect_package_manager(project: &Path) -> &'static str {
+	[
+		("bun.lock", "bun"),
+		("bun.lockb", "bun"),
+		("pnpm-lock.yaml", "pnpm"),
+		("yarn.lock", "yarn"),
+		("package-lock.json", "npm"),
+	]
+	.iter()
+	.find(|(lockfile, _)| project.join(lockfile).is_file())
+	.map(|(_, manager)| *manager)
+	.unwrap_or("npm")
+}
+
+pub fn parse_cli_version(output: &str) -> Option<String> {
+	output
+		.split_whitespace()
+		.map(|token| token.trim_start_matches('v'))
+		.find(|token| {
+			let mut parts = token.split('.');
+			parts.clone().count() == 3
+				&& parts.all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+		})
+		.map(str::to_string)
+}
+
+fn detected_cli_version(project: &Path) -> String {
+	launcher::version(Some(project))
+		.and_then(|output| parse_cli_version(&output))
+		.unwrap_or_else(|| "latest".to_string())
+}
+
+fn install_command(package_manager: &str, version: &str) -> String {
+	let args = global_install_args(package_manager, &openspec_package(version)).unwrap_or_default();
+	format!("{package_manager} {}", args.join(" "))
+}
+
+fn github_setup_steps(package_manager: &str) -> String {
+	let setup_node =
+		"      - uses: actions/setup-node@v4\n        with:\n          node-version: 20\n";
+	match package_manager {
+		"bun" => "      - uses: oven-sh/setup-bun@v2\n".to_string(),
+		"pnpm" => format!(
+			"      - uses: pnpm/action-setup@v4\n        with:\n          version: 9\n{setup_node}"
+		),
+		_ => setup_node.to_string(),
+	}
+}
+
+fn github_workflow(package_manager: &str, version: &str) -> String {
+	format!(
+		"name: OpenSpec validate\n\
+		\n\
+		on:\n  pull_request:\n    paths:\n      - \"openspec/**\"\n\
+		\n\
+		jobs:\n  validate:\n    runs-on: ubuntu-latest\n    steps:\n      - uses: actions/checkout@v4\n\
+		{setup}\
+		      - run: {install}\n\
+		      - run: {VALIDATE_COMMAND}\n",
+		setup = github_setup_steps(package_manager),
+		install = install_command(package_manager, version),
+	)
+}
+
+fn gitlab_job(package_manager: &str, version: &str) -> String {
+	let image = if package_manager == "bun" {
+		"oven/bun:1"
+	} else {
+		"node:20"
+	};
+	let prepare = match package_manager {
+		"pnpm" => "    - corepack enable\n",
+		_ => "",
+	};
+	format!(
+		"openspec-validate:\n  image: {image}\n  rules:\n    - if: $CI_PIPELINE_SOURCE == \"merge_request_event\"\n      changes:\n        - openspec/**/*\n  script:\n\
+		{prepare}\
+		    - {install}\n\
+		    - {VALIDATE_COMMAND}\n",
+		install = install_command(package_manager, version),
+	)
+}
+
+fn target_path(project: &Path, provider: CiProvider) -> &'static str {
+	match provider {
+		CiProvider::GithubActions => GITHUB_WORKFLOW,
+		CiProvider::GitlabCi if project.join(GITLAB_ROOT).exists() => GITLAB_FRAGMENT,
+		CiProvider::GitlabCi => GITLAB_ROOT,
+	}
+}
+
+fn render_ci_config(
+	project: &Path,
+	provider: CiProvider,
+	package_manager: &str,
+	version: &str,
+) -> Result<CiConfig, SpecError> {
+	let relative_path = target_path(project, provider);
+	let path = project.join(relative_path);
+	let content = match provider {
+		CiProvider::GithubActions => github_workflow(package_manager, version),
+		CiProvider::GitlabCi => gitlab_job(package_manager, version),
+	};
+	let existing = match fs::read_to_string(&path) {
+		Ok(existing) => Some(existing),
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => None,
+		Err(error) => return Err(error.into()),
+	};
+	Ok(CiConfig {
+		provider,
+		path: path.to_string_lossy().to_string(),
+		relative_path: relative_path.to_string(),
+		package_manager: package_manager.to_string(),
+		cli_version: version.to_string(),
+		diff: unified_diff(
+			existing.as_deref().unwrap_or_default(),
+			&content,
+			relative_path,
+		),
+		exists: existing.is_some(),
+		content,
+	})
+}
+
+fn write_ci_config(config: &CiConfig, overwrite: bool) -> Result<(), SpecError> {
+	if config.exists && !config.diff.is_empty() && !overwrite {
+		return Err(SpecError::TargetExists);
+	}
+	storage::write_atomic(Path::new(&config.path), config.content.as_bytes())?;
+	Ok(())
+}
+
+fn detected_ci_config(project: &Path, provider: CiProvider) -> Result<CiConfig, SpecError> {
+	render_ci_config(
+		project,
+		provider,
+		detect_package_manager(project),
+		&detected_cli_version(project),
+	)
+}
+
+#[tauri::command]
+pub fn preview_ci_config(
+	project: String,
+	provider: CiProvider,
+) -> Result<CiConfig, SpecErrorPayload> {
+	detected_ci_config(Path::new(&project), provider).map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn generate_ci_config(
+	project: String,
+	provider: CiProvider,
+	overwrite: Option<bool>,
+) -> Result<CiConfig, SpecErrorPayload> {
+	let config = detected_ci_config(Path::new(&project), provider)?;
+	write_ci_config(&config, overwrite.unwrap_or(false))?;
+	Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn detects_package_manager_from_lockfile() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		assert_eq!(detect_package_manager(temp_dir.path()), "npm");
+		fs::write(temp_dir.path().join("pnpm-lock.yaml"), "").expect("write lockfile");
+		assert_eq!(detect_package_manager(temp_dir.path()), "pnpm");
+		assert_eq!(
+			parse_cli_version("openspec v0.16.0"),
+			Some("0.16.0".to_string())
+		);
+		assert_eq!(parse_cli_version("unknown"), None);
+	}
+
+	#[test]
+	fn renders_github_workflow_with_pinned_cli() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let config = render_ci_config(temp_dir.path(), CiProvider::GithubActions, "bun", "0.16.0")
+			.expect("render");
+		assert_eq!(config.relative_path, GITHUB_WORKFLOW);
+		assert!(!config.exists);
+		assert!(config.content.contains("oven-sh/setup-bun@v2"));
+		assert!(config
+			.content
+			.contains("- run: bun add -g @fission-ai/openspec@0.16.0"));
+		assert!(config.content.contains(VALIDATE_COMMAND));
+		assert!(config.diff.starts_with("--- a/"));
+	}
+
+	#[test]
+	fn refuses_to_overwrite_changed_config() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		fs::write(temp_dir.path().join(GITLAB_ROOT), "stages: [test]\n").expect("write ci");
+		let config = render_ci_config(temp_dir.path(), CiProvider::GitlabCi, "npm", "latest")
+			.expect("render");
+		assert_eq!(config.relative_path, GITLAB_FRAGMENT);
+		write_ci_config(&config, false).expect("write fragment");
+
+		let config = render_ci_config(temp_dir.path(), CiProvider::GitlabCi, "yarn", "latest")
+			.expect("render");
+		assert!(matches!(
+			write_ci_config(&config, false),
+			Err(SpecError::TargetExists)
+		));
+		write_ci_config(&config, true).expect("overwrite");
+		assert!(fs::read_to_string(temp_dir.path().join(GITLAB_FRAGMENT))
+			.expect("read")
+			.contains("yarn global add @fission-ai/openspec@latest"));
+	}
+}
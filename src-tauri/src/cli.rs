@@ -0,0 +1,1016 @@
+use crate::{
+	changes::{change_dir, task_counts},
+	launcher,
+	lint::Severity,
+	ordering,
+	settings::SettingsStore,
+	spec::{
+		markdown_files, parse_heading, SourceText, SpecError, SpecErrorPayload, MAX_PARSE_BYTES,
+	},
+	webhook::{emit_webhook, WebhookEvent},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{path::Path, process::Output};
+use tauri::State;
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CliOutputFormat {
+	Json,
+	Text,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliChange {
+	id: String,
+	title: Option<String>,
+	tasks_done: usize,
+	tasks_total: usize,
+	completion: u8,
+	complete: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliChangeList {
+	format: CliOutputFormat,
+	changes: Vec<CliChange>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ShowKind {
+	Change,
+	Spec,
+}
+
+#[derive(Clone, Debug, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShowRequirement {
+	name: Option<String>,
+	text: String,
+	scenarios: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ShowDelta {
+	spec: Option<String>,
+	operation: String,
+	requirement: ShowRequirement,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShowDetails {
+	id: String,
+	kind: ShowKind,
+	format: CliOutputFormat,
+	title: Option<String>,
+	overview: Option<String>,
+	requirements: Vec<ShowRequirement>,
+	deltas: Vec<ShowDelta>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DeltaSource {
+	Cli,
+	Files,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeltaSection {
+	operation: String,
+	requirements: Vec<ShowRequirement>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilityDelta {
+	capability: String,
+	sections: Vec<DeltaSection>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeDeltas {
+	change_id: String,
+	source: DeltaSource,
+	capabilities: Vec<CapabilityDelta>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationDiagnostic {
+	item: Option<String>,
+	file: Option<String>,
+	rule: Option<String>,
+	message: String,
+	severity: Severity,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidationReport {
+	format: CliOutputFormat,
+	pub valid: bool,
+	errors: usize,
+	warnings: usize,
+	diagnostics: Vec<ValidationDiagnostic>,
+}
+
+/// One openspec invocation with its output decoded once for the parsers.
+#[derive(Debug)]
+pub struct CliRun {
+	pub success: bool,
+	pub stdout: String,
+	pub stderr: String,
+}
+
+impl CliRun {
+	fn from_output(output: &Output) -> Self {
+		Self {
+			success: output.status.success(),
+			stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+			stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+		}
+	}
+
+	pub fn combined(&self) -> String {
+		format!("{}\n{}", self.stdout, self.stderr)
+	}
+}
+
+#[derive(Debug)]
+pub struct CliOutput<T> {
+	pub format: CliOutputFormat,
+	pub success: bool,
+	pub value: T,
+}
+
+pub fn run_openspec_in(project: &Path, args: &[&str]) -> Result<Output, SpecError> {
+	let launcher = launcher::resolve(Some(project));
+	launcher
+		.command(Some(project), args)?
+		.output()
+		.map_err(|error| launcher.spawn_error(error))
+}
+
+/// Runs `args` with `--json` first and only scrapes the human-readable
+/// output when the JSON is missing or not understood. Parsers decide for
+/// themselves whether a failing exit status still carries usable output.
+pub fn run_preferring_json<T>(
+	project: &Path,
+	args: &[&str],
+	json: impl FnOnce(&CliRun) -> Option<T>,
+	text: impl FnOnce(&CliRun) -> Option<T>,
+) -> Result<CliOutput<T>, SpecError> {
+	let run = CliRun::from_output(&run_openspec_in(project, &[args, &["--json"]].concat())?);
+	if let Some(value) = json(&run) {
+		return Ok(CliOutput {
+			format: CliOutputFormat::Json,
+			success: run.success,
+			value,
+		});
+	}
+	let run = CliRun::from_output(&run_openspec_in(project, args)?);
+	match text(&run) {
+		Some(value) => Ok(CliOutput {
+			format: CliOutputFormat::Text,
+			success: run.success,
+			value,
+		}),
+		None if run.success => Err(SpecError::CliOutputUnparseable),
+		None => Err(SpecError::CliCommandFailed),
+	}
+}
+
+/// Reads the tool ids from `openspec init --help`, which has no JSON form;
+/// the list may wrap across several lines of the option description.
+pub fn parse_tools_help(help_text: &str) -> Option<Vec<String>> {
+	let mut lines = help_text.lines().peekable();
+
+	while let Some(line) = lines.next() {
+		if let Some(index) = line.find("list of:") {
+			let mut segments = Vec::new();
+			let list = line[index + "list of:".len()..].trim();
+			if !list.is_empty() {
+				segments.push(list.to_string());
+			}
+
+			while let Some(next) = lines.peek() {
+				let trimmed = next.trim();
+				if trimmed.is_empty()
+					|| trimmed.starts_with('-')
+					|| trimmed.starts_with("Options:")
+					|| trimmed.starts_with("Usage:")
+				{
+					break;
+				}
+				segments.push(trimmed.to_string());
+				lines.next();
+			}
+
+			let combined = segments.join(" ");
+			let tools = combined
+				.split(',')
+				.map(|entry| entry.trim().trim_end_matches('.'))
+				.filter(|entry| !entry.is_empty())
+				.map(String::from)
+				.collect::<Vec<_>>();
+			return Some(tools).filter(|tools| !tools.is_empty());
+		}
+	}
+
+	None
+}
+
+fn cli_change(id: &str, tasks_done: usize, tasks_total: usize, complete: bool) -> CliChange {
+	CliChange {
+		id: id.to_string(),
+		title: None,
+		tasks_done,
+		tasks_total,
+		completion: (tasks_done * 100).checked_div(tasks_total).unwrap_or(0) as u8,
+		complete: complete || (tasks_total > 0 && tasks_done == tasks_total),
+	}
+}
+
+fn count(entry: &Value, keys: &[&str]) -> Option<usize> {
+	keys.iter()
+		.find_map(|key| entry.get(*key).and_then(Value::as_u64))
+		.map(|value| value as usize)
+}
+
+pub fn parse_list_json(text: &str) -> Option<Vec<CliChange>> {
+	let value = serde_json::from_str::<Value>(text).ok()?;
+	let entries = match &value {
+		Value::Array(entries) => entries,
+		Value::Object(object) => object.get("changes")?.as_array()?,
+		_ => return None,
+	};
+	entries
+		.iter()
+		.map(|entry| {
+			let id = entry
+				.get("name")
+				.or_else(|| entry.get("id"))
+				.and_then(Value::as_str)?;
+			let done = count(entry, &["completedTasks", "tasksDone", "completed"]).unwrap_or(0);
+			let total = count(entry, &["totalTasks", "tasksTotal", "total"]).unwrap_or(0);
+			let complete = entry
+				.get("status")
+				.and_then(Value::as_str)
+				.is_some_and(|status| status.eq_ignore_ascii_case("complete"));
+			Some(cli_change(id, done, total, complete))
+		})
+		.collect()
+}
+
+fn parse_progress(token: &str) -> Option<(usize, usize)> {
+	let (done, total) = token.split_once('/')?;
+	Some((done.parse().ok()?, total.parse().ok()?))
+}
+
+pub fn parse_list_text(text: &str) -> Vec<CliChange> {
+	text.lines()
+		.filter(|line| line.starts_with([' ', '\t']))
+		.filter_map(|line| {
+			let mut tokens = line.split_whitespace();
+			let id = tokens.next()?;
+			let rest = tokens.collect::<Vec<_>>();
+			let (done, total) = rest
+				.windows(2)
+				.find(|pair| pair[1].starts_with("task"))
+				.and_then(|pair| parse_progress(pair[0]))
+				.unwrap_or((0, 0));
+			let complete = rest
+				.iter()
+				.any(|token| token.eq_ignore_ascii_case("complete"));
+			Some(cli_change(id, done, total, complete))
+		})
+		.collect()
+}
+
+fn proposal_title(project: &Path, change_id: &str) -> Option<String> {
+	let proposal = SourceText::read_limited(
+		&change_dir(project, change_id).ok()?.join("proposal.md"),
+		MAX_PARSE_BYTES,
+	)
+	.ok()?;
+	proposal
+		.lines
+		.iter()
+		.filter_map(|line| parse_heading(line))
+		.find(|(level, _)| *level == 1)
+		.map(|(_, title)| title.to_string())
+}
+
+fn fill_from_tree(project: &Path, change: &mut CliChange) {
+	change.title = proposal_title(project, &change.id);
+	if change.tasks_total == 0 {
+		let tasks = change_dir(project, &change.id)
+			.ok()
+			.and_then(|dir| SourceText::read_complete(&dir.join("tasks.md")).ok())
+			.map(|source| source.render());
+		if let Some((total, done)) = tasks.map(|tasks| task_counts(&tasks)) {
+			let title = change.title.take();
+			*change = CliChange {
+				title,
+				..cli_change(&change.id, done, total, change.complete)
+			};
+		}
+	}
+}
+
+pub fn list_changes_with_cli(project: &Path) -> Result<CliChangeList, SpecError> {
+	let CliOutput {
+		format,
+		value: mut changes,
+		..
+	} = run_preferring_json(
+		project,
+		&["list"],
+		|run| run.success.then(|| parse_list_json(&run.stdout)).flatten(),
+		|run| run.success.then(|| parse_list_text(&run.stdout)),
+	)?;
+	for change in &mut changes {
+		fill_from_tree(project, change);
+	}
+	changes.sort_by(|a, b| ordering::compare(&a.id, &b.id));
+	Ok(CliChangeList { format, changes })
+}
+
+fn text_field(value: &Value, keys: &[&str]) -> Option<String> {
+	keys.iter()
+		.find_map(|key| value.get(*key).and_then(Value::as_str))
+		.map(str::to_string)
+}
+
+fn json_requirement(value: &Value) -> ShowRequirement {
+	ShowRequirement {
+		name: text_field(value, &["name", "title"]),
+		text: text_field(value, &["text", "description"]).unwrap_or_default(),
+		scenarios: value
+			.get("scenarios")
+			.and_then(Value::as_array)
+			.map(|scenarios| {
+				scenarios
+					.iter()
+					.filter_map(|scenario| {
+						scenario
+							.as_str()
+							.map(str::to_string)
+							.or_else(|| text_field(scenario, &["rawText", "name", "text"]))
+					})
+					.collect()
+			})
+			.unwrap_or_default(),
+	}
+}
+
+fn json_array<'a>(value: &'a Value, key: &str) -> impl Iterator<Item = &'a Value> {
+	value
+		.get(key)
+		.and_then(Value::as_array)
+		.into_iter()
+		.flatten()
+}
+
+pub fn parse_show_json(id: &str, kind: ShowKind, text: &str) -> Option<ShowDetails> {
+	let value = serde_json::from_str::<Value>(text).ok()?;
+	if !value.is_object() {
+		return None;
+	}
+	Some(ShowDetails {
+		id: text_field(&value, &["id", "name"]).unwrap_or_else(|| id.to_string()),
+		kind,
+		format: CliOutputFormat::Json,
+		title: text_field(&value, &["title"]),
+		overview: text_field(&value, &["overview", "why", "purpose"]),
+		requirements: json_array(&value, "requirements")
+			.map(json_requirement)
+			.collect(),
+		deltas: json_array(&value, "deltas")
+			.map(|delta| ShowDelta {
+				spec: text_field(delta, &["spec", "capability"]),
+				operation: text_field(delta, &["operation"])
+					.unwrap_or_default()
+					.to_uppercase(),
+				requirement: delta
+					.get("requirement")
+					.map(json_requirement)
+					.unwrap_or_else(|| ShowRequirement {
+						text: text_field(delta, &["description"]).unwrap_or_default(),
+						..ShowRequirement::default()
+					}),
+			})
+			.collect(),
+	})
+}
+
+const DELTA_OPERATIONS: &[&str] = &["ADDED", "MODIFIED", "REMOVED", "RENAMED"];
+
+fn delta_operation(title: &str) -> Option<&'static str> {
+	let operation = title.strip_suffix(" Requirements")?;
+	DELTA_OPERATIONS
+		.iter()
+		.copied()
+		.find(|known| *known == operation)
+}
+
+pub fn parse_show_markdown(id: &str, kind: ShowKind, text: &str) -> ShowDetails {
+	let mut details = ShowDetails {
+		id: id.to_string(),
+		kind,
+		format: CliOutputFormat::Text,
+		title: None,
+		overview: None,
+		requirements: Vec::new(),
+		deltas: Vec::new(),
+	};
+	let mut operation = None;
+	let mut in_overview = false;
+	let mut current: Option<ShowRequirement> = None;
+	let mut flush = |current: &mut Option<ShowRequirement>, operation: Option<&str>| {
+		if let Some(mut requirement) = current.take() {
+			requirement.text = requirement.text.trim().to_string();
+			match operation {
+				Some(operation) => details.deltas.push(ShowDelta {
+					spec: None,
+					operation: operation.to_string(),
+					requirement,
+				}),
+				None => details.requirements.push(requirement),
+			}
+		}
+	};
+	let mut title = None;
+	let mut overview = String::new();
+	for line in text.lines() {
+		match parse_heading(line) {
+			Some((1, heading)) if title.is_none() => title = Some(heading.to_string()),
+			Some((2, heading)) => {
+				flush(&mut current, operation);
+				operation = delta_operation(heading);
+				in_overview = matches!(heading, "Purpose" | "Why" | "Overview");
+			}
+			Some((3, heading)) => {
+				flush(&mut current, operation);
+				in_overview = false;
+				if let Some(name) = heading.strip_prefix("Requirement:") {
+					current = Some(ShowRequirement {
+						name: Some(name.trim().to_string()),
+						..ShowRequirement::default()
+					});
+				}
+			}
+			Some((4, heading)) => {
+				if let (Some(requirement), Some(name)) =
+					(current.as_mut(), heading.strip_prefix("Scenario:"))
+				{
+					requirement.scenarios.push(name.trim().to_string());
+				}
+			}
+			Some(_) => in_overview = false,
+			None if in_overview => {
+				overview.push_str(line.trim());
+				overview.push('\n');
+			}
+			None => {
+				if let Some(requirement) = current.as_mut().filter(|req| req.scenarios.is_empty()) {
+					requirement.text.push_str(line.trim());
+					requirement.text.push('\n');
+				}
+			}
+		}
+	}
+	flush(&mut current, operation);
+	details.title = title;
+	details.overview = Some(overview.trim().to_string()).filter(|text| !text.is_empty());
+	details
+}
+
+pub fn show_with_cli(project: &Path, id: &str, kind: ShowKind) -> Result<ShowDetails, SpecError> {
+	if id.is_empty() || id.starts_with('-') {
+		return Err(match kind {
+			ShowKind::Change => SpecError::ChangeNotFound,
+			ShowKind::Spec => SpecError::SpecNotFound,
+		});
+	}
+	let kind_arg = match kind {
+		ShowKind::Change => "change",
+		ShowKind::Spec => "spec",
+	};
+	run_preferring_json(
+		project,
+		&["show", id, "--type", kind_arg],
+		|run| {
+			run.success
+				.then(|| parse_show_json(id, kind, &run.stdout))
+				.flatten()
+		},
+		|run| {
+			run.success
+				.then(|| parse_show_markdown(id, kind, &run.stdout))
+		},
+	)
+	.map(|output| output.value)
+}
+
+fn deltas_from_files(dir: &Path) -> Result<Vec<ShowDelta>, SpecError> {
+	let specs = dir.join("specs");
+	if !specs.is_dir() {
+		return Ok(Vec::new());
+	}
+	let mut deltas = Vec::new();
+	for file in markdown_files(&specs)? {
+		let capability = file
+			.parent()
+			.and_then(|parent| parent.strip_prefix(&specs).ok())
+			.map(|parent| parent.to_string_lossy().replace('\\', "/"))
+			.filter(|capability| !capability.is_empty());
+		let text = SourceText::read_complete(&file)?.render();
+		deltas.extend(
+			parse_show_markdown("", ShowKind::Change, &text)
+				.deltas
+				.into_iter()
+				.map(|delta| ShowDelta {
+					spec: capability.clone(),
+					..delta
+				}),
+		);
+	}
+	Ok(deltas)
+}
+
+fn operation_rank(operation: &str) -> usize {
+	DELTA_OPERATIONS
+		.iter()
+		.position(|known| *known == operation)
+		.unwrap_or(DELTA_OPERATIONS.len())
+}
+
+fn group_deltas(deltas: Vec<ShowDelta>) -> Vec<CapabilityDelta> {
+	let mut capabilities: Vec<CapabilityDelta> = Vec::new();
+	for delta in deltas {
+		let capability = delta.spec.unwrap_or_default();
+		let index = match capabilities
+			.iter()
+			.position(|entry| entry.capability == capability)
+		{
+			Some(index) => index,
+			None => {
+				capabilities.push(CapabilityDelta {
+					capability,
+					sections: Vec::new(),
+				});
+				capabilities.len() - 1
+			}
+		};
+		let sections = &mut capabilities[index].sections;
+		match sections
+			.iter_mut()
+			.find(|section| section.operation == delta.operation)
+		{
+			Some(section) => section.requirements.push(delta.requirement),
+			None => sections.push(DeltaSection {
+				operation: delta.operation,
+				requirements: vec![delta.requirement],
+			}),
+		}
+	}
+	for capability in &mut capabilities {
+		capability
+			.sections
+			.sort_by_key(|section| operation_rank(&section.operation));
+	}
+	capabilities.sort_by(|a, b| ordering::compare(&a.capability, &b.capability));
+	capabilities
+}
+
+pub fn change_deltas(project: &Path, change_id: &str) -> Result<ChangeDeltas, SpecError> {
+	if change_id.starts_with('-') {
+		return Err(SpecError::ChangeNotFound);
+	}
+	let dir = change_dir(project, change_id)?;
+	// Older CLIs omit the capability on each delta; the files always have it.
+	let cli = run_openspec_in(
+		project,
+		&[
+			"show",
+			change_id,
+			"--type",
+			"change",
+			"--json",
+			"--deltas-only",
+		],
+	)
+	.ok()
+	.filter(|output| output.status.success())
+	.and_then(|output| {
+		parse_show_json(
+			change_id,
+			ShowKind::Change,
+			&String::from_utf8_lossy(&output.stdout),
+		)
+	})
+	.map(|details| details.deltas)
+	.filter(|deltas| !deltas.is_empty() && deltas.iter().all(|delta| delta.spec.is_some()));
+	let (source, deltas) = match cli {
+		Some(deltas) => (DeltaSource::Cli, deltas),
+		None => (DeltaSource::Files, deltas_from_files(&dir)?),
+	};
+	Ok(ChangeDeltas {
+		change_id: change_id.to_string(),
+		source,
+		capabilities: group_deltas(deltas),
+	})
+}
+
+fn severity(level: &str) -> Option<Severity> {
+	match level.to_ascii_uppercase().as_str() {
+		"ERROR" => Some(Severity::Error),
+		"WARNING" | "WARN" => Some(Severity::Warning),
+		"INFO" => Some(Severity::Info),
+		_ => None,
+	}
+}
+
+fn item_file(kind: Option<&str>, item: Option<&str>) -> Option<String> {
+	match (kind?, item?) {
+		("change", id) => Some(format!("openspec/changes/{id}")),
+		("spec", id) => Some(format!("openspec/specs/{id}/spec.md")),
+		_ => None,
+	}
+}
+
+fn json_issues(
+	item: Option<&str>,
+	kind: Option<&str>,
+	value: &Value,
+) -> impl Iterator<Item = ValidationDiagnostic> {
+	let item = item.map(str::to_string);
+	let fallback_file = item_file(kind, item.as_deref());
+	json_array(value, "issues")
+		.map(move |issue| {
+			let path = text_field(issue, &["path"]);
+			let file = text_field(issue, &["file"])
+				.or_else(|| path.clone().filter(|path| path.ends_with(".md")))
+				.or_else(|| fallback_file.clone());
+			ValidationDiagnostic {
+				item: item.clone(),
+				file,
+				rule: text_field(issue, &["rule", "code"]).or(path),
+				message: text_field(issue, &["message"]).unwrap_or_default(),
+				severity: text_field(issue, &["level", "severity"])
+					.and_then(|level| severity(&level))
+					.unwrap_or(Severity::Error),
+			}
+		})
+		.collect::<Vec<_>>()
+		.into_iter()
+}
+
+pub fn parse_validate_json(text: &str) -> Option<Vec<ValidationDiagnostic>> {
+	let value = serde_json::from_str::<Value>(text).ok()?;
+	if let Some(items) = value.get("items").and_then(Value::as_array) {
+		return Some(
+			items
+				.iter()
+				.flat_map(|item| {
+					let id = item.get("id").and_then(Value::as_str);
+					let kind = item.get("type").and_then(Value::as_str);
+					json_issues(id, kind, item)
+				})
+				.collect(),
+		);
+	}
+	value
+		.get("issues")
+		.map(|_| json_issues(None, None, &value).collect())
+}
+
+fn text_diagnostic(item: Option<&str>, line: &str) -> Option<ValidationDiagnostic> {
+	let trimmed = line.trim().trim_start_matches(['✗', '⚠', 'ℹ', '-', ' ']);
+	let (level, rest) = trimmed
+		.trim_start_matches('[')
+		.split_once([']', ':', ' '])?;
+	let severity = severity(level)?;
+	let rest = rest.trim_start_matches([']', ':', ' ']);
+	let (file, message) = match rest.split_once(": ") {
+		Some((file, message)) if !file.contains(' ') && file.contains(['.', '/']) => {
+			(Some(file.to_string()), message)
+		}
+		_ => (None, rest),
+	};
+	Some(ValidationDiagnostic {
+		item: item.map(str::to_string),
+		file,
+		rule: None,
+		message: message.trim().to_string(),
+		severity,
+	})
+}
+
+pub fn parse_validate_text(text: &str) -> Vec<ValidationDiagnostic> {
+	let mut item = None;
+	let mut diagnostics = Vec::new();
+	for line in text.lines().filter(|line| !line.trim().is_empty()) {
+		match text_diagnostic(item, line) {
+			Some(diagnostic) => diagnostics.push(diagnostic),
+			None if !line.starts_with([' ', '\t']) => {
+				item = line
+					.trim_start_matches(['✗', '✓', ' '])
+					.split_whitespace()
+					.next()
+					.filter(|id| !id.ends_with(':'));
+			}
+			None => {}
+		}
+	}
+	diagnostics
+}
+
+fn validation_report(
+	format: CliOutputFormat,
+	valid: bool,
+	diagnostics: Vec<ValidationDiagnostic>,
+) -> ValidationReport {
+	let count = |severity| {
+		diagnostics
+			.iter()
+			.filter(|diagnostic| diagnostic.severity == severity)
+			.count()
+	};
+	ValidationReport {
+		format,
+		valid,
+		errors: count(Severity::Error),
+		warnings: count(Severity::Warning),
+		diagnostics,
+	}
+}
+
+pub fn validate_with_cli(
+	project: &Path,
+	change_id: Option<&str>,
+	strict: bool,
+) -> Result<ValidationReport, SpecError> {
+	let mut args = match change_id {
+		Some(id) if id.is_empty() || id.starts_with('-') => return Err(SpecError::ChangeNotFound),
+		Some(id) => vec!["validate", id, "--type", "change"],
+		None => vec!["validate", "--all"],
+	};
+	args.push("--no-interactive");
+	if strict {
+		args.push("--strict");
+	}
+	// A failed validation exits non-zero but still reports its diagnostics.
+	let output = run_preferring_json(
+		project,
+		&args,
+		|run| parse_validate_json(&run.stdout),
+		|run| {
+			let diagnostics = parse_validate_text(&run.combined());
+			(run.success || !diagnostics.is_empty()).then_some(diagnostics)
+		},
+	)?;
+	Ok(validation_report(
+		output.format,
+		output.success,
+		output.value,
+	))
+}
+
+#[tauri::command(async)]
+pub fn openspec_validate(
+	settings: State<'_, SettingsStore>,
+	project: String,
+	change_id: Option<String>,
+	strict: Option<bool>,
+) -> Result<ValidationReport, SpecErrorPayload> {
+	let report = validate_with_cli(
+		Path::new(&project),
+		change_id.as_deref(),
+		strict.unwrap_or(false),
+	)?;
+	if !report.valid {
+		emit_webhook(
+			&settings,
+			WebhookEvent::ValidationFailed,
+			json!({
+				"project": project,
+				"changeId": change_id,
+				"errors": report.errors,
+				"warnings": report.warnings,
+			}),
+		);
+	}
+	Ok(report)
+}
+
+#[tauri::command(async)]
+pub fn openspec_change_deltas(
+	project: String,
+	change_id: String,
+) -> Result<ChangeDeltas, SpecErrorPayload> {
+	change_deltas(Path::new(&project), &change_id).map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command(async)]
+pub fn openspec_show(
+	project: String,
+	item_id: String,
+	kind: ShowKind,
+) -> Result<ShowDetails, SpecErrorPayload> {
+	show_with_cli(Path::new(&project), &item_id, kind).map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command(async)]
+pub fn openspec_list_changes(project: String) -> Result<CliChangeList, SpecErrorPayload> {
+	list_changes_with_cli(Path::new(&project)).map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs;
+	use tempfile::TempDir;
+
+	#[test]
+	fn parses_json_and_text_list_output() {
+		let json = r#"{"changes":[{"name":"add-auth","completedTasks":2,"totalTasks":4,"status":"in-progress"},{"name":"fix-io","completedTasks":0,"totalTasks":0,"status":"complete"}]}"#;
+		let changes = parse_list_json(json).expect("json list");
+		assert_eq!(changes[0], cli_change("add-auth", 2, 4, false));
+		assert_eq!(changes[0].completion, 50);
+		assert!(changes[1].complete);
+		assert!(parse_list_json("Changes:\n  add-auth").is_none());
+
+		let text = "Changes:\n  add-auth     2/4 tasks     2h ago\n  fix-io       ✓ Complete\n";
+		assert_eq!(
+			parse_list_text(text),
+			vec![
+				cli_change("add-auth", 2, 4, false),
+				cli_change("fix-io", 0, 0, true)
+			]
+		);
+		assert!(parse_list_text("No active changes found.\n").is_empty());
+	}
+
+	#[test]
+	fn parses_tools_from_help() {
+		let help = r#"--tools <tools>  Configure AI tools non-interactively. Use "all", "none", or a comma-separated list of: auggie, claude, cline, cursor"#;
+		let tools = parse_tools_help(help).expect("tools parsed");
+		assert_eq!(tools, vec!["auggie", "claude", "cline", "cursor"]);
+		assert!(parse_tools_help("Usage: openspec init [path]").is_none());
+	}
+
+	#[test]
+	fn parses_wrapped_tools_from_help() {
+		let help = r#"Options:
+  --tools <tools>  Configure AI tools non-interactively. Use "all", "none", or a comma-separated list of: auggie, claude, cline,
+                   cursor, gemini, opencode
+  -h, --help       display help for command"#;
+		let tools = parse_tools_help(help).expect("tools parsed");
+		assert_eq!(
+			tools,
+			vec!["auggie", "claude", "cline", "cursor", "gemini", "opencode"],
+		);
+	}
+
+	#[test]
+	fn parses_show_json_for_changes_and_specs() {
+		let change = r#"{"id":"add-auth","title":"Add auth","deltas":[{"spec":"auth","operation":"ADDED","description":"Add login","requirement":{"text":"Users SHALL log in.","scenarios":[{"rawText":"Valid credentials"}]}}]}"#;
+		let details = parse_show_json("add-auth", ShowKind::Change, change).expect("change");
+		assert_eq!(details.title.as_deref(), Some("Add auth"));
+		assert_eq!(details.deltas[0].spec.as_deref(), Some("auth"));
+		assert_eq!(details.deltas[0].requirement.text, "Users SHALL log in.");
+		assert_eq!(
+			details.deltas[0].requirement.scenarios,
+			["Valid credentials"]
+		);
+
+		let spec = r#"{"id":"auth","overview":"Login","requirements":[{"text":"Users SHALL log in.","scenarios":[]}]}"#;
+		let details = parse_show_json("auth", ShowKind::Spec, spec).expect("spec");
+		assert_eq!(details.overview.as_deref(), Some("Login"));
+		assert_eq!(details.requirements.len(), 1);
+		assert!(parse_show_json("auth", ShowKind::Spec, "# Auth").is_none());
+	}
+
+	#[test]
+	fn parses_show_markdown_fallback() {
+		let spec = "# Auth\n\n## Purpose\nHandle logins.\n\n## Requirements\n### Requirement: Login\nUsers SHALL log in.\n\n#### Scenario: Valid\n- **WHEN** ok\n";
+		let details = parse_show_markdown("auth", ShowKind::Spec, spec);
+		assert_eq!(details.format, CliOutputFormat::Text);
+		assert_eq!(details.overview.as_deref(), Some("Handle logins."));
+		assert_eq!(
+			details.requirements,
+			vec![ShowRequirement {
+				name: Some("Login".to_string()),
+				text: "Users SHALL log in.".to_string(),
+				scenarios: vec!["Valid".to_string()],
+			}]
+		);
+
+		let delta = "## ADDED Requirements\n### Requirement: Logout\nUsers SHALL log out.\n## REMOVED Requirements\n### Requirement: Remember me\n";
+		let details = parse_show_markdown("add-auth", ShowKind::Change, delta);
+		let operations = details
+			.deltas
+			.iter()
+			.map(|delta| delta.operation.as_str())
+			.collect::<Vec<_>>();
+		assert_eq!(operations, ["ADDED", "REMOVED"]);
+		assert!(details.requirements.is_empty());
+	}
+
+	#[test]
+	fn groups_change_deltas_by_capability() {
+		let change = TempDir::new().expect("create change dir");
+		let write = |capability: &str, text: &str| {
+			let dir = change.path().join("specs").join(capability);
+			fs::create_dir_all(&dir).expect("create capability");
+			fs::write(dir.join("spec.md"), text).expect("write delta");
+		};
+		write(
+			"auth",
+			"## REMOVED Requirements\n### Requirement: Remember me\n## ADDED Requirements\n### Requirement: Logout\nUsers SHALL log out.\n### Requirement: Lockout\nAccounts SHALL lock.\n",
+		);
+		write(
+			"audit",
+			"## MODIFIED Requirements\n### Requirement: Events\nLogins SHALL be logged.\n",
+		);
+
+		let capabilities = group_deltas(deltas_from_files(change.path()).expect("deltas"));
+		let summary = capabilities
+			.iter()
+			.map(|capability| {
+				let sections = capability
+					.sections
+					.iter()
+					.map(|section| (section.operation.as_str(), section.requirements.len()))
+					.collect::<Vec<_>>();
+				(capability.capability.as_str(), sections)
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(
+			summary,
+			[
+				("audit", vec![("MODIFIED", 1)]),
+				("auth", vec![("ADDED", 2), ("REMOVED", 1)]),
+			]
+		);
+		assert_eq!(
+			capabilities[1].sections[0].requirements[0].name.as_deref(),
+			Some("Logout")
+		);
+	}
+
+	#[test]
+	fn parses_validate_json_and_text_output() {
+		let json = r#"{"items":[{"id":"add-auth","type":"change","valid":false,"issues":[{"level":"ERROR","path":"deltas","message":"Change must have at least one delta"},{"level":"WARNING","path":"specs/auth/spec.md","message":"Requirement lacks SHALL"}]}],"summary":{}}"#;
+		let diagnostics = parse_validate_json(json).expect("json diagnostics");
+		assert_eq!(
+			diagnostics[0],
+			ValidationDiagnostic {
+				item: Some("add-auth".to_string()),
+				file: Some("openspec/changes/add-auth".to_string()),
+				rule: Some("deltas".to_string()),
+				message: "Change must have at least one delta".to_string(),
+				severity: Severity::Error,
+			}
+		);
+		assert_eq!(diagnostics[1].file.as_deref(), Some("specs/auth/spec.md"));
+		assert_eq!(diagnostics[1].severity, Severity::Warning);
+		let report = validation_report(CliOutputFormat::Json, false, diagnostics);
+		assert_eq!((report.errors, report.warnings), (1, 1));
+		assert!(parse_validate_json("Validation failed").is_none());
+
+		let text = "✗ add-auth\n  [ERROR] proposal.md: Why section is too short\n  WARNING: Change has no tasks\n✓ fix-io\n";
+		let diagnostics = parse_validate_text(text);
+		assert_eq!(diagnostics.len(), 2);
+		assert_eq!(diagnostics[0].item.as_deref(), Some("add-auth"));
+		assert_eq!(diagnostics[0].file.as_deref(), Some("proposal.md"));
+		assert_eq!(diagnostics[0].message, "Why section is too short");
+		assert_eq!(diagnostics[1].severity, Severity::Warning);
+		assert_eq!(diagnostics[1].message, "Change has no tasks");
+	}
+
+	#[test]
+	fn fills_titles_and_tasks_from_the_tree() {
+		let project = TempDir::new().expect("create project dir");
+		let change = project.path().join("openspec/changes/add-auth");
+		fs::create_dir_all(&change).expect("create change dir");
+		fs::write(change.join("proposal.md"), "# Add authentication\n").expect("write proposal");
+		fs::write(change.join("tasks.md"), "- [x] one\n- [ ] two\n").expect("write tasks");
+
+		let mut parsed = cli_change("add-auth", 0, 0, false);
+		fill_from_tree(project.path(), &mut parsed);
+		assert_eq!(parsed.title.as_deref(), Some("Add authentication"));
+		assert_eq!((parsed.tasks_done, parsed.tasks_total), (1, 2));
+	}
+}
@@ -0,0 +1,290 @@
+use crate::{
+	changes::list_changes,
+	checklist::delta_capabilities,
+	git::git_output,
+	layout, ordering,
+	spec::{SpecError, SpecErrorPayload},
+	storage,
+};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::{Deserialize, Serialize};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	fs,
+	path::Path,
+};
+
+pub const CODE_MAP_CONFIG: &str = ".specops-code-map.json";
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CodeMap {
+	pub capabilities: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AffectedCapability {
+	capability: String,
+	files: Vec<String>,
+	spec_touched: bool,
+	covered_by: Vec<String>,
+	needs_delta: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AffectedCapabilities {
+	capabilities: Vec<AffectedCapability>,
+	unmapped: Vec<String>,
+}
+
+// Patterns follow the CODEOWNERS convention: a bare directory covers
+// everything below it, anything else is a glob relative to the project.
+fn pattern_globs(pattern: &str) -> Vec<String> {
+	let trimmed = pattern.trim().trim_start_matches('/').trim_end_matches('/');
+	if trimmed.contains(['*', '?', '[', '{']) {
+		vec![trimmed.to_string()]
+	} else {
+		vec![trimmed.to_string(), format!("{trimmed}/**")]
+	}
+}
+
+fn matcher(patterns: &[String]) -> Result<GlobSet, SpecError> {
+	let mut builder = GlobSetBuilder::new();
+	for glob in patterns.iter().flat_map(|pattern| pattern_globs(pattern)) {
+		builder.add(
+			GlobBuilder::new(&glob)
+				.literal_separator(true)
+				.build()
+				.map_err(|_| SpecError::InvalidCodeMap)?,
+		);
+	}
+	builder.build().map_err(|_| SpecError::InvalidCodeMap)
+}
+
+pub fn read_code_map(project: &Path) -> Result<CodeMap, SpecError> {
+	match fs::read(project.join(CODE_MAP_CONFIG)) {
+		Ok(bytes) => serde_json::from_slice(&bytes).map_err(|_| SpecError::InvalidCodeMap),
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(CodeMap::default()),
+		Err(error) => Err(error.into()),
+	}
+}
+
+fn write_code_map(project: &Path, map: &CodeMap) -> Result<(), SpecError> {
+	for patterns in map.capabilities.values() {
+		matcher(patterns)?;
+	}
+	let bytes = serde_json::to_vec_pretty(map).map_err(|_| SpecError::InvalidCodeMap)?;
+	storage::write_atomic(&project.join(CODE_MAP_CONFIG), &bytes)?;
+	Ok(())
+}
+
+/// Accepts either a unified diff or a plain list of paths such as
+/// `git diff --name-only` prints.
+pub fn diff_paths(diff: &str) -> Vec<String> {
+	let headers = diff
+		.lines()
+		.filter_map(|line| {
+			line.strip_prefix("+++ b/")
+				.or_else(|| line.strip_prefix("--- a/"))
+				.or_else(|| {
+					line.strip_prefix("diff --git a/")
+						.and_then(|rest| rest.rsplit_once(" b/"))
+						.map(|(_, path)| path)
+				})
+		})
+		.map(|path| path.trim().to_string())
+		.collect::<BTreeSet<_>>();
+	let paths = if headers.is_empty() {
+		diff.lines()
+			.map(str::trim)
+			.filter(|line| !line.is_empty())
+			.map(str::to_string)
+			.collect()
+	} else {
+		headers
+	};
+	paths.into_iter().collect()
+}
+
+fn working_tree_changes(project: &Path) -> Result<String, SpecError> {
+	let tracked = git_output(project, &["diff", "--name-only", "HEAD"])?;
+	let untracked = git_output(project, &["ls-files", "--others", "--exclude-standard"])?;
+	Ok(format!("{tracked}{untracked}"))
+}
+
+// Spec and delta files under the spec root name their capability in the path.
+fn spec_capability<'a>(root: &str, path: &'a str) -> Option<&'a str> {
+	let rest = path.strip_prefix(root)?.strip_prefix('/')?;
+	let rest = match rest.strip_prefix("changes/") {
+		Some(change) => change.split_once('/')?.1,
+		None => rest,
+	};
+	rest.strip_prefix("specs/")?.split('/').next()
+}
+
+pub fn affected(project: &Path, diff: &str) -> Result<AffectedCapabilities, SpecError> {
+	let map = read_code_map(project)?;
+	let matchers = map
+		.capabilities
+		.iter()
+		.map(|(capability, patterns)| Ok((capability.as_str(), matcher(patterns)?)))
+		.collect::<Result<Vec<_>, SpecError>>()?;
+	let root = layout::resolve(project).root;
+	let mut covered = BTreeMap::<String, Vec<String>>::new();
+	for change in list_changes(project, false).unwrap_or_default() {
+		for capability in delta_capabilities(Path::new(&change.path)) {
+			covered
+				.entry(capability)
+				.or_default()
+				.push(change.id.clone());
+		}
+	}
+
+	let mut files = BTreeMap::<&str, Vec<String>>::new();
+	let mut touched = BTreeSet::new();
+	let mut unmapped = Vec::new();
+	for path in diff_paths(diff) {
+		if let Some(capability) = spec_capability(&root, &path) {
+			touched.insert(capability.to_string());
+			continue;
+		}
+		if path.starts_with(&format!("{root}/")) {
+			continue;
+		}
+		let mut mapped = false;
+		for (capability, matcher) in &matchers {
+			if matcher.is_match(&path) {
+				files.entry(*capability).or_default().push(path.clone());
+				mapped = true;
+			}
+		}
+		if !mapped {
+			unmapped.push(path);
+		}
+	}
+
+	let mut capabilities = files
+		.into_iter()
+		.map(|(capability, files)| {
+			let covered_by = covered.get(capability).cloned().unwrap_or_default();
+			let spec_touched = touched.contains(capability);
+			AffectedCapability {
+				capability: capability.to_string(),
+				files,
+				needs_delta: !spec_touched && covered_by.is_empty(),
+				spec_touched,
+				covered_by,
+			}
+		})
+		.collect::<Vec<_>>();
+	capabilities.sort_by(|a, b| ordering::compare(&a.capability, &b.capability));
+	Ok(AffectedCapabilities {
+		capabilities,
+		unmapped,
+	})
+}
+
+#[tauri::command]
+pub fn capability_code_map(project: String) -> Result<CodeMap, SpecErrorPayload> {
+	read_code_map(Path::new(&project)).map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn set_capability_code_map(project: String, map: CodeMap) -> Result<CodeMap, SpecErrorPayload> {
+	write_code_map(Path::new(&project), &map)?;
+	Ok(map)
+}
+
+#[tauri::command]
+pub fn affected_capabilities(
+	project: String,
+	diff: Option<String>,
+) -> Result<AffectedCapabilities, SpecErrorPayload> {
+	let project = Path::new(&project);
+	let diff = match diff {
+		Some(diff) => diff,
+		None => working_tree_changes(project)?,
+	};
+	affected(project, &diff).map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn reads_paths_from_unified_diffs_and_name_lists() {
+		let diff = "diff --git a/src/auth/login.rs b/src/auth/login.rs\n--- a/src/auth/login.rs\n+++ b/src/auth/login.rs\n@@ -1 +1 @@\n-old\n+new\ndiff --git a/src/gone.rs b/src/gone.rs\n--- a/src/gone.rs\n+++ /dev/null\n";
+		assert_eq!(diff_paths(diff), ["src/auth/login.rs", "src/gone.rs"]);
+		assert_eq!(
+			diff_paths("src/b.rs\n\nsrc/a.rs\n"),
+			["src/a.rs", "src/b.rs"]
+		);
+		assert_eq!(
+			spec_capability("openspec", "openspec/changes/add-x/specs/auth/spec.md"),
+			Some("auth")
+		);
+		assert_eq!(
+			spec_capability("openspec", "openspec/specs/billing/spec.md"),
+			Some("billing")
+		);
+		assert_eq!(spec_capability("openspec", "openspec/project.md"), None);
+	}
+
+	#[test]
+	fn reports_capabilities_that_need_deltas() {
+		let project = TempDir::new().expect("create project dir");
+		let openspec = project.path().join("openspec");
+		fs::create_dir_all(openspec.join("changes/add-sso/specs/session")).expect("create delta");
+		write_code_map(
+			project.path(),
+			&CodeMap {
+				capabilities: BTreeMap::from([
+					("auth".to_string(), vec!["src/auth/".to_string()]),
+					(
+						"billing".to_string(),
+						vec!["src/**/invoice*.rs".to_string()],
+					),
+					("session".to_string(), vec!["src/session".to_string()]),
+				]),
+			},
+		)
+		.expect("write map");
+
+		let report = affected(
+			project.path(),
+			"src/auth/login.rs\nsrc/session/store.rs\nsrc/billing/invoice_pdf.rs\nopenspec/specs/billing/spec.md\nREADME.md\n",
+		)
+		.expect("affected");
+		let summary = report
+			.capabilities
+			.iter()
+			.map(|capability| {
+				(
+					capability.capability.as_str(),
+					capability.files.len(),
+					capability.needs_delta,
+				)
+			})
+			.collect::<Vec<_>>();
+		assert_eq!(
+			summary,
+			[
+				("auth", 1, true),
+				("billing", 1, false),
+				("session", 1, false)
+			]
+		);
+		assert_eq!(report.capabilities[2].covered_by, ["add-sso"]);
+		assert_eq!(report.unmapped, ["README.md"]);
+
+		fs::write(project.path().join(CODE_MAP_CONFIG), "[").expect("write broken map");
+		assert!(matches!(
+			read_code_map(project.path()),
+			Err(SpecError::InvalidCodeMap)
+		));
+	}
+}
@@ -0,0 +1,339 @@
+use crate::{
+	remote,
+	settings::{self, AppSettings, SettingsStore},
+	spec::{SpecError, SpecErrorPayload},
+	wsl,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{fs, path::Path, process::Command};
+use tauri::State;
+
+const DEVCONTAINER_LABEL: &str = "devcontainer.local_folder";
+const IMAGE_WORKSPACE: &str = "/workspace";
+
+/// Where OpenSpec and package-manager commands run for a project. Teams that
+/// keep Node off their hosts point this at a container instead. The choice is
+/// kept in the app settings, never in the repository, so a cloned repo cannot
+/// pick the image its commands run in.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ExecutionBackend {
+	#[default]
+	Host,
+	/// `docker exec` into the project's running devcontainer, found through
+	/// the label the devcontainer CLI sets unless a container is named.
+	Devcontainer {
+		#[serde(default)]
+		container: Option<String>,
+	},
+	/// `docker run` of a throwaway container with the repo mounted.
+	Image { image: String },
+}
+
+// Names reach `docker` as positional arguments, so anything that could be
+// read as a flag is rejected.
+fn valid_reference(reference: &str) -> bool {
+	!reference.is_empty()
+		&& !reference.starts_with('-')
+		&& !reference.chars().any(char::is_whitespace)
+}
+
+impl ExecutionBackend {
+	fn is_valid(&self) -> bool {
+		match self {
+			ExecutionBackend::Host => true,
+			ExecutionBackend::Devcontainer { container } => {
+				container.as_deref().is_none_or(valid_reference)
+			}
+			ExecutionBackend::Image { image } => valid_reference(image),
+		}
+	}
+}
+
+fn project_key(project: &Path) -> String {
+	project.to_string_lossy().to_string()
+}
+
+pub fn backend_for(settings: &AppSettings, project: &Path) -> Result<ExecutionBackend, SpecError> {
+	match settings.execution_backends.get(&project_key(project)) {
+		Some(backend) if backend.is_valid() => Ok(backend.clone()),
+		Some(_) => Err(SpecError::InvalidExecutionBackend),
+		None => Ok(ExecutionBackend::default()),
+	}
+}
+
+fn write_backend(
+	settings: &SettingsStore,
+	project: &Path,
+	backend: &ExecutionBackend,
+) -> Result<(), SpecError> {
+	if !backend.is_valid() {
+		return Err(SpecError::InvalidExecutionBackend);
+	}
+	settings.update(|current| {
+		if *backend == ExecutionBackend::Host {
+			current.execution_backends.remove(&project_key(project));
+		} else {
+			current
+				.execution_backends
+				.insert(project_key(project), backend.clone());
+		}
+	})?;
+	Ok(())
+}
+
+fn docker_output(args: &[&str]) -> Option<String> {
+	let output = Command::new("docker").args(args).output().ok()?;
+	output
+		.status
+		.success()
+		.then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn find_devcontainer(project: &Path) -> Option<String> {
+	let filter = format!("label={DEVCONTAINER_LABEL}={}", project.display());
+	docker_output(&["ps", "-q", "--filter", filter.as_str()])?
+		.lines()
+		.next()
+		.map(str::to_string)
+}
+
+pub fn container_path(root: &str, rest: &Path) -> String {
+	let rest = rest.to_string_lossy().replace('\\', "/");
+	if rest.is_empty() {
+		root.to_string()
+	} else {
+		format!("{}/{rest}", root.trim_end_matches('/'))
+	}
+}
+
+/// Reads `docker inspect` output for the folder `project` is mounted at and
+/// the `remoteUser` the devcontainer config asks commands to run as.
+pub fn parse_inspect(text: &str, project: &Path) -> Option<(String, Option<String>)> {
+	let value = serde_json::from_str::<Value>(text).ok()?;
+	let container = value.get(0)?;
+	let workdir = container["Mounts"].as_array()?.iter().find_map(|mount| {
+		let rest = project.strip_prefix(mount["Source"].as_str()?).ok()?;
+		Some(container_path(mount["Destination"].as_str()?, rest))
+	})?;
+	let user = container["Config"]["Labels"]["devcontainer.metadata"]
+		.as_str()
+		.and_then(|metadata| serde_json::from_str::<Vec<Value>>(metadata).ok())
+		.and_then(|entries| {
+			entries
+				.iter()
+				.rev()
+				.find_map(|entry| entry["remoteUser"].as_str().map(str::to_string))
+		});
+	Some((workdir, user))
+}
+
+#[cfg(unix)]
+fn owner(project: &Path) -> Option<String> {
+	use std::os::unix::fs::MetadataExt;
+	let metadata = fs::metadata(project).ok()?;
+	Some(format!("{}:{}", metadata.uid(), metadata.gid()))
+}
+
+#[cfg(not(unix))]
+fn owner(_project: &Path) -> Option<String> {
+	None
+}
+
+fn docker_args(
+	backend: ExecutionBackend,
+	project: &Path,
+) -> Result<Option<(Vec<String>, String)>, SpecError> {
+	let mut args = Vec::new();
+	let workdir = match backend {
+		ExecutionBackend::Host => return Ok(None),
+		ExecutionBackend::Devcontainer { container } => {
+			let container = container
+				.or_else(|| find_devcontainer(project))
+				.ok_or(SpecError::ContainerNotRunning)?;
+			let (workdir, user) = docker_output(&["inspect", container.as_str()])
+				.and_then(|text| parse_inspect(&text, project))
+				.ok_or(SpecError::ContainerNotRunning)?;
+			args.extend(["exec".to_string(), "-i".to_string()]);
+			args.extend(["-w".to_string(), workdir.clone()]);
+			if let Some(user) = user {
+				args.extend(["-u".to_string(), user]);
+			}
+			args.push(container);
+			workdir
+		}
+		ExecutionBackend::Image { image } => {
+			// Files the command writes into the bind mount should stay owned by
+			// whoever owns the checkout, not by the image's root user.
+			args.extend(["run", "--rm", "-i", "-v"].map(str::to_string));
+			args.push(format!("{}:{IMAGE_WORKSPACE}", project.display()));
+			args.extend(["-w".to_string(), IMAGE_WORKSPACE.to_string()]);
+			if let Some(owner) = owner(project) {
+				args.extend(["--user".to_string(), owner]);
+			}
+			args.push(image);
+			IMAGE_WORKSPACE.to_string()
+		}
+	};
+	Ok(Some((args, workdir)))
+}
+
+/// The `docker` invocation that runs `program` in the container configured
+/// for `project`, or `None` when the project runs commands on the host.
+pub fn invocation(
+	settings: &AppSettings,
+	program: &str,
+	args: &[impl AsRef<str>],
+	project: Option<&Path>,
+) -> Result<Option<(String, Vec<String>)>, SpecError> {
+	let Some(project) = project else {
+		return Ok(None);
+	};
+	let Some((mut docker, workdir)) = docker_args(backend_for(settings, project)?, project)? else {
+		return Ok(None);
+	};
+	docker.push(program.to_string());
+	docker.extend(args.iter().map(|arg| {
+		let arg = arg.as_ref();
+		match Path::new(arg).strip_prefix(project) {
+			Ok(rest) if Path::new(arg).is_absolute() => container_path(&workdir, rest),
+			_ => arg.to_string(),
+		}
+	}));
+	Ok(Some(("docker".to_string(), docker)))
+}
+
+/// Like [`invocation`], for callers that run outside a command handler and
+/// read the saved settings themselves.
+pub fn command(
+	program: &str,
+	args: &[impl AsRef<str>],
+	project: Option<&Path>,
+) -> Result<Command, SpecError> {
+	let settings = settings::load_settings().get();
+	Ok(match invocation(&settings, program, args, project)? {
+		Some((docker, docker_args)) => {
+			let mut command = Command::new(docker);
+			command.args(docker_args);
+			command
+		}
+		None => match remote::invocation(program, args, project) {
+			Some((ssh, ssh_args)) => {
+				let mut command = Command::new(ssh);
+				command.args(ssh_args);
+				command
+			}
+			None => wsl::command(program, args, project),
+		},
+	})
+}
+
+#[tauri::command]
+pub fn execution_backend(
+	settings: State<'_, SettingsStore>,
+	project: String,
+) -> Result<ExecutionBackend, SpecErrorPayload> {
+	backend_for(&settings.get(), Path::new(&project)).map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn set_execution_backend(
+	settings: State<'_, SettingsStore>,
+	project: String,
+	backend: ExecutionBackend,
+) -> Result<ExecutionBackend, SpecErrorPayload> {
+	write_backend(&settings, Path::new(&project), &backend)?;
+	Ok(backend)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn stores_backend_per_project_in_settings() {
+		let project = TempDir::new().expect("create project dir");
+		let settings = SettingsStore::load(None);
+		assert_eq!(
+			backend_for(&settings.get(), project.path()).expect("default"),
+			ExecutionBackend::Host
+		);
+		assert_eq!(
+			invocation(&settings.get(), "openspec", &["list"], Some(project.path())).expect("host"),
+			None
+		);
+
+		// A backend file committed to the repository is not a setting.
+		fs::write(
+			project.path().join(".specops-execution.json"),
+			r#"{"kind": "image", "image": "attacker/image"}"#,
+		)
+		.expect("write repo config");
+		assert_eq!(
+			backend_for(&settings.get(), project.path()).expect("ignores repo file"),
+			ExecutionBackend::Host
+		);
+
+		let backend = ExecutionBackend::Image {
+			image: "node:22-slim".to_string(),
+		};
+		write_backend(&settings, project.path(), &backend).expect("write backend");
+		assert_eq!(
+			backend_for(&settings.get(), project.path()).expect("read"),
+			backend
+		);
+		let (program, args) = invocation(
+			&settings.get(),
+			"npx",
+			&[
+				"openspec".to_string(),
+				project.path().join("openspec").display().to_string(),
+			],
+			Some(project.path()),
+		)
+		.expect("invocation")
+		.expect("docker");
+		assert_eq!(program, "docker");
+		assert_eq!(args[..3], ["run", "--rm", "-i"]);
+		assert_eq!(
+			args[args.len() - 3..],
+			["npx", "openspec", "/workspace/openspec"]
+		);
+
+		assert!(matches!(
+			write_backend(
+				&settings,
+				project.path(),
+				&ExecutionBackend::Image {
+					image: "--privileged".to_string()
+				}
+			),
+			Err(SpecError::InvalidExecutionBackend)
+		));
+		write_backend(&settings, project.path(), &ExecutionBackend::Host).expect("reset");
+		assert!(settings.get().execution_backends.is_empty());
+	}
+
+	#[test]
+	fn reads_devcontainer_workspace_from_inspect_output() {
+		let inspect = r#"[{
+			"Mounts": [
+				{"Source": "/var/lib/docker/volumes/x", "Destination": "/home/node/.cache"},
+				{"Source": "/home/dev/repo", "Destination": "/workspaces/repo"}
+			],
+			"Config": {"Labels": {
+				"devcontainer.metadata": "[{\"id\":\"node\"},{\"remoteUser\":\"node\"}]"
+			}}
+		}]"#;
+		assert_eq!(
+			parse_inspect(inspect, Path::new("/home/dev/repo/packages/app")),
+			Some((
+				"/workspaces/repo/packages/app".to_string(),
+				Some("node".to_string())
+			))
+		);
+		assert_eq!(parse_inspect(inspect, Path::new("/home/dev/other")), None);
+	}
+}
@@ -0,0 +1,387 @@
+use crate::{
+	settings::SettingsStore,
+	spec::{SpecError, SpecErrorPayload},
+	storage::{self, StorageError},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+	any::Any,
+	backtrace::Backtrace,
+	fs,
+	panic::{self, Location},
+	path::{Path, PathBuf},
+	sync::atomic::{AtomicBool, Ordering},
+	thread,
+};
+use tauri::State;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CrashKind {
+	Panic,
+	Signal,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+	pub id: String,
+	pub kind: CrashKind,
+	pub created_at: u64,
+	pub message: String,
+	pub location: Option<String>,
+	pub thread: Option<String>,
+	pub app_version: String,
+	pub os: String,
+	pub arch: String,
+	pub backtrace: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReportSummary {
+	id: String,
+	kind: CrashKind,
+	created_at: u64,
+	message: String,
+}
+
+fn crash_root() -> Result<PathBuf, SpecError> {
+	storage::specops_home()
+		.map(|home| home.join("crashes"))
+		.ok_or(SpecError::Storage(StorageError::Unavailable))
+}
+
+fn report(kind: CrashKind, message: String) -> CrashReport {
+	CrashReport {
+		id: storage::new_id("crash"),
+		kind,
+		created_at: storage::now_secs(),
+		message,
+		location: None,
+		thread: None,
+		app_version: env!("CARGO_PKG_VERSION").to_string(),
+		os: std::env::consts::OS.to_string(),
+		arch: std::env::consts::ARCH.to_string(),
+		backtrace: String::new(),
+	}
+}
+
+fn panic_report(
+	payload: &(dyn Any + Send),
+	location: Option<&Location<'_>>,
+	backtrace: &Backtrace,
+) -> CrashReport {
+	let message = payload
+		.downcast_ref::<&str>()
+		.map(|message| message.to_string())
+		.or_else(|| payload.downcast_ref::<String>().cloned())
+		.unwrap_or_else(|| "Box<dyn Any>".to_string());
+	CrashReport {
+		location: location.map(|location| {
+			format!(
+				"{}:{}:{}",
+				location.file(),
+				location.line(),
+				location.column()
+			)
+		}),
+		thread: thread::current().name().map(str::to_string),
+		backtrace: backtrace.to_string(),
+		..report(CrashKind::Panic, message)
+	}
+}
+
+/// Reports for crashes that never reach the panic hook: segfaults, aborts
+/// and other fatal signals from native code. The handler may only make
+/// async-signal-safe calls, so the report is rendered up front and the
+/// handler just appends the timestamp and writes it out.
+#[cfg(unix)]
+mod native {
+	use super::{report, CrashKind, ENABLED};
+	use std::{
+		ffi::CString,
+		mem,
+		os::unix::ffi::OsStrExt,
+		path::Path,
+		ptr,
+		sync::{atomic::Ordering, OnceLock},
+	};
+
+	const SIGNALS: [(libc::c_int, &str); 5] = [
+		(libc::SIGSEGV, "SIGSEGV"),
+		(libc::SIGBUS, "SIGBUS"),
+		(libc::SIGILL, "SIGILL"),
+		(libc::SIGFPE, "SIGFPE"),
+		(libc::SIGABRT, "SIGABRT"),
+	];
+
+	struct Handler {
+		dir: CString,
+		path: CString,
+		// One per entry in SIGNALS, ending right before the timestamp.
+		templates: Vec<Vec<u8>>,
+		previous: Vec<libc::sigaction>,
+	}
+
+	static HANDLER: OnceLock<Handler> = OnceLock::new();
+
+	/// The report for `signal` as JSON, cut off where `createdAt` goes.
+	pub(super) fn template(id: &str, signal: &str) -> Option<Vec<u8>> {
+		let report = super::CrashReport {
+			id: id.to_string(),
+			..report(CrashKind::Signal, format!("Terminated by {signal}"))
+		};
+		let serde_json::Value::Object(mut fields) = serde_json::to_value(report).ok()? else {
+			return None;
+		};
+		fields.remove("createdAt");
+		let mut bytes = serde_json::to_vec(&fields).ok()?;
+		bytes.pop();
+		bytes.extend_from_slice(b",\"createdAt\":");
+		Some(bytes)
+	}
+
+	pub(super) fn decimal(mut value: u64, digits: &mut [u8; 20]) -> &[u8] {
+		let mut start = digits.len();
+		loop {
+			start -= 1;
+			digits[start] = b'0' + (value % 10) as u8;
+			value /= 10;
+			if value == 0 {
+				return &digits[start..];
+			}
+		}
+	}
+
+	impl Handler {
+		fn record(&self, index: usize) {
+			let mut digits = [0; 20];
+			// SAFETY: time, mkdir, open, write and close are async-signal-safe,
+			// and every buffer was allocated before the handler was installed.
+			unsafe {
+				let timestamp = decimal(libc::time(ptr::null_mut()).max(0) as u64, &mut digits);
+				libc::mkdir(self.dir.as_ptr(), 0o700);
+				let fd = libc::open(
+					self.path.as_ptr(),
+					libc::O_WRONLY | libc::O_CREAT | libc::O_EXCL,
+					0o600,
+				);
+				if fd < 0 {
+					return;
+				}
+				for part in [&self.templates[index][..], timestamp, b"}"] {
+					libc::write(fd, part.as_ptr().cast(), part.len());
+				}
+				libc::close(fd);
+			}
+		}
+	}
+
+	extern "C" fn on_signal(signal: libc::c_int) {
+		let Some(handler) = HANDLER.get() else {
+			return;
+		};
+		let Some(index) = SIGNALS.iter().position(|(number, _)| *number == signal) else {
+			return;
+		};
+		if ENABLED.load(Ordering::SeqCst) {
+			handler.record(index);
+		}
+		// Put the previous disposition back. A fault re-runs the faulting
+		// instruction on return and reaches it, which keeps std's stack
+		// overflow message and the default core dump; an abort is raised
+		// again explicitly.
+		// SAFETY: sigaction and raise are async-signal-safe.
+		unsafe {
+			libc::sigaction(signal, &handler.previous[index], ptr::null_mut());
+			if signal == libc::SIGABRT {
+				libc::raise(signal);
+			}
+		}
+	}
+
+	pub fn install(root: &Path, id: &str) {
+		let Some(templates) = SIGNALS
+			.iter()
+			.map(|(_, name)| template(id, name))
+			.collect::<Option<Vec<_>>>()
+		else {
+			return;
+		};
+		let (Ok(dir), Ok(path)) = (
+			CString::new(root.as_os_str().as_bytes()),
+			CString::new(root.join(format!("{id}.json")).as_os_str().as_bytes()),
+		) else {
+			return;
+		};
+		// SAFETY: querying the current actions only reads into zeroed structs.
+		let previous = SIGNALS
+			.iter()
+			.map(|(signal, _)| unsafe {
+				let mut action = mem::zeroed();
+				libc::sigaction(*signal, ptr::null(), &mut action);
+				action
+			})
+			.collect();
+		if HANDLER
+			.set(Handler {
+				dir,
+				path,
+				templates,
+				previous,
+			})
+			.is_err()
+		{
+			return;
+		}
+		// SAFETY: the handler only touches HANDLER, which is set above and
+		// never changes again. SA_ONSTACK lets it run on std's alternate
+		// stack after a stack overflow.
+		unsafe {
+			let mut action: libc::sigaction = mem::zeroed();
+			action.sa_sigaction = on_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+			action.sa_flags = libc::SA_ONSTACK;
+			libc::sigemptyset(&mut action.sa_mask);
+			for (signal, _) in SIGNALS {
+				libc::sigaction(signal, &action, ptr::null_mut());
+			}
+		}
+	}
+}
+
+fn write_report_in(root: &Path, report: &CrashReport) -> Result<(), SpecError> {
+	let bytes = serde_json::to_vec_pretty(report).map_err(StorageError::from)?;
+	storage::write_atomic(&root.join(format!("{}.json", report.id)), &bytes)?;
+	Ok(())
+}
+
+fn list_in(root: &Path) -> Vec<CrashReport> {
+	let mut reports = fs::read_dir(root)
+		.map(|entries| {
+			entries
+				.filter_map(Result::ok)
+				.filter_map(|entry| fs::read(entry.path()).ok())
+				.filter_map(|bytes| serde_json::from_slice::<CrashReport>(&bytes).ok())
+				.collect::<Vec<_>>()
+		})
+		.unwrap_or_default();
+	reports.sort_by(|a, b| (b.created_at, &b.id).cmp(&(a.created_at, &a.id)));
+	reports
+}
+
+fn read_in(root: &Path, id: &str) -> Result<String, SpecError> {
+	if id.contains(['/', '\\']) || id.starts_with('.') {
+		return Err(SpecError::CrashReportNotFound);
+	}
+	match fs::read_to_string(root.join(format!("{id}.json"))) {
+		Ok(content) => Ok(content),
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+			Err(SpecError::CrashReportNotFound)
+		}
+		Err(error) => Err(error.into()),
+	}
+}
+
+pub fn install(enabled: bool) {
+	ENABLED.store(enabled, Ordering::SeqCst);
+	let previous = panic::take_hook();
+	panic::set_hook(Box::new(move |info| {
+		if ENABLED.load(Ordering::SeqCst) {
+			let report = panic_report(info.payload(), info.location(), &Backtrace::force_capture());
+			if let Ok(root) = crash_root() {
+				let _ = write_report_in(&root, &report);
+			}
+		}
+		previous(info);
+	}));
+	#[cfg(unix)]
+	if let Ok(root) = crash_root() {
+		native::install(&root, &storage::new_id("crash"));
+	}
+}
+
+#[tauri::command]
+pub fn set_crash_reporting(
+	settings: State<'_, SettingsStore>,
+	enabled: bool,
+) -> Result<bool, SpecErrorPayload> {
+	settings
+		.update(|current| current.crash_reporting = enabled)
+		.map_err(|error| SpecErrorPayload::from(SpecError::from(error)))?;
+	ENABLED.store(enabled, Ordering::SeqCst);
+	Ok(enabled)
+}
+
+#[tauri::command]
+pub fn list_crash_reports() -> Result<Vec<CrashReportSummary>, SpecErrorPayload> {
+	Ok(list_in(&crash_root()?)
+		.into_iter()
+		.map(|report| CrashReportSummary {
+			id: report.id,
+			kind: report.kind,
+			created_at: report.created_at,
+			message: report.message,
+		})
+		.collect())
+}
+
+#[tauri::command]
+pub fn export_crash_report(id: String) -> Result<String, SpecErrorPayload> {
+	read_in(&crash_root()?, &id).map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn stores_panic_reports_and_exports_them_by_id() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		// Built from a caught panic so the process-wide hook, which other
+		// tests share, is never swapped out.
+		let report = thread::Builder::new()
+			.name("indexer".to_string())
+			.spawn(|| {
+				let payload = panic::catch_unwind(|| panic!("index corrupted")).expect_err("panic");
+				panic_report(&*payload, Some(Location::caller()), &Backtrace::disabled())
+			})
+			.expect("spawn")
+			.join()
+			.expect("join");
+		assert_eq!(report.message, "index corrupted");
+		assert_eq!(report.thread.as_deref(), Some("indexer"));
+		assert!(report
+			.location
+			.as_deref()
+			.is_some_and(|location| location.contains("crash.rs")));
+
+		write_report_in(temp_dir.path(), &report).expect("write");
+		assert_eq!(list_in(temp_dir.path()).len(), 1);
+		let exported = read_in(temp_dir.path(), &report.id).expect("export");
+		assert!(exported.contains("index corrupted"));
+		assert!(matches!(
+			read_in(temp_dir.path(), "../settings"),
+			Err(SpecError::CrashReportNotFound)
+		));
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn native_reports_read_back_like_panic_reports() {
+		let mut digits = [0; 20];
+		assert_eq!(native::decimal(0, &mut digits), b"0");
+		let mut bytes = native::template("crash-native", "SIGSEGV").expect("template");
+		bytes.extend_from_slice(native::decimal(1_760_000_000, &mut digits));
+		bytes.push(b'}');
+
+		let report = serde_json::from_slice::<CrashReport>(&bytes).expect("parse report");
+		assert_eq!(report.id, "crash-native");
+		assert_eq!(report.kind, CrashKind::Signal);
+		assert_eq!(report.created_at, 1_760_000_000);
+		assert_eq!(report.message, "Terminated by SIGSEGV");
+	}
+}
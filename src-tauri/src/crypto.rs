@@ -0,0 +1,172 @@
+use crate::{
+	journal::{self, journal_root},
+	secrets::keychain_entry,
+	settings::SettingsStore,
+	snapshots::{self, snapshots_root},
+	spec::{SpecError, SpecErrorPayload},
+	storage::project_key,
+};
+use aes_gcm::{
+	aead::{Aead, AeadCore, KeyInit, OsRng},
+	Aes256Gcm, Nonce,
+};
+use serde::Serialize;
+use std::path::Path;
+use tauri::State;
+
+const MAGIC: &[u8] = b"SPOENC1\0";
+const NONCE_LEN: usize = 12;
+
+#[derive(Clone)]
+pub struct ArtifactCipher(Aes256Gcm);
+
+impl ArtifactCipher {
+	pub fn from_key(key: &[u8]) -> Result<Self, SpecError> {
+		Aes256Gcm::new_from_slice(key)
+			.map(Self)
+			.map_err(|_| SpecError::Encryption)
+	}
+
+	pub fn seal(&self, plaintext: &[u8]) -> Result<Vec<u8>, SpecError> {
+		let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+		let ciphertext = self
+			.0
+			.encrypt(&nonce, plaintext)
+			.map_err(|_| SpecError::Encryption)?;
+		Ok([MAGIC, nonce.as_slice(), &ciphertext].concat())
+	}
+
+	/// Opens sealed data. Plaintext is refused rather than passed through, so
+	/// a file swapped on disk cannot stand in for an encrypted artifact.
+	/// Migrations tell sealed from plaintext artifacts by their own records.
+	pub fn open(&self, data: &[u8]) -> Result<Vec<u8>, SpecError> {
+		let Some(sealed) = data.strip_prefix(MAGIC) else {
+			return Err(SpecError::Encryption);
+		};
+		if sealed.len() < NONCE_LEN {
+			return Err(SpecError::Encryption);
+		}
+		let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+		self.0
+			.decrypt(Nonce::from_slice(nonce), ciphertext)
+			.map_err(|_| SpecError::Encryption)
+	}
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EncryptionStatus {
+	project: String,
+	enabled: bool,
+}
+
+fn project_key_entry(project: &Path) -> Result<keyring::Entry, SpecError> {
+	keychain_entry(&format!("artifact-key:{}", project_key(project)))
+}
+
+fn load_or_create_cipher(project: &Path) -> Result<ArtifactCipher, SpecError> {
+	let entry = project_key_entry(project)?;
+	match entry.get_secret() {
+		Ok(key) => ArtifactCipher::from_key(&key),
+		Err(keyring::Error::NoEntry) => {
+			let key = Aes256Gcm::generate_key(OsRng);
+			entry
+				.set_secret(key.as_slice())
+				.map_err(|_| SpecError::KeychainUnavailable)?;
+			ArtifactCipher::from_key(key.as_slice())
+		}
+		Err(_) => Err(SpecError::KeychainUnavailable),
+	}
+}
+
+fn is_encrypted(settings: &SettingsStore, project: &Path) -> bool {
+	let project = project.to_string_lossy();
+	settings
+		.get()
+		.encrypted_projects
+		.iter()
+		.any(|path| *path == project)
+}
+
+pub fn project_cipher(
+	settings: &SettingsStore,
+	project: &Path,
+) -> Result<Option<ArtifactCipher>, SpecError> {
+	if is_encrypted(settings, project) {
+		load_or_create_cipher(project).map(Some)
+	} else {
+		Ok(None)
+	}
+}
+
+fn set_encryption(
+	settings: &SettingsStore,
+	project: &Path,
+	enabled: bool,
+) -> Result<EncryptionStatus, SpecError> {
+	let path = project.to_string_lossy().to_string();
+	if enabled != is_encrypted(settings, project) {
+		let cipher = load_or_create_cipher(project)?;
+		let (from, to) = if enabled {
+			(None, Some(&cipher))
+		} else {
+			(Some(&cipher), None)
+		};
+		snapshots::transcode_snapshots_in(&snapshots_root()?, project, from, to)?;
+		journal::transcode_in(&journal_root()?, project, from, to)?;
+		settings.update(|settings| {
+			settings.encrypted_projects.retain(|entry| *entry != path);
+			if enabled {
+				settings.encrypted_projects.push(path.clone());
+			}
+		})?;
+		if !enabled {
+			let _ = project_key_entry(project)?.delete_credential();
+		}
+	}
+	Ok(EncryptionStatus {
+		project: path,
+		enabled,
+	})
+}
+
+#[tauri::command]
+pub fn project_encryption_status(
+	settings: State<'_, SettingsStore>,
+	project: String,
+) -> EncryptionStatus {
+	EncryptionStatus {
+		enabled: is_encrypted(&settings, Path::new(&project)),
+		project,
+	}
+}
+
+#[tauri::command]
+pub fn set_project_encryption(
+	settings: State<'_, SettingsStore>,
+	project: String,
+	enabled: bool,
+) -> Result<EncryptionStatus, SpecErrorPayload> {
+	set_encryption(&settings, Path::new(&project), enabled).map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn seals_and_opens_artifacts() {
+		let cipher = ArtifactCipher::from_key(&[7u8; 32]).expect("cipher");
+		let sealed = cipher.seal(b"proprietary spec").expect("seal");
+		assert!(sealed.starts_with(MAGIC));
+		assert_ne!(sealed, cipher.seal(b"proprietary spec").expect("seal"));
+		assert_eq!(cipher.open(&sealed).expect("open"), b"proprietary spec");
+		assert!(matches!(cipher.open(b"plain"), Err(SpecError::Encryption)));
+
+		let mut tampered = sealed.clone();
+		*tampered.last_mut().expect("byte") ^= 1;
+		assert!(matches!(cipher.open(&tampered), Err(SpecError::Encryption)));
+		let other = ArtifactCipher::from_key(&[8u8; 32]).expect("cipher");
+		assert!(other.open(&sealed).is_err());
+	}
+}
@@ -0,0 +1,291 @@
+use crate::{
+	changes::{list_changes, read_metadata, update_metadata, ChangeState},
+	ordering, power,
+	projects::RecentProjects,
+	settings::{DeadlineReminders, SettingsStore},
+	spec::{SpecError, SpecErrorPayload},
+	storage, supervisor,
+};
+use serde::Serialize;
+use std::{
+	collections::HashSet,
+	path::{Path, PathBuf},
+	sync::Mutex,
+	thread,
+	time::Duration,
+};
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_notification::NotificationExt;
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+const DEFAULT_WINDOW_DAYS: u32 = 14;
+const REMINDER_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Deadline {
+	change_id: String,
+	state: ChangeState,
+	target_date: String,
+	days_remaining: i64,
+}
+
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+	let year = if month <= 2 { year - 1 } else { year };
+	let era = year.div_euclid(400);
+	let year_of_era = year - era * 400;
+	let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+	let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+	era * 146_097 + day_of_era - 719_468
+}
+
+pub fn parse_date(value: &str) -> Option<i64> {
+	let mut parts = value.trim().splitn(3, '-');
+	let year: i64 = parts.next().filter(|year| year.len() == 4)?.parse().ok()?;
+	let month: i64 = parts
+		.next()
+		.filter(|month| month.len() == 2)?
+		.parse()
+		.ok()
+		.filter(|month| (1..=12).contains(month))?;
+	let day: i64 = parts.next().filter(|day| day.len() == 2)?.parse().ok()?;
+	let next_month = if month == 12 {
+		days_from_civil(year + 1, 1, 1)
+	} else {
+		days_from_civil(year, month + 1, 1)
+	};
+	let first = days_from_civil(year, month, 1);
+	(day >= 1 && day <= next_month - first).then(|| first + day - 1)
+}
+
+pub fn format_date(days: i64) -> String {
+	let shifted = days + 719_468;
+	let era = shifted.div_euclid(146_097);
+	let day_of_era = shifted - era * 146_097;
+	let year_of_era =
+		(day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+	let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let month_index = (5 * day_of_year + 2) / 153;
+	let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+	let month = if month_index < 10 {
+		month_index + 3
+	} else {
+		month_index - 9
+	};
+	let year = year_of_era + era * 400 + i64::from(month <= 2);
+	format!("{year:04}-{month:02}-{day:02}")
+}
+
+fn today() -> i64 {
+	(storage::now_secs() / DAY_SECS) as i64
+}
+
+fn deadlines(project: &Path, within_days: u32, today: i64) -> Result<Vec<Deadline>, SpecError> {
+	let mut deadlines = Vec::new();
+	for change in list_changes(project, false)? {
+		let target_date = match read_metadata(project, &change.id) {
+			Ok(metadata) => metadata.target_date,
+			// One unreadable metadata file should not hide every other deadline.
+			Err(SpecError::InvalidChangeMetadata) => None,
+			Err(error) => return Err(error),
+		};
+		let Some(target_date) = target_date else {
+			continue;
+		};
+		let Some(target) = parse_date(&target_date) else {
+			continue;
+		};
+		let days_remaining = target - today;
+		if days_remaining <= i64::from(within_days) {
+			deadlines.push(Deadline {
+				change_id: change.id,
+				state: change.state,
+				target_date,
+				days_remaining,
+			});
+		}
+	}
+	deadlines.sort_by(|a, b| {
+		a.days_remaining
+			.cmp(&b.days_remaining)
+			.then_with(|| ordering::compare(&a.change_id, &b.change_id))
+	});
+	Ok(deadlines)
+}
+
+fn reminder_text(deadline: &Deadline) -> String {
+	match deadline.days_remaining {
+		days if days < 0 => format!("{} is {} day(s) overdue", deadline.change_id, -days),
+		0 => format!("{} is due today", deadline.change_id),
+		days => format!("{} is due in {days} day(s)", deadline.change_id),
+	}
+}
+
+fn due_reminders(
+	sent: &mut HashSet<String>,
+	project: &Path,
+	policy: DeadlineReminders,
+	today: i64,
+) -> Vec<Deadline> {
+	deadlines(project, policy.lead_days, today)
+		.unwrap_or_default()
+		.into_iter()
+		.filter(|deadline| deadline.state != ChangeState::Complete)
+		.filter(|deadline| {
+			sent.insert(format!(
+				"{}\0{}\0{}\0{today}",
+				project.display(),
+				deadline.change_id,
+				deadline.target_date
+			))
+		})
+		.collect()
+}
+
+pub fn spawn_reminders(app: AppHandle) {
+	let sent = Mutex::new(HashSet::new());
+	supervisor::supervise(app.clone(), "deadlines".to_string(), move || loop {
+		let settings = app.state::<SettingsStore>();
+		let policy = settings.get().deadline_reminders;
+		if policy.enabled && !power::low_power(&settings) {
+			let projects = app
+				.state::<RecentProjects>()
+				.get()
+				.into_iter()
+				.filter(|project| project.openspec_present)
+				.collect::<Vec<_>>();
+			for project in projects {
+				let due = match sent.lock() {
+					Ok(mut sent) => {
+						due_reminders(&mut sent, &PathBuf::from(&project.path), policy, today())
+					}
+					Err(_) => Vec::new(),
+				};
+				for deadline in due {
+					let _ = app
+						.notification()
+						.builder()
+						.title(format!("{} deadline", project.name))
+						.body(reminder_text(&deadline))
+						.show();
+				}
+			}
+		}
+		thread::sleep(REMINDER_INTERVAL);
+	});
+}
+
+#[tauri::command]
+pub fn set_target_date(
+	project: String,
+	change_id: String,
+	target_date: Option<String>,
+) -> Result<Option<String>, SpecErrorPayload> {
+	let target_date = target_date
+		.map(|date| date.trim().to_string())
+		.filter(|date| !date.is_empty());
+	if target_date
+		.as_deref()
+		.is_some_and(|date| parse_date(date).is_none())
+	{
+		return Err(SpecError::InvalidTargetDate.into());
+	}
+	update_metadata(Path::new(&project), &change_id, |metadata| {
+		metadata.target_date = target_date.clone()
+	})?;
+	Ok(target_date)
+}
+
+#[tauri::command]
+pub fn upcoming_deadlines(
+	project: String,
+	within_days: Option<u32>,
+) -> Result<Vec<Deadline>, SpecErrorPayload> {
+	deadlines(
+		Path::new(&project),
+		within_days.unwrap_or(DEFAULT_WINDOW_DAYS),
+		today(),
+	)
+	.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn deadline_reminders(settings: State<'_, SettingsStore>) -> DeadlineReminders {
+	settings.get().deadline_reminders
+}
+
+#[tauri::command]
+pub fn set_deadline_reminders(
+	settings: State<'_, SettingsStore>,
+	reminders: DeadlineReminders,
+) -> Result<DeadlineReminders, SpecErrorPayload> {
+	settings
+		.update(|current| current.deadline_reminders = reminders)
+		.map_err(|error| SpecErrorPayload::from(SpecError::from(error)))?;
+	Ok(reminders)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs;
+	use tempfile::TempDir;
+
+	#[test]
+	fn parses_calendar_dates() {
+		assert_eq!(parse_date("1970-01-01"), Some(0));
+		assert_eq!(parse_date("2024-02-29"), Some(19_782));
+		assert_eq!(parse_date("2023-02-29"), None);
+		assert_eq!(parse_date("2024-13-01"), None);
+		assert_eq!(parse_date("24-1-1"), None);
+		assert_eq!(format_date(0), "1970-01-01");
+		assert_eq!(format_date(19_782), "2024-02-29");
+		assert_eq!(format_date(20_710), "2026-09-14");
+	}
+
+	#[test]
+	fn lists_and_reminds_upcoming_deadlines_once_per_day() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let project = temp_dir.path();
+		for (change, date) in [
+			("add-login", Some("2024-03-05")),
+			("add-billing", Some("2024-02-20")),
+			("add-search", Some("2024-06-01")),
+			("add-export", None),
+		] {
+			fs::create_dir_all(project.join("openspec/changes").join(change))
+				.expect("create change");
+			update_metadata(project, change, |metadata| {
+				metadata.target_date = date.map(str::to_string)
+			})
+			.expect("write metadata");
+		}
+		let today = parse_date("2024-03-01").expect("date");
+
+		let upcoming = deadlines(project, 14, today).expect("deadlines");
+		assert_eq!(
+			upcoming
+				.iter()
+				.map(|deadline| (deadline.change_id.as_str(), deadline.days_remaining))
+				.collect::<Vec<_>>(),
+			vec![("add-billing", -10), ("add-login", 4)]
+		);
+		assert_eq!(
+			reminder_text(&upcoming[0]),
+			"add-billing is 10 day(s) overdue"
+		);
+
+		let policy = DeadlineReminders {
+			enabled: true,
+			lead_days: 3,
+		};
+		let mut sent = HashSet::new();
+		let due = due_reminders(&mut sent, project, policy, today);
+		assert_eq!(due.len(), 1);
+		assert!(due_reminders(&mut sent, project, policy, today).is_empty());
+		assert_eq!(
+			due_reminders(&mut sent, project, policy, today + 1).len(),
+			2
+		);
+	}
+}
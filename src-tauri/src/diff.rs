@@ -0,0 +1,232 @@
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Edit {
+	Keep,
+	Remove,
+	Insert,
+}
+
+const CONTEXT: usize = 3;
+
+fn common_prefix(old: &[&str], new: &[&str]) -> usize {
+	old.iter()
+		.zip(new.iter())
+		.take_while(|(left, right)| left == right)
+		.count()
+}
+
+fn common_suffix(old: &[&str], new: &[&str]) -> usize {
+	old.iter()
+		.rev()
+		.zip(new.iter().rev())
+		.take_while(|(left, right)| left == right)
+		.count()
+}
+
+/// Finds the middle snake of Myers' O(ND) algorithm: a run of equal lines
+/// that some shortest edit script passes through halfway. Only two diagonal
+/// vectors are kept, so memory stays linear in the input.
+fn middle_snake(old: &[&str], new: &[&str]) -> (usize, usize, usize, usize) {
+	let (n, m) = (old.len() as isize, new.len() as isize);
+	let delta = n - m;
+	let odd = delta % 2 != 0;
+	let offset = n + m + 1;
+	let mut forward = vec![0isize; (2 * offset + 1) as usize];
+	let mut backward = forward.clone();
+	for d in 0..=(n + m + 1) / 2 {
+		for k in (-d..=d).step_by(2) {
+			let at = (k + offset) as usize;
+			let mut x = if k == -d || (k != d && forward[at - 1] < forward[at + 1]) {
+				forward[at + 1]
+			} else {
+				forward[at - 1] + 1
+			};
+			let (start_x, start_y) = (x, x - k);
+			while x < n && x - k < m && old[x as usize] == new[(x - k) as usize] {
+				x += 1;
+			}
+			forward[at] = x;
+			if odd && (delta - k).abs() < d && x + backward[(delta - k + offset) as usize] >= n {
+				return (
+					start_x as usize,
+					start_y as usize,
+					x as usize,
+					(x - k) as usize,
+				);
+			}
+		}
+		for k in (-d..=d).step_by(2) {
+			let at = (k + offset) as usize;
+			let mut x = if k == -d || (k != d && backward[at - 1] < backward[at + 1]) {
+				backward[at + 1]
+			} else {
+				backward[at - 1] + 1
+			};
+			let (start_x, start_y) = (x, x - k);
+			while x < n && x - k < m && old[(n - 1 - x) as usize] == new[(m - 1 - (x - k)) as usize]
+			{
+				x += 1;
+			}
+			backward[at] = x;
+			if !odd && (delta - k).abs() <= d && x + forward[(delta - k + offset) as usize] >= n {
+				return (
+					(n - x) as usize,
+					(m - (x - k)) as usize,
+					(n - start_x) as usize,
+					(m - start_y) as usize,
+				);
+			}
+		}
+	}
+	unreachable!("the forward and backward searches always meet")
+}
+
+fn diff_range(old: &[&str], new: &[&str], edits: &mut Vec<Edit>) {
+	let prefix = common_prefix(old, new);
+	let suffix = common_suffix(&old[prefix..], &new[prefix..]);
+	let old_mid = &old[prefix..old.len() - suffix];
+	let new_mid = &new[prefix..new.len() - suffix];
+	edits.extend(std::iter::repeat_n(Edit::Keep, prefix));
+	if old_mid.is_empty() || new_mid.is_empty() {
+		edits.extend(std::iter::repeat_n(Edit::Remove, old_mid.len()));
+		edits.extend(std::iter::repeat_n(Edit::Insert, new_mid.len()));
+	} else {
+		// With the common ends trimmed, both halves around the snake are
+		// strictly smaller than the whole, so the recursion terminates.
+		let (x, y, u, v) = middle_snake(old_mid, new_mid);
+		diff_range(&old_mid[..x], &new_mid[..y], edits);
+		edits.extend(std::iter::repeat_n(Edit::Keep, u - x));
+		diff_range(&old_mid[u..], &new_mid[v..], edits);
+	}
+	edits.extend(std::iter::repeat_n(Edit::Keep, suffix));
+}
+
+fn edit_script(old: &[&str], new: &[&str]) -> Vec<Edit> {
+	let mut edits = Vec::with_capacity(old.len().max(new.len()));
+	diff_range(old, new, &mut edits);
+	edits
+}
+
+pub fn unified_diff(old: &str, new: &str, label: &str) -> String {
+	if old == new {
+		return String::new();
+	}
+	let old_lines = old.lines().collect::<Vec<_>>();
+	let new_lines = new.lines().collect::<Vec<_>>();
+	let edits = edit_script(&old_lines, &new_lines);
+
+	let mut rendered = Vec::with_capacity(edits.len());
+	let (mut old_index, mut new_index) = (0, 0);
+	for edit in &edits {
+		match edit {
+			Edit::Keep => {
+				rendered.push(format!(" {}", old_lines[old_index]));
+				old_index += 1;
+				new_index += 1;
+			}
+			Edit::Remove => {
+				rendered.push(format!("-{}", old_lines[old_index]));
+				old_index += 1;
+			}
+			Edit::Insert => {
+				rendered.push(format!("+{}", new_lines[new_index]));
+				new_index += 1;
+			}
+		}
+	}
+
+	let mut output = format!("--- a/{label}\n+++ b/{label}\n");
+	let mut index = 0;
+	while index < edits.len() {
+		if edits[index] == Edit::Keep {
+			index += 1;
+			continue;
+		}
+		let start = index.saturating_sub(CONTEXT);
+		let mut end = index;
+		let mut keep_run = 0;
+		while end < edits.len() && keep_run <= CONTEXT * 2 {
+			if edits[end] == Edit::Keep {
+				keep_run += 1;
+			} else {
+				keep_run = 0;
+			}
+			end += 1;
+		}
+		let end = (end - keep_run + CONTEXT).min(edits.len());
+
+		let old_start = edits[..start]
+			.iter()
+			.filter(|edit| **edit != Edit::Insert)
+			.count();
+		let new_start = edits[..start]
+			.iter()
+			.filter(|edit| **edit != Edit::Remove)
+			.count();
+		let old_count = edits[start..end]
+			.iter()
+			.filter(|edit| **edit != Edit::Insert)
+			.count();
+		let new_count = edits[start..end]
+			.iter()
+			.filter(|edit| **edit != Edit::Remove)
+			.count();
+		output.push_str(&format!(
+			"@@ -{},{} +{},{} @@\n",
+			old_start + 1,
+			old_count,
+			new_start + 1,
+			new_count
+		));
+		for line in &rendered[start..end] {
+			output.push_str(line);
+			output.push('\n');
+		}
+		index = end;
+	}
+	output
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn identical_inputs_produce_empty_diff() {
+		assert!(unified_diff("a\nb\n", "a\nb\n", "spec.md").is_empty());
+	}
+
+	#[test]
+	fn renders_single_hunk() {
+		let diff = unified_diff("a\nb\nc\n", "a\nB\nc\n", "spec.md");
+		assert_eq!(
+			diff,
+			"--- a/spec.md\n+++ b/spec.md\n@@ -1,3 +1,3 @@\n a\n-b\n+B\n c\n"
+		);
+	}
+
+	#[test]
+	fn splits_distant_changes_into_hunks() {
+		let old = (1..=20)
+			.map(|n| n.to_string())
+			.collect::<Vec<_>>()
+			.join("\n");
+		let new = old.replace("\n2\n", "\ntwo\n").replace("19", "nineteen");
+		let diff = unified_diff(&old, &new, "spec.md");
+		assert_eq!(diff.matches("@@ -").count(), 2);
+	}
+
+	#[test]
+	fn diffs_large_inputs_without_a_quadratic_table() {
+		let old = (0..200_000)
+			.map(|n| format!("line {n}"))
+			.collect::<Vec<_>>()
+			.join("\n");
+		let new = old
+			.replace("line 1000\n", "changed 1000\n")
+			.replace("line 150000\n", "");
+		let diff = unified_diff(&old, &new, "spec.md");
+		assert_eq!(diff.matches("@@ -").count(), 2);
+		assert_eq!(diff.lines().filter(|line| line.starts_with('-')).count(), 3);
+		assert_eq!(diff.lines().filter(|line| line.starts_with('+')).count(), 2);
+	}
+}
@@ -0,0 +1,144 @@
+use crate::spec::openspec_root;
+use std::{
+	collections::HashMap,
+	fs,
+	path::{Path, PathBuf},
+	sync::Mutex,
+	time::SystemTime,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct DiscoveryKey {
+	head: Option<String>,
+	openspec_modified: Option<SystemTime>,
+}
+
+struct CachedDiscovery<T> {
+	repo: PathBuf,
+	key: DiscoveryKey,
+	value: T,
+}
+
+pub struct DiscoveryCache<T>(Mutex<HashMap<PathBuf, CachedDiscovery<T>>>);
+
+impl<T> Default for DiscoveryCache<T> {
+	fn default() -> Self {
+		Self(Mutex::new(HashMap::new()))
+	}
+}
+
+fn git_dir(repo: &Path) -> Option<PathBuf> {
+	let dot_git = repo.join(".git");
+	if dot_git.is_dir() {
+		return Some(dot_git);
+	}
+	let pointer = fs::read_to_string(&dot_git).ok()?;
+	let target = Path::new(pointer.trim().strip_prefix("gitdir:")?.trim());
+	Some(repo.join(target))
+}
+
+fn resolve_ref(git_dir: &Path, name: &str) -> Option<String> {
+	let common = fs::read_to_string(git_dir.join("commondir"))
+		.map(|dir| git_dir.join(dir.trim()))
+		.unwrap_or_else(|_| git_dir.to_path_buf());
+	[git_dir, common.as_path()]
+		.iter()
+		.find_map(|dir| fs::read_to_string(dir.join(name)).ok())
+		.map(|hash| hash.trim().to_string())
+		.or_else(|| {
+			fs::read_to_string(common.join("packed-refs"))
+				.ok()?
+				.lines()
+				.find_map(|line| {
+					let (hash, reference) = line.split_once(' ')?;
+					(reference == name).then(|| hash.to_string())
+				})
+		})
+}
+
+pub fn head_commit(repo: &Path) -> Option<String> {
+	let git_dir = git_dir(repo)?;
+	let head = fs::read_to_string(git_dir.join("HEAD")).ok()?;
+	match head.trim().strip_prefix("ref:") {
+		Some(name) => {
+			let name = name.trim();
+			Some(resolve_ref(&git_dir, name).unwrap_or_else(|| name.to_string()))
+		}
+		None => Some(head.trim().to_string()),
+	}
+}
+
+fn discovery_key(repo: &Path) -> DiscoveryKey {
+	DiscoveryKey {
+		head: head_commit(repo),
+		openspec_modified: fs::metadata(openspec_root(repo))
+			.and_then(|metadata| metadata.modified())
+			.ok(),
+	}
+}
+
+impl<T: Clone> DiscoveryCache<T> {
+	pub fn get(&self, path: &Path) -> Option<T> {
+		let cache = self.0.lock().ok()?;
+		let cached = cache.get(path)?;
+		(path.is_dir() && discovery_key(&cached.repo) == cached.key).then(|| cached.value.clone())
+	}
+
+	pub fn insert(&self, path: &Path, repo: &Path, value: T) {
+		if let Ok(mut cache) = self.0.lock() {
+			cache.insert(
+				path.to_path_buf(),
+				CachedDiscovery {
+					repo: repo.to_path_buf(),
+					key: discovery_key(repo),
+					value,
+				},
+			);
+		}
+	}
+
+	pub fn invalidate(&self, path: &Path) {
+		if let Ok(mut cache) = self.0.lock() {
+			cache.retain(|key, cached| {
+				!key.starts_with(path)
+					&& !cached.repo.starts_with(path)
+					&& !path.starts_with(&cached.repo)
+			});
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn reuses_entries_until_head_or_openspec_changes() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let repo = temp_dir.path();
+		fs::create_dir_all(repo.join(".git/refs/heads")).expect("create git dir");
+		fs::write(repo.join(".git/HEAD"), "ref: refs/heads/main\n").expect("write head");
+		fs::write(
+			repo.join(".git/packed-refs"),
+			"# pack-refs with: peeled\naaaa refs/heads/main\n",
+		)
+		.expect("write packed refs");
+		assert_eq!(head_commit(repo).as_deref(), Some("aaaa"));
+
+		let cache = DiscoveryCache::default();
+		cache.insert(repo, repo, "first");
+		assert_eq!(cache.get(repo), Some("first"));
+
+		fs::write(repo.join(".git/refs/heads/main"), "bbbb\n").expect("write ref");
+		assert_eq!(cache.get(repo), None);
+
+		cache.insert(repo, repo, "second");
+		fs::create_dir(repo.join("openspec")).expect("create openspec");
+		assert_eq!(cache.get(repo), None);
+
+		cache.insert(repo, repo, "third");
+		cache.invalidate(&repo.join("nested"));
+		assert_eq!(cache.get(repo), None);
+	}
+}
@@ -0,0 +1,153 @@
+use crate::{
+	spec::{SpecError, SpecErrorPayload},
+	storage,
+};
+use serde::Serialize;
+use std::{
+	collections::HashMap,
+	fs,
+	path::{Path, PathBuf},
+	sync::Mutex,
+};
+use tauri::State;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EditSession {
+	pub id: String,
+	path: String,
+	opened_at: u64,
+}
+
+#[derive(Default)]
+pub struct EditSessions(Mutex<HashMap<PathBuf, EditSession>>);
+
+fn session_key(path: &Path) -> PathBuf {
+	fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+impl EditSessions {
+	pub fn open(&self, path: &Path) -> Result<EditSession, SpecError> {
+		let mut sessions = self.0.lock().map_err(|_| SpecError::FileLocked)?;
+		let session = sessions
+			.entry(session_key(path))
+			.or_insert_with(|| EditSession {
+				id: storage::new_id("edit"),
+				path: path.to_string_lossy().to_string(),
+				opened_at: storage::now_secs(),
+			});
+		Ok(session.clone())
+	}
+
+	pub fn close(&self, id: &str) {
+		if let Ok(mut sessions) = self.0.lock() {
+			sessions.retain(|_, session| session.id != id);
+		}
+	}
+
+	pub fn list(&self) -> Vec<EditSession> {
+		self.0
+			.lock()
+			.map(|sessions| sessions.values().cloned().collect())
+			.unwrap_or_default()
+	}
+
+	pub fn is_locked(&self, path: &Path) -> bool {
+		let key = session_key(path);
+		self.0
+			.lock()
+			.map(|sessions| sessions.contains_key(&key))
+			.unwrap_or(true)
+	}
+
+	pub fn is_locked_within(&self, dir: &Path) -> bool {
+		let dir = session_key(dir);
+		self.0
+			.lock()
+			.map(|sessions| sessions.keys().any(|path| path.starts_with(&dir)))
+			.unwrap_or(true)
+	}
+
+	pub fn ensure_unlocked(&self, path: &Path) -> Result<(), SpecError> {
+		if self.is_locked(path) {
+			Err(SpecError::FileLocked)
+		} else {
+			Ok(())
+		}
+	}
+
+	fn save(&self, id: &str, contents: &str) -> Result<(), SpecError> {
+		let path = self
+			.list()
+			.into_iter()
+			.find(|session| session.id == id)
+			.map(|session| PathBuf::from(session.path))
+			.ok_or(SpecError::EditSessionNotFound)?;
+		storage::write_atomic(&path, contents.as_bytes())?;
+		Ok(())
+	}
+}
+
+#[tauri::command]
+pub fn open_edit_session(
+	sessions: State<'_, EditSessions>,
+	path: String,
+) -> Result<EditSession, SpecErrorPayload> {
+	sessions
+		.open(Path::new(&path))
+		.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn save_edit_session(
+	sessions: State<'_, EditSessions>,
+	session_id: String,
+	contents: String,
+) -> Result<(), SpecErrorPayload> {
+	sessions
+		.save(&session_id, &contents)
+		.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn close_edit_session(sessions: State<'_, EditSessions>, session_id: String) {
+	sessions.close(&session_id);
+}
+
+#[tauri::command]
+pub fn list_edit_sessions(sessions: State<'_, EditSessions>) -> Vec<EditSession> {
+	sessions.list()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn locks_files_until_the_session_closes() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let path = temp_dir.path().join("openspec/specs/auth/spec.md");
+		fs::create_dir_all(path.parent().expect("parent")).expect("create specs");
+		fs::write(&path, "old").expect("write spec");
+
+		let sessions = EditSessions::default();
+		let session = sessions.open(&path).expect("open");
+		assert_eq!(sessions.open(&path).expect("reopen").id, session.id);
+		assert!(matches!(
+			sessions.ensure_unlocked(&path),
+			Err(SpecError::FileLocked)
+		));
+		assert!(sessions.is_locked_within(&temp_dir.path().join("openspec")));
+
+		sessions.save(&session.id, "new").expect("save");
+		assert_eq!(fs::read_to_string(&path).expect("read"), "new");
+
+		sessions.close(&session.id);
+		assert!(sessions.ensure_unlocked(&path).is_ok());
+		assert!(matches!(
+			sessions.save(&session.id, "late"),
+			Err(SpecError::EditSessionNotFound)
+		));
+	}
+}
@@ -0,0 +1,116 @@
+use std::process::ExitStatus;
+
+const SIGNAL_NAMES: &[(i32, &str)] = &[
+	(1, "SIGHUP"),
+	(2, "SIGINT"),
+	(3, "SIGQUIT"),
+	(4, "SIGILL"),
+	(6, "SIGABRT"),
+	(8, "SIGFPE"),
+	(9, "SIGKILL"),
+	(11, "SIGSEGV"),
+	(13, "SIGPIPE"),
+	(14, "SIGALRM"),
+	(15, "SIGTERM"),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Termination {
+	pub status: i32,
+	pub signal: Option<i32>,
+	pub reason: String,
+}
+
+pub fn signal_name(signal: i32) -> Option<&'static str> {
+	SIGNAL_NAMES
+		.iter()
+		.find_map(|(number, name)| (*number == signal).then_some(*name))
+}
+
+fn signal_reason(signal: i32, core_dumped: bool) -> String {
+	let mut reason = match signal_name(signal) {
+		Some(name) => format!("terminated by {name} (signal {signal})"),
+		None => format!("terminated by signal {signal}"),
+	};
+	if core_dumped {
+		reason.push_str(", core dumped");
+	}
+	reason
+}
+
+impl Termination {
+	pub fn exited(status: i32) -> Self {
+		Self {
+			status,
+			signal: None,
+			reason: format!("exited with status {status}"),
+		}
+	}
+
+	fn signaled(signal: i32, core_dumped: bool) -> Self {
+		Self {
+			status: -1,
+			signal: Some(signal),
+			reason: signal_reason(signal, core_dumped),
+		}
+	}
+
+	pub fn from_status(status: &ExitStatus) -> Self {
+		#[cfg(unix)]
+		let (signal, core_dumped) = {
+			use std::os::unix::process::ExitStatusExt;
+			(status.signal(), status.core_dumped())
+		};
+		#[cfg(not(unix))]
+		let (signal, core_dumped) = (None::<i32>, false);
+		match (status.code(), signal) {
+			(Some(code), _) => Self::exited(code),
+			(None, Some(signal)) => Self::signaled(signal, core_dumped),
+			(None, None) => Self {
+				status: -1,
+				signal: None,
+				reason: "terminated without an exit status".to_string(),
+			},
+		}
+	}
+
+	pub fn from_pty(status: &portable_pty::ExitStatus) -> Self {
+		match status.signal() {
+			Some(signal) => Self {
+				status: -1,
+				signal: None,
+				reason: format!("terminated by signal: {signal}"),
+			},
+			None => Self::exited(status.exit_code() as i32),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[cfg(unix)]
+	#[test]
+	fn describes_signal_terminations() {
+		use std::os::unix::process::ExitStatusExt;
+
+		assert_eq!(
+			Termination::from_status(&ExitStatus::from_raw(3 << 8)),
+			Termination::exited(3)
+		);
+		let killed = Termination::from_status(&ExitStatus::from_raw(9));
+		assert_eq!(
+			(killed.status, killed.signal, killed.reason.as_str()),
+			(-1, Some(9), "terminated by SIGKILL (signal 9)")
+		);
+		assert_eq!(
+			Termination::from_status(&ExitStatus::from_raw(11 | 0x80)).reason,
+			"terminated by SIGSEGV (signal 11), core dumped"
+		);
+		assert_eq!(
+			Termination::from_pty(&portable_pty::ExitStatus::with_signal("Terminated")).reason,
+			"terminated by signal: Terminated"
+		);
+	}
+}
@@ -0,0 +1,265 @@
+use crate::{
+	diff::unified_diff,
+	editing::EditSessions,
+	lint::{bold_scenario_name, DELTA_HEADERS},
+	spec::{
+		existing_openspec_root, is_delta_spec, is_fence, markdown_files, parse_heading, SourceText,
+		SpecError, SpecErrorPayload,
+	},
+	storage,
+};
+use serde::Serialize;
+use std::path::Path;
+use tauri::State;
+
+const SCENARIO_KEYWORDS: [&str; 4] = ["GIVEN", "WHEN", "THEN", "AND"];
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormatResult {
+	path: String,
+	changed: bool,
+	locked: bool,
+	diff: String,
+}
+
+fn heading_level(text: &str, level: usize, delta_spec: bool) -> usize {
+	if text.starts_with("Requirement:") {
+		3
+	} else if text.starts_with("Scenario:") {
+		4
+	} else if delta_spec && DELTA_HEADERS.contains(&text) {
+		2
+	} else {
+		level
+	}
+}
+
+fn normalize_list_marker(line: &str) -> String {
+	let indent = line.len() - line.trim_start().len();
+	let content = &line[indent..];
+	match content
+		.strip_prefix("* ")
+		.or_else(|| content.strip_prefix("+ "))
+	{
+		Some(rest) => format!("{}- {}", &line[..indent], rest.trim_start()),
+		None => line.to_string(),
+	}
+}
+
+fn normalize_scenario_step(line: &str) -> String {
+	let indent = line.len() - line.trim_start().len();
+	let Some(content) = line[indent..].strip_prefix("- ") else {
+		return line.to_string();
+	};
+	let bare = content.trim_start_matches('*');
+	for keyword in SCENARIO_KEYWORDS {
+		let Some(rest) = bare.strip_prefix(keyword) else {
+			continue;
+		};
+		if rest.starts_with(|ch: char| ch.is_alphanumeric()) {
+			continue;
+		}
+		let rest = rest.trim_start_matches(['*', ':']).trim_start();
+		return format!("{}- **{keyword}** {rest}", &line[..indent]);
+	}
+	line.to_string()
+}
+
+pub fn format_lines(path: &Path, lines: &[String]) -> Vec<String> {
+	let delta_spec = is_delta_spec(path);
+	let mut output: Vec<String> = Vec::with_capacity(lines.len());
+	let mut in_fence = false;
+	let mut last_was_heading = false;
+	let mut in_scenario = false;
+
+	for raw in lines {
+		if in_fence {
+			if is_fence(raw) {
+				in_fence = false;
+				output.push(raw.trim_end().to_string());
+			} else {
+				output.push(raw.clone());
+			}
+			continue;
+		}
+
+		let line = raw.trim_end();
+		if line.is_empty() {
+			if output.last().is_some_and(|last| !last.is_empty()) {
+				output.push(String::new());
+			}
+			last_was_heading = false;
+			continue;
+		}
+
+		let heading = parse_heading(line)
+			.map(|(level, text)| (level, text.trim_end_matches('#').trim_end().to_string()))
+			.or_else(|| bold_scenario_name(line).map(|name| (4, format!("Scenario: {name}"))));
+		if let Some((level, text)) = heading {
+			if output.last().is_some_and(|last| !last.is_empty()) {
+				output.push(String::new());
+			}
+			let level = heading_level(&text, level, delta_spec);
+			output.push(format!("{} {text}", "#".repeat(level)));
+			in_scenario = text.starts_with("Scenario:");
+			last_was_heading = true;
+			continue;
+		}
+
+		if last_was_heading {
+			output.push(String::new());
+			last_was_heading = false;
+		}
+		if is_fence(line) {
+			in_fence = true;
+			output.push(line.to_string());
+			continue;
+		}
+
+		let line = normalize_list_marker(line);
+		if in_scenario {
+			output.push(normalize_scenario_step(&line));
+		} else {
+			output.push(line);
+		}
+	}
+
+	while output.last().is_some_and(|last| last.is_empty()) {
+		output.pop();
+	}
+	output
+}
+
+fn format_file(
+	path: &Path,
+	label: &str,
+	check: bool,
+	sessions: &EditSessions,
+) -> Result<FormatResult, SpecError> {
+	let mut source = SourceText::read_complete(path)?;
+	let text = source.render();
+	source.lines = format_lines(path, &source.lines);
+	source.trailing_newline = true;
+	let formatted = source.render();
+	let changed = formatted != text;
+	let locked = changed && !check && sessions.is_locked(path);
+	if changed && !check && !locked {
+		storage::write_atomic(path, formatted.as_bytes())?;
+	}
+
+	Ok(FormatResult {
+		path: path.to_string_lossy().to_string(),
+		changed,
+		locked,
+		diff: unified_diff(&text, &formatted, label),
+	})
+}
+
+pub fn format_project_files(
+	project: &Path,
+	check: bool,
+	sessions: &EditSessions,
+) -> Result<Vec<FormatResult>, SpecError> {
+	let root = existing_openspec_root(project)?;
+	let mut results = Vec::new();
+	for file in markdown_files(&root)? {
+		let label = file
+			.strip_prefix(project)
+			.unwrap_or(&file)
+			.to_string_lossy()
+			.to_string();
+		let result = format_file(&file, &label, check, sessions)?;
+		if result.changed {
+			results.push(result);
+		}
+	}
+	Ok(results)
+}
+
+#[tauri::command]
+pub fn format_spec(
+	sessions: State<'_, EditSessions>,
+	path: String,
+	check: bool,
+) -> Result<FormatResult, SpecErrorPayload> {
+	let file = Path::new(&path);
+	if !check {
+		sessions
+			.ensure_unlocked(file)
+			.map_err(SpecErrorPayload::from)?;
+	}
+	let label = file
+		.file_name()
+		.map(|name| name.to_string_lossy().to_string())
+		.unwrap_or_else(|| path.clone());
+	format_file(file, &label, check, &sessions).map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn format_project(
+	sessions: State<'_, EditSessions>,
+	path: String,
+	check: bool,
+) -> Result<Vec<FormatResult>, SpecErrorPayload> {
+	format_project_files(Path::new(&path), check, &sessions).map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs;
+	use tempfile::TempDir;
+
+	const MESSY: &str = "# auth Specification\n## Purpose\nAuth.\n\n\n\n## Requirements\n## Requirement: Login   \nThe system SHALL log in.\n* first\n+ second\n- **Scenario: Works**\n- WHEN a user signs in\n- **THEN**: a session starts\n```text\n*  kept   \n```\n\n";
+
+	fn format_text(path: &str, text: &str) -> String {
+		let mut source = SourceText::parse(text);
+		source.lines = format_lines(Path::new(path), &source.lines);
+		source.trailing_newline = true;
+		source.render()
+	}
+
+	#[test]
+	fn normalizes_headings_lists_and_scenarios() {
+		let formatted = format_text("/repo/openspec/specs/auth/spec.md", MESSY);
+		assert_eq!(
+			formatted,
+			"# auth Specification\n\n## Purpose\n\nAuth.\n\n## Requirements\n\n### Requirement: Login\n\nThe system SHALL log in.\n- first\n- second\n\n#### Scenario: Works\n\n- **WHEN** a user signs in\n- **THEN** a session starts\n```text\n*  kept   \n```\n",
+		);
+	}
+
+	#[test]
+	fn formatting_is_idempotent() {
+		let once = format_text("/repo/openspec/changes/x/specs/auth/spec.md", MESSY);
+		let twice = format_text("/repo/openspec/changes/x/specs/auth/spec.md", &once);
+		assert_eq!(once, twice);
+	}
+
+	#[test]
+	fn check_mode_reports_diff_without_writing() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let path = temp_dir.path().join("spec.md");
+		fs::write(&path, "## Requirement: A\nText SHALL hold.").expect("write spec");
+
+		let sessions = EditSessions::default();
+		let result = format_file(&path, "spec.md", true, &sessions).expect("format");
+		assert!(result.changed);
+		assert!(result.diff.contains("+### Requirement: A"));
+		assert_eq!(
+			fs::read_to_string(&path).expect("read spec"),
+			"## Requirement: A\nText SHALL hold."
+		);
+
+		let session = sessions.open(&path).expect("open session");
+		let result = format_file(&path, "spec.md", false, &sessions).expect("format");
+		assert!(result.locked);
+		assert!(result.changed);
+		sessions.close(&session.id);
+
+		format_file(&path, "spec.md", false, &sessions).expect("format");
+		let result = format_file(&path, "spec.md", true, &sessions).expect("format");
+		assert!(!result.changed);
+		assert!(result.diff.is_empty());
+	}
+}
@@ -0,0 +1,352 @@
+use crate::{
+	journal::{self, OperationStatus},
+	process,
+	settings::SettingsStore,
+	spec::{SpecError, SpecErrorPayload},
+	wsl,
+};
+use serde::Serialize;
+use std::{collections::BTreeMap, io::Read, path::Path, process::Stdio};
+use tauri::{Emitter, Manager, Window};
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum GitPhase {
+	Enumerating,
+	Counting,
+	Compressing,
+	Receiving,
+	Resolving,
+	Writing,
+	Updating,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct GitProgress {
+	phase: GitPhase,
+	percent: Option<u8>,
+	current: Option<u64>,
+	total: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GitProgressEvent {
+	operation: String,
+	operation_id: String,
+	#[serde(flatten)]
+	progress: GitProgress,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GitAuthFailure {
+	PublicKeyDenied,
+	AgentUnavailable,
+	HostKeyVerification,
+	CredentialHelperMissing,
+	CredentialsRejected,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitRunOutput {
+	operation_id: String,
+	status: i32,
+	stderr: String,
+}
+
+pub fn git_output(project: &Path, args: &[&str]) -> Result<String, SpecError> {
+	let output = wsl::command("git", args, Some(project)).output()?;
+	if !output.status.success() {
+		return Err(SpecError::GitCommandFailed);
+	}
+	Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+pub fn classify_auth_failure(stderr: &str) -> Option<GitAuthFailure> {
+	let stderr = stderr.to_lowercase();
+	let matches = |needles: &[&str]| needles.iter().any(|needle| stderr.contains(needle));
+	if matches(&[
+		"could not open a connection to your authentication agent",
+		"agent refused operation",
+		"sign_and_send_pubkey: signing failed",
+	]) {
+		Some(GitAuthFailure::AgentUnavailable)
+	} else if matches(&["permission denied (publickey"]) {
+		Some(GitAuthFailure::PublicKeyDenied)
+	} else if matches(&["host key verification failed"]) {
+		Some(GitAuthFailure::HostKeyVerification)
+	} else if matches(&[
+		"could not read username",
+		"could not read password",
+		"terminal prompts disabled",
+	]) || (stderr.contains("credential-") && stderr.contains("is not a git command"))
+	{
+		Some(GitAuthFailure::CredentialHelperMissing)
+	} else if matches(&[
+		"authentication failed",
+		"invalid username or password",
+		"http basic: access denied",
+	]) {
+		Some(GitAuthFailure::CredentialsRejected)
+	} else {
+		None
+	}
+}
+
+fn phase(label: &str) -> Option<GitPhase> {
+	[
+		("Enumerating", GitPhase::Enumerating),
+		("Counting", GitPhase::Counting),
+		("Compressing", GitPhase::Compressing),
+		("Receiving", GitPhase::Receiving),
+		("Resolving", GitPhase::Resolving),
+		("Writing", GitPhase::Writing),
+		("Updating", GitPhase::Updating),
+	]
+	.into_iter()
+	.find_map(|(prefix, phase)| label.starts_with(prefix).then_some(phase))
+}
+
+pub fn parse_progress(line: &str) -> Option<GitProgress> {
+	let line = line.trim().trim_start_matches("remote:").trim();
+	let (label, rest) = line.split_once(':')?;
+	let phase = phase(label)?;
+	let percent = rest
+		.split_once('%')
+		.and_then(|(before, _)| before.trim().parse::<u8>().ok());
+	let counts = match rest.split_once('(') {
+		Some((_, counts)) => counts.split(')').next().unwrap_or_default(),
+		None => rest.split(',').next().unwrap_or_default(),
+	};
+	let (current, total) = match counts.split_once('/') {
+		Some((current, total)) => (current.trim().parse().ok(), total.trim().parse().ok()),
+		None => (counts.trim().parse().ok(), None),
+	};
+	Some(GitProgress {
+		phase,
+		percent,
+		current,
+		total,
+	})
+}
+
+fn for_each_progress_line(mut reader: impl Read, mut handle: impl FnMut(&str)) {
+	let mut pending = Vec::new();
+	let mut chunk = [0u8; 4096];
+	while let Ok(read) = reader.read(&mut chunk) {
+		if read == 0 {
+			break;
+		}
+		for byte in &chunk[..read] {
+			if matches!(byte, b'\r' | b'\n') {
+				if !pending.is_empty() {
+					handle(&String::from_utf8_lossy(&pending));
+					pending.clear();
+				}
+			} else {
+				pending.push(*byte);
+			}
+		}
+	}
+	if !pending.is_empty() {
+		handle(&String::from_utf8_lossy(&pending));
+	}
+}
+
+fn run_git_with_progress(
+	window: &Window,
+	operation: &str,
+	project: Option<&Path>,
+	args: &[&str],
+) -> Result<GitRunOutput, SpecError> {
+	let root = journal::journal_root()?;
+	let command_line = format!("git {}", args.join(" "));
+	let settings = window.state::<SettingsStore>();
+	let scrubber = journal::Scrubber::new(settings.get().history_privacy, &BTreeMap::new());
+	let cipher = journal::project_journal_cipher(&settings, project)?;
+	let record = journal::begin_scrubbed_in(
+		&root,
+		operation,
+		&command_line,
+		project,
+		&[],
+		&scrubber,
+		cipher.as_ref(),
+	)?;
+	let _turn = match process::wait_for_turn(window, operation, &record.id, project) {
+		Ok(turn) => turn,
+		Err(error) => {
+			let outcome = if matches!(error, SpecError::OperationDequeued) {
+				OperationStatus::Cancelled
+			} else {
+				OperationStatus::Failed
+			};
+			let _ = journal::finish_in(&root, &record.id, outcome, None);
+			return Err(error);
+		}
+	};
+	let log = journal::OperationLog::create_in(&root, &record.id)
+		.ok()
+		.map(|log| log.scrubbed(scrubber).sealed(cipher));
+	let mut child = wsl::command("git", args, project)
+		.stdout(Stdio::null())
+		.stderr(Stdio::piped())
+		.spawn()?;
+
+	let mut stderr = Vec::new();
+	let mut last = None;
+	if let Some(pipe) = child.stderr.take() {
+		for_each_progress_line(pipe, |line| match parse_progress(line) {
+			Some(progress) => {
+				let key = (progress.phase, progress.percent);
+				if last != Some(key) {
+					last = Some(key);
+					let _ = window.emit(
+						"openspec://git-progress",
+						GitProgressEvent {
+							operation: operation.to_string(),
+							operation_id: record.id.clone(),
+							progress,
+						},
+					);
+				}
+			}
+			None => {
+				if let Some(log) = &log {
+					log.append("stderr", line);
+				}
+				stderr.push(line.to_string());
+			}
+		});
+	}
+	let status = child.wait()?;
+	let outcome = if status.success() {
+		OperationStatus::Succeeded
+	} else {
+		OperationStatus::Failed
+	};
+	let _ = journal::finish_in(&root, &record.id, outcome, status.code());
+	if !status.success() {
+		return Err(match classify_auth_failure(&stderr.join("\n")) {
+			Some(failure) => SpecError::GitAuthFailed(failure),
+			None => SpecError::GitCommandFailed,
+		});
+	}
+	Ok(GitRunOutput {
+		operation_id: record.id,
+		status: status.code().unwrap_or(-1),
+		stderr: stderr.join("\n"),
+	})
+}
+
+#[tauri::command(async)]
+pub fn git_clone(
+	window: Window,
+	url: String,
+	dest: String,
+) -> Result<GitRunOutput, SpecErrorPayload> {
+	run_git_with_progress(
+		&window,
+		"git-clone",
+		None,
+		&["clone", "--progress", &url, &dest],
+	)
+	.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command(async)]
+pub fn git_fetch(window: Window, project: String) -> Result<GitRunOutput, SpecErrorPayload> {
+	run_git_with_progress(
+		&window,
+		"git-fetch",
+		Some(Path::new(&project)),
+		&["fetch", "--progress"],
+	)
+	.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command(async)]
+pub fn git_push(window: Window, project: String) -> Result<GitRunOutput, SpecErrorPayload> {
+	run_git_with_progress(
+		&window,
+		"git-push",
+		Some(Path::new(&project)),
+		&["push", "--progress"],
+	)
+	.map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_progress_phases_from_carriage_return_output() {
+		let output = b"Cloning into 'repo'...\nremote: Enumerating objects: 1200, done.\nremote: Counting objects:  50% (600/1200)\rremote: Counting objects: 100% (1200/1200), done.\nReceiving objects:  45% (540/1200), 1.20 MiB | 1.10 MiB/s\rResolving deltas: 100% (30/30), done.\n";
+		let mut lines = Vec::new();
+		for_each_progress_line(&output[..], |line| lines.push(line.to_string()));
+		let progress = lines
+			.iter()
+			.filter_map(|line| parse_progress(line))
+			.collect::<Vec<_>>();
+
+		assert_eq!(lines.len(), 6);
+		assert_eq!(progress.len(), 5);
+		assert_eq!(
+			progress[0],
+			GitProgress {
+				phase: GitPhase::Enumerating,
+				percent: None,
+				current: Some(1200),
+				total: None,
+			}
+		);
+		assert_eq!(
+			progress[3],
+			GitProgress {
+				phase: GitPhase::Receiving,
+				percent: Some(45),
+				current: Some(540),
+				total: Some(1200),
+			}
+		);
+		assert_eq!(progress[4].phase, GitPhase::Resolving);
+		assert!(parse_progress("Cloning into 'repo'...").is_none());
+	}
+
+	#[test]
+	fn classifies_auth_failures_from_stderr() {
+		let cases = [
+			(
+				"git@github.com: Permission denied (publickey).\nfatal: Could not read from remote repository.",
+				Some(GitAuthFailure::PublicKeyDenied),
+			),
+			(
+				"sign_and_send_pubkey: signing failed for ED25519: agent refused operation",
+				Some(GitAuthFailure::AgentUnavailable),
+			),
+			(
+				"Host key verification failed.",
+				Some(GitAuthFailure::HostKeyVerification),
+			),
+			(
+				"git: 'credential-osxkeychain' is not a git command. See 'git --help'.",
+				Some(GitAuthFailure::CredentialHelperMissing),
+			),
+			(
+				"fatal: could not read Username for 'https://github.com': terminal prompts disabled",
+				Some(GitAuthFailure::CredentialHelperMissing),
+			),
+			(
+				"remote: Invalid username or password.\nfatal: Authentication failed for 'https://github.com/a/b.git/'",
+				Some(GitAuthFailure::CredentialsRejected),
+			),
+			("fatal: repository 'x' does not exist", None),
+		];
+		for (stderr, expected) in cases {
+			assert_eq!(classify_auth_failure(stderr), expected, "{stderr}");
+		}
+	}
+}
@@ -0,0 +1,239 @@
+use crate::{
+	changes::list_changes,
+	cli::validate_with_cli,
+	editing::EditSessions,
+	format::format_project_files,
+	lint::{lint_project_files, LintOutcome, Severity},
+	spec::SpecError,
+};
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use clap_complete::Shell;
+use serde::Serialize;
+use std::{
+	io::{self, Write},
+	path::Path,
+	process::ExitCode,
+};
+
+const BIN_NAME: &str = "specops-cli";
+// Exit status for a run that completed but found problems, as opposed to
+// one that could not run at all.
+const FAILED_CHECK: u8 = 1;
+const RUN_ERROR: u8 = 2;
+
+fn project_arg() -> Arg {
+	Arg::new("project")
+		.help("Repository that contains the openspec folder")
+		.default_value(".")
+}
+
+/// The full argument tree. Completions and the man page are generated from
+/// it, so they always match what the binary accepts.
+pub fn command() -> Command {
+	Command::new(BIN_NAME)
+		.about("Lint, format and validate OpenSpec projects without the desktop app")
+		.version(env!("CARGO_PKG_VERSION"))
+		.arg_required_else_help(true)
+		.args_conflicts_with_subcommands(true)
+		.arg(
+			Arg::new("generate-completions")
+				.long("generate-completions")
+				.value_name("SHELL")
+				.help("Print a completion script for SHELL and exit")
+				.value_parser(value_parser!(Shell))
+				.exclusive(true),
+		)
+		.arg(
+			Arg::new("generate-man")
+				.long("generate-man")
+				.help("Print the man page in roff format and exit")
+				.action(ArgAction::SetTrue)
+				.exclusive(true),
+		)
+		.subcommand(
+			Command::new("list")
+				.about("List changes with their task progress")
+				.arg(project_arg())
+				.arg(
+					Arg::new("archived")
+						.long("archived")
+						.help("Include archived changes")
+						.action(ArgAction::SetTrue),
+				),
+		)
+		.subcommand(
+			Command::new("lint")
+				.about("Lint every spec; fails when a finding reaches the gate")
+				.arg(project_arg())
+				.arg(
+					Arg::new("gate")
+						.long("gate")
+						.help("Lowest severity that fails the run")
+						.value_parser(["info", "warning", "error"])
+						.default_value("error"),
+				),
+		)
+		.subcommand(
+			Command::new("format")
+				.about("Format every spec in place")
+				.arg(project_arg())
+				.arg(
+					Arg::new("check")
+						.long("check")
+						.help("Report files that need formatting without writing them")
+						.action(ArgAction::SetTrue),
+				),
+		)
+		.subcommand(
+			Command::new("validate")
+				.about("Run `openspec validate` and report its findings")
+				.arg(project_arg())
+				.arg(
+					Arg::new("change")
+						.long("change")
+						.value_name("ID")
+						.help("Validate a single change instead of the whole project"),
+				)
+				.arg(
+					Arg::new("strict")
+						.long("strict")
+						.help("Treat warnings as failures")
+						.action(ArgAction::SetTrue),
+				),
+		)
+}
+
+fn gate(name: &str) -> Severity {
+	match name {
+		"info" => Severity::Info,
+		"warning" => Severity::Warning,
+		_ => Severity::Error,
+	}
+}
+
+fn print_json(out: &mut impl Write, value: &impl Serialize) -> Result<(), SpecError> {
+	serde_json::to_writer_pretty(&mut *out, value).map_err(io::Error::from)?;
+	writeln!(out)?;
+	Ok(())
+}
+
+/// Runs the parsed command line, writing results to `out`. Returns whether
+/// the checks passed.
+pub fn run(matches: &ArgMatches, out: &mut impl Write) -> Result<bool, SpecError> {
+	if let Some(shell) = matches.get_one::<Shell>("generate-completions") {
+		clap_complete::generate(*shell, &mut command(), BIN_NAME, out);
+		return Ok(true);
+	}
+	if matches.get_flag("generate-man") {
+		clap_mangen::Man::new(command()).render(out)?;
+		return Ok(true);
+	}
+
+	let Some((name, args)) = matches.subcommand() else {
+		return Ok(true);
+	};
+	let project = Path::new(
+		args.get_one::<String>("project")
+			.map(String::as_str)
+			.unwrap_or("."),
+	);
+	match name {
+		"list" => {
+			print_json(out, &list_changes(project, args.get_flag("archived"))?)?;
+			Ok(true)
+		}
+		"lint" => {
+			let gate = gate(
+				args.get_one::<String>("gate")
+					.map_or("error", String::as_str),
+			);
+			let outcome = LintOutcome::gated(lint_project_files(project)?, gate);
+			print_json(out, &outcome)?;
+			Ok(outcome.passed)
+		}
+		"format" => {
+			let check = args.get_flag("check");
+			let results = format_project_files(project, check, &EditSessions::default())?;
+			print_json(out, &results)?;
+			Ok(!check || results.is_empty())
+		}
+		"validate" => {
+			let report = validate_with_cli(
+				project,
+				args.get_one::<String>("change").map(String::as_str),
+				args.get_flag("strict"),
+			)?;
+			print_json(out, &report)?;
+			Ok(report.valid)
+		}
+		_ => Ok(true),
+	}
+}
+
+pub fn main() -> ExitCode {
+	let matches = command().get_matches();
+	match run(&matches, &mut io::stdout().lock()) {
+		Ok(true) => ExitCode::SUCCESS,
+		Ok(false) => ExitCode::from(FAILED_CHECK),
+		Err(error) => {
+			eprintln!("{BIN_NAME}: {error}");
+			ExitCode::from(RUN_ERROR)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs;
+	use tempfile::TempDir;
+
+	fn output(args: &[&str]) -> (bool, String) {
+		let matches = command()
+			.try_get_matches_from(std::iter::once(BIN_NAME).chain(args.iter().copied()))
+			.expect("parse args");
+		let mut out = Vec::new();
+		let passed = run(&matches, &mut out).expect("run");
+		(passed, String::from_utf8(out).expect("utf-8 output"))
+	}
+
+	#[test]
+	fn argument_tree_is_consistent() {
+		command().debug_assert();
+	}
+
+	#[test]
+	fn generates_completions_and_man_page() {
+		let (_, bash) = output(&["--generate-completions", "bash"]);
+		assert!(bash.contains(BIN_NAME));
+		assert!(bash.contains("validate"));
+		let (_, zsh) = output(&["--generate-completions", "zsh"]);
+		assert!(zsh.starts_with("#compdef"));
+
+		let (_, man) = output(&["--generate-man"]);
+		assert!(man.contains(".TH"));
+		assert!(man.contains("lint"));
+		assert!(command()
+			.try_get_matches_from([BIN_NAME, "--generate-man", "lint"])
+			.is_err());
+	}
+
+	#[test]
+	fn lint_fails_only_at_the_requested_gate() {
+		let project = TempDir::new().expect("create project dir");
+		let spec = project.path().join("openspec/specs/auth");
+		fs::create_dir_all(&spec).expect("create specs");
+		fs::write(
+			spec.join("spec.md"),
+			"### Requirement: Login\nUsers log in.\n\n#### Scenario: Works\n",
+		)
+		.expect("write spec");
+		let path = project.path().to_string_lossy().to_string();
+
+		let (passed, json) = output(&["lint", &path]);
+		assert!(passed);
+		assert!(json.contains("requirement-missing-shall"));
+		let (passed, _) = output(&["lint", &path, "--gate", "warning"]);
+		assert!(!passed);
+	}
+}
@@ -0,0 +1,216 @@
+use crate::{
+	spec::{SpecError, SpecErrorPayload},
+	wsl,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+const MARKER: &str = "# specops-managed-hook";
+const CHAINED_SUFFIX: &str = "specops-previous";
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum GitHook {
+	PreCommit,
+	PrePush,
+}
+
+impl GitHook {
+	fn file_name(self) -> &'static str {
+		match self {
+			GitHook::PreCommit => "pre-commit",
+			GitHook::PrePush => "pre-push",
+		}
+	}
+
+	fn validation(self) -> &'static str {
+		match self {
+			GitHook::PreCommit => concat!(
+				"git diff --cached --name-only --diff-filter=ACMR -- openspec/changes openspec/specs \\\n",
+				"\t| awk -F/ '$2 == \"changes\" && $3 != \"archive\" && NF > 3 { print $3 \" change\" } $2 == \"specs\" && NF > 3 { print $3 \" spec\" }' \\\n",
+				"\t| sort -u \\\n",
+				"\t| while read -r item kind; do\n",
+				"\t\topenspec validate \"$item\" --type \"$kind\" --strict --no-interactive || exit 1\n",
+				"\tdone || exit 1\n",
+			),
+			GitHook::PrePush => {
+				"[ -d openspec ] || exit 0\nopenspec validate --all --strict --no-interactive || exit 1\n"
+			}
+		}
+	}
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookStatus {
+	hook: GitHook,
+	path: String,
+	installed: bool,
+	chained: bool,
+	foreign: bool,
+}
+
+fn hooks_dir(project: &Path) -> Result<PathBuf, SpecError> {
+	let output =
+		wsl::command("git", &["rev-parse", "--git-path", "hooks"], Some(project)).output()?;
+	if !output.status.success() {
+		return Err(SpecError::NotGitRepository);
+	}
+	let dir = wsl::host_path(project, String::from_utf8_lossy(&output.stdout).trim());
+	Ok(if dir.is_absolute() {
+		dir
+	} else {
+		project.join(dir)
+	})
+}
+
+fn chained_path(hook_path: &Path) -> PathBuf {
+	hook_path.with_extension(CHAINED_SUFFIX)
+}
+
+fn hook_script(hook: GitHook) -> String {
+	let name = hook.file_name();
+	format!(
+		"#!/bin/sh\n\
+		{MARKER}\n\
+		hook_dir=$(dirname \"$0\")\n\
+		if [ -x \"$hook_dir/{name}.{CHAINED_SUFFIX}\" ]; then\n\
+		\t\"$hook_dir/{name}.{CHAINED_SUFFIX}\" \"$@\" || exit $?\n\
+		fi\n\
+		command -v openspec >/dev/null 2>&1 || {{\n\
+		\techo \"specops: openspec CLI not found, skipping spec validation\" >&2\n\
+		\texit 0\n\
+		}}\n\
+		{validation}",
+		validation = hook.validation(),
+	)
+}
+
+fn is_managed(path: &Path) -> bool {
+	fs::read_to_string(path)
+		.map(|contents| contents.lines().any(|line| line == MARKER))
+		.unwrap_or(false)
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> std::io::Result<()> {
+	use std::os::unix::fs::PermissionsExt;
+	fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> std::io::Result<()> {
+	Ok(())
+}
+
+fn status_in(dir: &Path, hook: GitHook) -> HookStatus {
+	let path = dir.join(hook.file_name());
+	let installed = is_managed(&path);
+	HookStatus {
+		hook,
+		chained: installed && chained_path(&path).exists(),
+		foreign: !installed && path.exists(),
+		installed,
+		path: path.to_string_lossy().to_string(),
+	}
+}
+
+fn install_in(dir: &Path, hook: GitHook) -> Result<HookStatus, SpecError> {
+	fs::create_dir_all(dir)?;
+	let path = dir.join(hook.file_name());
+	if path.exists() && !is_managed(&path) {
+		fs::rename(&path, chained_path(&path))?;
+	}
+	fs::write(&path, hook_script(hook))?;
+	make_executable(&path)?;
+	Ok(status_in(dir, hook))
+}
+
+fn uninstall_in(dir: &Path, hook: GitHook) -> Result<HookStatus, SpecError> {
+	let path = dir.join(hook.file_name());
+	if is_managed(&path) {
+		fs::remove_file(&path)?;
+		let chained = chained_path(&path);
+		if chained.exists() {
+			fs::rename(chained, &path)?;
+		}
+	}
+	Ok(status_in(dir, hook))
+}
+
+#[tauri::command]
+pub fn install_git_hook(project: String, hook: GitHook) -> Result<HookStatus, SpecErrorPayload> {
+	let dir = hooks_dir(Path::new(&project))?;
+	install_in(&dir, hook).map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn uninstall_git_hook(project: String, hook: GitHook) -> Result<HookStatus, SpecErrorPayload> {
+	let dir = hooks_dir(Path::new(&project))?;
+	uninstall_in(&dir, hook).map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn git_hook_status(project: String) -> Result<Vec<HookStatus>, SpecErrorPayload> {
+	let dir = hooks_dir(Path::new(&project))?;
+	Ok([GitHook::PreCommit, GitHook::PrePush]
+		.into_iter()
+		.map(|hook| status_in(&dir, hook))
+		.collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::process::Command;
+	use tempfile::TempDir;
+
+	#[test]
+	fn chains_existing_hook_and_restores_it() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let dir = temp_dir.path();
+		fs::write(dir.join("pre-commit"), "#!/bin/sh\nexit 0\n").expect("write hook");
+		assert!(status_in(dir, GitHook::PreCommit).foreign);
+
+		let status = install_in(dir, GitHook::PreCommit).expect("install");
+		assert!(status.installed && status.chained);
+		let script = fs::read_to_string(dir.join("pre-commit")).expect("read hook");
+		assert!(script.contains("pre-commit.specops-previous"));
+		assert!(script.contains("--type \"$kind\" --strict"));
+
+		install_in(dir, GitHook::PreCommit).expect("reinstall");
+		assert_eq!(
+			fs::read_to_string(dir.join("pre-commit.specops-previous")).expect("read chained"),
+			"#!/bin/sh\nexit 0\n"
+		);
+
+		let status = uninstall_in(dir, GitHook::PreCommit).expect("uninstall");
+		assert!(!status.installed && status.foreign);
+		assert_eq!(
+			fs::read_to_string(dir.join("pre-commit")).expect("read restored"),
+			"#!/bin/sh\nexit 0\n"
+		);
+	}
+
+	#[test]
+	fn resolves_hooks_dir_inside_git_repo() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		if Command::new("git")
+			.arg("-C")
+			.arg(temp_dir.path())
+			.args(["init", "-q"])
+			.status()
+			.map(|status| !status.success())
+			.unwrap_or(true)
+		{
+			return;
+		}
+		let dir = hooks_dir(temp_dir.path()).expect("hooks dir");
+		assert!(dir.ends_with(".git/hooks"));
+		install_in(&dir, GitHook::PrePush).expect("install");
+		assert!(is_managed(&temp_dir.path().join(".git/hooks/pre-push")));
+	}
+}
@@ -0,0 +1,504 @@
+use crate::{
+	git::git_output,
+	operations::{self, CancellationToken},
+	preview,
+	settings::{ExportBranding, SettingsStore},
+	spec::{
+		existing_openspec_root, is_fence, markdown_files, parse_heading, SourceText, SpecError,
+		SpecErrorPayload,
+	},
+	storage,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+use tauri::{State, Window};
+
+const REQUIREMENT_PREFIX: &str = "Requirement:";
+
+const SCREEN_STYLE: &str = "body{font-family:system-ui,sans-serif;line-height:1.5;max-width:52rem;margin:2rem auto;padding:0 1rem;color:var(--fg);background:var(--bg)}\
+a{color:var(--link)}code,pre{font-family:ui-monospace,monospace;background:var(--code-bg)}pre{padding:.75rem;overflow:auto}\
+nav.toc ol{padding-left:1.25rem}.requirement{border-left:3px solid var(--border);padding-left:.75rem;margin:1rem 0}\
+img.logo{display:block;max-height:3rem;margin-bottom:1rem}header.running,footer.running{display:none}";
+
+const LIGHT_THEME: &str = ":root{--fg:#1f2328;--bg:#ffffff;--muted:#57606a;--code-bg:#f6f8fa;--border:#d0d7de;--link:#0969da}";
+
+const DARK_THEME: &str = ":root{--fg:#e6edf3;--bg:#0d1117;--muted:#8d96a0;--code-bg:#161b22;--border:#30363d;--link:#4493f8}\
+body{print-color-adjust:exact;-webkit-print-color-adjust:exact}";
+
+const PRINT_STYLE: &str = "@page{size:A4;margin:22mm 18mm;@top-center{content:string(running-header)}@bottom-right{content:counter(page) \" / \" counter(pages)}}\
+@media print{body{max-width:none;margin:0}header.running,footer.running{display:block;position:fixed;left:0;right:0;font-size:9pt;color:var(--muted)}\
+header.running{top:0;string-set:running-header content()}footer.running{bottom:0}\
+section.capability{break-before:page}.requirement{break-inside:avoid}h2,h3,h4{break-after:avoid}\
+nav.toc{break-after:page}nav.toc a::after{content:leader('.') target-counter(attr(href),page)}a{color:inherit;text-decoration:none}}";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum ExportTheme {
+	#[default]
+	Light,
+	Dark,
+	Custom {
+		css: String,
+	},
+}
+
+impl ExportTheme {
+	fn css(&self) -> String {
+		match self {
+			Self::Light => LIGHT_THEME.to_string(),
+			Self::Dark => DARK_THEME.to_string(),
+			Self::Custom { css } => format!("{LIGHT_THEME}{}", css.replace("</", "<\\/")),
+		}
+	}
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HtmlExportOptions {
+	print: bool,
+	theme: ExportTheme,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HtmlExport {
+	path: String,
+	capability_count: usize,
+	requirement_count: usize,
+}
+
+struct TocEntry {
+	level: usize,
+	anchor: String,
+	text: String,
+}
+
+struct Rendered {
+	html: String,
+	toc: Vec<TocEntry>,
+}
+
+fn escape(text: &str) -> String {
+	text.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+		.replace('"', "&quot;")
+}
+
+fn inline(text: &str) -> String {
+	let mut out = String::new();
+	for (index, segment) in text.split('`').enumerate() {
+		if index % 2 == 1 {
+			out.push_str(&format!("<code>{}</code>", escape(segment)));
+			continue;
+		}
+		for (index, part) in escape(segment).split("**").enumerate() {
+			if index % 2 == 1 {
+				out.push_str(&format!("<strong>{part}</strong>"));
+			} else {
+				out.push_str(part);
+			}
+		}
+	}
+	out
+}
+
+fn slug(text: &str) -> String {
+	text.to_lowercase()
+		.chars()
+		.map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+		.collect::<String>()
+		.split('-')
+		.filter(|part| !part.is_empty())
+		.collect::<Vec<_>>()
+		.join("-")
+}
+
+fn render_markdown(text: &str, prefix: &str) -> Rendered {
+	let mut html = String::new();
+	let mut toc = Vec::new();
+	let mut paragraph = Vec::new();
+	let mut in_list = false;
+	let mut in_fence = false;
+	let mut requirement_level = None;
+
+	let flush = |html: &mut String, paragraph: &mut Vec<String>, in_list: &mut bool| {
+		if !paragraph.is_empty() {
+			html.push_str(&format!("<p>{}</p>\n", paragraph.join(" ")));
+			paragraph.clear();
+		}
+		if *in_list {
+			html.push_str("</ul>\n");
+			*in_list = false;
+		}
+	};
+
+	for line in text.lines() {
+		if is_fence(line) {
+			flush(&mut html, &mut paragraph, &mut in_list);
+			html.push_str(if in_fence {
+				"</code></pre>\n"
+			} else {
+				"<pre><code>"
+			});
+			in_fence = !in_fence;
+			continue;
+		}
+		if in_fence {
+			html.push_str(&escape(line));
+			html.push('\n');
+			continue;
+		}
+		if let Some((level, heading)) = parse_heading(line) {
+			flush(&mut html, &mut paragraph, &mut in_list);
+			if requirement_level.is_some_and(|open| level <= open) {
+				html.push_str("</article>\n");
+				requirement_level = None;
+			}
+			let anchor = format!("{prefix}-{}", slug(heading));
+			if heading.starts_with(REQUIREMENT_PREFIX) {
+				html.push_str("<article class=\"requirement\">\n");
+				requirement_level = Some(level);
+			}
+			html.push_str(&format!(
+				"<h{level} id=\"{anchor}\">{}</h{level}>\n",
+				inline(heading)
+			));
+			toc.push(TocEntry {
+				level,
+				anchor,
+				text: heading.to_string(),
+			});
+			continue;
+		}
+		let trimmed = line.trim();
+		if trimmed.is_empty() {
+			flush(&mut html, &mut paragraph, &mut in_list);
+		} else if let Some(item) = trimmed.strip_prefix("- ") {
+			if !paragraph.is_empty() {
+				html.push_str(&format!("<p>{}</p>\n", paragraph.join(" ")));
+				paragraph.clear();
+			}
+			if !in_list {
+				html.push_str("<ul>\n");
+				in_list = true;
+			}
+			html.push_str(&format!("<li>{}</li>\n", inline(item)));
+		} else {
+			paragraph.push(inline(trimmed));
+		}
+	}
+	flush(&mut html, &mut paragraph, &mut in_list);
+	if in_fence {
+		html.push_str("</code></pre>\n");
+	}
+	if requirement_level.is_some() {
+		html.push_str("</article>\n");
+	}
+	Rendered { html, toc }
+}
+
+fn render_toc(capabilities: &[(String, Rendered)]) -> String {
+	let mut html = String::from("<nav class=\"toc\">\n<h2>Contents</h2>\n<ol>\n");
+	for (name, rendered) in capabilities {
+		html.push_str(&format!(
+			"<li><a href=\"#cap-{}\">{}</a>\n<ol>\n",
+			slug(name),
+			escape(name)
+		));
+		for entry in rendered
+			.toc
+			.iter()
+			.filter(|entry| entry.level > 1 && entry.text.starts_with(REQUIREMENT_PREFIX))
+		{
+			html.push_str(&format!(
+				"<li><a href=\"#{}\">{}</a></li>\n",
+				entry.anchor,
+				inline(entry.text.trim_start_matches(REQUIREMENT_PREFIX).trim())
+			));
+		}
+		html.push_str("</ol>\n</li>\n");
+	}
+	html.push_str("</ol>\n</nav>\n");
+	html
+}
+
+fn logo_data_url(project: &Path, logo: &str) -> Option<String> {
+	let path = project.join(logo);
+	let extension = path.extension()?.to_string_lossy().to_lowercase();
+	let mime = preview::image_mime(&extension)?;
+	let bytes = fs::read(&path).ok()?;
+	Some(format!("data:{mime};base64,{}", STANDARD.encode(bytes)))
+}
+
+fn render_document(
+	project_name: &str,
+	logo: Option<&str>,
+	commit: Option<&str>,
+	capabilities: &[(String, Rendered)],
+	options: &HtmlExportOptions,
+) -> String {
+	let running = match commit {
+		Some(commit) => format!("{} — {commit}", escape(project_name)),
+		None => escape(project_name),
+	};
+	let mut html = format!(
+		"<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{} specifications</title>\n<style>{}{SCREEN_STYLE}{}</style>\n</head>\n<body>\n",
+		escape(project_name),
+		options.theme.css(),
+		if options.print { PRINT_STYLE } else { "" }
+	);
+	if options.print {
+		html.push_str(&format!("<header class=\"running\">{running}</header>\n"));
+		html.push_str("<footer class=\"running\">Exported from SpecOps</footer>\n");
+	}
+	if let Some(logo) = logo {
+		html.push_str(&format!("<img class=\"logo\" src=\"{logo}\" alt=\"\">\n"));
+	}
+	html.push_str(&format!(
+		"<h1>{} specifications</h1>\n",
+		escape(project_name)
+	));
+	if let Some(commit) = commit {
+		html.push_str(&format!("<p>Commit <code>{commit}</code></p>\n"));
+	}
+	html.push_str(&render_toc(capabilities));
+	for (name, rendered) in capabilities {
+		html.push_str(&format!(
+			"<section class=\"capability\" id=\"cap-{}\">\n{}</section>\n",
+			slug(name),
+			rendered.html
+		));
+	}
+	html.push_str("</body>\n</html>\n");
+	html
+}
+
+fn export_html(
+	project: &Path,
+	dest: &Path,
+	options: &HtmlExportOptions,
+	branding: &ExportBranding,
+	cancel: &CancellationToken,
+) -> Result<HtmlExport, SpecError> {
+	let specs = existing_openspec_root(project)?.join("specs");
+	let mut capabilities = Vec::new();
+	if specs.is_dir() {
+		for file in markdown_files(&specs)? {
+			cancel.check()?;
+			let name = file
+				.parent()
+				.and_then(|dir| dir.strip_prefix(&specs).ok())
+				.map(|dir| dir.to_string_lossy().replace('\\', "/"))
+				.unwrap_or_default();
+			let text = SourceText::read_complete(&file)?.render();
+			let rendered = render_markdown(&text, &slug(&name));
+			capabilities.push((name, rendered));
+		}
+	}
+	let project_name = branding.name.clone().unwrap_or_else(|| {
+		project
+			.file_name()
+			.map(|name| name.to_string_lossy().to_string())
+			.unwrap_or_default()
+	});
+	let logo = branding
+		.logo
+		.as_deref()
+		.and_then(|logo| logo_data_url(project, logo));
+	let commit = git_output(project, &["rev-parse", "--short", "HEAD"])
+		.ok()
+		.map(|commit| commit.trim().to_string());
+	let html = render_document(
+		&project_name,
+		logo.as_deref(),
+		commit.as_deref(),
+		&capabilities,
+		options,
+	);
+	storage::write_atomic(dest, html.as_bytes())?;
+	Ok(HtmlExport {
+		path: dest.to_string_lossy().to_string(),
+		capability_count: capabilities.len(),
+		requirement_count: capabilities
+			.iter()
+			.flat_map(|(_, rendered)| &rendered.toc)
+			.filter(|entry| entry.text.starts_with(REQUIREMENT_PREFIX))
+			.count(),
+	})
+}
+
+#[tauri::command(async)]
+pub fn export_specs_html(
+	window: Window,
+	settings: State<'_, SettingsStore>,
+	project: String,
+	dest: String,
+	options: Option<HtmlExportOptions>,
+) -> Result<HtmlExport, SpecErrorPayload> {
+	let branding = settings
+		.get()
+		.export_branding
+		.remove(&project)
+		.unwrap_or_default();
+	let options = options.unwrap_or_default();
+	operations::run_native(
+		&window,
+		"html-export",
+		format!("export {project}"),
+		|cancel| {
+			export_html(
+				Path::new(&project),
+				Path::new(&dest),
+				&options,
+				&branding,
+				cancel,
+			)
+		},
+	)
+	.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn export_branding(settings: State<'_, SettingsStore>, project: String) -> ExportBranding {
+	settings
+		.get()
+		.export_branding
+		.remove(&project)
+		.unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn set_export_branding(
+	settings: State<'_, SettingsStore>,
+	project: String,
+	branding: ExportBranding,
+) -> Result<ExportBranding, SpecErrorPayload> {
+	settings
+		.update(|current| {
+			current
+				.export_branding
+				.insert(project.clone(), branding.clone())
+		})
+		.map_err(|error| SpecErrorPayload::from(SpecError::from(error)))?;
+	Ok(branding)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn renders_requirements_as_breakable_articles() {
+		let rendered = render_markdown(
+			"# Auth\n\n### Requirement: Login <SSO>\nUsers **SHALL** sign in.\n\n#### Scenario: Works\n- WHEN `ok`\n\n### Requirement: Logout\nText.\n",
+			"auth",
+		);
+		assert_eq!(
+			rendered
+				.html
+				.matches("<article class=\"requirement\">")
+				.count(),
+			2
+		);
+		assert_eq!(rendered.html.matches("</article>").count(), 2);
+		assert!(rendered
+			.html
+			.contains("<h3 id=\"auth-requirement-login-sso\">Requirement: Login &lt;SSO&gt;</h3>"));
+		assert!(rendered
+			.html
+			.contains("<p>Users <strong>SHALL</strong> sign in.</p>"));
+		assert!(rendered.html.contains("<li>WHEN <code>ok</code></li>"));
+	}
+
+	#[test]
+	fn print_export_adds_toc_page_breaks_and_running_header() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let spec_dir = temp_dir.path().join("openspec/specs/auth");
+		fs::create_dir_all(&spec_dir).expect("create specs");
+		fs::write(
+			spec_dir.join("spec.md"),
+			"# auth\n\n### Requirement: Login\nThe system SHALL log in.\n",
+		)
+		.expect("write spec");
+
+		let dest = temp_dir.path().join("out/specs.html");
+		let options = HtmlExportOptions {
+			print: true,
+			..HtmlExportOptions::default()
+		};
+		let export = export_html(
+			temp_dir.path(),
+			&dest,
+			&options,
+			&ExportBranding::default(),
+			&CancellationToken::default(),
+		)
+		.expect("export");
+		assert_eq!(export.capability_count, 1);
+		assert_eq!(export.requirement_count, 1);
+
+		let html = fs::read_to_string(&dest).expect("read html");
+		assert!(html.contains("<a href=\"#auth-requirement-login\">Login</a>"));
+		assert!(html.contains("section.capability{break-before:page}"));
+		assert!(html.contains("target-counter(attr(href),page)"));
+		assert!(html.contains("<header class=\"running\">"));
+
+		export_html(
+			temp_dir.path(),
+			&dest,
+			&HtmlExportOptions::default(),
+			&ExportBranding::default(),
+			&CancellationToken::default(),
+		)
+		.expect("export");
+		let html = fs::read_to_string(&dest).expect("read html");
+		assert!(!html.contains("break-before:page"));
+	}
+
+	#[test]
+	fn applies_theme_and_project_branding() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		fs::create_dir_all(temp_dir.path().join("openspec/specs")).expect("create specs");
+		fs::write(temp_dir.path().join("logo.png"), [0x89, b'P', b'N', b'G']).expect("write logo");
+		let dest = temp_dir.path().join("specs.html");
+		let branding = ExportBranding {
+			name: Some("Acme Platform".to_string()),
+			logo: Some("logo.png".to_string()),
+		};
+
+		let options = serde_json::from_str::<HtmlExportOptions>(r#"{"theme":{"kind":"dark"}}"#)
+			.expect("options");
+		export_html(
+			temp_dir.path(),
+			&dest,
+			&options,
+			&branding,
+			&CancellationToken::default(),
+		)
+		.expect("export");
+		let html = fs::read_to_string(&dest).expect("read html");
+		assert!(html.contains("--bg:#0d1117"));
+		assert!(html.contains("<h1>Acme Platform specifications</h1>"));
+		assert!(html.contains("<img class=\"logo\" src=\"data:image/png;base64,iVBORw==\""));
+
+		let options = serde_json::from_str::<HtmlExportOptions>(
+			r#"{"theme":{"kind":"custom","css":"h1{color:teal}</style>"}}"#,
+		)
+		.expect("options");
+		export_html(
+			temp_dir.path(),
+			&dest,
+			&options,
+			&ExportBranding::default(),
+			&CancellationToken::default(),
+		)
+		.expect("export");
+		let html = fs::read_to_string(&dest).expect("read html");
+		assert!(html.contains("--bg:#ffffff"));
+		assert!(html.contains("h1{color:teal}<\\/style>"));
+		assert_eq!(html.matches("</style>").count(), 1);
+	}
+}
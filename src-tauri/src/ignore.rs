@@ -0,0 +1,164 @@
+use crate::{
+	search::SearchState,
+	settings::SettingsStore,
+	spec::{SpecError, SpecErrorPayload},
+};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::Serialize;
+use std::path::Path;
+use tauri::State;
+
+pub const DEFAULT_IGNORE_PATTERNS: &[&str] = &[
+	"**/node_modules",
+	"**/.git",
+	"**/target",
+	"**/dist",
+	"**/.next",
+	"**/coverage",
+	"**/*.swp",
+	"**/*~",
+];
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IgnorePatterns {
+	project: String,
+	patterns: Vec<String>,
+	custom: bool,
+}
+
+pub struct IgnoreRules {
+	matcher: GlobSet,
+}
+
+impl Default for IgnoreRules {
+	fn default() -> Self {
+		Self::new(DEFAULT_IGNORE_PATTERNS).expect("default ignore patterns are valid")
+	}
+}
+
+impl IgnoreRules {
+	pub fn new<S: AsRef<str>>(patterns: &[S]) -> Result<Self, SpecError> {
+		let mut builder = GlobSetBuilder::new();
+		for pattern in patterns {
+			let pattern = pattern.as_ref().trim().trim_end_matches('/');
+			let glob = GlobBuilder::new(pattern)
+				.literal_separator(true)
+				.build()
+				.map_err(|_| SpecError::InvalidIgnorePattern(pattern.to_string()))?;
+			builder.add(glob);
+		}
+		Ok(Self {
+			matcher: builder
+				.build()
+				.map_err(|_| SpecError::InvalidIgnorePattern(String::new()))?,
+		})
+	}
+
+	pub fn is_ignored(&self, project: &Path, path: &Path) -> bool {
+		let relative = path.strip_prefix(project).unwrap_or(path);
+		relative
+			.ancestors()
+			.filter(|ancestor| !ancestor.as_os_str().is_empty())
+			.any(|ancestor| self.matcher.is_match(ancestor))
+	}
+}
+
+fn project_key(project: &Path) -> String {
+	project.to_string_lossy().to_string()
+}
+
+fn patterns_for(settings: &SettingsStore, project: &Path) -> IgnorePatterns {
+	let custom = settings
+		.get()
+		.ignore_patterns
+		.get(&project_key(project))
+		.cloned();
+	IgnorePatterns {
+		project: project_key(project),
+		custom: custom.is_some(),
+		patterns: custom.unwrap_or_else(|| {
+			DEFAULT_IGNORE_PATTERNS
+				.iter()
+				.map(|pattern| pattern.to_string())
+				.collect()
+		}),
+	}
+}
+
+pub fn rules_for(settings: &SettingsStore, project: &Path) -> IgnoreRules {
+	IgnoreRules::new(&patterns_for(settings, project).patterns).unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn get_ignore_patterns(settings: State<'_, SettingsStore>, project: String) -> IgnorePatterns {
+	patterns_for(&settings, Path::new(&project))
+}
+
+#[tauri::command]
+pub fn set_ignore_patterns(
+	settings: State<'_, SettingsStore>,
+	search: State<'_, SearchState>,
+	project: String,
+	patterns: Vec<String>,
+) -> Result<IgnorePatterns, SpecErrorPayload> {
+	let patterns = patterns
+		.into_iter()
+		.map(|pattern| pattern.trim().to_string())
+		.filter(|pattern| !pattern.is_empty())
+		.collect::<Vec<_>>();
+	IgnoreRules::new(&patterns)?;
+	settings
+		.update(|current| {
+			current
+				.ignore_patterns
+				.insert(project.clone(), patterns.clone())
+		})
+		.map_err(|error| SpecErrorPayload::from(SpecError::from(error)))?;
+	search.invalidate(Path::new(&project));
+	Ok(patterns_for(&settings, Path::new(&project)))
+}
+
+#[tauri::command]
+pub fn reset_ignore_patterns(
+	settings: State<'_, SettingsStore>,
+	search: State<'_, SearchState>,
+	project: String,
+) -> Result<IgnorePatterns, SpecErrorPayload> {
+	settings
+		.update(|current| current.ignore_patterns.remove(&project))
+		.map_err(|error| SpecErrorPayload::from(SpecError::from(error)))?;
+	search.invalidate(Path::new(&project));
+	Ok(patterns_for(&settings, Path::new(&project)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn ignores_paths_under_matching_directories() {
+		let project = Path::new("/work/app");
+		let rules = IgnoreRules::default();
+		assert!(rules.is_ignored(
+			project,
+			Path::new("/work/app/openspec/examples/node_modules/pkg/README.md")
+		));
+		assert!(rules.is_ignored(
+			project,
+			Path::new("/work/app/openspec/specs/auth/.spec.md.swp")
+		));
+		assert!(!rules.is_ignored(project, Path::new("/work/app/openspec/specs/auth/spec.md")));
+
+		let rules = IgnoreRules::new(&["openspec/changes/archive/"]).expect("rules");
+		assert!(rules.is_ignored(
+			project,
+			Path::new("/work/app/openspec/changes/archive/old/proposal.md")
+		));
+		assert!(!rules.is_ignored(project, Path::new("/work/app/docs/changes/archive/x.md")));
+		assert!(matches!(
+			IgnoreRules::new(&["specs/[unclosed"]),
+			Err(SpecError::InvalidIgnorePattern(_))
+		));
+	}
+}
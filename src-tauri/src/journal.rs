@@ -0,0 +1,1538 @@
+use crate::{
+	crypto::{project_cipher, ArtifactCipher},
+	settings::{HistoryPrivacy, JournalRetention, SettingsStore},
+	spec::{SpecError, SpecErrorPayload},
+	storage::{self, StorageError},
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	env,
+	fs::{self, File, OpenOptions},
+	io::{BufRead, BufReader, Write},
+	path::{Path, PathBuf},
+	process::Output,
+	sync::Mutex,
+};
+use tauri::State;
+
+const RECORD: &str = "operation.json";
+const BACKUP_DIR: &str = "backup";
+const LOG: &str = "output.jsonl";
+const MAX_LOG_PAGE: usize = 1000;
+const MAX_HISTORY_PAGE: usize = 200;
+const MAX_OUTPUT_LINES: usize = 20_000;
+const PROBE: &str = "probe";
+const DAY_SECS: u64 = 24 * 60 * 60;
+const SCRUBBED: &str = "***";
+// Values this short (`1`, `dev`) would blank out unrelated output.
+const MIN_SECRET_LEN: usize = 4;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum OperationStatus {
+	Running,
+	Succeeded,
+	Failed,
+	Cancelled,
+	RolledBack,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupManifest {
+	paths: Vec<String>,
+	files: Vec<String>,
+	dirs: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationRecord {
+	pub id: String,
+	pub operation: String,
+	pub project: Option<String>,
+	#[serde(default)]
+	pub command: String,
+	#[serde(default)]
+	pub actor: Option<String>,
+	#[serde(default)]
+	pub cwd: Option<String>,
+	pub status: OperationStatus,
+	#[serde(default)]
+	pub exit_code: Option<i32>,
+	pub started_at: u64,
+	pub finished_at: Option<u64>,
+	#[serde(default)]
+	pub backup: Option<BackupManifest>,
+	/// Backups and output of encrypted projects are sealed with the
+	/// project's key, like its snapshots.
+	#[serde(default)]
+	pub sealed: bool,
+	#[serde(default)]
+	pub log_lines: Option<usize>,
+	#[serde(default)]
+	pub output_hash: Option<String>,
+	#[serde(default = "single_occurrence")]
+	pub occurrences: u32,
+}
+
+fn single_occurrence() -> u32 {
+	1
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct LogLine {
+	stream: String,
+	line: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogPage {
+	operation_id: String,
+	offset: usize,
+	total_lines: usize,
+	complete: bool,
+	lines: Vec<LogLine>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Scrubber {
+	home: Option<String>,
+	secrets: Vec<String>,
+}
+
+fn user_home() -> Option<String> {
+	let home = if cfg!(windows) {
+		env::var("USERPROFILE")
+	} else {
+		env::var("HOME")
+	};
+	home.ok()
+		.map(|home| home.trim_end_matches(['/', '\\']).to_string())
+		.filter(|home| !home.is_empty())
+}
+
+fn ends_path_segment(rest: &str) -> bool {
+	rest.chars()
+		.next()
+		.is_none_or(|c| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.')))
+}
+
+impl Scrubber {
+	pub fn new(privacy: HistoryPrivacy, env: &BTreeMap<String, String>) -> Self {
+		let mut secrets = if privacy.scrub_environment {
+			env.values()
+				.filter(|value| value.len() >= MIN_SECRET_LEN)
+				.cloned()
+				.collect::<Vec<_>>()
+		} else {
+			Vec::new()
+		};
+		secrets.sort_by_key(|secret| std::cmp::Reverse(secret.len()));
+		Self {
+			home: privacy.scrub_home_paths.then(user_home).flatten(),
+			secrets,
+		}
+	}
+
+	pub fn scrub(&self, text: &str) -> String {
+		let mut text = self.secrets.iter().fold(text.to_string(), |text, secret| {
+			text.replace(secret, SCRUBBED)
+		});
+		if let Some(home) = &self.home {
+			let mut out = String::with_capacity(text.len());
+			let mut rest = text.as_str();
+			while let Some(index) = rest.find(home.as_str()) {
+				out.push_str(&rest[..index]);
+				rest = &rest[index + home.len()..];
+				// `/home/al` must not rewrite `/home/alice`.
+				out.push_str(if ends_path_segment(rest) {
+					"~"
+				} else {
+					home.as_str()
+				});
+			}
+			out.push_str(rest);
+			text = out;
+		}
+		text
+	}
+}
+
+pub struct OperationLog {
+	file: Mutex<File>,
+	scrubber: Scrubber,
+	cipher: Option<ArtifactCipher>,
+}
+
+impl OperationLog {
+	pub fn create_in(root: &Path, id: &str) -> Result<Self, SpecError> {
+		let file = OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(operation_dir(root, id)?.join(LOG))?;
+		Ok(Self {
+			file: Mutex::new(file),
+			scrubber: Scrubber::default(),
+			cipher: None,
+		})
+	}
+
+	pub fn scrubbed(self, scrubber: Scrubber) -> Self {
+		Self { scrubber, ..self }
+	}
+
+	pub fn sealed(self, cipher: Option<ArtifactCipher>) -> Self {
+		Self { cipher, ..self }
+	}
+
+	pub fn append(&self, stream: &str, line: &str) {
+		let entry = LogLine {
+			stream: stream.to_string(),
+			line: self.scrubber.scrub(line),
+		};
+		let Ok(bytes) = serde_json::to_vec(&entry) else {
+			return;
+		};
+		let Ok(mut bytes) = encode_line(bytes, self.cipher.as_ref()) else {
+			return;
+		};
+		if let Ok(mut file) = self.file.lock() {
+			bytes.push(b'\n');
+			let _ = file.write_all(&bytes);
+		}
+	}
+}
+
+// Sealed lines are base64 so the log stays one entry per line.
+fn encode_line(bytes: Vec<u8>, cipher: Option<&ArtifactCipher>) -> Result<Vec<u8>, SpecError> {
+	match cipher {
+		Some(cipher) => Ok(STANDARD.encode(cipher.seal(&bytes)?).into_bytes()),
+		None => Ok(bytes),
+	}
+}
+
+fn decode_line(line: String, cipher: Option<&ArtifactCipher>) -> Result<LogLine, SpecError> {
+	let Some(cipher) = cipher else {
+		return Ok(serde_json::from_str(&line).unwrap_or(LogLine {
+			stream: "stdout".to_string(),
+			line,
+		}));
+	};
+	let sealed = STANDARD
+		.decode(line.trim())
+		.map_err(|_| SpecError::Encryption)?;
+	serde_json::from_slice(&cipher.open(&sealed)?).map_err(|_| SpecError::Encryption)
+}
+
+/// The cipher a journal entry was written with, or `None` for plaintext
+/// entries. Sealed entries whose project no longer has a key cannot be read.
+pub fn record_cipher(
+	settings: &SettingsStore,
+	record: &OperationRecord,
+) -> Result<Option<ArtifactCipher>, SpecError> {
+	if !record.sealed {
+		return Ok(None);
+	}
+	let project = record.project.as_deref().ok_or(SpecError::Encryption)?;
+	project_cipher(settings, Path::new(project))?
+		.map(Some)
+		.ok_or(SpecError::Encryption)
+}
+
+/// The cipher new journal entries for `project` are sealed with.
+pub fn project_journal_cipher(
+	settings: &SettingsStore,
+	project: Option<&Path>,
+) -> Result<Option<ArtifactCipher>, SpecError> {
+	Ok(match project {
+		Some(project) => project_cipher(settings, project)?,
+		None => None,
+	})
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AuditFormat {
+	Csv,
+	Json,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TimeRange {
+	from: Option<u64>,
+	to: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditExport {
+	format: AuditFormat,
+	count: usize,
+	content: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistoryQuery {
+	project: Option<String>,
+	operation: Option<String>,
+	status: Option<OperationStatus>,
+	#[serde(flatten)]
+	range: TimeRange,
+	offset: usize,
+	limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+	#[serde(flatten)]
+	record: OperationRecord,
+	duration_secs: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryPage {
+	total: usize,
+	entries: Vec<HistoryEntry>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationOutput {
+	#[serde(flatten)]
+	entry: HistoryEntry,
+	truncated: bool,
+	lines: Vec<LogLine>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RollbackReport {
+	operation_id: String,
+	removed: Vec<String>,
+	restored: Vec<String>,
+}
+
+pub fn journal_root() -> Result<PathBuf, SpecError> {
+	storage::specops_home()
+		.map(|home| home.join("journal"))
+		.ok_or(SpecError::Storage(StorageError::Unavailable))
+}
+
+fn relative_name(path: &Path) -> String {
+	path.to_string_lossy().replace('\\', "/")
+}
+
+fn collect(
+	project: &Path,
+	relative: &Path,
+	files: &mut BTreeSet<String>,
+	dirs: &mut BTreeSet<String>,
+) -> Result<(), SpecError> {
+	let metadata = match fs::symlink_metadata(project.join(relative)) {
+		Ok(metadata) => metadata,
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+		Err(error) => return Err(error.into()),
+	};
+	if !metadata.is_dir() {
+		files.insert(relative_name(relative));
+		return Ok(());
+	}
+	dirs.insert(relative_name(relative));
+	for entry in fs::read_dir(project.join(relative))? {
+		collect(project, &relative.join(entry?.file_name()), files, dirs)?;
+	}
+	Ok(())
+}
+
+pub fn collect_paths(
+	project: &Path,
+	paths: &[String],
+) -> Result<(BTreeSet<String>, BTreeSet<String>), SpecError> {
+	let mut files = BTreeSet::new();
+	let mut dirs = BTreeSet::new();
+	for path in paths {
+		collect(project, Path::new(path), &mut files, &mut dirs)?;
+	}
+	Ok((files, dirs))
+}
+
+fn copy_file(from: &Path, to: &Path, cipher: Option<&ArtifactCipher>) -> Result<(), SpecError> {
+	if let Some(parent) = to.parent() {
+		fs::create_dir_all(parent)?;
+	}
+	match cipher {
+		Some(cipher) => fs::write(to, cipher.seal(&fs::read(from)?)?)?,
+		None => {
+			fs::copy(from, to)?;
+		}
+	}
+	Ok(())
+}
+
+fn back_up(
+	project: &Path,
+	dir: &Path,
+	paths: &[&str],
+	cipher: Option<&ArtifactCipher>,
+) -> Result<BackupManifest, SpecError> {
+	let paths = paths
+		.iter()
+		.map(|path| path.to_string())
+		.collect::<Vec<_>>();
+	let (files, dirs) = collect_paths(project, &paths)?;
+	for file in &files {
+		copy_file(&project.join(file), &dir.join(file), cipher)?;
+	}
+	Ok(BackupManifest {
+		paths,
+		files: files.into_iter().collect(),
+		dirs: dirs.into_iter().collect(),
+	})
+}
+
+fn operation_dir(root: &Path, id: &str) -> Result<PathBuf, SpecError> {
+	if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+		return Err(SpecError::OperationNotFound);
+	}
+	Ok(root.join(id))
+}
+
+fn write_record(root: &Path, record: &OperationRecord) -> Result<(), SpecError> {
+	let bytes = serde_json::to_vec_pretty(record).map_err(StorageError::from)?;
+	storage::write_atomic(&operation_dir(root, &record.id)?.join(RECORD), &bytes)?;
+	Ok(())
+}
+
+pub fn load_in(root: &Path, id: &str) -> Result<OperationRecord, SpecError> {
+	let bytes = fs::read(operation_dir(root, id)?.join(RECORD))
+		.map_err(|_| SpecError::OperationNotFound)?;
+	serde_json::from_slice(&bytes).map_err(|_| SpecError::OperationNotFound)
+}
+
+fn current_actor() -> Option<String> {
+	env::var("USER")
+		.or_else(|_| env::var("USERNAME"))
+		.ok()
+		.filter(|user| !user.is_empty())
+}
+
+pub fn begin_in(
+	root: &Path,
+	operation: &str,
+	command: &str,
+	project: Option<&Path>,
+	backup_paths: &[&str],
+) -> Result<OperationRecord, SpecError> {
+	begin_scrubbed_in(
+		root,
+		operation,
+		command,
+		project,
+		backup_paths,
+		&Scrubber::default(),
+		None,
+	)
+}
+
+/// The project path is kept verbatim: rollback and history filters need it.
+pub fn begin_scrubbed_in(
+	root: &Path,
+	operation: &str,
+	command: &str,
+	project: Option<&Path>,
+	backup_paths: &[&str],
+	scrubber: &Scrubber,
+	cipher: Option<&ArtifactCipher>,
+) -> Result<OperationRecord, SpecError> {
+	let id = storage::new_id("op");
+	let backup = match project {
+		Some(project) if !backup_paths.is_empty() => Some(back_up(
+			project,
+			&operation_dir(root, &id)?.join(BACKUP_DIR),
+			backup_paths,
+			cipher,
+		)?),
+		_ => None,
+	};
+	let record = OperationRecord {
+		id,
+		operation: operation.to_string(),
+		project: project.map(|project| project.to_string_lossy().to_string()),
+		command: scrubber.scrub(command),
+		actor: current_actor(),
+		cwd: project
+			.map(Path::to_path_buf)
+			.or_else(|| env::current_dir().ok())
+			.map(|dir| scrubber.scrub(&dir.to_string_lossy())),
+		status: OperationStatus::Running,
+		exit_code: None,
+		started_at: storage::now_secs(),
+		finished_at: None,
+		backup,
+		sealed: cipher.is_some(),
+		log_lines: None,
+		output_hash: None,
+		occurrences: 1,
+	};
+	write_record(root, &record)?;
+	Ok(record)
+}
+
+pub fn finish_in(
+	root: &Path,
+	id: &str,
+	status: OperationStatus,
+	exit_code: Option<i32>,
+) -> Result<OperationRecord, SpecError> {
+	let mut record = load_in(root, id)?;
+	record.exit_code = exit_code;
+	record.status = status;
+	record.finished_at = Some(storage::now_secs());
+	record.log_lines = Some(count_log_lines(root, id)?);
+	write_record(root, &record)?;
+	Ok(record)
+}
+
+fn log_reader(root: &Path, id: &str) -> Result<Option<BufReader<File>>, SpecError> {
+	match File::open(operation_dir(root, id)?.join(LOG)) {
+		Ok(file) => Ok(Some(BufReader::new(file))),
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+		Err(error) => Err(error.into()),
+	}
+}
+
+fn count_log_lines(root: &Path, id: &str) -> Result<usize, SpecError> {
+	Ok(log_reader(root, id)?
+		.map(|reader| reader.lines().count())
+		.unwrap_or(0))
+}
+
+fn read_log_lines(
+	root: &Path,
+	id: &str,
+	offset: usize,
+	limit: usize,
+	cipher: Option<&ArtifactCipher>,
+) -> Result<Vec<LogLine>, SpecError> {
+	Ok(match log_reader(root, id)? {
+		Some(reader) => reader
+			.lines()
+			.skip(offset)
+			.take(limit)
+			.map(|line| decode_line(line?, cipher))
+			.collect::<Result<Vec<_>, SpecError>>()?,
+		None => Vec::new(),
+	})
+}
+
+// Refuses to read a sealed entry as plaintext, or to open a plaintext one.
+fn entry_cipher<'a>(
+	record: &OperationRecord,
+	cipher: Option<&'a ArtifactCipher>,
+) -> Result<Option<&'a ArtifactCipher>, SpecError> {
+	match (record.sealed, cipher) {
+		(true, None) => Err(SpecError::Encryption),
+		(true, cipher) => Ok(cipher),
+		(false, _) => Ok(None),
+	}
+}
+
+pub fn read_log_page_in(
+	root: &Path,
+	id: &str,
+	offset: usize,
+	limit: usize,
+	cipher: Option<&ArtifactCipher>,
+) -> Result<LogPage, SpecError> {
+	let record = load_in(root, id)?;
+	let cipher = entry_cipher(&record, cipher)?;
+	let total_lines = match record.log_lines {
+		Some(count) => count,
+		None => count_log_lines(root, id)?,
+	};
+	let lines = read_log_lines(root, id, offset, limit.min(MAX_LOG_PAGE), cipher)?;
+	Ok(LogPage {
+		operation_id: record.id,
+		offset,
+		total_lines,
+		complete: record.finished_at.is_some(),
+		lines,
+	})
+}
+
+// Restoring an older backup over a later operation's output would throw
+// that work away, so only the project's latest operation can roll back.
+fn superseded(root: &Path, record: &OperationRecord) -> bool {
+	list_in(root)
+		.into_iter()
+		.filter(|other| other.operation != PROBE && other.project == record.project)
+		.skip_while(|other| other.id != record.id)
+		.nth(1)
+		.is_some()
+}
+
+pub fn rollback_in(
+	root: &Path,
+	id: &str,
+	cipher: Option<&ArtifactCipher>,
+) -> Result<RollbackReport, SpecError> {
+	let mut record = load_in(root, id)?;
+	let cipher = entry_cipher(&record, cipher)?;
+	let (Some(project), Some(backup), OperationStatus::Failed | OperationStatus::Cancelled) =
+		(&record.project, &record.backup, record.status)
+	else {
+		return Err(SpecError::RollbackUnavailable);
+	};
+	if superseded(root, &record) {
+		return Err(SpecError::RollbackUnavailable);
+	}
+	let project = Path::new(project);
+	let backup_dir = operation_dir(root, id)?.join(BACKUP_DIR);
+	// Every backup is read before the project is touched, so a missing one
+	// leaves the tree as it was and the rollback can be retried.
+	let saved = backup
+		.files
+		.iter()
+		.map(|file| {
+			let contents = fs::read(backup_dir.join(file))?;
+			Ok((
+				file,
+				match cipher {
+					Some(cipher) => cipher.open(&contents)?,
+					None => contents,
+				},
+			))
+		})
+		.collect::<Result<Vec<_>, SpecError>>()?;
+	let (files, dirs) = collect_paths(project, &backup.paths)?;
+
+	let mut removed = Vec::new();
+	for file in files.iter().filter(|file| !backup.files.contains(file)) {
+		fs::remove_file(project.join(file))?;
+		removed.push(file.clone());
+	}
+	for dir in dirs.iter().rev().filter(|dir| !backup.dirs.contains(dir)) {
+		if fs::remove_dir(project.join(dir)).is_ok() {
+			removed.push(dir.clone());
+		}
+	}
+
+	let mut restored = Vec::new();
+	for (file, contents) in saved {
+		if fs::read(project.join(file)).ok().as_ref() != Some(&contents) {
+			storage::write_atomic(&project.join(file), &contents)?;
+			restored.push(file.clone());
+		}
+	}
+
+	// Only a complete restore retires the record; a failure above leaves it
+	// failed so the user can try again.
+	record.status = OperationStatus::RolledBack;
+	write_record(root, &record)?;
+	Ok(RollbackReport {
+		operation_id: record.id,
+		removed,
+		restored,
+	})
+}
+
+fn transcode_file(
+	path: &Path,
+	from: Option<&ArtifactCipher>,
+	to: Option<&ArtifactCipher>,
+) -> Result<(), SpecError> {
+	let contents = fs::read(path)?;
+	let plaintext = match from {
+		Some(cipher) => cipher.open(&contents)?,
+		None => contents,
+	};
+	let contents = match to {
+		Some(cipher) => cipher.seal(&plaintext)?,
+		None => plaintext,
+	};
+	storage::write_atomic(path, &contents)?;
+	Ok(())
+}
+
+fn transcode_log(
+	path: &Path,
+	from: Option<&ArtifactCipher>,
+	to: Option<&ArtifactCipher>,
+) -> Result<(), SpecError> {
+	let file = match File::open(path) {
+		Ok(file) => file,
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+		Err(error) => return Err(error.into()),
+	};
+	let mut contents = Vec::new();
+	for line in BufReader::new(file).lines() {
+		let entry = decode_line(line?, from)?;
+		let bytes = serde_json::to_vec(&entry).map_err(StorageError::from)?;
+		contents.extend(encode_line(bytes, to)?);
+		contents.push(b'\n');
+	}
+	storage::write_atomic(path, &contents)?;
+	Ok(())
+}
+
+/// Re-seals the finished journal entries of `project` when its encryption
+/// is switched on or off, so backups and output follow the project's key.
+pub fn transcode_in(
+	root: &Path,
+	project: &Path,
+	from: Option<&ArtifactCipher>,
+	to: Option<&ArtifactCipher>,
+) -> Result<(), SpecError> {
+	let project = project.to_string_lossy();
+	for mut record in list_in(root) {
+		if record.project.as_deref() != Some(&*project)
+			|| record.status == OperationStatus::Running
+			|| record.sealed == to.is_some()
+		{
+			continue;
+		}
+		let from = entry_cipher(&record, from)?;
+		let dir = operation_dir(root, &record.id)?;
+		if let Some(backup) = &record.backup {
+			for file in &backup.files {
+				transcode_file(&dir.join(BACKUP_DIR).join(file), from, to)?;
+			}
+		}
+		transcode_log(&dir.join(LOG), from, to)?;
+		record.sealed = to.is_some();
+		write_record(root, &record)?;
+	}
+	Ok(())
+}
+
+fn output_hash(output: &[u8]) -> String {
+	Sha256::digest(output)
+		.iter()
+		.map(|byte| format!("{byte:02x}"))
+		.collect()
+}
+
+pub fn record_probe_in(
+	root: &Path,
+	command: &str,
+	exit_code: Option<i32>,
+	output: &[u8],
+) -> Result<OperationRecord, SpecError> {
+	let hash = output_hash(output);
+	let previous = list_in(root)
+		.into_iter()
+		.rev()
+		.find(|record| record.operation == PROBE && record.command == command);
+	if let Some(mut record) = previous.filter(|record| {
+		record.exit_code == exit_code && record.output_hash.as_deref() == Some(&hash)
+	}) {
+		record.occurrences += 1;
+		record.finished_at = Some(storage::now_secs());
+		write_record(root, &record)?;
+		return Ok(record);
+	}
+	let mut record = begin_in(root, PROBE, command, None, &[])?;
+	record.status = if exit_code == Some(0) {
+		OperationStatus::Succeeded
+	} else {
+		OperationStatus::Failed
+	};
+	record.exit_code = exit_code;
+	record.output_hash = Some(hash);
+	record.finished_at = Some(record.started_at);
+	write_record(root, &record)?;
+	Ok(record)
+}
+
+pub fn prune_in(root: &Path, retention: JournalRetention, now: u64) -> Result<usize, SpecError> {
+	let mut records = list_in(root)
+		.into_iter()
+		.filter(|record| record.status != OperationStatus::Running)
+		.collect::<Vec<_>>();
+	records
+		.sort_by_key(|record| std::cmp::Reverse(record.finished_at.unwrap_or(record.started_at)));
+	let cutoff = now.saturating_sub(retention.max_age_days.saturating_mul(DAY_SECS));
+	let output_cutoff = now.saturating_sub(retention.output_max_age_days.saturating_mul(DAY_SECS));
+	let mut removed = 0;
+	for (index, record) in records.iter_mut().enumerate() {
+		let last_seen = record.finished_at.unwrap_or(record.started_at);
+		if index >= retention.max_entries || last_seen < cutoff {
+			fs::remove_dir_all(operation_dir(root, &record.id)?)?;
+			removed += 1;
+		} else if last_seen < output_cutoff {
+			let log = operation_dir(root, &record.id)?.join(LOG);
+			if log.exists() {
+				fs::remove_file(log)?;
+				record.log_lines = Some(0);
+				write_record(root, record)?;
+			}
+		}
+	}
+	Ok(removed)
+}
+
+pub fn purge_in(root: &Path, range: &TimeRange) -> Result<usize, SpecError> {
+	let mut removed = 0;
+	for record in list_in(root)
+		.into_iter()
+		.filter(|record| record.status != OperationStatus::Running)
+		.filter(|record| range.from.is_none_or(|from| record.started_at >= from))
+		.filter(|record| range.to.is_none_or(|to| record.started_at <= to))
+	{
+		fs::remove_dir_all(operation_dir(root, &record.id)?)?;
+		removed += 1;
+	}
+	Ok(removed)
+}
+
+pub fn record_probe(command: &str, output: &Output) {
+	if let Ok(root) = journal_root() {
+		let combined = [output.stdout.as_slice(), b"\0", output.stderr.as_slice()].concat();
+		let _ = record_probe_in(&root, command, output.status.code(), &combined);
+	}
+}
+
+pub fn prune(settings: &SettingsStore) {
+	if let Ok(root) = journal_root() {
+		let _ = prune_in(&root, settings.get().journal_retention, storage::now_secs());
+	}
+}
+
+pub fn list_in(root: &Path) -> Vec<OperationRecord> {
+	let mut records = fs::read_dir(root)
+		.map(|entries| {
+			entries
+				.filter_map(Result::ok)
+				.filter_map(|entry| fs::read(entry.path().join(RECORD)).ok())
+				.filter_map(|bytes| serde_json::from_slice::<OperationRecord>(&bytes).ok())
+				.collect::<Vec<_>>()
+		})
+		.unwrap_or_default();
+	records.sort_by(|a, b| (a.started_at, &a.id).cmp(&(b.started_at, &b.id)));
+	records
+}
+
+fn history_entry(record: OperationRecord) -> HistoryEntry {
+	HistoryEntry {
+		duration_secs: record
+			.finished_at
+			.map(|finished| finished.saturating_sub(record.started_at)),
+		record,
+	}
+}
+
+fn history_in(root: &Path, query: &HistoryQuery) -> HistoryPage {
+	let mut records = list_in(root)
+		.into_iter()
+		.filter(|record| record.operation != PROBE)
+		.filter(|record| {
+			query
+				.project
+				.as_ref()
+				.is_none_or(|project| record.project.as_ref() == Some(project))
+		})
+		.filter(|record| {
+			query
+				.operation
+				.as_ref()
+				.is_none_or(|operation| &record.operation == operation)
+		})
+		.filter(|record| query.status.is_none_or(|status| record.status == status))
+		.filter(|record| {
+			query
+				.range
+				.from
+				.is_none_or(|from| record.started_at >= from)
+		})
+		.filter(|record| query.range.to.is_none_or(|to| record.started_at <= to))
+		.collect::<Vec<_>>();
+	records.reverse();
+	HistoryPage {
+		total: records.len(),
+		entries: records
+			.into_iter()
+			.skip(query.offset)
+			.take(
+				query
+					.limit
+					.unwrap_or(MAX_HISTORY_PAGE)
+					.min(MAX_HISTORY_PAGE),
+			)
+			.map(history_entry)
+			.collect(),
+	}
+}
+
+fn output_in(
+	root: &Path,
+	id: &str,
+	cipher: Option<&ArtifactCipher>,
+) -> Result<OperationOutput, SpecError> {
+	let record = load_in(root, id)?;
+	let cipher = entry_cipher(&record, cipher)?;
+	let lines = read_log_lines(root, id, 0, MAX_OUTPUT_LINES + 1, cipher)?;
+	Ok(OperationOutput {
+		entry: history_entry(record),
+		truncated: lines.len() > MAX_OUTPUT_LINES,
+		lines: lines.into_iter().take(MAX_OUTPUT_LINES).collect(),
+	})
+}
+
+fn csv_field(value: &str) -> String {
+	if value.contains([',', '"', '\n', '\r']) {
+		format!("\"{}\"", value.replace('"', "\"\""))
+	} else {
+		value.to_string()
+	}
+}
+
+fn status_name(status: OperationStatus) -> &'static str {
+	match status {
+		OperationStatus::Running => "running",
+		OperationStatus::Succeeded => "succeeded",
+		OperationStatus::Failed => "failed",
+		OperationStatus::Cancelled => "cancelled",
+		OperationStatus::RolledBack => "rolledBack",
+	}
+}
+
+fn render_csv(records: &[OperationRecord]) -> String {
+	let mut out =
+		String::from("id,operation,project,actor,command,status,exitCode,startedAt,finishedAt\n");
+	for record in records {
+		let fields = [
+			record.id.clone(),
+			record.operation.clone(),
+			record.project.clone().unwrap_or_default(),
+			record.actor.clone().unwrap_or_default(),
+			record.command.clone(),
+			status_name(record.status).to_string(),
+			record
+				.exit_code
+				.map(|code| code.to_string())
+				.unwrap_or_default(),
+			record.started_at.to_string(),
+			record
+				.finished_at
+				.map(|finished| finished.to_string())
+				.unwrap_or_default(),
+		];
+		out.push_str(
+			&fields
+				.iter()
+				.map(|field| csv_field(field))
+				.collect::<Vec<_>>()
+				.join(","),
+		);
+		out.push('\n');
+	}
+	out
+}
+
+fn export_audit(
+	root: &Path,
+	project: &str,
+	range: &TimeRange,
+	format: AuditFormat,
+) -> Result<AuditExport, SpecError> {
+	let records = list_in(root)
+		.into_iter()
+		.filter(|record| record.operation != PROBE)
+		.filter(|record| record.project.as_deref() == Some(project) || record.project.is_none())
+		.filter(|record| range.from.is_none_or(|from| record.started_at >= from))
+		.filter(|record| range.to.is_none_or(|to| record.started_at <= to))
+		.collect::<Vec<_>>();
+	let content = match format {
+		AuditFormat::Csv => render_csv(&records),
+		AuditFormat::Json => serde_json::to_string_pretty(&records).map_err(StorageError::from)?,
+	};
+	Ok(AuditExport {
+		format,
+		count: records.len(),
+		content,
+	})
+}
+
+#[tauri::command]
+pub fn export_audit_log(
+	project: String,
+	range: Option<TimeRange>,
+	format: AuditFormat,
+) -> Result<AuditExport, SpecErrorPayload> {
+	journal_root()
+		.and_then(|root| export_audit(&root, &project, &range.unwrap_or_default(), format))
+		.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn read_operation_log_page(
+	settings: State<'_, SettingsStore>,
+	operation_id: String,
+	offset: usize,
+	limit: Option<usize>,
+) -> Result<LogPage, SpecErrorPayload> {
+	journal_root()
+		.and_then(|root| {
+			let cipher = record_cipher(&settings, &load_in(&root, &operation_id)?)?;
+			read_log_page_in(
+				&root,
+				&operation_id,
+				offset,
+				limit.unwrap_or(MAX_LOG_PAGE),
+				cipher.as_ref(),
+			)
+		})
+		.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn list_operation_history(
+	query: Option<HistoryQuery>,
+) -> Result<HistoryPage, SpecErrorPayload> {
+	journal_root()
+		.map(|root| history_in(&root, &query.unwrap_or_default()))
+		.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn get_operation_output(
+	settings: State<'_, SettingsStore>,
+	operation_id: String,
+) -> Result<OperationOutput, SpecErrorPayload> {
+	journal_root()
+		.and_then(|root| {
+			let cipher = record_cipher(&settings, &load_in(&root, &operation_id)?)?;
+			output_in(&root, &operation_id, cipher.as_ref())
+		})
+		.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn journal_retention(settings: State<'_, SettingsStore>) -> JournalRetention {
+	settings.get().journal_retention
+}
+
+#[tauri::command]
+pub fn set_journal_retention(
+	settings: State<'_, SettingsStore>,
+	retention: JournalRetention,
+) -> Result<JournalRetention, SpecErrorPayload> {
+	settings
+		.update(|current| current.journal_retention = retention)
+		.map_err(|error| SpecErrorPayload::from(SpecError::from(error)))?;
+	prune(&settings);
+	Ok(retention)
+}
+
+#[tauri::command]
+pub fn purge_history(range: Option<TimeRange>) -> Result<usize, SpecErrorPayload> {
+	journal_root()
+		.and_then(|root| purge_in(&root, &range.unwrap_or_default()))
+		.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn history_privacy(settings: State<'_, SettingsStore>) -> HistoryPrivacy {
+	settings.get().history_privacy
+}
+
+#[tauri::command]
+pub fn set_history_privacy(
+	settings: State<'_, SettingsStore>,
+	privacy: HistoryPrivacy,
+) -> Result<HistoryPrivacy, SpecErrorPayload> {
+	settings
+		.update(|current| current.history_privacy = privacy)
+		.map_err(|error| SpecErrorPayload::from(SpecError::from(error)))?;
+	Ok(privacy)
+}
+
+#[tauri::command]
+pub fn rollback_failed_init(
+	settings: State<'_, SettingsStore>,
+	operation_id: String,
+) -> Result<RollbackReport, SpecErrorPayload> {
+	journal_root()
+		.and_then(|root| {
+			let cipher = record_cipher(&settings, &load_in(&root, &operation_id)?)?;
+			rollback_in(&root, &operation_id, cipher.as_ref())
+		})
+		.map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn rolls_back_partial_init_to_prior_state() {
+		let journal = TempDir::new().expect("create journal dir");
+		let project = TempDir::new().expect("create project dir");
+		fs::write(project.path().join("AGENTS.md"), "# Agents\n").expect("write agents");
+
+		let record = begin_in(
+			journal.path(),
+			"init",
+			"openspec init",
+			Some(project.path()),
+			&["openspec", "AGENTS.md"],
+		)
+		.expect("begin");
+		fs::create_dir_all(project.path().join("openspec/specs")).expect("create openspec");
+		fs::write(project.path().join("openspec/project.md"), "x").expect("write project");
+		fs::write(project.path().join("AGENTS.md"), "rewritten").expect("rewrite agents");
+		finish_in(journal.path(), &record.id, OperationStatus::Failed, Some(1)).expect("finish");
+
+		let report = rollback_in(journal.path(), &record.id, None).expect("rollback");
+		assert_eq!(report.restored, vec!["AGENTS.md"]);
+		assert_eq!(report.removed.len(), 3);
+		assert!(!project.path().join("openspec").exists());
+		assert_eq!(
+			fs::read_to_string(project.path().join("AGENTS.md")).expect("read agents"),
+			"# Agents\n"
+		);
+		assert!(matches!(
+			rollback_in(journal.path(), &record.id, None),
+			Err(SpecError::RollbackUnavailable)
+		));
+	}
+
+	#[test]
+	fn seals_backups_and_output_of_encrypted_projects() {
+		let journal = TempDir::new().expect("create journal dir");
+		let project = TempDir::new().expect("create project dir");
+		fs::write(project.path().join("AGENTS.md"), "# Agents\n").expect("write agents");
+		let cipher = ArtifactCipher::from_key(&[3u8; 32]).expect("cipher");
+
+		let record = begin_scrubbed_in(
+			journal.path(),
+			"init",
+			"openspec init",
+			Some(project.path()),
+			&["AGENTS.md"],
+			&Scrubber::default(),
+			Some(&cipher),
+		)
+		.expect("begin");
+		assert!(record.sealed);
+		OperationLog::create_in(journal.path(), &record.id)
+			.expect("log")
+			.sealed(Some(cipher.clone()))
+			.append("stdout", "wrote AGENTS.md");
+		fs::write(project.path().join("AGENTS.md"), "rewritten").expect("rewrite agents");
+		finish_in(journal.path(), &record.id, OperationStatus::Failed, Some(1)).expect("finish");
+
+		let dir = journal.path().join(&record.id);
+		let backup = fs::read(dir.join(BACKUP_DIR).join("AGENTS.md")).expect("read backup");
+		assert_eq!(cipher.open(&backup).expect("open backup"), b"# Agents\n");
+		let log = fs::read_to_string(dir.join(LOG)).expect("read log");
+		assert!(!log.contains("AGENTS.md"));
+
+		for cipher in [
+			None,
+			Some(&ArtifactCipher::from_key(&[4u8; 32]).expect("cipher")),
+		] {
+			assert!(matches!(
+				read_log_page_in(journal.path(), &record.id, 0, 10, cipher),
+				Err(SpecError::Encryption)
+			));
+			assert!(rollback_in(journal.path(), &record.id, cipher).is_err());
+		}
+		let page =
+			read_log_page_in(journal.path(), &record.id, 0, 10, Some(&cipher)).expect("page");
+		assert_eq!(page.lines[0].line, "wrote AGENTS.md");
+		rollback_in(journal.path(), &record.id, Some(&cipher)).expect("rollback");
+		assert_eq!(
+			fs::read_to_string(project.path().join("AGENTS.md")).expect("read agents"),
+			"# Agents\n"
+		);
+	}
+
+	#[test]
+	fn transcodes_entries_when_encryption_is_toggled() {
+		let journal = TempDir::new().expect("create journal dir");
+		let project = TempDir::new().expect("create project dir");
+		fs::write(project.path().join("AGENTS.md"), "# Agents\n").expect("write agents");
+		let record = begin_in(
+			journal.path(),
+			"init",
+			"openspec init",
+			Some(project.path()),
+			&["AGENTS.md"],
+		)
+		.expect("begin");
+		OperationLog::create_in(journal.path(), &record.id)
+			.expect("log")
+			.append("stdout", "done");
+		finish_in(journal.path(), &record.id, OperationStatus::Failed, Some(1)).expect("finish");
+		let cipher = ArtifactCipher::from_key(&[5u8; 32]).expect("cipher");
+
+		transcode_in(journal.path(), project.path(), None, Some(&cipher)).expect("seal");
+		assert!(load_in(journal.path(), &record.id).expect("load").sealed);
+		let backup = journal
+			.path()
+			.join(&record.id)
+			.join(BACKUP_DIR)
+			.join("AGENTS.md");
+		assert_eq!(
+			cipher
+				.open(&fs::read(&backup).expect("read"))
+				.expect("open backup"),
+			b"# Agents\n"
+		);
+		let page =
+			read_log_page_in(journal.path(), &record.id, 0, 10, Some(&cipher)).expect("page");
+		assert_eq!(page.lines[0].line, "done");
+
+		transcode_in(journal.path(), project.path(), Some(&cipher), None).expect("unseal");
+		assert!(!load_in(journal.path(), &record.id).expect("load").sealed);
+		assert_eq!(fs::read(&backup).expect("read"), b"# Agents\n");
+		let page = read_log_page_in(journal.path(), &record.id, 0, 10, None).expect("page");
+		assert_eq!(page.lines[0].line, "done");
+	}
+
+	#[test]
+	fn refuses_rollback_for_successful_or_unknown_operations() {
+		let journal = TempDir::new().expect("create journal dir");
+		let project = TempDir::new().expect("create project dir");
+		let record = begin_in(
+			journal.path(),
+			"init",
+			"openspec init",
+			Some(project.path()),
+			&["openspec"],
+		)
+		.expect("begin");
+		finish_in(
+			journal.path(),
+			&record.id,
+			OperationStatus::Succeeded,
+			Some(0),
+		)
+		.expect("finish");
+		assert!(matches!(
+			rollback_in(journal.path(), &record.id, None),
+			Err(SpecError::RollbackUnavailable)
+		));
+		assert!(matches!(
+			rollback_in(journal.path(), "../escape", None),
+			Err(SpecError::OperationNotFound)
+		));
+	}
+
+	#[test]
+	fn refuses_rollback_once_a_later_operation_touched_the_project() {
+		let journal = TempDir::new().expect("create journal dir");
+		let project = TempDir::new().expect("create project dir");
+		fs::write(project.path().join("AGENTS.md"), "# Agents\n").expect("write agents");
+		let failed = begin_in(
+			journal.path(),
+			"init",
+			"openspec init",
+			Some(project.path()),
+			&["AGENTS.md"],
+		)
+		.expect("begin");
+		finish_in(journal.path(), &failed.id, OperationStatus::Failed, Some(1)).expect("finish");
+		record_probe_in(journal.path(), "openspec --version", Some(0), b"0.9.0")
+			.expect("record probe");
+
+		// A missing backup fails before anything in the project changes.
+		let backup = journal.path().join(&failed.id).join(BACKUP_DIR);
+		fs::rename(backup.join("AGENTS.md"), backup.join("moved")).expect("hide backup");
+		fs::write(project.path().join("AGENTS.md"), "rewritten").expect("rewrite agents");
+		assert!(matches!(
+			rollback_in(journal.path(), &failed.id, None),
+			Err(SpecError::Io(_))
+		));
+		assert_eq!(
+			load_in(journal.path(), &failed.id).expect("load").status,
+			OperationStatus::Failed
+		);
+		assert_eq!(
+			fs::read_to_string(project.path().join("AGENTS.md")).expect("read agents"),
+			"rewritten"
+		);
+		fs::rename(backup.join("moved"), backup.join("AGENTS.md")).expect("restore backup");
+
+		let mut later = begin_in(
+			journal.path(),
+			"update",
+			"openspec update",
+			Some(project.path()),
+			&[],
+		)
+		.expect("begin later");
+		later.started_at = failed.started_at + 1;
+		later.status = OperationStatus::Succeeded;
+		write_record(journal.path(), &later).expect("finish later");
+		assert!(matches!(
+			rollback_in(journal.path(), &failed.id, None),
+			Err(SpecError::RollbackUnavailable)
+		));
+	}
+
+	#[test]
+	fn exports_audit_log_filtered_by_project_and_range() {
+		let journal = TempDir::new().expect("create journal dir");
+		let project = TempDir::new().expect("create project dir");
+		let other = TempDir::new().expect("create other dir");
+		let record = begin_in(
+			journal.path(),
+			"init",
+			"openspec init \"my, app\"",
+			Some(project.path()),
+			&[],
+		)
+		.expect("begin");
+		finish_in(journal.path(), &record.id, OperationStatus::Failed, Some(2)).expect("finish");
+		begin_in(
+			journal.path(),
+			"init",
+			"openspec init",
+			Some(other.path()),
+			&[],
+		)
+		.expect("begin other");
+
+		let project_name = project.path().to_string_lossy().to_string();
+		let export = export_audit(
+			journal.path(),
+			&project_name,
+			&TimeRange::default(),
+			AuditFormat::Csv,
+		)
+		.expect("export csv");
+		assert_eq!(export.count, 1);
+		let row = export.content.lines().nth(1).expect("row");
+		assert!(row.contains(",\"openspec init \"\"my, app\"\"\",failed,2,"));
+
+		let future = TimeRange {
+			from: Some(storage::now_secs() + 60),
+			to: None,
+		};
+		let export = export_audit(journal.path(), &project_name, &future, AuditFormat::Json)
+			.expect("export json");
+		assert_eq!(export.count, 0);
+		assert_eq!(export.content, "[]");
+	}
+
+	#[test]
+	fn pages_through_persisted_operation_output() {
+		let journal = TempDir::new().expect("create journal dir");
+		let record = begin_in(journal.path(), "install", "npm install", None, &[]).expect("begin");
+		let log = OperationLog::create_in(journal.path(), &record.id).expect("create log");
+		for index in 0..25 {
+			log.append("stdout", &format!("line {index}"));
+		}
+		log.append("stderr", "warning");
+
+		let page = read_log_page_in(journal.path(), &record.id, 20, 10, None).expect("page");
+		assert_eq!(page.total_lines, 26);
+		assert!(!page.complete);
+		assert_eq!(page.lines.len(), 6);
+		assert_eq!(page.lines[0].line, "line 20");
+		assert_eq!(page.lines[5].stream, "stderr");
+
+		let finished = finish_in(
+			journal.path(),
+			&record.id,
+			OperationStatus::Succeeded,
+			Some(0),
+		)
+		.expect("finish");
+		assert_eq!(finished.log_lines, Some(26));
+		let page = read_log_page_in(journal.path(), &record.id, 40, 10, None).expect("page");
+		assert!(page.complete);
+		assert!(page.lines.is_empty());
+	}
+
+	#[test]
+	fn scrubs_environment_values_and_home_paths() {
+		let scrubber = Scrubber {
+			home: Some("/home/al".to_string()),
+			secrets: vec!["s3cret-token".to_string()],
+		};
+		assert_eq!(
+			scrubber.scrub("token=s3cret-token in /home/al/repo, not /home/alice"),
+			"token=*** in ~/repo, not /home/alice"
+		);
+		assert_eq!(scrubber.scrub("/home/al"), "~");
+
+		let env = BTreeMap::from([
+			("DEBUG".to_string(), "1".to_string()),
+			("NPM_TOKEN".to_string(), "abcd1234".to_string()),
+		]);
+		let off = Scrubber::new(HistoryPrivacy::default(), &env);
+		assert_eq!(off.scrub("abcd1234 1"), "abcd1234 1");
+		let on = Scrubber::new(
+			HistoryPrivacy {
+				scrub_environment: true,
+				scrub_home_paths: false,
+			},
+			&env,
+		);
+		assert_eq!(on.scrub("abcd1234 1"), "*** 1");
+
+		let journal = TempDir::new().expect("create journal dir");
+		let record = begin_scrubbed_in(
+			journal.path(),
+			"install",
+			"npm install --token abcd1234",
+			None,
+			&[],
+			&on,
+			None,
+		)
+		.expect("begin");
+		assert_eq!(record.command, "npm install --token ***");
+		let log = OperationLog::create_in(journal.path(), &record.id)
+			.expect("log")
+			.scrubbed(on);
+		log.append("stdout", "using abcd1234");
+		let page = read_log_page_in(journal.path(), &record.id, 0, 10, None).expect("page");
+		assert_eq!(page.lines[0].line, "using ***");
+	}
+
+	#[test]
+	fn prunes_old_outputs_and_purges_ranges() {
+		let journal = TempDir::new().expect("create journal dir");
+		let record = begin_in(journal.path(), "init", "openspec init", None, &[]).expect("begin");
+		OperationLog::create_in(journal.path(), &record.id)
+			.expect("log")
+			.append("stdout", "done");
+		finish_in(
+			journal.path(),
+			&record.id,
+			OperationStatus::Succeeded,
+			Some(0),
+		)
+		.expect("finish");
+
+		let retention = JournalRetention {
+			output_max_age_days: 1,
+			..JournalRetention::default()
+		};
+		let later = storage::now_secs() + 2 * DAY_SECS;
+		assert_eq!(
+			prune_in(journal.path(), retention, later).expect("prune"),
+			0
+		);
+		let kept = load_in(journal.path(), &record.id).expect("record kept");
+		assert_eq!(kept.log_lines, Some(0));
+		assert!(read_log_page_in(journal.path(), &record.id, 0, 10, None)
+			.expect("page")
+			.lines
+			.is_empty());
+
+		let running = begin_in(journal.path(), "init", "openspec init", None, &[]).expect("begin");
+		let future = TimeRange {
+			from: Some(storage::now_secs() + DAY_SECS),
+			to: None,
+		};
+		assert_eq!(purge_in(journal.path(), &future).expect("purge"), 0);
+		assert_eq!(
+			purge_in(journal.path(), &TimeRange::default()).expect("purge"),
+			1
+		);
+		let remaining = list_in(journal.path());
+		assert_eq!(remaining.len(), 1);
+		assert_eq!(remaining[0].id, running.id);
+	}
+
+	#[test]
+	fn collapses_repeated_probes_and_prunes_old_entries() {
+		let journal = TempDir::new().expect("create journal dir");
+		let first =
+			record_probe_in(journal.path(), "npm --version", Some(0), b"10.2.0").expect("probe");
+		let repeat =
+			record_probe_in(journal.path(), "npm --version", Some(0), b"10.2.0").expect("probe");
+		assert_eq!(repeat.id, first.id);
+		assert_eq!(repeat.occurrences, 2);
+		record_probe_in(journal.path(), "npm --version", Some(0), b"10.3.0").expect("probe");
+		assert_eq!(list_in(journal.path()).len(), 2);
+
+		let retention = JournalRetention {
+			max_entries: 1,
+			..JournalRetention::default()
+		};
+		assert_eq!(
+			prune_in(journal.path(), retention, storage::now_secs()).expect("prune"),
+			1
+		);
+		let far_future = storage::now_secs() + 31 * DAY_SECS;
+		assert_eq!(
+			prune_in(journal.path(), JournalRetention::default(), far_future).expect("prune"),
+			1
+		);
+		assert!(list_in(journal.path()).is_empty());
+	}
+
+	#[test]
+	fn lists_history_newest_first_with_output() {
+		let journal = TempDir::new().expect("create journal dir");
+		let project = TempDir::new().expect("create project dir");
+		let install = begin_in(
+			journal.path(),
+			"install",
+			"npm install -g openspec",
+			None,
+			&[],
+		)
+		.expect("begin install");
+		let log = OperationLog::create_in(journal.path(), &install.id).expect("create log");
+		log.append("stdout", "added 1 package");
+		finish_in(
+			journal.path(),
+			&install.id,
+			OperationStatus::Succeeded,
+			Some(0),
+		)
+		.expect("finish install");
+		let mut init = begin_in(
+			journal.path(),
+			"init",
+			"openspec init",
+			Some(project.path()),
+			&[],
+		)
+		.expect("begin init");
+		init.started_at += 1;
+		write_record(journal.path(), &init).expect("write init");
+		finish_in(journal.path(), &init.id, OperationStatus::Failed, Some(1)).expect("finish init");
+		record_probe_in(journal.path(), "openspec --version", Some(0), b"1.0.0").expect("probe");
+
+		let page = history_in(journal.path(), &HistoryQuery::default());
+		assert_eq!(page.total, 2);
+		assert_eq!(page.entries[0].record.id, init.id);
+		assert!(page.entries[1].duration_secs.is_some());
+		assert!(page.entries[1].record.cwd.is_some());
+		let failed = history_in(
+			journal.path(),
+			&HistoryQuery {
+				status: Some(OperationStatus::Failed),
+				..HistoryQuery::default()
+			},
+		);
+		assert_eq!(failed.total, 1);
+
+		let output = output_in(journal.path(), &install.id, None).expect("output");
+		assert!(!output.truncated);
+		assert_eq!(output.lines[0].line, "added 1 package");
+		assert!(matches!(
+			output_in(journal.path(), "missing", None),
+			Err(SpecError::OperationNotFound)
+		));
+	}
+}
@@ -0,0 +1,174 @@
+use crate::{
+	discover_and_record, projects::RecentProjects, DiscoveryCache, ProjectDiscovery,
+	ProjectDiscoveryErrorPayload,
+};
+use serde::Serialize;
+use std::{
+	env,
+	path::{Path, PathBuf},
+	sync::Mutex,
+};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct LaunchArgs {
+	path: Option<PathBuf>,
+	change: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchIntent {
+	path: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	project: Option<ProjectDiscovery>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	change_id: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	file: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	error: Option<ProjectDiscoveryErrorPayload>,
+}
+
+#[derive(Default)]
+pub struct PendingLaunch(Mutex<Option<LaunchIntent>>);
+
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> LaunchArgs {
+	let mut parsed = LaunchArgs::default();
+	let mut args = args.into_iter();
+	while let Some(arg) = args.next() {
+		if arg == "--change" {
+			parsed.change = args.next().filter(|id| !id.is_empty());
+		} else if let Some(id) = arg.strip_prefix("--change=") {
+			parsed.change = Some(id.to_string()).filter(|id| !id.is_empty());
+		} else if arg.starts_with('-') {
+			// Platform launchers append their own flags (e.g. macOS `-psn_*`).
+			continue;
+		} else if parsed.path.is_none() {
+			parsed.path = Some(PathBuf::from(arg));
+		}
+	}
+	parsed
+}
+
+fn absolute(path: &Path) -> PathBuf {
+	if path.is_absolute() {
+		return path.to_path_buf();
+	}
+	env::current_dir()
+		.map(|dir| dir.join(path))
+		.unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn resolve_intent(
+	recent: &RecentProjects,
+	cache: &DiscoveryCache,
+	path: &Path,
+	change: Option<String>,
+) -> LaunchIntent {
+	let path = absolute(path);
+	let (directory, file) = if path.is_file() {
+		let parent = path.parent().map(Path::to_path_buf).unwrap_or_default();
+		(parent, Some(path.to_string_lossy().to_string()))
+	} else {
+		(path.clone(), None)
+	};
+	let (project, error) = match discover_and_record(recent, cache, &directory) {
+		Ok(project) => (Some(project), None),
+		Err(error) => (None, Some(error)),
+	};
+	LaunchIntent {
+		path: path.to_string_lossy().to_string(),
+		project,
+		change_id: change,
+		file,
+		error,
+	}
+}
+
+fn publish(app: &AppHandle, intent: LaunchIntent) {
+	let _ = app.emit("openspec://launch-intent", &intent);
+	*app.state::<PendingLaunch>()
+		.0
+		.lock()
+		.unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(intent);
+}
+
+pub fn handle_args(app: &AppHandle, args: impl IntoIterator<Item = String>) {
+	let LaunchArgs { path, change } = parse_args(args);
+	let Some(path) = path else {
+		return;
+	};
+	let intent = resolve_intent(
+		&app.state::<RecentProjects>(),
+		&app.state::<DiscoveryCache>(),
+		&path,
+		change,
+	);
+	publish(app, intent);
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+pub fn handle_opened(app: &AppHandle, urls: Vec<tauri::Url>) {
+	let Some(path) = urls.iter().find_map(|url| url.to_file_path().ok()) else {
+		return;
+	};
+	let intent = resolve_intent(
+		&app.state::<RecentProjects>(),
+		&app.state::<DiscoveryCache>(),
+		&path,
+		None,
+	);
+	publish(app, intent);
+}
+
+#[tauri::command]
+pub fn take_launch_intent(pending: State<'_, PendingLaunch>) -> Option<LaunchIntent> {
+	pending
+		.0
+		.lock()
+		.unwrap_or_else(|poisoned| poisoned.into_inner())
+		.take()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn args(values: &[&str]) -> Vec<String> {
+		values.iter().map(|value| value.to_string()).collect()
+	}
+
+	#[test]
+	fn parses_path_and_change_flags() {
+		assert_eq!(parse_args(args(&[])), LaunchArgs::default());
+		assert_eq!(
+			parse_args(args(&["/repo", "--change", "add-auth"])),
+			LaunchArgs {
+				path: Some(PathBuf::from("/repo")),
+				change: Some("add-auth".to_string()),
+			}
+		);
+		assert_eq!(
+			parse_args(args(&["-psn_0_12345", "--change=fix-io", "repo", "other"])),
+			LaunchArgs {
+				path: Some(PathBuf::from("repo")),
+				change: Some("fix-io".to_string()),
+			}
+		);
+		assert_eq!(parse_args(args(&["repo", "--change"])).change, None);
+	}
+
+	#[test]
+	fn resolves_files_to_their_project() {
+		let temp = tempfile::TempDir::new().expect("create temp dir");
+		let file = temp.path().join("spec.md");
+		std::fs::write(&file, "# Spec\n").expect("write spec");
+		let recent = RecentProjects::load(None);
+		let intent = resolve_intent(&recent, &DiscoveryCache::default(), &file, None);
+		assert_eq!(intent.file, Some(file.to_string_lossy().to_string()));
+		assert!(intent.project.is_none());
+		assert!(intent.error.is_some());
+		assert!(recent.get().is_empty());
+	}
+}
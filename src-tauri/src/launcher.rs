@@ -0,0 +1,262 @@
+use crate::{
+	ci::detect_package_manager,
+	container, openspec_package, probe_version,
+	settings::SettingsStore,
+	spec::{SpecError, SpecErrorPayload},
+	OPENSPEC_PACKAGE,
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+	fs, io,
+	path::Path,
+	process::Command,
+	sync::atomic::{AtomicBool, Ordering},
+};
+use tauri::State;
+
+static ON_DEMAND: AtomicBool = AtomicBool::new(false);
+
+/// Files a project-local install rewrites, backed up before installing.
+pub const MANIFEST_FILES: &[&str] = &[
+	"package.json",
+	"package-lock.json",
+	"pnpm-lock.yaml",
+	"yarn.lock",
+	"bun.lock",
+	"bun.lockb",
+];
+
+#[derive(Clone, Copy, Debug, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CliMode {
+	#[default]
+	Global,
+	Project,
+	OnDemand,
+}
+
+/// How `openspec` is started: directly from `PATH`, or through the project's
+/// package manager when the repo pins OpenSpec as a dependency.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpenSpecLauncher {
+	pub mode: CliMode,
+	pub program: String,
+	prefix: Vec<String>,
+}
+
+impl OpenSpecLauncher {
+	fn global() -> Self {
+		Self {
+			mode: CliMode::Global,
+			program: "openspec".to_string(),
+			prefix: Vec::new(),
+		}
+	}
+
+	fn project(runner: &str) -> Self {
+		// `npx --no` refuses to download the package when the local install is
+		// missing instead of silently running whatever the registry serves.
+		let (program, prefix): (&str, &[&str]) = match runner {
+			"bun" => ("bunx", &["openspec"]),
+			"pnpm" => ("pnpm", &["exec", "openspec"]),
+			"yarn" => ("yarn", &["run", "openspec"]),
+			_ => ("npx", &["--no", "openspec"]),
+		};
+		Self {
+			mode: CliMode::Project,
+			program: program.to_string(),
+			prefix: prefix.iter().map(|arg| arg.to_string()).collect(),
+		}
+	}
+
+	// `npx -y` fetches the package into npm's cache on first use, so nothing
+	// is installed globally or into the project.
+	fn on_demand() -> Self {
+		Self {
+			mode: CliMode::OnDemand,
+			program: "npx".to_string(),
+			prefix: vec!["-y".to_string(), openspec_package("latest")],
+		}
+	}
+
+	pub fn args(&self, args: &[String]) -> Vec<String> {
+		self.prefix.iter().chain(args).cloned().collect()
+	}
+
+	/// An OpenSpec command started in `project` so the package manager finds
+	/// the local install; WSL and container projects run it inside their
+	/// distro or container.
+	pub fn command(
+		&self,
+		project: Option<&Path>,
+		args: &[impl AsRef<str>],
+	) -> Result<Command, SpecError> {
+		let args = args
+			.iter()
+			.map(|arg| arg.as_ref().to_string())
+			.collect::<Vec<_>>();
+		container::command(&self.program, &self.args(&args), project)
+	}
+
+	/// Maps a failed spawn to the missing piece: Node itself when OpenSpec is
+	/// fetched on demand, otherwise the CLI.
+	pub fn spawn_error(&self, error: io::Error) -> SpecError {
+		match error.kind() {
+			io::ErrorKind::NotFound if self.mode == CliMode::OnDemand => SpecError::NodeUnavailable,
+			io::ErrorKind::NotFound => SpecError::CliUnavailable,
+			_ => SpecError::Io(error),
+		}
+	}
+
+	pub fn command_line(&self) -> String {
+		std::iter::once(self.program.as_str())
+			.chain(self.prefix.iter().map(String::as_str))
+			.collect::<Vec<_>>()
+			.join(" ")
+	}
+}
+
+fn declares_openspec(project: &Path) -> bool {
+	let Some(manifest) = fs::read(project.join("package.json"))
+		.ok()
+		.and_then(|bytes| serde_json::from_slice::<Value>(&bytes).ok())
+	else {
+		return false;
+	};
+	["dependencies", "devDependencies"]
+		.iter()
+		.any(|section| manifest[section].get(OPENSPEC_PACKAGE).is_some())
+}
+
+fn has_local_bin(project: &Path) -> bool {
+	let bin = project.join("node_modules/.bin");
+	bin.join("openspec").is_file() || bin.join("openspec.cmd").is_file()
+}
+
+pub fn resolve(project: Option<&Path>) -> OpenSpecLauncher {
+	match project {
+		Some(project) if declares_openspec(project) || has_local_bin(project) => {
+			OpenSpecLauncher::project(detect_package_manager(project))
+		}
+		_ if ON_DEMAND.load(Ordering::SeqCst) => OpenSpecLauncher::on_demand(),
+		_ => OpenSpecLauncher::global(),
+	}
+}
+
+/// `npx` is a Node script, so a shim left behind without Node fails at run
+/// time rather than at spawn.
+pub fn node_missing(stderr: &str) -> bool {
+	let stderr = stderr.to_lowercase();
+	[
+		"env: node: no such file",
+		"env: 'node': no such file",
+		"node: not found",
+		"'node' is not recognized",
+	]
+	.iter()
+	.any(|needle| stderr.contains(needle))
+}
+
+pub fn set_on_demand(enabled: bool) {
+	ON_DEMAND.store(enabled, Ordering::SeqCst);
+}
+
+pub fn version(project: Option<&Path>) -> Option<String> {
+	let launcher = resolve(project);
+	probe_version(
+		launcher.command(project, &[] as &[&str]).ok()?,
+		&launcher.command_line(),
+	)
+}
+
+/// Arguments that add OpenSpec to the project's devDependencies.
+pub fn local_install_args(package_manager: &str, package: &str) -> Option<Vec<String>> {
+	let args = match package_manager {
+		"npm" => vec!["install", "--save-dev", package],
+		"bun" => vec!["add", "--dev", package],
+		"yarn" => vec!["add", "--dev", package],
+		"pnpm" => vec!["add", "--save-dev", package],
+		_ => return None,
+	};
+	Some(args.into_iter().map(String::from).collect())
+}
+
+#[tauri::command]
+pub fn on_demand_cli(settings: State<'_, SettingsStore>) -> bool {
+	settings.get().on_demand_cli
+}
+
+#[tauri::command]
+pub fn set_on_demand_cli(
+	settings: State<'_, SettingsStore>,
+	enabled: bool,
+) -> Result<bool, SpecErrorPayload> {
+	settings
+		.update(|current| current.on_demand_cli = enabled)
+		.map_err(|error| SpecErrorPayload::from(SpecError::from(error)))?;
+	set_on_demand(enabled);
+	Ok(enabled)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn runs_pinned_cli_through_the_project_package_manager() {
+		let project = TempDir::new().expect("create project dir");
+		assert_eq!(resolve(Some(project.path())), OpenSpecLauncher::global());
+		assert_eq!(resolve(None).mode, CliMode::Global);
+
+		fs::write(
+			project.path().join("package.json"),
+			r#"{"devDependencies":{"@fission-ai/openspec":"^0.16.0"}}"#,
+		)
+		.expect("write manifest");
+		let launcher = resolve(Some(project.path()));
+		assert_eq!(launcher.mode, CliMode::Project);
+		assert_eq!(launcher.command_line(), "npx --no openspec");
+		assert_eq!(
+			launcher.args(&["list".to_string()]),
+			["--no", "openspec", "list"]
+		);
+
+		fs::write(project.path().join("pnpm-lock.yaml"), "").expect("write lockfile");
+		assert_eq!(
+			resolve(Some(project.path())).command_line(),
+			"pnpm exec openspec"
+		);
+	}
+
+	#[test]
+	fn fetches_cli_on_demand_without_a_local_install() {
+		let launcher = OpenSpecLauncher::on_demand();
+		assert_eq!(
+			launcher.command_line(),
+			"npx -y @fission-ai/openspec@latest"
+		);
+		assert!(matches!(
+			launcher.spawn_error(io::ErrorKind::NotFound.into()),
+			SpecError::NodeUnavailable
+		));
+		assert!(matches!(
+			OpenSpecLauncher::global().spawn_error(io::ErrorKind::NotFound.into()),
+			SpecError::CliUnavailable
+		));
+		assert!(node_missing(
+			"/usr/bin/env: 'node': No such file or directory"
+		));
+		assert!(!node_missing("npm ERR! 404 Not Found"));
+	}
+
+	#[test]
+	fn builds_local_install_args() {
+		assert_eq!(
+			local_install_args("pnpm", "@fission-ai/openspec@latest").map(|args| args.join(" ")),
+			Some("add --save-dev @fission-ai/openspec@latest".to_string())
+		);
+		assert_eq!(local_install_args("cargo", "openspec"), None);
+	}
+}
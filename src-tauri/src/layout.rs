@@ -0,0 +1,141 @@
+use crate::{
+	spec::{SpecError, SpecErrorPayload},
+	storage,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+	fs,
+	path::{Component, Path, PathBuf},
+};
+
+pub const LAYOUT_CONFIG: &str = ".specops-layout.json";
+const DEFAULT_ROOT: &str = "openspec";
+pub const DETECTED_ROOTS: &[&str] = &[DEFAULT_ROOT, "docs/openspec", "specs/openspec"];
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LayoutConfig {
+	root: String,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum LayoutSource {
+	Configured,
+	Detected,
+	Default,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecLayout {
+	pub root: String,
+	pub source: LayoutSource,
+}
+
+fn valid_root(root: &str) -> bool {
+	let path = Path::new(root);
+	!root.trim().is_empty()
+		&& path
+			.components()
+			.all(|component| matches!(component, Component::Normal(_)))
+}
+
+fn configured_root(project: &Path) -> Option<String> {
+	let bytes = fs::read(project.join(LAYOUT_CONFIG)).ok()?;
+	let config = serde_json::from_slice::<LayoutConfig>(&bytes).ok()?;
+	let root = config.root.trim().trim_matches('/').to_string();
+	valid_root(&root).then_some(root)
+}
+
+pub fn resolve(project: &Path) -> SpecLayout {
+	if let Some(root) = configured_root(project) {
+		return SpecLayout {
+			root,
+			source: LayoutSource::Configured,
+		};
+	}
+	match DETECTED_ROOTS
+		.iter()
+		.find(|root| project.join(root).is_dir())
+	{
+		Some(root) => SpecLayout {
+			root: root.to_string(),
+			source: LayoutSource::Detected,
+		},
+		None => SpecLayout {
+			root: DEFAULT_ROOT.to_string(),
+			source: LayoutSource::Default,
+		},
+	}
+}
+
+pub fn root_path(project: &Path) -> PathBuf {
+	project.join(resolve(project).root)
+}
+
+fn set_layout(project: &Path, root: Option<&str>) -> Result<SpecLayout, SpecError> {
+	let path = project.join(LAYOUT_CONFIG);
+	match root.map(|root| root.trim().trim_matches('/')) {
+		Some(root) if !valid_root(root) => return Err(SpecError::InvalidLayout(root.to_string())),
+		Some(root) => {
+			let config = LayoutConfig {
+				root: root.to_string(),
+			};
+			let bytes = serde_json::to_vec_pretty(&config).map_err(storage::StorageError::from)?;
+			storage::write_atomic(&path, &bytes)?;
+		}
+		None => match fs::remove_file(&path) {
+			Err(error) if error.kind() != std::io::ErrorKind::NotFound => return Err(error.into()),
+			_ => {}
+		},
+	}
+	Ok(resolve(project))
+}
+
+#[tauri::command]
+pub fn spec_layout(project: String) -> SpecLayout {
+	resolve(Path::new(&project))
+}
+
+#[tauri::command]
+pub fn set_spec_layout(
+	project: String,
+	root: Option<String>,
+) -> Result<SpecLayout, SpecErrorPayload> {
+	set_layout(Path::new(&project), root.as_deref()).map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn prefers_configured_root_then_detected_then_default() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let project = temp_dir.path();
+		assert_eq!(resolve(project).source, LayoutSource::Default);
+		assert_eq!(root_path(project), project.join("openspec"));
+
+		fs::create_dir_all(project.join("docs/openspec")).expect("create docs");
+		assert_eq!(
+			resolve(project),
+			SpecLayout {
+				root: "docs/openspec".to_string(),
+				source: LayoutSource::Detected,
+			}
+		);
+
+		set_layout(project, Some("/design/specs/")).expect("configure");
+		assert_eq!(resolve(project).root, "design/specs");
+		assert_eq!(resolve(project).source, LayoutSource::Configured);
+		assert!(matches!(
+			set_layout(project, Some("../elsewhere")),
+			Err(SpecError::InvalidLayout(_))
+		));
+
+		set_layout(project, None).expect("reset");
+		assert_eq!(resolve(project).source, LayoutSource::Detected);
+	}
+}
@@ -1,21 +1,101 @@
+mod adopt;
+mod ansi;
+mod approvals;
+mod archive;
+#[cfg(any(test, feature = "bench"))]
+#[doc(hidden)]
+pub mod bench;
+mod bundle;
+mod cache;
+mod changes;
+mod checklist;
+mod ci;
+mod cli;
+mod codemap;
+mod container;
+mod crash;
+mod crypto;
+mod deadlines;
+mod diff;
+mod discovery;
+mod editing;
+mod exit;
+mod format;
+mod git;
+#[cfg(any(test, feature = "cli"))]
+#[doc(hidden)]
+pub mod headless;
+mod hooks;
+mod html;
+mod ignore;
+mod journal;
+mod launch;
+mod launcher;
+mod layout;
+mod lint;
+mod metrics;
+mod migrate;
+mod mounts;
+mod operations;
+mod ordering;
+mod outline;
+mod output;
+mod ownership;
+mod power;
+mod preview;
+mod process;
+mod progress;
+mod projects;
+mod pty;
+mod registry;
+mod remote;
+mod repair;
+mod retry;
+mod review;
+mod sarif;
+mod search;
+mod secrets;
+mod settings;
+mod snapshots;
+mod spec;
+mod storage;
+mod supervisor;
+mod tasks;
+mod timesheet;
+mod todos;
+mod tools;
+mod tree;
+mod upgrade;
+mod vcs;
+mod watcher;
+mod webhook;
+mod wsl;
+
 use serde::{Deserialize, Serialize};
 use std::{
+	collections::BTreeMap,
 	fs,
 	io::{BufRead, BufReader},
-	path::{Path, PathBuf},
+	path::Path,
 	process::{Command, Stdio},
 	sync::{Arc, Mutex},
 	thread,
+	time::Duration,
 };
-use tauri::{Emitter, Window};
+use tauri::{ipc::Invoke, Emitter, Manager, State, Window};
 use thiserror::Error;
 
-#[derive(Debug, Serialize)]
+#[derive(Clone, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct ProjectDiscovery {
 	repo_path: String,
 	repo_name: String,
 	openspec_present: bool,
+	vcs: vcs::VcsKind,
+	vcs_read_only: bool,
+	storage: mounts::StorageKind,
+	polling_watcher: bool,
+	remote: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -23,6 +103,7 @@ struct ProjectDiscovery {
 struct OpenSpecCliStatus {
 	available: bool,
 	version: Option<String>,
+	mode: launcher::CliMode,
 }
 
 #[derive(Debug, Serialize)]
@@ -35,26 +116,77 @@ struct PackageManagerStatus {
 
 #[derive(Clone, Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
-struct CliOutputEvent {
+struct CliRawOutputEvent {
 	operation: String,
-	stream: String,
-	line: String,
+	operation_id: String,
+	data: String,
 }
 
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 struct CommandRunOutput {
+	operation_id: String,
 	status: i32,
+	signal: Option<i32>,
+	termination: String,
+	log_lines: usize,
 	stdout: String,
 	stderr: String,
 }
 
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OperationStartedEvent {
+	operation: String,
+	operation_id: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OperationFinishedEvent {
+	operation: String,
+	operation_id: String,
+	status: journal::OperationStatus,
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct OpenSpecInitRequest {
 	path: String,
 	tools_mode: OpenSpecToolsMode,
 	tools: Vec<String>,
+	#[serde(default)]
+	env: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OpenSpecInstallRequest {
+	package_manager: String,
+	#[serde(default)]
+	env: BTreeMap<String, String>,
+	#[serde(default)]
+	project: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "operation", rename_all = "camelCase")]
+enum OperationPreviewRequest {
+	Install(OpenSpecInstallRequest),
+	Init(OpenSpecInitRequest),
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OperationPreview {
+	operation: String,
+	command: String,
+	args: Vec<String>,
+	command_line: String,
+	cwd: Option<String>,
+	env: BTreeMap<String, String>,
+	timeout_secs: Option<u64>,
+	pty: bool,
 }
 
 #[derive(Debug, Deserialize)]
@@ -77,6 +209,12 @@ struct ProjectDiscoveryErrorPayload {
 struct OpenSpecCommandErrorPayload {
 	code: String,
 	message: String,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	operation_id: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	stdout: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	stderr: Option<String>,
 }
 
 #[derive(Debug, Error)]
@@ -91,6 +229,12 @@ enum DiscoveryError {
 	NotGitWorkTree,
 	#[error("Could not resolve repository root")]
 	RepoRootUnavailable,
+	#[error("SSH is not available on this system")]
+	SshUnavailable,
+	#[error("Remote host could not be reached")]
+	RemoteUnreachable,
+	#[error("Remote project could not be synced")]
+	RemoteSyncFailed,
 	#[error(transparent)]
 	Io(#[from] std::io::Error),
 }
@@ -103,16 +247,40 @@ enum OpenSpecCommandError {
 	UnsupportedPackageManager,
 	#[error("Package manager is not available")]
 	PackageManagerUnavailable,
+	#[error("Node.js is not available")]
+	NodeUnavailable,
+	#[error("Version is not a release number or dist-tag")]
+	InvalidVersion,
 	#[error("No tools were selected")]
 	MissingToolsSelection,
-	#[error("Unable to parse tools list")]
-	ToolsParseFailed,
+	#[error("Change could not be found")]
+	ChangeNotFound,
+	#[error("Change has {present} of {required} required approvals")]
+	ApprovalsMissing { required: usize, present: usize },
+	#[error("File is open in an editing session")]
+	FileLocked,
 	#[error("Command failed: {command}")]
 	CommandFailed {
 		command: String,
 		status: i32,
+		reason: String,
 		stderr: String,
 	},
+	#[error("Operation was cancelled")]
+	Cancelled,
+	#[error("Command timed out: {command}")]
+	TimedOut {
+		command: String,
+		timeout_secs: u64,
+		stdout: String,
+		stderr: String,
+	},
+	#[error("Another operation is running for this project")]
+	Busy,
+	#[error("Execution backend is unavailable: {0}")]
+	ExecutionUnavailable(String),
+	#[error("Operation journal is unavailable")]
+	Journal(#[from] spec::SpecError),
 	#[error(transparent)]
 	Io(#[from] std::io::Error),
 }
@@ -127,6 +295,15 @@ impl From<DiscoveryError> for ProjectDiscoveryErrorPayload {
 			DiscoveryError::RepoRootUnavailable => {
 				("repo_root_unavailable", "Could not resolve repository root")
 			}
+			DiscoveryError::SshUnavailable => ("ssh_unavailable", "SSH is not available"),
+			DiscoveryError::RemoteUnreachable => (
+				"remote_unreachable",
+				"Could not connect to the remote host. Check that key-based SSH login works",
+			),
+			DiscoveryError::RemoteSyncFailed => (
+				"remote_sync_failed",
+				"Could not copy the project's specs from the remote host",
+			),
 			DiscoveryError::Io(_) => ("io_error", "File system error"),
 		};
 
@@ -139,6 +316,7 @@ impl From<DiscoveryError> for ProjectDiscoveryErrorPayload {
 
 impl From<OpenSpecCommandError> for OpenSpecCommandErrorPayload {
 	fn from(error: OpenSpecCommandError) -> Self {
+		let mut partial = None;
 		let (code, message) = match error {
 			OpenSpecCommandError::CliUnavailable => (
 				"openspec_unavailable",
@@ -152,36 +330,109 @@ impl From<OpenSpecCommandError> for OpenSpecCommandErrorPayload {
 				"package_manager_unavailable",
 				"Package manager is not available".to_string(),
 			),
+			OpenSpecCommandError::NodeUnavailable => (
+				"node_unavailable",
+				"Node.js is required to run OpenSpec on demand. Install Node.js or install the OpenSpec CLI"
+					.to_string(),
+			),
+			OpenSpecCommandError::InvalidVersion => (
+				"invalid_version",
+				"Version must be a release number or dist-tag".to_string(),
+			),
 			OpenSpecCommandError::MissingToolsSelection => {
 				("tools_missing", "Select at least one tool".to_string())
 			}
-			OpenSpecCommandError::ToolsParseFailed => (
-				"tools_parse_failed",
-				"Unable to parse the OpenSpec tools list".to_string(),
+			OpenSpecCommandError::ChangeNotFound => {
+				("change_not_found", "Change could not be found".to_string())
+			}
+			OpenSpecCommandError::ApprovalsMissing { required, present } => (
+				"approvals_missing",
+				format!(
+					"Change has {present} of {required} required approvals for its current content"
+				),
+			),
+			OpenSpecCommandError::FileLocked => (
+				"file_locked",
+				"File has unsaved edits in an open editing session".to_string(),
 			),
 			OpenSpecCommandError::CommandFailed {
 				command,
-				status,
+				reason,
 				stderr,
+				..
 			} => {
-				let mut message =
-					format!("{command} exited with status {status}");
+				let mut message = format!("{command} {reason}");
 				if !stderr.trim().is_empty() {
 					message.push_str(&format!(": {stderr}"));
 				}
 				("command_failed", message)
 			}
+			OpenSpecCommandError::Cancelled => {
+				("cancelled", "Operation was cancelled".to_string())
+			}
+			OpenSpecCommandError::TimedOut {
+				command,
+				timeout_secs,
+				stdout,
+				stderr,
+			} => {
+				partial = Some((stdout, stderr));
+				(
+					"timed_out",
+					format!("{command} did not finish within {timeout_secs}s and was stopped"),
+				)
+			}
+			OpenSpecCommandError::Busy => (
+				"operation_busy",
+				"Another operation is already running for this project".to_string(),
+			),
+			OpenSpecCommandError::ExecutionUnavailable(reason) => {
+				("execution_unavailable", reason)
+			}
+			OpenSpecCommandError::Journal(_) => (
+				"journal_unavailable",
+				"Operation journal could not be written".to_string(),
+			),
 			OpenSpecCommandError::Io(_) => ("io_error", "Command failed to run".to_string()),
 		};
 
+		let (stdout, stderr) = partial.unzip();
 		Self {
 			code: code.to_string(),
 			message,
+			operation_id: None,
+			stdout,
+			stderr,
 		}
 	}
 }
 
+// Remote projects are read from a local mirror; the mirror path is what the
+// rest of the app opens, while commands run on the remote host.
+fn discover_remote(target: remote::RemoteTarget) -> Result<ProjectDiscovery, DiscoveryError> {
+	let (root, mirror) = remote::discover(target)?;
+	let repo_name = Path::new(&root.path)
+		.file_name()
+		.map(|name| name.to_string_lossy().to_string())
+		.unwrap_or_else(|| root.path.clone());
+	Ok(ProjectDiscovery {
+		repo_path: mirror.to_string_lossy().to_string(),
+		repo_name,
+		openspec_present: spec::openspec_root(&mirror).is_dir(),
+		vcs: vcs::VcsKind::Git,
+		vcs_read_only: true,
+		storage: mounts::StorageKind::Network,
+		polling_watcher: false,
+		remote: Some(root.url()),
+	})
+}
+
 fn discover_project_info(path: &Path) -> Result<ProjectDiscovery, DiscoveryError> {
+	if let Some(target) =
+		remote::parse_url(&path.to_string_lossy()).or_else(|| remote::target_for(path))
+	{
+		return discover_remote(target);
+	}
 	if !path.exists() {
 		return Err(DiscoveryError::MissingPath);
 	}
@@ -189,43 +440,39 @@ fn discover_project_info(path: &Path) -> Result<ProjectDiscovery, DiscoveryError
 		return Err(DiscoveryError::NotDirectory);
 	}
 
-	let output = Command::new("git")
-		.arg("-C")
-		.arg(path)
-		.arg("rev-parse")
-		.arg("--show-toplevel")
-		.output()
-		.map_err(|_| DiscoveryError::GitUnavailable)?;
-
-	if !output.status.success() {
-		return Err(DiscoveryError::NotGitWorkTree);
-	}
-
-	let stdout = String::from_utf8_lossy(&output.stdout);
-	let repo_root_str = stdout.trim();
-	if repo_root_str.is_empty() {
+	let vcs = vcs::resolve_repo(path)?;
+	let repo_root = vcs.root;
+	if repo_root.as_os_str().is_empty() {
 		return Err(DiscoveryError::RepoRootUnavailable);
 	}
-
-	let repo_root = PathBuf::from(repo_root_str);
-	let openspec_present = fs::metadata(repo_root.join("openspec"))
+	let openspec_present = fs::metadata(spec::openspec_root(&repo_root))
 		.map(|metadata| metadata.is_dir())
 		.unwrap_or(false);
 	let repo_name = repo_root
 		.file_name()
-		.and_then(|name| name.to_str())
-		.unwrap_or(repo_root_str)
-		.to_string();
+		.map(|name| name.to_string_lossy().to_string())
+		.unwrap_or_else(|| repo_root.to_string_lossy().to_string());
+	let storage = mounts::storage_kind(&repo_root);
 
 	Ok(ProjectDiscovery {
 		repo_path: repo_root.to_string_lossy().to_string(),
 		repo_name,
 		openspec_present,
+		vcs: vcs.kind,
+		vcs_read_only: vcs.read_only,
+		storage,
+		polling_watcher: storage.is_slow(),
+		remote: None,
 	})
 }
 
 fn command_version(command: &str) -> Option<String> {
-	let output = Command::new(command).arg("--version").output().ok()?;
+	probe_version(Command::new(command), command)
+}
+
+fn probe_version(mut command: Command, label: &str) -> Option<String> {
+	let output = command.arg("--version").output().ok()?;
+	journal::record_probe(&format!("{label} --version"), &output);
 	if !output.status.success() {
 		return None;
 	}
@@ -249,45 +496,32 @@ fn package_manager_status(name: &str) -> PackageManagerStatus {
 	}
 }
 
-fn parse_openspec_tools(help_text: &str) -> Result<Vec<String>, OpenSpecCommandError> {
-	let mut lines = help_text.lines().peekable();
+const OPENSPEC_PACKAGE: &str = "@fission-ai/openspec";
 
-	while let Some(line) = lines.next() {
-		if let Some(index) = line.find("list of:") {
-			let mut segments = Vec::new();
-			let list = line[index + "list of:".len()..].trim();
-			if !list.is_empty() {
-				segments.push(list.to_string());
-			}
-
-			while let Some(next) = lines.peek() {
-				let trimmed = next.trim();
-				if trimmed.is_empty()
-					|| trimmed.starts_with('-')
-					|| trimmed.starts_with("Options:")
-					|| trimmed.starts_with("Usage:")
-				{
-					break;
-				}
-				segments.push(trimmed.to_string());
-				lines.next();
-			}
+fn openspec_package(version: &str) -> String {
+	format!("{OPENSPEC_PACKAGE}@{version}")
+}
 
-			let combined = segments.join(" ");
-			let tools = combined
-				.split(',')
-				.map(|entry| entry.trim().trim_end_matches('.'))
-				.filter(|entry| !entry.is_empty())
-				.map(String::from)
-				.collect::<Vec<_>>();
-			if tools.is_empty() {
-				return Err(OpenSpecCommandError::ToolsParseFailed);
-			}
-			return Ok(tools);
-		}
-	}
+fn global_install_args(package_manager: &str, package: &str) -> Option<Vec<String>> {
+	let args = match package_manager {
+		"npm" => vec!["install", "-g", package],
+		"bun" => vec!["add", "-g", package],
+		"yarn" => vec!["global", "add", package],
+		"pnpm" => vec!["add", "-g", package],
+		_ => return None,
+	};
+	Some(args.into_iter().map(String::from).collect())
+}
 
-	Err(OpenSpecCommandError::ToolsParseFailed)
+fn global_uninstall_args(package_manager: &str, package: &str) -> Option<Vec<String>> {
+	let args = match package_manager {
+		"npm" => vec!["uninstall", "-g", package],
+		"bun" => vec!["remove", "-g", package],
+		"yarn" => vec!["global", "remove", package],
+		"pnpm" => vec!["remove", "-g", package],
+		_ => return None,
+	};
+	Some(args.into_iter().map(String::from).collect())
 }
 
 fn build_tools_arg(
@@ -311,138 +545,524 @@ fn build_tools_arg(
 	}
 }
 
+#[derive(Default)]
+struct RunOptions<'a> {
+	current_dir: Option<&'a Path>,
+	log: Option<Arc<journal::OperationLog>>,
+	timeout: Option<Duration>,
+	pty: bool,
+	env: BTreeMap<String, String>,
+}
+
+fn spawn_error(command: &str, error: std::io::Error) -> OpenSpecCommandError {
+	if error.kind() == std::io::ErrorKind::NotFound {
+		if command == "openspec" {
+			OpenSpecCommandError::CliUnavailable
+		} else if command == "npx" {
+			// npx ships with Node, so it only goes missing along with it.
+			OpenSpecCommandError::NodeUnavailable
+		} else {
+			OpenSpecCommandError::PackageManagerUnavailable
+		}
+	} else {
+		OpenSpecCommandError::Io(error)
+	}
+}
+
+fn finished_status(
+	cancelled: &operations::CancellationToken,
+	succeeded: bool,
+) -> journal::OperationStatus {
+	if cancelled.is_cancelled() {
+		journal::OperationStatus::Cancelled
+	} else if succeeded {
+		journal::OperationStatus::Succeeded
+	} else {
+		journal::OperationStatus::Failed
+	}
+}
+
+fn run_pty_command_with_events(
+	window: &Window,
+	operation: &str,
+	operation_id: &str,
+	command: &str,
+	args: &[String],
+	options: RunOptions,
+) -> Result<CommandRunOutput, OpenSpecCommandError> {
+	let (mut child, reader, writer) = pty::spawn(command, args, options.current_dir, &options.env)
+		.map_err(|error| spawn_error(command, error))?;
+	let operations = window.state::<operations::RunningOperations>();
+	let pid = child.id().unwrap_or_default();
+	let cancelled = operations.register(
+		operation_id,
+		operation,
+		format!("{command} {}", args.join(" ")),
+		pid,
+	);
+	operations.attach_input(operation_id, writer);
+	let watchdog = options
+		.timeout
+		.map(|timeout| operations::Watchdog::start(pid, timeout));
+
+	let lines = Arc::new(Mutex::new(Vec::new()));
+	let store = Arc::clone(&lines);
+	let reader_window = window.clone();
+	let operation_name = operation.to_string();
+	let operation_id_out = operation_id.to_string();
+	let log = options.log;
+	let reporter = progress::ProgressReporter::new(window, operation, operation_id);
+	let reader_handle = thread::spawn(move || {
+		let mut style = ansi::Style::default();
+		pty::read_output(
+			reader,
+			|data| {
+				let _ = reader_window.emit(
+					"openspec://cli-raw-output",
+					CliRawOutputEvent {
+						operation: operation_name.clone(),
+						operation_id: operation_id_out.clone(),
+						data: data.to_string(),
+					},
+				);
+			},
+			|line| {
+				let plain = ansi::parse(&line, &mut style).plain;
+				reporter.observe(&plain);
+				if let Some(log) = &log {
+					log.append("stdout", &plain);
+				}
+				if let Ok(mut stored) = store.lock() {
+					stored.push(plain);
+				}
+			},
+		);
+	});
+
+	let status = child.wait();
+	let timed_out = watchdog.is_some_and(operations::Watchdog::finish);
+	operations.finish(
+		operation_id,
+		finished_status(
+			&cancelled,
+			!timed_out && status.as_ref().is_ok_and(|(success, _)| *success),
+		),
+	);
+	let (success, termination) = status.map_err(OpenSpecCommandError::Io)?;
+	let _ = reader_handle.join();
+	if cancelled.is_cancelled() {
+		return Err(OpenSpecCommandError::Cancelled);
+	}
+	let (stdout, log_lines) = lines
+		.lock()
+		.map(|lines| (lines.join("\n"), lines.len()))
+		.unwrap_or_default();
+
+	if timed_out {
+		Err(OpenSpecCommandError::TimedOut {
+			command: command.to_string(),
+			timeout_secs: options
+				.timeout
+				.map(|timeout| timeout.as_secs())
+				.unwrap_or_default(),
+			stdout,
+			stderr: String::new(),
+		})
+	} else if success {
+		Ok(CommandRunOutput {
+			operation_id: operation_id.to_string(),
+			status: termination.status,
+			signal: termination.signal,
+			termination: termination.reason,
+			log_lines,
+			stdout,
+			stderr: String::new(),
+		})
+	} else {
+		Err(OpenSpecCommandError::CommandFailed {
+			command: command.to_string(),
+			status: termination.status,
+			reason: termination.reason,
+			stderr: stdout,
+		})
+	}
+}
+
 fn run_command_with_events(
 	window: &Window,
 	operation: &str,
+	operation_id: &str,
 	command: &str,
 	args: &[String],
-	current_dir: Option<&Path>,
+	options: RunOptions,
 ) -> Result<CommandRunOutput, OpenSpecCommandError> {
+	if options.pty {
+		return run_pty_command_with_events(
+			window,
+			operation,
+			operation_id,
+			command,
+			args,
+			options,
+		);
+	}
+	let RunOptions {
+		current_dir,
+		log,
+		timeout,
+		env,
+		..
+	} = options;
 	let mut command_builder = Command::new(command);
-	command_builder.args(args);
+	command_builder.args(args).envs(&env);
 	if let Some(dir) = current_dir {
 		command_builder.current_dir(dir);
 	}
+	operations::isolate(&mut command_builder);
 	let mut child = command_builder
+		.stdin(Stdio::piped())
 		.stdout(Stdio::piped())
 		.stderr(Stdio::piped())
 		.spawn()
-		.map_err(|error| {
-			if error.kind() == std::io::ErrorKind::NotFound {
-				if command == "openspec" {
-					OpenSpecCommandError::CliUnavailable
-				} else {
-					OpenSpecCommandError::PackageManagerUnavailable
-				}
-			} else {
-				OpenSpecCommandError::Io(error)
-			}
-		})?;
+		.map_err(|error| spawn_error(command, error))?;
+
+	let operations = window.state::<operations::RunningOperations>();
+	let cancelled = operations.register(
+		operation_id,
+		operation,
+		format!("{command} {}", args.join(" ")),
+		child.id(),
+	);
+	if let Some(stdin) = child.stdin.take() {
+		operations.attach_input(operation_id, Box::new(stdin));
+	}
+	let watchdog = timeout.map(|timeout| operations::Watchdog::start(child.id(), timeout));
 
 	let stdout = child
 		.stdout
 		.take()
-		.ok_or_else(|| OpenSpecCommandError::Io(std::io::Error::new(
-			std::io::ErrorKind::Other,
-			"Missing stdout",
-		)))?;
+		.ok_or_else(|| OpenSpecCommandError::Io(std::io::Error::other("Missing stdout")))?;
 	let stderr = child
 		.stderr
 		.take()
-		.ok_or_else(|| OpenSpecCommandError::Io(std::io::Error::new(
-			std::io::ErrorKind::Other,
-			"Missing stderr",
-		)))?;
+		.ok_or_else(|| OpenSpecCommandError::Io(std::io::Error::other("Missing stderr")))?;
 
 	let stdout_lines = Arc::new(Mutex::new(Vec::new()));
 	let stderr_lines = Arc::new(Mutex::new(Vec::new()));
 
 	let stdout_store = Arc::clone(&stdout_lines);
 	let stderr_store = Arc::clone(&stderr_lines);
-	let stdout_window = window.clone();
-	let stderr_window = window.clone();
-	let operation_name = operation.to_string();
-	let operation_err = operation.to_string();
+	let stdout_batch = Arc::new(output::OutputBatch::new(window, operation, operation_id));
+	let stderr_batch = Arc::clone(&stdout_batch);
+	let ticker = output::FlushTicker::start(&stdout_batch);
+	let stdout_log = log.clone();
+	let stderr_log = log;
+	let stdout_reporter = Arc::new(progress::ProgressReporter::new(
+		window,
+		operation,
+		operation_id,
+	));
+	let stderr_reporter = Arc::clone(&stdout_reporter);
 
 	let stdout_handle = thread::spawn(move || {
 		let reader = BufReader::new(stdout);
-		for line in reader.lines().flatten() {
+		let mut style = ansi::Style::default();
+		for line in reader.lines().map_while(Result::ok) {
+			let styled = ansi::parse(&line, &mut style);
+			stdout_reporter.observe(&styled.plain);
 			if let Ok(mut stored) = stdout_store.lock() {
-				stored.push(line.clone());
+				stored.push(styled.plain.clone());
 			}
-			let _ = stdout_window.emit(
-				"openspec://cli-output",
-				CliOutputEvent {
-					operation: operation_name.clone(),
-					stream: "stdout".to_string(),
-					line,
-				},
-			);
+			if let Some(log) = &stdout_log {
+				log.append("stdout", &styled.plain);
+			}
+			stdout_batch.push(output::CliOutputLine {
+				stream: "stdout".to_string(),
+				line,
+				plain: styled.plain,
+				segments: styled.segments,
+			});
 		}
 	});
 
 	let stderr_handle = thread::spawn(move || {
 		let reader = BufReader::new(stderr);
-		for line in reader.lines().flatten() {
+		let mut style = ansi::Style::default();
+		for line in reader.lines().map_while(Result::ok) {
+			let styled = ansi::parse(&line, &mut style);
+			stderr_reporter.observe(&styled.plain);
 			if let Ok(mut stored) = stderr_store.lock() {
-				stored.push(line.clone());
+				stored.push(styled.plain.clone());
+			}
+			if let Some(log) = &stderr_log {
+				log.append("stderr", &styled.plain);
 			}
-			let _ = stderr_window.emit(
-				"openspec://cli-output",
-				CliOutputEvent {
-					operation: operation_err.clone(),
-					stream: "stderr".to_string(),
-					line,
-				},
-			);
+			stderr_batch.push(output::CliOutputLine {
+				stream: "stderr".to_string(),
+				line,
+				plain: styled.plain,
+				segments: styled.segments,
+			});
 		}
 	});
 
-	let status = child.wait().map_err(OpenSpecCommandError::Io)?;
+	let status = child.wait();
+	let timed_out = watchdog.is_some_and(operations::Watchdog::finish);
+	operations.finish(
+		operation_id,
+		finished_status(
+			&cancelled,
+			!timed_out && status.as_ref().is_ok_and(|status| status.success()),
+		),
+	);
+	let status = status.map_err(OpenSpecCommandError::Io)?;
 	let _ = stdout_handle.join();
 	let _ = stderr_handle.join();
+	ticker.stop();
+	if cancelled.is_cancelled() {
+		return Err(OpenSpecCommandError::Cancelled);
+	}
 
-	let stdout = stdout_lines
+	let (stdout, stdout_count) = stdout_lines
 		.lock()
-		.map(|lines| lines.join("\n"))
+		.map(|lines| (lines.join("\n"), lines.len()))
 		.unwrap_or_default();
-	let stderr = stderr_lines
+	let (stderr, stderr_count) = stderr_lines
 		.lock()
-		.map(|lines| lines.join("\n"))
+		.map(|lines| (lines.join("\n"), lines.len()))
 		.unwrap_or_default();
-	let status_code = status.code().unwrap_or(-1);
+	let termination = exit::Termination::from_status(&status);
 
-	if status.success() {
+	if timed_out {
+		Err(OpenSpecCommandError::TimedOut {
+			command: command.to_string(),
+			timeout_secs: timeout.map(|timeout| timeout.as_secs()).unwrap_or_default(),
+			stdout,
+			stderr,
+		})
+	} else if status.success() {
 		Ok(CommandRunOutput {
-			status: status_code,
+			operation_id: operation_id.to_string(),
+			status: termination.status,
+			signal: termination.signal,
+			termination: termination.reason,
+			log_lines: stdout_count + stderr_count,
 			stdout,
 			stderr,
 		})
 	} else {
 		Err(OpenSpecCommandError::CommandFailed {
 			command: command.to_string(),
-			status: status_code,
+			status: termination.status,
+			reason: termination.reason,
 			stderr,
 		})
 	}
 }
 
+/// A command once the project's OpenSpec launcher and execution backend
+/// have been applied, which is what actually gets spawned.
+struct RoutedCommand {
+	program: String,
+	args: Vec<String>,
+	on_demand: bool,
+}
+
+fn route_command(
+	settings: &settings::AppSettings,
+	command: &str,
+	args: &[String],
+	project: Option<&Path>,
+) -> Result<RoutedCommand, OpenSpecCommandError> {
+	let launcher = (command == "openspec").then(|| launcher::resolve(project));
+	let on_demand = launcher
+		.as_ref()
+		.is_some_and(|launcher| launcher.mode == launcher::CliMode::OnDemand);
+	let (command, args) = match &launcher {
+		Some(launcher) => (launcher.program.as_str(), launcher.args(args)),
+		None => (command, args.to_vec()),
+	};
+	let routed = container::invocation(settings, command, &args, project)
+		.map_err(|error| OpenSpecCommandError::ExecutionUnavailable(error.to_string()))?
+		.or_else(|| remote::invocation(command, &args, project))
+		.or_else(|| wsl::invocation(command, &args, project));
+	let (program, args) = routed.unwrap_or_else(|| (command.to_string(), args));
+	Ok(RoutedCommand {
+		program,
+		args,
+		on_demand,
+	})
+}
+
+fn operation_timeout(
+	settings: &settings::AppSettings,
+	operation: &str,
+	project: Option<&Path>,
+) -> Option<Duration> {
+	mounts::scaled_timeout(
+		settings.command_timeouts.for_operation(operation),
+		project.map(mounts::storage_kind).unwrap_or_default(),
+	)
+}
+
+/// Blocks until the command exits, so every command that calls this is
+/// declared `#[tauri::command(async)]`. Run on the main thread it would
+/// freeze the UI and leave `cancel_operation` unable to reach the child.
+fn run_journaled_command(
+	window: &Window,
+	env: &BTreeMap<String, String>,
+	operation: &str,
+	project: Option<&Path>,
+	backup_paths: &[&str],
+	command: &str,
+	args: &[String],
+) -> Result<CommandRunOutput, OpenSpecCommandErrorPayload> {
+	let settings = window.state::<settings::SettingsStore>();
+	let root = journal::journal_root().map_err(OpenSpecCommandError::from)?;
+	let current = settings.get();
+	let routed = route_command(&current, command, args, project)?;
+	let command_line = command_line(&routed.program, &routed.args);
+	let scrubber = journal::Scrubber::new(current.history_privacy, &command_env(&current, env));
+	let cipher =
+		journal::project_journal_cipher(&settings, project).map_err(OpenSpecCommandError::from)?;
+	let record = journal::begin_scrubbed_in(
+		&root,
+		operation,
+		&command_line,
+		project,
+		backup_paths,
+		&scrubber,
+		cipher.as_ref(),
+	)
+	.map_err(OpenSpecCommandError::from)?;
+	let _ = window.emit(
+		"openspec://operation-started",
+		OperationStartedEvent {
+			operation: operation.to_string(),
+			operation_id: record.id.clone(),
+		},
+	);
+	let result = process::wait_for_turn(window, operation, &record.id, project)
+		.map_err(|error| match error {
+			spec::SpecError::OperationDequeued => OpenSpecCommandError::Cancelled,
+			spec::SpecError::OperationBusy => OpenSpecCommandError::Busy,
+			error => OpenSpecCommandError::from(error),
+		})
+		.and_then(|_turn| {
+			if let Some((project, target)) =
+				project.and_then(|project| Some((project, remote::target_for(project)?)))
+			{
+				remote::push_edits(&target, project)?;
+			}
+			let log = journal::OperationLog::create_in(&root, &record.id)
+				.ok()
+				.map(|log| Arc::new(log.scrubbed(scrubber).sealed(cipher)));
+			let current = settings.get();
+			let options = RunOptions {
+				current_dir: project,
+				log,
+				timeout: operation_timeout(&current, operation, project),
+				pty: current.pty_operations.contains(operation),
+				env: command_env(&current, env),
+				..RunOptions::default()
+			};
+			run_command_with_events(
+				window,
+				operation,
+				&record.id,
+				&routed.program,
+				&routed.args,
+				options,
+			)
+		});
+	let (status, exit_code) = match &result {
+		Ok(output) => (journal::OperationStatus::Succeeded, Some(output.status)),
+		Err(OpenSpecCommandError::CommandFailed { status, .. }) => {
+			(journal::OperationStatus::Failed, Some(*status))
+		}
+		Err(OpenSpecCommandError::Cancelled) => (journal::OperationStatus::Cancelled, None),
+		Err(_) => (journal::OperationStatus::Failed, None),
+	};
+	let _ = journal::finish_in(&root, &record.id, status, exit_code);
+	if let Some((project, target)) =
+		project.and_then(|project| Some((project, remote::target_for(project)?)))
+	{
+		let _ = remote::sync(&target, project);
+	}
+	let _ = window.emit(
+		"openspec://operation-finished",
+		OperationFinishedEvent {
+			operation: operation.to_string(),
+			operation_id: record.id.clone(),
+			status,
+		},
+	);
+	journal::prune(&settings);
+	result.map_err(|error| {
+		let error = match error {
+			OpenSpecCommandError::CommandFailed { stderr, .. }
+				if routed.on_demand && launcher::node_missing(&stderr) =>
+			{
+				OpenSpecCommandError::NodeUnavailable
+			}
+			error => error,
+		};
+		OpenSpecCommandErrorPayload {
+			operation_id: Some(record.id),
+			..error.into()
+		}
+	})
+}
+
+type DiscoveryCache = discovery::DiscoveryCache<ProjectDiscovery>;
+
+fn discover_and_record(
+	recent: &projects::RecentProjects,
+	cache: &DiscoveryCache,
+	path: &Path,
+) -> Result<ProjectDiscovery, ProjectDiscoveryErrorPayload> {
+	let discovery = match cache.get(path) {
+		Some(discovery) => discovery,
+		None => {
+			let discovery =
+				discover_project_info(path).map_err(ProjectDiscoveryErrorPayload::from)?;
+			cache.insert(path, Path::new(&discovery.repo_path), discovery.clone());
+			discovery
+		}
+	};
+	projects::record_discovery(recent, &discovery);
+	Ok(discovery)
+}
+
 #[tauri::command]
-fn discover_project(path: String) -> Result<ProjectDiscovery, ProjectDiscoveryErrorPayload> {
-	discover_project_info(Path::new(&path)).map_err(ProjectDiscoveryErrorPayload::from)
+fn discover_project(
+	recent: State<'_, projects::RecentProjects>,
+	cache: State<'_, DiscoveryCache>,
+	path: String,
+) -> Result<ProjectDiscovery, ProjectDiscoveryErrorPayload> {
+	discover_and_record(&recent, &cache, Path::new(&path))
 }
 
 #[tauri::command]
-fn openspec_cli_status() -> OpenSpecCliStatus {
-	if let Some(version) = command_version("openspec") {
+fn invalidate_discovery(cache: State<'_, DiscoveryCache>, path: String) {
+	cache.invalidate(Path::new(&path));
+}
+
+#[tauri::command]
+fn openspec_cli_status(project: Option<String>) -> OpenSpecCliStatus {
+	let project = project.as_deref().map(Path::new);
+	let mode = launcher::resolve(project).mode;
+	if let Some(version) = launcher::version(project) {
 		OpenSpecCliStatus {
 			available: true,
 			version: Some(version),
+			mode,
 		}
 	} else {
 		OpenSpecCliStatus {
 			available: false,
 			version: None,
+			mode,
 		}
 	}
 }
@@ -455,89 +1075,454 @@ fn package_manager_statuses() -> Vec<PackageManagerStatus> {
 		.collect()
 }
 
-#[tauri::command]
-fn openspec_tools() -> Result<Vec<String>, OpenSpecCommandErrorPayload> {
-	let output = Command::new("openspec")
-		.arg("init")
-		.arg("--help")
+/// The tool ids the installed CLI lists in `openspec init --help`, or `None`
+/// when its help text no longer has a list SpecOps can read.
+fn reported_tools(project: Option<&Path>) -> Result<Option<Vec<String>>, OpenSpecCommandError> {
+	let launcher = launcher::resolve(project);
+	let output = launcher
+		.command(project, &["init", "--help"])
+		.map_err(|error| OpenSpecCommandError::ExecutionUnavailable(error.to_string()))?
 		.output()
-		.map_err(|error| {
-			if error.kind() == std::io::ErrorKind::NotFound {
-				OpenSpecCommandError::CliUnavailable
-			} else {
-				OpenSpecCommandError::Io(error)
-			}
+		.map_err(|error| match launcher.spawn_error(error) {
+			spec::SpecError::NodeUnavailable => OpenSpecCommandError::NodeUnavailable,
+			spec::SpecError::Io(error) => OpenSpecCommandError::Io(error),
+			_ => OpenSpecCommandError::CliUnavailable,
 		})?;
+	journal::record_probe(&format!("{} init --help", launcher.command_line()), &output);
 
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	let stderr = String::from_utf8_lossy(&output.stderr);
 	if !output.status.success() {
-		return Err(OpenSpecCommandError::CliUnavailable.into());
+		return Err(if launcher::node_missing(&stderr) {
+			OpenSpecCommandError::NodeUnavailable
+		} else {
+			OpenSpecCommandError::CliUnavailable
+		});
 	}
+	Ok(cli::parse_tools_help(&format!("{stdout}\n{stderr}")))
+}
 
-	let stdout = String::from_utf8_lossy(&output.stdout);
-	let stderr = String::from_utf8_lossy(&output.stderr);
-	let combined = format!("{stdout}\n{stderr}");
-	parse_openspec_tools(&combined).map_err(OpenSpecCommandErrorPayload::from)
+#[tauri::command]
+fn openspec_tools(project: Option<String>) -> Result<Vec<String>, OpenSpecCommandErrorPayload> {
+	let project = project.as_deref().map(Path::new);
+	let mut tools = reported_tools(project)?.unwrap_or_else(tools::registry_tool_ids);
+	tools.sort_by(|a, b| ordering::compare(a, b));
+	Ok(tools)
+}
+
+#[tauri::command]
+fn openspec_tools_detailed(project: Option<String>) -> tools::DetailedTools {
+	let project = project.as_deref().map(Path::new);
+	let reported = reported_tools(project).ok().flatten();
+	tools::reconcile(reported.as_deref())
+}
+
+fn command_line(command: &str, args: &[String]) -> String {
+	std::iter::once(command)
+		.chain(args.iter().map(String::as_str))
+		.collect::<Vec<_>>()
+		.join(" ")
+}
+
+fn command_env(
+	settings: &settings::AppSettings,
+	env: &BTreeMap<String, String>,
+) -> BTreeMap<String, String> {
+	let mut command_env = settings.command_env.clone();
+	command_env.extend(env.clone());
+	command_env
+}
+
+fn install_args(
+	package_manager: &str,
+	project: Option<&Path>,
+) -> Result<Vec<String>, OpenSpecCommandError> {
+	if !package_manager_status(package_manager).installed {
+		return Err(OpenSpecCommandError::PackageManagerUnavailable);
+	}
+	let package = openspec_package("latest");
+	match project {
+		Some(_) => launcher::local_install_args(package_manager, &package),
+		None => global_install_args(package_manager, &package),
+	}
+	.ok_or(OpenSpecCommandError::UnsupportedPackageManager)
+}
+
+fn uninstall_args(package_manager: &str) -> Result<Vec<String>, OpenSpecCommandError> {
+	if !package_manager_status(package_manager).installed {
+		return Err(OpenSpecCommandError::PackageManagerUnavailable);
+	}
+	global_uninstall_args(package_manager, OPENSPEC_PACKAGE)
+		.ok_or(OpenSpecCommandError::UnsupportedPackageManager)
+}
+
+fn init_args(request: &OpenSpecInitRequest) -> Result<Vec<String>, OpenSpecCommandError> {
+	let tools_arg = build_tools_arg(&request.tools_mode, &request.tools)?;
+	Ok(vec![
+		"init".to_string(),
+		request.path.clone(),
+		"--tools".to_string(),
+		tools_arg,
+	])
 }
 
 #[tauri::command]
+fn preview_operation(
+	settings: State<'_, settings::SettingsStore>,
+	request: OperationPreviewRequest,
+) -> Result<OperationPreview, OpenSpecCommandErrorPayload> {
+	let (operation, command, args, env, project) = match &request {
+		OperationPreviewRequest::Install(install) => {
+			let project = install.project.as_deref().map(Path::new);
+			(
+				"install",
+				install.package_manager.as_str(),
+				install_args(&install.package_manager, project)?,
+				&install.env,
+				project,
+			)
+		}
+		OperationPreviewRequest::Init(init) => (
+			"init",
+			"openspec",
+			init_args(init)?,
+			&init.env,
+			Some(Path::new(&init.path)),
+		),
+	};
+	let current = settings.get();
+	let routed = route_command(&current, command, &args, project)?;
+	Ok(OperationPreview {
+		operation: operation.to_string(),
+		command_line: command_line(&routed.program, &routed.args),
+		command: routed.program,
+		args: routed.args,
+		cwd: project
+			.map(Path::to_path_buf)
+			.or_else(|| std::env::current_dir().ok())
+			.map(|dir| dir.to_string_lossy().to_string()),
+		env: command_env(&current, env),
+		timeout_secs: operation_timeout(&current, operation, project)
+			.map(|timeout| timeout.as_secs()),
+		pty: current.pty_operations.contains(operation),
+	})
+}
+
+/// Installs OpenSpec globally, or as a devDependency of `project` so the repo
+/// pins its own CLI version.
+#[tauri::command(async)]
 fn install_openspec_cli(
 	window: Window,
 	package_manager: String,
+	env: Option<BTreeMap<String, String>>,
+	project: Option<String>,
 ) -> Result<CommandRunOutput, OpenSpecCommandErrorPayload> {
-	let status = package_manager_status(&package_manager);
-	if !status.installed {
-		return Err(OpenSpecCommandError::PackageManagerUnavailable.into());
-	}
-
-	let (command, args) = match package_manager.as_str() {
-		"npm" => (
-			"npm",
-			vec!["install", "-g", "@fission-ai/openspec@latest"],
-		),
-		"bun" => ("bun", vec!["add", "-g", "@fission-ai/openspec@latest"]),
-		"yarn" => ("yarn", vec!["global", "add", "@fission-ai/openspec@latest"]),
-		"pnpm" => ("pnpm", vec!["add", "-g", "@fission-ai/openspec@latest"]),
-		_ => return Err(OpenSpecCommandError::UnsupportedPackageManager.into()),
+	let project = project.as_deref().map(Path::new);
+	let args = install_args(&package_manager, project)?;
+	let backup_paths: &[&str] = if project.is_some() {
+		launcher::MANIFEST_FILES
+	} else {
+		&[]
 	};
+	let env = env.unwrap_or_default();
+	let mut attempt = 1;
+	loop {
+		let result = run_journaled_command(
+			&window,
+			&env,
+			"install",
+			project,
+			backup_paths,
+			&package_manager,
+			&args,
+		);
+		let reason = match &result {
+			Err(error) if error.code == "command_failed" && attempt < retry::MAX_ATTEMPTS => {
+				retry::transient_reason(&error.message)
+			}
+			_ => None,
+		};
+		let Some(reason) = reason else {
+			return result;
+		};
+		let delay = retry::delay(retry::BASE_DELAY, attempt);
+		let operation_id = result.err().and_then(|error| error.operation_id);
+		let _ = window.emit(
+			"openspec://operation-retry",
+			retry::RetryEvent {
+				operation: "install".to_string(),
+				operation_id: operation_id.clone(),
+				attempt,
+				max_attempts: retry::MAX_ATTEMPTS,
+				delay_secs: delay.as_secs(),
+				reason: reason.to_string(),
+			},
+		);
+		// The backoff is listed as its own operation so it can be cancelled
+		// like the attempts around it.
+		let waited = operations::run_native(
+			&window,
+			"install-retry",
+			format!("retry {package_manager} install"),
+			|cancelled| cancelled.sleep(delay),
+		);
+		if waited.is_err() {
+			return Err(OpenSpecCommandErrorPayload {
+				operation_id,
+				..OpenSpecCommandError::Cancelled.into()
+			});
+		}
+		attempt += 1;
+	}
+}
 
-	let args = args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
-	run_command_with_events(&window, "install", command, &args, None)
-		.map_err(OpenSpecCommandErrorPayload::from)
+#[tauri::command(async)]
+fn uninstall_openspec_cli(
+	window: Window,
+	package_manager: String,
+	env: Option<BTreeMap<String, String>>,
+) -> Result<CommandRunOutput, OpenSpecCommandErrorPayload> {
+	let args = uninstall_args(&package_manager)?;
+	run_journaled_command(
+		&window,
+		&env.unwrap_or_default(),
+		"uninstall",
+		None,
+		&[],
+		&package_manager,
+		&args,
+	)
 }
 
-#[tauri::command]
+#[tauri::command(async)]
 fn openspec_init(
 	window: Window,
 	request: OpenSpecInitRequest,
 ) -> Result<CommandRunOutput, OpenSpecCommandErrorPayload> {
-	let tools_arg = build_tools_arg(&request.tools_mode, &request.tools)
-		.map_err(OpenSpecCommandErrorPayload::from)?;
-	let args = vec![
-		"init".to_string(),
-		request.path,
-		"--tools".to_string(),
-		tools_arg,
-	];
+	let args = init_args(&request)?;
+	let project = Path::new(&request.path);
+
+	run_journaled_command(
+		&window,
+		&request.env,
+		"init",
+		Some(project),
+		tools::CONFIG_PATHS,
+		"openspec",
+		&args,
+	)
+}
 
-	run_command_with_events(&window, "init", "openspec", &args, None)
-		.map_err(OpenSpecCommandErrorPayload::from)
+fn scoped_handler(
+	handler: impl Fn(Invoke) -> bool + Send + Sync + 'static,
+) -> impl Fn(Invoke) -> bool + Send + Sync + 'static {
+	move |invoke: Invoke| {
+		let command = invoke.message.command().to_string();
+		if !registry::is_permitted(invoke.message.webview().label(), &command) {
+			invoke
+				.resolver
+				.reject(format!("`{command}` is not permitted in this window"));
+			return true;
+		}
+		handler(invoke)
+	}
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+	let settings = settings::load_settings();
+	crash::install(settings.get().crash_reporting);
+	launcher::set_on_demand(settings.get().on_demand_cli);
+	ordering::install(settings.get().ordering);
 	tauri::Builder::default()
 		.plugin(tauri_plugin_dialog::init())
 		.plugin(tauri_plugin_opener::init())
-		.invoke_handler(tauri::generate_handler![
+		.plugin(tauri_plugin_notification::init())
+		.manage(settings)
+		.manage(projects::load_recent_projects())
+		.manage(search::SearchState::persistent())
+		.manage(tree::SpecTrees::persistent())
+		.manage(watcher::ProjectWatchers::default())
+		.manage(watcher::ExternalEdits::default())
+		.manage(editing::EditSessions::default())
+		.manage(DiscoveryCache::default())
+		.manage(operations::RunningOperations::default())
+		.manage(process::OperationQueue::default())
+		.manage(supervisor::Subsystems::default())
+		.manage(timesheet::load_time_entries())
+		.manage(launch::PendingLaunch::default())
+		.manage(repair::RepairPlans::default())
+		.manage(lint::LintModels::default())
+		.setup(|app| {
+			launch::handle_args(app.handle(), std::env::args().skip(1));
+			snapshots::spawn_maintenance(app.handle().clone());
+			deadlines::spawn_reminders(app.handle().clone());
+			Ok(())
+		})
+		.invoke_handler(scoped_handler(tauri::generate_handler![
 			discover_project,
+			invalidate_discovery,
 			openspec_cli_status,
 			package_manager_statuses,
 			openspec_tools,
+			openspec_tools_detailed,
 			install_openspec_cli,
-			openspec_init
-		])
-		.run(tauri::generate_context!())
-		.expect("error while running tauri application");
+			upgrade::openspec_cli_update_check,
+			upgrade::upgrade_openspec_cli,
+			uninstall_openspec_cli,
+			openspec_init,
+			preview_operation,
+			archive::openspec_archive,
+			lint::lint_project,
+			lint::apply_quick_fix,
+			lint::validate_file,
+			lint::list_suppressions,
+			lint::severity_gate,
+			lint::set_severity_gate,
+			sarif::export_diagnostics_sarif,
+			metrics::export_metrics,
+			todos::scan_code_todos,
+			codemap::capability_code_map,
+			codemap::set_capability_code_map,
+			container::execution_backend,
+			container::set_execution_backend,
+			remote::sync_remote_project,
+			codemap::affected_capabilities,
+			repair::repair_plan,
+			repair::apply_repair,
+			format::format_spec,
+			format::format_project,
+			outline::document_outline,
+			search::search_project,
+			search::search_all_projects,
+			search::rebuild_search_index,
+			search::save_search,
+			search::list_saved_searches,
+			search::delete_saved_search,
+			search::run_saved_search,
+			tree::spec_tree,
+			watcher::watch_project,
+			watcher::unwatch_project,
+			watcher::external_edit_sessions,
+			ci::preview_ci_config,
+			ci::generate_ci_config,
+			hooks::install_git_hook,
+			hooks::uninstall_git_hook,
+			hooks::git_hook_status,
+			ownership::ownership_report,
+			snapshots::create_snapshot,
+			snapshots::list_snapshots,
+			snapshots::snapshot_usage,
+			crypto::project_encryption_status,
+			crypto::set_project_encryption,
+			secrets::store_secret,
+			secrets::has_secret,
+			secrets::delete_secret,
+			changes::openspec_list,
+			cli::openspec_list_changes,
+			cli::openspec_show,
+			cli::openspec_change_deltas,
+			cli::openspec_validate,
+			changes::openspec_create_change,
+			changes::link_change_to_issue,
+			changes::unlink_change_issue,
+			changes::fetch_issue_status,
+			webhook::webhook_settings,
+			webhook::configure_webhook,
+			webhook::test_webhook,
+			bundle::export_openspec_bundle,
+			bundle::import_openspec_bundle,
+			power::power_status,
+			power::set_power_mode,
+			ordering::ordering_policy,
+			ordering::set_ordering_policy,
+			projects::bulk_project_status,
+			projects::set_project_tags,
+			projects::set_project_group,
+			projects::list_recent_projects,
+			projects::remove_recent_project,
+			projects::filter_recent_projects,
+			projects::project_labels,
+			registry::list_app_commands,
+			journal::rollback_failed_init,
+			operations::cancel_operation,
+			operations::send_operation_input,
+			operations::list_operations,
+			process::queue_status,
+			process::move_queued_operation,
+			process::cancel_queued_operation,
+			preview::preview_file,
+			operations::command_timeouts,
+			operations::set_command_timeouts,
+			operations::command_environment,
+			operations::set_command_environment,
+			journal::export_audit_log,
+			journal::read_operation_log_page,
+			journal::journal_retention,
+			journal::set_journal_retention,
+			journal::history_privacy,
+			journal::set_history_privacy,
+			journal::purge_history,
+			adopt::adopt_project,
+			tools::preview_tool_update,
+			tools::openspec_update,
+			tools::check_instruction_drift,
+			layout::spec_layout,
+			layout::set_spec_layout,
+			migrate::migrate_external_docs,
+			crash::set_crash_reporting,
+			launcher::on_demand_cli,
+			launcher::set_on_demand_cli,
+			crash::list_crash_reports,
+			crash::export_crash_report,
+			supervisor::subsystem_status,
+			tools::tool_launchers,
+			tools::launch_tool,
+			tools::generate_kickoff_prompt,
+			tasks::read_tasks,
+			tasks::burndown,
+			review::add_review_comment,
+			review::list_review_comments,
+			review::export_review_comments,
+			editing::open_edit_session,
+			editing::save_edit_session,
+			editing::close_edit_session,
+			editing::list_edit_sessions,
+			html::export_specs_html,
+			html::export_branding,
+			html::set_export_branding,
+			checklist::review_checklist,
+			checklist::tick_checklist_item,
+			timesheet::start_timer,
+			timesheet::stop_timer,
+			timesheet::time_summary,
+			deadlines::set_target_date,
+			deadlines::upcoming_deadlines,
+			deadlines::deadline_reminders,
+			deadlines::set_deadline_reminders,
+			journal::list_operation_history,
+			journal::get_operation_output,
+			launch::take_launch_intent,
+			approvals::approve_change,
+			approvals::approval_status,
+			approvals::set_required_approvals,
+			git::git_clone,
+			git::git_fetch,
+			git::git_push,
+			ignore::get_ignore_patterns,
+			ignore::set_ignore_patterns,
+			ignore::reset_ignore_patterns
+		]))
+		.build(tauri::generate_context!())
+		.expect("error while building tauri application")
+		.run(|app, event| {
+			#[cfg(any(target_os = "macos", target_os = "ios"))]
+			if let tauri::RunEvent::Opened { urls } = event {
+				launch::handle_opened(app, urls);
+			}
+			#[cfg(not(any(target_os = "macos", target_os = "ios")))]
+			let _ = (app, event);
+		});
 }
 
 #[cfg(test)]
@@ -614,23 +1599,20 @@ mod tests {
 	}
 
 	#[test]
-	fn parses_tools_from_help() {
-		let help = r#"--tools <tools>  Configure AI tools non-interactively. Use "all", "none", or a comma-separated list of: auggie, claude, cline, cursor"#;
-		let tools = parse_openspec_tools(help).expect("tools parsed");
-		assert_eq!(tools, vec!["auggie", "claude", "cline", "cursor"]);
-	}
-
-	#[test]
-	fn parses_wrapped_tools_from_help() {
-		let help = r#"Options:
-  --tools <tools>  Configure AI tools non-interactively. Use "all", "none", or a comma-separated list of: auggie, claude, cline,
-                   cursor, gemini, opencode
-  -h, --help       display help for command"#;
-		let tools = parse_openspec_tools(help).expect("tools parsed");
+	fn builds_global_uninstall_args() {
+		assert_eq!(
+			global_uninstall_args("bun", OPENSPEC_PACKAGE),
+			Some(vec![
+				"remove".to_string(),
+				"-g".to_string(),
+				"@fission-ai/openspec".to_string()
+			])
+		);
 		assert_eq!(
-			tools,
-			vec!["auggie", "claude", "cline", "cursor", "gemini", "opencode"],
+			global_uninstall_args("yarn", OPENSPEC_PACKAGE).map(|args| args.join(" ")),
+			Some("global remove @fission-ai/openspec".to_string())
 		);
+		assert_eq!(global_uninstall_args("cargo", OPENSPEC_PACKAGE), None);
 	}
 
 	#[test]
@@ -660,4 +1642,67 @@ mod tests {
 		.expect("custom");
 		assert_eq!(result, "claude,cline");
 	}
+
+	#[test]
+	fn previews_init_command_line_and_env() {
+		let request: OperationPreviewRequest = serde_json::from_value(serde_json::json!({
+			"operation": "init",
+			"path": "/repo",
+			"toolsMode": "custom",
+			"tools": ["claude"],
+			"env": { "NODE_ENV": "test" },
+		}))
+		.expect("parse preview request");
+		let OperationPreviewRequest::Init(init) = request else {
+			panic!("expected init request");
+		};
+		let args = init_args(&init).expect("init args");
+		assert_eq!(
+			command_line("openspec", &args),
+			"openspec init /repo --tools claude"
+		);
+
+		let mut settings = settings::AppSettings::default();
+		settings
+			.command_env
+			.insert("NODE_ENV".to_string(), "production".to_string());
+		settings
+			.command_env
+			.insert("HTTPS_PROXY".to_string(), "http://proxy".to_string());
+		let env = command_env(&settings, &init.env);
+		assert_eq!(env.get("NODE_ENV").map(String::as_str), Some("test"));
+		assert_eq!(env.len(), 2);
+	}
+
+	#[test]
+	fn routes_commands_through_the_project_launcher_and_backend() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let project = temp_dir.path();
+		fs::write(
+			project.join("package.json"),
+			r#"{"devDependencies": {"@fission-ai/openspec": "^0.9.0"}}"#,
+		)
+		.expect("write manifest");
+		let args = vec!["list".to_string()];
+		let mut settings = settings::AppSettings::default();
+
+		let routed = route_command(&settings, "openspec", &args, Some(project)).expect("route");
+		assert_eq!(
+			command_line(&routed.program, &routed.args),
+			"npx --no openspec list"
+		);
+
+		settings.execution_backends.insert(
+			project.to_string_lossy().to_string(),
+			container::ExecutionBackend::Image {
+				image: "node:22-slim".to_string(),
+			},
+		);
+		let routed = route_command(&settings, "openspec", &args, Some(project)).expect("route");
+		assert_eq!(routed.program, "docker");
+		assert_eq!(
+			routed.args[routed.args.len() - 4..],
+			["npx", "--no", "openspec", "list"]
+		);
+	}
 }
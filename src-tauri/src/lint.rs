@@ -0,0 +1,999 @@
+use crate::{
+	editing::EditSessions,
+	preview,
+	settings::SettingsStore,
+	spec::{
+		existing_openspec_root, is_delta_spec, is_fence, markdown_files, openspec_root,
+		parse_heading, SourceText, SpecError, SpecErrorPayload, MAX_PARSE_BYTES,
+	},
+	storage,
+	webhook::{emit_webhook, WebhookEvent},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{
+	collections::{BTreeMap, HashMap},
+	fs,
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex},
+	time::SystemTime,
+};
+use tauri::State;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LintRule {
+	ScenarioHeaderLevel,
+	RequirementHeaderLevel,
+	DeltaHeaderLevel,
+	RequirementMissingScenario,
+	RequirementMissingShall,
+	FileTruncated,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+	Info,
+	Warning,
+	#[default]
+	Error,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct LintConfig {
+	disabled_rules: Vec<String>,
+	severity: BTreeMap<String, Severity>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Suppression {
+	path: String,
+	line: usize,
+	target_line: usize,
+	rules: Vec<String>,
+	unknown_rules: Vec<String>,
+}
+
+/// What an on-save check needs to know about the surrounding project,
+/// resolved once and reused until the lint config changes on disk.
+#[derive(Debug)]
+struct ProjectModel {
+	root: PathBuf,
+	config: LintConfig,
+	config_stamp: Option<(SystemTime, u64)>,
+}
+
+#[derive(Default)]
+pub struct LintModels(Mutex<HashMap<PathBuf, Arc<ProjectModel>>>);
+
+pub const LINT_CONFIG: &str = ".specops-lint.json";
+const SUPPRESSION_MARKER: &str = "<!-- specops-ignore:";
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+	pub id: String,
+	pub path: String,
+	pub line: usize,
+	pub rule: LintRule,
+	pub severity: Severity,
+	pub message: String,
+	pub fixable: bool,
+}
+
+/// A project lint run judged against the project's severity gate. Findings
+/// below the gate are still listed but do not fail the run.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LintOutcome {
+	pub gate: Severity,
+	pub passed: bool,
+	pub blocking: usize,
+	pub diagnostics: Vec<Diagnostic>,
+}
+
+impl LintOutcome {
+	pub fn gated(diagnostics: Vec<Diagnostic>, gate: Severity) -> Self {
+		let blocking = diagnostics
+			.iter()
+			.filter(|diagnostic| diagnostic.severity >= gate)
+			.count();
+		Self {
+			gate,
+			passed: blocking == 0,
+			blocking,
+			diagnostics,
+		}
+	}
+}
+
+pub const RULES: [LintRule; 6] = [
+	LintRule::ScenarioHeaderLevel,
+	LintRule::RequirementHeaderLevel,
+	LintRule::DeltaHeaderLevel,
+	LintRule::RequirementMissingScenario,
+	LintRule::RequirementMissingShall,
+	LintRule::FileTruncated,
+];
+
+pub const DELTA_HEADERS: [&str; 4] = [
+	"ADDED Requirements",
+	"MODIFIED Requirements",
+	"REMOVED Requirements",
+	"RENAMED Requirements",
+];
+
+impl LintRule {
+	pub fn id(self) -> &'static str {
+		match self {
+			LintRule::ScenarioHeaderLevel => "scenario-header-level",
+			LintRule::RequirementHeaderLevel => "requirement-header-level",
+			LintRule::DeltaHeaderLevel => "delta-header-level",
+			LintRule::RequirementMissingScenario => "requirement-missing-scenario",
+			LintRule::RequirementMissingShall => "requirement-missing-shall",
+			LintRule::FileTruncated => "file-truncated",
+		}
+	}
+
+	pub fn from_id(id: &str) -> Option<Self> {
+		RULES.into_iter().find(|rule| rule.id() == id)
+	}
+
+	pub fn description(self) -> &'static str {
+		match self {
+			LintRule::ScenarioHeaderLevel => "Scenarios use a level 4 `#### Scenario:` header",
+			LintRule::RequirementHeaderLevel => {
+				"Requirements use a level 3 `### Requirement:` header"
+			}
+			LintRule::DeltaHeaderLevel => "Delta sections use level 2 headers",
+			LintRule::RequirementMissingScenario => "Every requirement has at least one scenario",
+			LintRule::RequirementMissingShall => "Requirement text uses SHALL or MUST",
+			LintRule::FileTruncated => "Specs fit within the parse size limit",
+		}
+	}
+
+	pub fn severity(self) -> Severity {
+		match self {
+			LintRule::RequirementMissingShall | LintRule::FileTruncated => Severity::Warning,
+			_ => Severity::Error,
+		}
+	}
+
+	/// Whether findings of this rule can have a quick fix at all. A missing
+	/// SHALL additionally needs a weaker modal word to upgrade, which
+	/// `lint_source` checks while it has the requirement block at hand.
+	pub fn has_quick_fix(self) -> bool {
+		!matches!(self, LintRule::FileTruncated)
+	}
+}
+
+enum LineKind {
+	Heading { level: usize, text: String },
+	BoldScenario { name: String },
+	Text,
+}
+
+fn classify_lines(lines: &[String]) -> Vec<LineKind> {
+	let mut in_fence = false;
+	lines
+		.iter()
+		.map(|line| {
+			if is_fence(line) {
+				in_fence = !in_fence;
+				return LineKind::Text;
+			}
+			if in_fence {
+				return LineKind::Text;
+			}
+			if let Some((level, text)) = parse_heading(line) {
+				return LineKind::Heading {
+					level,
+					text: text.to_string(),
+				};
+			}
+			match bold_scenario_name(line) {
+				Some(name) => LineKind::BoldScenario { name },
+				None => LineKind::Text,
+			}
+		})
+		.collect()
+}
+
+pub fn bold_scenario_name(line: &str) -> Option<String> {
+	let trimmed = line.trim();
+	let trimmed = trimmed
+		.strip_prefix("- ")
+		.or_else(|| trimmed.strip_prefix("* "))
+		.unwrap_or(trimmed);
+	let rest = trimmed.strip_prefix("**Scenario")?;
+	let name = rest
+		.trim_start_matches(':')
+		.trim_start_matches("**")
+		.trim_start_matches(':')
+		.trim_end_matches("**")
+		.trim();
+	Some(name.to_string())
+}
+
+fn scenario_name(kind: &LineKind) -> Option<&str> {
+	match kind {
+		LineKind::Heading { text, .. } => text.strip_prefix("Scenario:").map(str::trim),
+		LineKind::BoldScenario { name } => Some(name),
+		LineKind::Text => None,
+	}
+}
+
+fn requirement_name(kind: &LineKind) -> Option<&str> {
+	match kind {
+		LineKind::Heading { text, .. } => text.strip_prefix("Requirement:").map(str::trim),
+		_ => None,
+	}
+}
+
+fn is_requirement_keyword(word: &str) -> bool {
+	word == "SHALL" || word == "MUST"
+}
+
+fn normative_word_index(line: &str) -> Option<(usize, usize, &'static str)> {
+	let mut offset = 0;
+	for word in line.split_inclusive(|ch: char| !ch.is_alphanumeric()) {
+		let bare = word.trim_end_matches(|ch: char| !ch.is_alphanumeric());
+		let replacement = match bare.to_ascii_lowercase().as_str() {
+			"shall" | "should" | "will" => Some("SHALL"),
+			"must" => Some("MUST"),
+			_ => None,
+		};
+		if let Some(replacement) = replacement {
+			return Some((offset, bare.len(), replacement));
+		}
+		offset += word.len();
+	}
+	None
+}
+
+struct RequirementBlock {
+	heading: usize,
+	end: usize,
+	body_end: usize,
+	has_scenario: bool,
+	exempt: bool,
+}
+
+fn requirement_blocks(kinds: &[LineKind]) -> Vec<RequirementBlock> {
+	let mut blocks = Vec::new();
+	let mut section_exempt = false;
+	let mut index = 0;
+	while index < kinds.len() {
+		if let LineKind::Heading { level, text } = &kinds[index] {
+			if *level <= 2 {
+				section_exempt = text.starts_with("REMOVED") || text.starts_with("RENAMED");
+			}
+		}
+		if requirement_name(&kinds[index]).is_none() {
+			index += 1;
+			continue;
+		}
+
+		let heading = index;
+		let mut end = index + 1;
+		let mut body_end = None;
+		let mut has_scenario = false;
+		while end < kinds.len() {
+			let kind = &kinds[end];
+			if requirement_name(kind).is_some() {
+				break;
+			}
+			if let LineKind::Heading { level, .. } = kind {
+				if *level <= 2 {
+					break;
+				}
+			}
+			if scenario_name(kind).is_some() {
+				has_scenario = true;
+				body_end.get_or_insert(end);
+			} else if matches!(kind, LineKind::Heading { .. }) {
+				body_end.get_or_insert(end);
+			}
+			end += 1;
+		}
+
+		blocks.push(RequirementBlock {
+			heading,
+			end,
+			body_end: body_end.unwrap_or(end),
+			has_scenario,
+			exempt: section_exempt,
+		});
+		index = end;
+	}
+	blocks
+}
+
+fn parse_suppressions(path: &Path, lines: &[String]) -> Vec<Suppression> {
+	let mut suppressions = Vec::new();
+	let mut in_fence = false;
+	for (index, line) in lines.iter().enumerate() {
+		if is_fence(line) {
+			in_fence = !in_fence;
+			continue;
+		}
+		if in_fence {
+			continue;
+		}
+		let Some(start) = line.find(SUPPRESSION_MARKER) else {
+			continue;
+		};
+		let rest = &line[start + SUPPRESSION_MARKER.len()..];
+		let Some(end) = rest.find("-->") else {
+			continue;
+		};
+		let rules = rest[..end]
+			.split(|ch: char| ch == ',' || ch.is_whitespace())
+			.filter(|rule| !rule.is_empty())
+			.map(str::to_string)
+			.collect::<Vec<_>>();
+		let standalone =
+			line.trim().starts_with(SUPPRESSION_MARKER) && rest[end + 3..].trim().is_empty();
+		let target = if standalone {
+			lines
+				.iter()
+				.enumerate()
+				.skip(index + 1)
+				.find(|(_, next)| !next.trim().is_empty())
+				.map(|(next, _)| next)
+				.unwrap_or(index)
+		} else {
+			index
+		};
+		suppressions.push(Suppression {
+			path: path.to_string_lossy().to_string(),
+			line: index + 1,
+			target_line: target + 1,
+			unknown_rules: rules
+				.iter()
+				.filter(|rule| LintRule::from_id(rule).is_none())
+				.cloned()
+				.collect(),
+			rules,
+		});
+	}
+	suppressions
+}
+
+fn is_suppressed(suppressions: &[Suppression], rule: LintRule, line: usize) -> bool {
+	suppressions.iter().any(|suppression| {
+		suppression.target_line == line
+			&& (suppression.rules.is_empty() || suppression.rules.iter().any(|id| id == rule.id()))
+	})
+}
+
+fn diagnostic_id(rule: LintRule, line: usize, path: &Path) -> String {
+	format!("{}:{}:{}", rule.id(), line, path.display())
+}
+
+fn parse_diagnostic_id(id: &str) -> Option<(LintRule, usize, PathBuf)> {
+	let mut parts = id.splitn(3, ':');
+	let rule = LintRule::from_id(parts.next()?)?;
+	let line = parts.next()?.parse().ok()?;
+	let path = PathBuf::from(parts.next()?);
+	Some((rule, line, path))
+}
+
+pub fn lint_source(path: &Path, source: &SourceText) -> Vec<Diagnostic> {
+	let kinds = classify_lines(&source.lines);
+	let mut findings = Vec::new();
+	let mut unrewordable = Vec::new();
+	let delta_spec = is_delta_spec(path);
+
+	for (index, kind) in kinds.iter().enumerate() {
+		match kind {
+			LineKind::Heading { level, text } => {
+				if text.starts_with("Scenario:") && *level != 4 {
+					findings.push((
+						LintRule::ScenarioHeaderLevel,
+						index,
+						format!("Scenario headers must use level 4 (####), found level {level}"),
+					));
+				} else if text.starts_with("Requirement:") && *level != 3 {
+					findings.push((
+						LintRule::RequirementHeaderLevel,
+						index,
+						format!("Requirement headers must use level 3 (###), found level {level}"),
+					));
+				} else if delta_spec && DELTA_HEADERS.contains(&text.as_str()) && *level != 2 {
+					findings.push((
+						LintRule::DeltaHeaderLevel,
+						index,
+						format!("Delta section headers must use level 2 (##), found level {level}"),
+					));
+				}
+			}
+			LineKind::BoldScenario { .. } => findings.push((
+				LintRule::ScenarioHeaderLevel,
+				index,
+				"Scenarios must use a `#### Scenario:` header, not bold text".to_string(),
+			)),
+			LineKind::Text => {}
+		}
+	}
+
+	for block in requirement_blocks(&kinds) {
+		if block.exempt {
+			continue;
+		}
+		if !block.has_scenario {
+			findings.push((
+				LintRule::RequirementMissingScenario,
+				block.heading,
+				"Requirement has no scenario".to_string(),
+			));
+		}
+		let has_keyword = source.lines[block.heading + 1..block.body_end]
+			.iter()
+			.flat_map(|line| line.split(|ch: char| !ch.is_alphanumeric()))
+			.any(is_requirement_keyword);
+		if !has_keyword {
+			if !source.lines[block.heading + 1..block.body_end]
+				.iter()
+				.any(|line| normative_word_index(line).is_some())
+			{
+				unrewordable.push(block.heading);
+			}
+			findings.push((
+				LintRule::RequirementMissingShall,
+				block.heading,
+				"Requirement text does not contain SHALL or MUST".to_string(),
+			));
+		}
+	}
+
+	if source.truncated {
+		findings.push((
+			LintRule::FileTruncated,
+			source.lines.len(),
+			format!(
+				"File exceeds the {} MiB parse limit; only the first {} lines were checked",
+				MAX_PARSE_BYTES / (1024 * 1024),
+				source.lines.len()
+			),
+		));
+	}
+
+	let suppressions = parse_suppressions(path, &source.lines);
+	findings.retain(|(rule, index, _)| !is_suppressed(&suppressions, *rule, index + 1));
+	findings.sort_by_key(|(_, line, _)| *line);
+	findings
+		.into_iter()
+		.map(|(rule, index, message)| Diagnostic {
+			id: diagnostic_id(rule, index + 1, path),
+			path: path.to_string_lossy().to_string(),
+			line: index + 1,
+			rule,
+			severity: rule.severity(),
+			message,
+			fixable: rule.has_quick_fix()
+				&& !(rule == LintRule::RequirementMissingShall && unrewordable.contains(&index)),
+		})
+		.collect()
+}
+
+fn quick_fix(rule: LintRule, lines: &[String], index: usize) -> Option<Vec<String>> {
+	let kinds = classify_lines(lines);
+	let mut fixed = lines.to_vec();
+	match rule {
+		LintRule::ScenarioHeaderLevel => {
+			let name = scenario_name(&kinds[index])?;
+			fixed[index] = format!("#### Scenario: {name}");
+		}
+		LintRule::RequirementHeaderLevel | LintRule::DeltaHeaderLevel => {
+			let LineKind::Heading { text, .. } = &kinds[index] else {
+				return None;
+			};
+			let level = if rule == LintRule::DeltaHeaderLevel {
+				2
+			} else {
+				3
+			};
+			fixed[index] = format!("{} {text}", "#".repeat(level));
+		}
+		LintRule::RequirementMissingScenario => {
+			let block = requirement_blocks(&kinds)
+				.into_iter()
+				.find(|block| block.heading == index)?;
+			let name = requirement_name(&kinds[index])?;
+			let mut insert_at = block.end;
+			while insert_at > block.heading + 1 && fixed[insert_at - 1].trim().is_empty() {
+				insert_at -= 1;
+			}
+			let mut scenario = vec![
+				String::new(),
+				format!("#### Scenario: {name}"),
+				"- **WHEN** <condition>".to_string(),
+				"- **THEN** <expected outcome>".to_string(),
+			];
+			if insert_at < fixed.len() && !fixed[insert_at].trim().is_empty() {
+				scenario.push(String::new());
+			}
+			fixed.splice(insert_at..insert_at, scenario);
+		}
+		LintRule::RequirementMissingShall => {
+			let block = requirement_blocks(&kinds)
+				.into_iter()
+				.find(|block| block.heading == index)?;
+			let (line_index, (offset, len, replacement)) = (block.heading + 1..block.body_end)
+				.find_map(|line| normative_word_index(&lines[line]).map(|found| (line, found)))?;
+			fixed[line_index].replace_range(offset..offset + len, replacement);
+		}
+		LintRule::FileTruncated => return None,
+	}
+	Some(fixed)
+}
+
+pub fn lint_file(path: &Path) -> Result<Vec<Diagnostic>, SpecError> {
+	let source = SourceText::read_limited(path, MAX_PARSE_BYTES)?;
+	Ok(lint_source(path, &source))
+}
+
+pub fn lint_config(project: &Path) -> Result<LintConfig, SpecError> {
+	match fs::read(project.join(LINT_CONFIG)) {
+		Ok(bytes) => serde_json::from_slice(&bytes).map_err(|_| SpecError::InvalidLintConfig),
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(LintConfig::default()),
+		Err(error) => Err(error.into()),
+	}
+}
+
+impl LintConfig {
+	pub fn apply(&self, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+		diagnostics
+			.into_iter()
+			.filter(|diagnostic| {
+				!self
+					.disabled_rules
+					.iter()
+					.any(|rule| rule == diagnostic.rule.id())
+			})
+			.map(|diagnostic| Diagnostic {
+				severity: self
+					.severity
+					.get(diagnostic.rule.id())
+					.copied()
+					.unwrap_or(diagnostic.severity),
+				..diagnostic
+			})
+			.collect()
+	}
+}
+
+pub fn lint_project_files(project: &Path) -> Result<Vec<Diagnostic>, SpecError> {
+	let root = existing_openspec_root(project)?;
+	let config = lint_config(project)?;
+	let mut diagnostics = Vec::new();
+	for file in markdown_files(&root)? {
+		diagnostics.extend(lint_file(&file)?);
+	}
+	Ok(config.apply(diagnostics))
+}
+
+fn config_stamp(project: &Path) -> Option<(SystemTime, u64)> {
+	let metadata = fs::metadata(project.join(LINT_CONFIG)).ok()?;
+	Some((metadata.modified().ok()?, metadata.len()))
+}
+
+impl LintModels {
+	fn cached(&self, file: &Path) -> Option<Arc<ProjectModel>> {
+		let models = self.0.lock().ok()?;
+		models
+			.iter()
+			.filter(|(_, model)| file.starts_with(&model.root))
+			.max_by_key(|(_, model)| model.root.as_os_str().len())
+			.filter(|(project, model)| {
+				model.root.is_dir() && model.config_stamp == config_stamp(project)
+			})
+			.map(|(_, model)| Arc::clone(model))
+	}
+
+	fn model_for(&self, file: &Path) -> Result<Arc<ProjectModel>, SpecError> {
+		if let Some(model) = self.cached(file) {
+			return Ok(model);
+		}
+		let (project, root) = file
+			.ancestors()
+			.skip(1)
+			.map(|dir| (dir, openspec_root(dir)))
+			.find(|(_, root)| root.is_dir() && file.starts_with(root))
+			.ok_or(SpecError::MissingOpenSpec)?;
+		let model = Arc::new(ProjectModel {
+			config: lint_config(project)?,
+			config_stamp: config_stamp(project),
+			root,
+		});
+		if let Ok(mut models) = self.0.lock() {
+			models.insert(project.to_path_buf(), Arc::clone(&model));
+		}
+		Ok(model)
+	}
+}
+
+/// Lints one spec file, optionally from an unsaved buffer, without walking
+/// the rest of the project.
+pub fn validate_single_file(
+	models: &LintModels,
+	path: &Path,
+	content: Option<&str>,
+) -> Result<Vec<Diagnostic>, SpecError> {
+	if path.extension().and_then(|ext| ext.to_str()) != Some("md") {
+		return Ok(Vec::new());
+	}
+	let model = models.model_for(path)?;
+	let source = match content {
+		Some(text) => SourceText::parse(text),
+		None => SourceText::read_limited(path, MAX_PARSE_BYTES)?,
+	};
+	Ok(model.config.apply(lint_source(path, &source)))
+}
+
+fn project_suppressions(project: &Path) -> Result<Vec<Suppression>, SpecError> {
+	let root = existing_openspec_root(project)?;
+	let mut suppressions = Vec::new();
+	for file in markdown_files(&root)? {
+		let source = SourceText::read_limited(&file, MAX_PARSE_BYTES)?;
+		suppressions.extend(parse_suppressions(&file, &source.lines));
+	}
+	Ok(suppressions)
+}
+
+fn count_severity(diagnostics: &[Diagnostic], severity: Severity) -> usize {
+	diagnostics
+		.iter()
+		.filter(|diagnostic| diagnostic.severity == severity)
+		.count()
+}
+
+fn apply_fix_by_id(
+	project: &Path,
+	diagnostic_id: &str,
+	sessions: &EditSessions,
+) -> Result<Vec<Diagnostic>, SpecError> {
+	let (rule, line, path) =
+		parse_diagnostic_id(diagnostic_id).ok_or(SpecError::UnknownDiagnostic)?;
+	// The id comes from the webview, so its path is not trusted to stay
+	// inside the project it claims to belong to.
+	let path = preview::resolve(project, path)?;
+	sessions.ensure_unlocked(&path)?;
+	let mut source = SourceText::read_complete(&path)?;
+	let still_present = lint_source(&path, &source)
+		.iter()
+		.any(|diagnostic| diagnostic.rule == rule && diagnostic.line == line);
+	if !still_present {
+		return Err(SpecError::UnknownDiagnostic);
+	}
+
+	source.lines = quick_fix(rule, &source.lines, line - 1).ok_or(SpecError::FixUnavailable)?;
+	storage::write_atomic(&path, source.render().as_bytes())?;
+	Ok(lint_source(&path, &source))
+}
+
+#[tauri::command]
+pub fn lint_project(
+	settings: State<'_, SettingsStore>,
+	path: String,
+) -> Result<LintOutcome, SpecErrorPayload> {
+	let gate = settings
+		.get()
+		.severity_gates
+		.get(&path)
+		.copied()
+		.unwrap_or_default();
+	let outcome = LintOutcome::gated(lint_project_files(Path::new(&path))?, gate);
+	if !outcome.passed {
+		let diagnostics = &outcome.diagnostics;
+		emit_webhook(
+			&settings,
+			WebhookEvent::ValidationFailed,
+			json!({
+				"project": path,
+				"gate": gate,
+				"errors": count_severity(diagnostics, Severity::Error),
+				"warnings": count_severity(diagnostics, Severity::Warning),
+				"info": count_severity(diagnostics, Severity::Info),
+			}),
+		);
+	}
+	Ok(outcome)
+}
+
+#[tauri::command]
+pub fn validate_file(
+	models: State<'_, LintModels>,
+	path: String,
+	content: Option<String>,
+) -> Result<Vec<Diagnostic>, SpecErrorPayload> {
+	validate_single_file(&models, Path::new(&path), content.as_deref())
+		.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn list_suppressions(project: String) -> Result<Vec<Suppression>, SpecErrorPayload> {
+	project_suppressions(Path::new(&project)).map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn severity_gate(settings: State<'_, SettingsStore>, project: String) -> Severity {
+	settings
+		.get()
+		.severity_gates
+		.get(&project)
+		.copied()
+		.unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn set_severity_gate(
+	settings: State<'_, SettingsStore>,
+	project: String,
+	severity: Severity,
+) -> Result<Severity, SpecErrorPayload> {
+	settings
+		.update(|current| current.severity_gates.insert(project, severity))
+		.map_err(|error| SpecErrorPayload::from(SpecError::from(error)))?;
+	Ok(severity)
+}
+
+#[tauri::command]
+pub fn apply_quick_fix(
+	sessions: State<'_, EditSessions>,
+	project: String,
+	diagnostic_id: String,
+) -> Result<Vec<Diagnostic>, SpecErrorPayload> {
+	apply_fix_by_id(Path::new(&project), &diagnostic_id, &sessions).map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	fn rules(diagnostics: &[Diagnostic]) -> Vec<(LintRule, usize)> {
+		diagnostics
+			.iter()
+			.map(|diagnostic| (diagnostic.rule, diagnostic.line))
+			.collect()
+	}
+
+	#[test]
+	fn reports_header_level_problems() {
+		let text = "# ADDED Requirements\n## Requirement: Login\nThe system SHALL log in.\n\n### Scenario: Works\n- **WHEN** x\n";
+		let path = Path::new("/repo/openspec/changes/add-login/specs/auth/spec.md");
+		let diagnostics = lint_source(path, &SourceText::parse(text));
+		assert_eq!(
+			rules(&diagnostics),
+			vec![
+				(LintRule::DeltaHeaderLevel, 1),
+				(LintRule::RequirementHeaderLevel, 2),
+				(LintRule::ScenarioHeaderLevel, 5),
+			],
+		);
+		assert!(diagnostics.iter().all(|diagnostic| diagnostic.fixable));
+	}
+
+	#[test]
+	fn skips_removed_requirements() {
+		let text = "## REMOVED Requirements\n### Requirement: Old\nGone.\n";
+		let path = Path::new("/repo/openspec/changes/x/specs/auth/spec.md");
+		assert!(lint_source(path, &SourceText::parse(text)).is_empty());
+	}
+
+	#[test]
+	fn fixes_bold_scenario() {
+		let lines =
+			SourceText::parse("### Requirement: A\nIt SHALL work.\n\n- **Scenario: Happy path**\n")
+				.lines;
+		let fixed = quick_fix(LintRule::ScenarioHeaderLevel, &lines, 3).expect("fix");
+		assert_eq!(fixed[3], "#### Scenario: Happy path");
+	}
+
+	#[test]
+	fn inserts_missing_scenario_before_next_requirement() {
+		let lines = SourceText::parse(
+			"### Requirement: A\nIt SHALL work.\n\n### Requirement: B\nIt SHALL too.\n\n#### Scenario: B\n",
+		)
+		.lines;
+		let fixed = quick_fix(LintRule::RequirementMissingScenario, &lines, 0).expect("fix");
+		assert_eq!(fixed[3], "#### Scenario: A");
+		assert_eq!(fixed[7], "### Requirement: B");
+	}
+
+	#[test]
+	fn promotes_normative_word_to_shall() {
+		let lines = SourceText::parse("### Requirement: A\nThe app should work.\n").lines;
+		let fixed = quick_fix(LintRule::RequirementMissingShall, &lines, 0).expect("fix");
+		assert_eq!(fixed[1], "The app SHALL work.");
+
+		let lines = SourceText::parse("### Requirement: A\nThe app works.\n").lines;
+		assert!(quick_fix(LintRule::RequirementMissingShall, &lines, 0).is_none());
+
+		let path = Path::new("/repo/openspec/specs/auth/spec.md");
+		let source = SourceText::parse(
+			"### Requirement: A\nThe app should work.\n\n#### Scenario: A\n\n\
+			 ### Requirement: B\nThe app works.\n\n#### Scenario: B\n",
+		);
+		let fixable = lint_source(path, &source)
+			.iter()
+			.map(|diagnostic| (diagnostic.line, diagnostic.fixable))
+			.collect::<Vec<_>>();
+		assert_eq!(fixable, vec![(1, true), (6, false)]);
+	}
+
+	#[test]
+	fn validates_single_file_with_cached_project_config() {
+		let project = TempDir::new().expect("create project dir");
+		let spec_dir = project.path().join("openspec/specs/auth");
+		fs::create_dir_all(&spec_dir).expect("create specs");
+		let path = spec_dir.join("spec.md");
+		fs::write(&path, "### Requirement: A\nThe app SHALL work.\n").expect("write spec");
+		let models = LintModels::default();
+
+		let diagnostics = validate_single_file(&models, &path, None).expect("validate");
+		assert_eq!(
+			rules(&diagnostics),
+			vec![(LintRule::RequirementMissingScenario, 1)]
+		);
+		let unsaved = "### Requirement: A\nThe app works.\n\n#### Scenario: A\n";
+		let diagnostics = validate_single_file(&models, &path, Some(unsaved)).expect("validate");
+		assert_eq!(
+			rules(&diagnostics),
+			vec![(LintRule::RequirementMissingShall, 1)]
+		);
+
+		fs::write(
+			project.path().join(LINT_CONFIG),
+			r#"{"disabledRules":["requirement-missing-scenario"]}"#,
+		)
+		.expect("write config");
+		assert!(validate_single_file(&models, &path, None)
+			.expect("validate")
+			.is_empty());
+		assert!(
+			validate_single_file(&models, &project.path().join("README.txt"), None)
+				.expect("skip non-markdown")
+				.is_empty()
+		);
+	}
+
+	#[test]
+	fn applies_quick_fix_by_id() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let project = temp_dir.path();
+		let spec_dir = project.join("openspec/specs/auth");
+		fs::create_dir_all(&spec_dir).expect("create specs");
+		let path = spec_dir.join("spec.md");
+		fs::write(&path, "### Requirement: A\nThe app SHALL work.\n").expect("write spec");
+
+		let diagnostics = lint_file(&path).expect("lint");
+		assert_eq!(
+			rules(&diagnostics),
+			vec![(LintRule::RequirementMissingScenario, 1)]
+		);
+
+		let sessions = EditSessions::default();
+		let session = sessions.open(&path).expect("open session");
+		let error = apply_fix_by_id(project, &diagnostics[0].id, &sessions).expect_err("locked");
+		assert!(matches!(error, SpecError::FileLocked));
+		sessions.close(&session.id);
+
+		let remaining = apply_fix_by_id(project, &diagnostics[0].id, &sessions).expect("apply fix");
+		assert!(remaining.is_empty());
+		let written = fs::read_to_string(&path).expect("read spec");
+		assert!(written.contains("#### Scenario: A\n"));
+
+		let error =
+			apply_fix_by_id(project, &diagnostics[0].id, &sessions).expect_err("already fixed");
+		assert!(matches!(error, SpecError::UnknownDiagnostic));
+	}
+
+	#[test]
+	fn quick_fix_ids_cannot_reach_outside_the_openspec_folder() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let project = temp_dir.path().join("repo");
+		fs::create_dir_all(project.join("openspec/specs")).expect("create openspec");
+		let outside = temp_dir.path().join("notes.md");
+		fs::write(&outside, "### Requirement: A\nThe app SHALL work.\n").expect("write notes");
+		let rule = LintRule::RequirementMissingScenario.id();
+		let sessions = EditSessions::default();
+
+		for path in [
+			outside.to_string_lossy().to_string(),
+			"openspec/../../notes.md".to_string(),
+		] {
+			let error = apply_fix_by_id(&project, &format!("{rule}:1:{path}"), &sessions)
+				.expect_err("outside the openspec folder");
+			assert!(matches!(error, SpecError::PathOutsideOpenSpec));
+		}
+		assert!(!fs::read_to_string(&outside)
+			.expect("read notes")
+			.contains("Scenario"));
+	}
+
+	#[test]
+	fn project_config_disables_and_downgrades_rules() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let spec_dir = temp_dir.path().join("openspec/specs/auth");
+		fs::create_dir_all(&spec_dir).expect("create specs");
+		fs::write(
+			spec_dir.join("spec.md"),
+			"### Requirement: A\nThe app works.\n",
+		)
+		.expect("write spec");
+		assert_eq!(lint_project_files(temp_dir.path()).expect("lint").len(), 2);
+
+		fs::write(
+			temp_dir.path().join(LINT_CONFIG),
+			r#"{"disabledRules":["requirement-missing-scenario"],"severity":{"requirement-missing-shall":"info"}}"#,
+		)
+		.expect("write config");
+		let diagnostics = lint_project_files(temp_dir.path()).expect("lint");
+		assert_eq!(
+			rules(&diagnostics),
+			vec![(LintRule::RequirementMissingShall, 1)]
+		);
+		assert_eq!(diagnostics[0].severity, Severity::Info);
+		assert!(Severity::Info < Severity::Warning && Severity::Warning < Severity::Error);
+
+		fs::write(temp_dir.path().join(LINT_CONFIG), "{").expect("write config");
+		assert!(matches!(
+			lint_project_files(temp_dir.path()),
+			Err(SpecError::InvalidLintConfig)
+		));
+	}
+
+	#[test]
+	fn suppression_comments_silence_the_next_line() {
+		let text = "<!-- specops-ignore: requirement-missing-shall -->\n### Requirement: A\nThe app works.\n\n#### Scenario: Works\n\n### Requirement: B <!-- specops-ignore: requirement-missing-scenario, typo-rule -->\nIt SHALL work.\n\n```md\n<!-- specops-ignore: -->\n```\n";
+		let path = Path::new("/repo/openspec/specs/auth/spec.md");
+		let source = SourceText::parse(text);
+		assert!(lint_source(path, &source).is_empty());
+
+		let suppressions = parse_suppressions(path, &source.lines);
+		assert_eq!(suppressions.len(), 2);
+		assert_eq!(suppressions[0].target_line, 2);
+		assert_eq!(suppressions[1].target_line, 7);
+		assert_eq!(suppressions[1].unknown_rules, vec!["typo-rule".to_string()]);
+	}
+
+	#[test]
+	fn gates_the_outcome_on_the_project_severity() {
+		let path = Path::new("/repo/openspec/specs/auth/spec.md");
+		let source = SourceText::parse("### Requirement: A\nIt works.\n\n#### Scenario: Works\n");
+		let diagnostics = lint_source(path, &source);
+		assert_eq!(
+			rules(&diagnostics),
+			vec![(LintRule::RequirementMissingShall, 1)]
+		);
+
+		let outcome = LintOutcome::gated(diagnostics.clone(), Severity::Error);
+		assert!(outcome.passed);
+		assert_eq!(outcome.blocking, 0);
+		assert_eq!(outcome.diagnostics.len(), 1);
+
+		let outcome = LintOutcome::gated(diagnostics, Severity::Warning);
+		assert!(!outcome.passed);
+		assert_eq!(outcome.blocking, 1);
+	}
+
+	#[test]
+	fn flags_files_cut_off_at_the_parse_limit() {
+		let path = Path::new("/repo/openspec/specs/auth/spec.md");
+		let mut source =
+			SourceText::parse("### Requirement: A\nIt SHALL work.\n\n#### Scenario: Works\n");
+		assert!(lint_source(path, &source).is_empty());
+
+		source.truncated = true;
+		let diagnostics = lint_source(path, &source);
+		assert_eq!(rules(&diagnostics), vec![(LintRule::FileTruncated, 5)]);
+		assert_eq!(diagnostics[0].severity, Severity::Warning);
+		assert!(!diagnostics[0].fixable);
+	}
+}
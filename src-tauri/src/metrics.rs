@@ -0,0 +1,244 @@
+use crate::{
+	changes::{list_changes, ChangeState},
+	deadlines::parse_date,
+	lint::{lint_project_files, Severity},
+	spec::{SpecError, SpecErrorPayload},
+	storage,
+};
+use serde::{Deserialize, Serialize};
+use std::{fmt::Write, path::Path};
+
+const DAY_SECS: i64 = 24 * 60 * 60;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MetricsFormat {
+	Prometheus,
+	Json,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectMetrics {
+	project: String,
+	generated_at: u64,
+	active_changes: usize,
+	archived_changes: usize,
+	open_tasks: usize,
+	completed_tasks: usize,
+	validation_errors: usize,
+	validation_warnings: usize,
+	last_archive_date: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetricsExport {
+	path: String,
+	format: MetricsFormat,
+	metrics: ProjectMetrics,
+}
+
+// Archived changes are stored as `YYYY-MM-DD-<id>`; the prefix is the only
+// archive date OpenSpec records.
+fn archive_date(change_id: &str) -> Option<&str> {
+	let date = change_id.get(..10)?;
+	parse_date(date)?;
+	Some(date)
+}
+
+pub fn project_metrics(project: &Path) -> Result<ProjectMetrics, SpecError> {
+	let changes = list_changes(project, true)?;
+	let (archived, active): (Vec<_>, Vec<_>) = changes
+		.iter()
+		.partition(|change| change.state == ChangeState::Archived);
+	let diagnostics = lint_project_files(project)?;
+	let count = |severity: Severity| {
+		diagnostics
+			.iter()
+			.filter(|diagnostic| diagnostic.severity == severity)
+			.count()
+	};
+	Ok(ProjectMetrics {
+		project: project
+			.file_name()
+			.map(|name| name.to_string_lossy().to_string())
+			.unwrap_or_else(|| project.to_string_lossy().to_string()),
+		generated_at: storage::now_secs(),
+		active_changes: active.len(),
+		archived_changes: archived.len(),
+		open_tasks: active
+			.iter()
+			.map(|change| change.tasks_total - change.tasks_done)
+			.sum(),
+		completed_tasks: active.iter().map(|change| change.tasks_done).sum(),
+		validation_errors: count(Severity::Error),
+		validation_warnings: count(Severity::Warning),
+		last_archive_date: archived
+			.iter()
+			.filter_map(|change| archive_date(&change.id))
+			.max()
+			.map(str::to_string),
+	})
+}
+
+fn label_value(value: &str) -> String {
+	value
+		.replace('\\', "\\\\")
+		.replace('"', "\\\"")
+		.replace('\n', "\\n")
+}
+
+pub fn prometheus_text(metrics: &ProjectMetrics) -> String {
+	let labels = format!("{{project=\"{}\"}}", label_value(&metrics.project));
+	let mut gauges = vec![
+		(
+			"specops_active_changes",
+			"Changes that have not been archived.",
+			metrics.active_changes as i64,
+		),
+		(
+			"specops_archived_changes",
+			"Changes moved to the archive.",
+			metrics.archived_changes as i64,
+		),
+		(
+			"specops_open_tasks",
+			"Unchecked tasks across active changes.",
+			metrics.open_tasks as i64,
+		),
+		(
+			"specops_completed_tasks",
+			"Checked tasks across active changes.",
+			metrics.completed_tasks as i64,
+		),
+		(
+			"specops_validation_errors",
+			"Spec diagnostics reported as errors.",
+			metrics.validation_errors as i64,
+		),
+		(
+			"specops_validation_warnings",
+			"Spec diagnostics reported as warnings.",
+			metrics.validation_warnings as i64,
+		),
+	];
+	if let Some(days) = metrics.last_archive_date.as_deref().and_then(parse_date) {
+		gauges.push((
+			"specops_last_archive_timestamp_seconds",
+			"Start of the day the most recent change was archived.",
+			days * DAY_SECS,
+		));
+	}
+	gauges.push((
+		"specops_metrics_generated_timestamp_seconds",
+		"When these metrics were exported.",
+		metrics.generated_at as i64,
+	));
+
+	let mut text = String::new();
+	for (name, help, value) in gauges {
+		let _ = write!(
+			text,
+			"# HELP {name} {help}\n# TYPE {name} gauge\n{name}{labels} {value}\n"
+		);
+	}
+	text
+}
+
+// Textfile collectors read whatever is in the directory, so the file is
+// replaced atomically rather than rewritten in place.
+fn export_project_metrics(
+	project: &Path,
+	format: MetricsFormat,
+	dest: &Path,
+) -> Result<MetricsExport, SpecError> {
+	let metrics = project_metrics(project)?;
+	let bytes = match format {
+		MetricsFormat::Prometheus => prometheus_text(&metrics).into_bytes(),
+		MetricsFormat::Json => {
+			serde_json::to_vec_pretty(&metrics).map_err(std::io::Error::other)?
+		}
+	};
+	storage::write_atomic(dest, &bytes)?;
+	Ok(MetricsExport {
+		path: dest.to_string_lossy().to_string(),
+		format,
+		metrics,
+	})
+}
+
+#[tauri::command]
+pub fn export_metrics(
+	project: String,
+	format: MetricsFormat,
+	dest: String,
+) -> Result<MetricsExport, SpecErrorPayload> {
+	export_project_metrics(Path::new(&project), format, Path::new(&dest))
+		.map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::Value;
+	use std::fs;
+	use tempfile::TempDir;
+
+	fn fixture() -> TempDir {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let openspec = temp_dir.path().join("openspec");
+		let spec_dir = openspec.join("specs/auth");
+		fs::create_dir_all(&spec_dir).expect("create specs");
+		fs::write(
+			spec_dir.join("spec.md"),
+			"### Requirement: Login\nThe system logs in.\n",
+		)
+		.expect("write spec");
+		let change = openspec.join("changes/add-login");
+		fs::create_dir_all(&change).expect("create change");
+		fs::write(
+			change.join("tasks.md"),
+			"## 1. Implementation\n- [x] 1.1 Form\n- [ ] 1.2 Session\n- [ ] 1.3 Logout\n",
+		)
+		.expect("write tasks");
+		for archived in ["2026-03-02-add-auth", "2026-09-14-add-billing", "legacy"] {
+			fs::create_dir_all(openspec.join("changes/archive").join(archived))
+				.expect("create archived change");
+		}
+		temp_dir
+	}
+
+	#[test]
+	fn collects_change_task_and_validation_counts() {
+		let project = fixture();
+		let metrics = project_metrics(project.path()).expect("metrics");
+		assert_eq!((metrics.active_changes, metrics.archived_changes), (1, 3));
+		assert_eq!((metrics.open_tasks, metrics.completed_tasks), (2, 1));
+		assert_eq!(metrics.validation_errors, 1);
+		assert_eq!(metrics.validation_warnings, 1);
+		assert_eq!(metrics.last_archive_date.as_deref(), Some("2026-09-14"));
+	}
+
+	#[test]
+	fn exports_prometheus_textfile_and_json() {
+		let project = fixture();
+		let dest = project.path().join("out/specops.prom");
+		export_project_metrics(project.path(), MetricsFormat::Prometheus, &dest).expect("export");
+		let text = fs::read_to_string(&dest).expect("read metrics");
+		let name = project.path().file_name().expect("name").to_string_lossy();
+		assert!(text.contains("# TYPE specops_open_tasks gauge\n"));
+		assert!(text.contains(&format!("specops_open_tasks{{project=\"{name}\"}} 2\n")));
+		assert!(text.contains(&format!(
+			"specops_last_archive_timestamp_seconds{{project=\"{name}\"}} 1789344000\n"
+		)));
+
+		let dest = project.path().join("out/specops.json");
+		export_project_metrics(project.path(), MetricsFormat::Json, &dest).expect("export");
+		let json: Value =
+			serde_json::from_slice(&fs::read(&dest).expect("read json")).expect("parse json");
+		assert_eq!(json["activeChanges"], 1);
+		assert_eq!(json["validationErrors"], 1);
+		assert_eq!(json["lastArchiveDate"], "2026-09-14");
+	}
+}
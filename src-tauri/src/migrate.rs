@@ -0,0 +1,413 @@
+use crate::{
+	editing::EditSessions,
+	ignore::{rules_for, IgnoreRules},
+	settings::SettingsStore,
+	spec::{
+		markdown_files_except, openspec_root, parse_heading, SourceText, SpecError,
+		SpecErrorPayload,
+	},
+	storage,
+};
+use globset::GlobBuilder;
+use serde::{Deserialize, Serialize};
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+use tauri::State;
+
+const NORMATIVE_WORDS: &[&str] = &["shall", "must", "should", "required to"];
+const DECISION_HEADINGS: &[&str] = &["decision", "requirements", "specification", "proposal"];
+const MAX_TITLE_WORDS: usize = 8;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MigrationTarget {
+	#[default]
+	Capability,
+	Change,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MigrationMapping {
+	target: MigrationTarget,
+	prefix: Option<String>,
+	dry_run: bool,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum MigrationStatus {
+	Planned,
+	Created,
+	Conflict,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationEntry {
+	source: String,
+	target: String,
+	title: String,
+	requirements: usize,
+	inferred: bool,
+	status: MigrationStatus,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationReport {
+	dry_run: bool,
+	target: MigrationTarget,
+	entries: Vec<MigrationEntry>,
+}
+
+struct Requirement {
+	title: String,
+	text: String,
+}
+
+struct Document {
+	title: String,
+	summary: String,
+	requirements: Vec<Requirement>,
+	inferred: bool,
+}
+
+fn slug(text: &str) -> String {
+	let stem = text.trim_start_matches(|c: char| c.is_ascii_digit() || c == '-' || c == '_');
+	let stem = if stem.is_empty() { text } else { stem };
+	stem.to_lowercase()
+		.chars()
+		.map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+		.collect::<String>()
+		.split('-')
+		.filter(|part| !part.is_empty())
+		.collect::<Vec<_>>()
+		.join("-")
+}
+
+fn is_normative(text: &str) -> bool {
+	let lower = text.to_lowercase();
+	NORMATIVE_WORDS.iter().any(|word| {
+		lower.match_indices(word).any(|(index, _)| {
+			!lower[..index].ends_with(char::is_alphanumeric)
+				&& !lower[index + word.len()..].starts_with(char::is_alphanumeric)
+		})
+	})
+}
+
+fn normalize(text: &str) -> String {
+	if text.contains("SHALL") {
+		return text.to_string();
+	}
+	for word in ["MUST", "must", "Must"] {
+		if let Some(index) = text.find(word) {
+			return format!("{}SHALL{}", &text[..index], &text[index + word.len()..]);
+		}
+	}
+	text.to_string()
+}
+
+fn requirement_title(text: &str) -> String {
+	let words = text
+		.trim_end_matches('.')
+		.split_whitespace()
+		.take(MAX_TITLE_WORDS)
+		.collect::<Vec<_>>();
+	words.join(" ")
+}
+
+fn parse_document(text: &str, fallback_title: &str) -> Document {
+	let mut title = None;
+	let mut summary = None;
+	let mut section = None::<String>;
+	let mut decision = Vec::new();
+	let mut requirements = Vec::new();
+	for line in text.lines() {
+		if let Some((level, heading)) = parse_heading(line) {
+			if level == 1 && title.is_none() {
+				title = Some(heading.trim().to_string());
+			}
+			section = Some(heading.trim().to_lowercase());
+			continue;
+		}
+		let item = line
+			.trim()
+			.trim_start_matches(['-', '*'])
+			.trim()
+			.to_string();
+		if item.is_empty() {
+			continue;
+		}
+		if summary.is_none() {
+			summary = Some(item.clone());
+		}
+		if section
+			.as_deref()
+			.is_some_and(|section| DECISION_HEADINGS.iter().any(|name| section.contains(name)))
+		{
+			decision.push(item.clone());
+		}
+		if is_normative(&item) {
+			requirements.push(Requirement {
+				title: requirement_title(&item),
+				text: normalize(&item),
+			});
+		}
+	}
+	let title = title.unwrap_or_else(|| fallback_title.to_string());
+	let inferred = requirements.is_empty();
+	if inferred {
+		let text = if decision.is_empty() {
+			summary.clone().unwrap_or_default()
+		} else {
+			decision.join(" ")
+		};
+		requirements.push(Requirement {
+			title: title.clone(),
+			text: format!("The system SHALL follow this decision: {text}"),
+		});
+	}
+	Document {
+		title,
+		summary: summary.unwrap_or_default(),
+		requirements,
+		inferred,
+	}
+}
+
+fn render_requirements(source: &str, requirements: &[Requirement]) -> String {
+	requirements
+		.iter()
+		.map(|requirement| {
+			format!(
+				"### Requirement: {}\n{}\n\n#### Scenario: Documented in {source}\n- **WHEN** the documented situation applies\n- **THEN** the system behaves as described in `{source}`\n",
+				requirement.title, requirement.text
+			)
+		})
+		.collect::<Vec<_>>()
+		.join("\n")
+}
+
+fn render(
+	target: MigrationTarget,
+	name: &str,
+	source: &str,
+	document: &Document,
+) -> Vec<(PathBuf, String)> {
+	let requirements = render_requirements(source, &document.requirements);
+	match target {
+		MigrationTarget::Capability => vec![(
+			PathBuf::from("specs").join(name).join("spec.md"),
+			format!(
+				"# {}\n\n## Purpose\nImported from `{source}`.\n\n## Requirements\n\n{requirements}",
+				document.title
+			),
+		)],
+		MigrationTarget::Change => {
+			let dir = PathBuf::from("changes").join(format!("import-{name}"));
+			vec![
+				(
+					dir.join("proposal.md"),
+					format!(
+						"## Why\n{}\n\n## What Changes\n- Import `{source}` as the `{name}` capability.\n\n## Impact\n- Affected specs: {name}\n",
+						document.summary
+					),
+				),
+				(
+					dir.join("specs").join(name).join("spec.md"),
+					format!("## ADDED Requirements\n\n{requirements}"),
+				),
+			]
+		}
+	}
+}
+
+fn source_files(
+	project: &Path,
+	source_glob: &str,
+	ignore: &IgnoreRules,
+) -> Result<Vec<PathBuf>, SpecError> {
+	let matcher = GlobBuilder::new(source_glob)
+		.literal_separator(true)
+		.build()
+		.map_err(|_| SpecError::InvalidSourceGlob(source_glob.to_string()))?
+		.compile_matcher();
+	let root = openspec_root(project);
+	Ok(markdown_files_except(project, &|path| {
+		path.starts_with(&root) || ignore.is_ignored(project, path)
+	})?
+	.into_iter()
+	.filter(|path| {
+		path.strip_prefix(project)
+			.is_ok_and(|relative| matcher.is_match(relative))
+	})
+	.collect())
+}
+
+fn migrate(
+	project: &Path,
+	source_glob: &str,
+	mapping: &MigrationMapping,
+	ignore: &IgnoreRules,
+) -> Result<MigrationReport, SpecError> {
+	let root = openspec_root(project);
+	let mut entries = Vec::new();
+	for file in source_files(project, source_glob, ignore)? {
+		let source = file
+			.strip_prefix(project)
+			.unwrap_or(&file)
+			.to_string_lossy()
+			.replace('\\', "/");
+		let stem = file
+			.file_stem()
+			.map(|stem| stem.to_string_lossy().to_string())
+			.unwrap_or_default();
+		let name = format!(
+			"{}{}",
+			mapping.prefix.as_deref().unwrap_or_default(),
+			slug(&stem)
+		);
+		let document = parse_document(&SourceText::read_complete(&file)?.render(), &stem);
+		let outputs = render(mapping.target, &name, &source, &document);
+		let conflict = outputs.iter().any(|(path, _)| root.join(path).exists());
+		let status = if conflict {
+			MigrationStatus::Conflict
+		} else if mapping.dry_run {
+			MigrationStatus::Planned
+		} else {
+			for (path, contents) in &outputs {
+				storage::write_atomic(&root.join(path), contents.as_bytes())?;
+			}
+			MigrationStatus::Created
+		};
+		entries.push(MigrationEntry {
+			source,
+			target: outputs
+				.last()
+				.map(|(path, _)| path.to_string_lossy().replace('\\', "/"))
+				.unwrap_or_default(),
+			title: document.title,
+			requirements: document.requirements.len(),
+			inferred: document.inferred,
+			status,
+		});
+	}
+	Ok(MigrationReport {
+		dry_run: mapping.dry_run,
+		target: mapping.target,
+		entries,
+	})
+}
+
+#[tauri::command]
+pub fn migrate_external_docs(
+	settings: State<'_, SettingsStore>,
+	sessions: State<'_, EditSessions>,
+	project: String,
+	source_glob: String,
+	mapping: Option<MigrationMapping>,
+) -> Result<MigrationReport, SpecErrorPayload> {
+	let project = Path::new(&project);
+	let mapping = mapping.unwrap_or_default();
+	if !mapping.dry_run && sessions.is_locked_within(&openspec_root(project)) {
+		return Err(SpecError::FileLocked.into());
+	}
+	migrate(
+		project,
+		&source_glob,
+		&mapping,
+		&rules_for(&settings, project),
+	)
+	.map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ignore::DEFAULT_IGNORE_PATTERNS;
+	use tempfile::TempDir;
+
+	#[test]
+	fn extracts_normative_statements_and_falls_back_to_the_decision() {
+		let document = parse_document(
+			"# Use Postgres\n\n## Context\nWe need storage.\n\n## Decision\nAdopt Postgres 16.\n",
+			"0001-use-postgres",
+		);
+		assert!(document.inferred);
+		assert_eq!(document.requirements.len(), 1);
+		assert_eq!(
+			document.requirements[0].text,
+			"The system SHALL follow this decision: Adopt Postgres 16."
+		);
+
+		let document = parse_document(
+			"Auth notes\n- Users must sign in with SSO.\n- Sessions should expire after 1h.\n- Mustard is tasty.\n",
+			"auth",
+		);
+		assert!(!document.inferred);
+		assert_eq!(document.title, "auth");
+		assert_eq!(document.requirements.len(), 2);
+		assert_eq!(
+			document.requirements[0].text,
+			"Users SHALL sign in with SSO."
+		);
+	}
+
+	#[test]
+	fn dry_run_reports_mapping_without_writing() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let project = temp_dir.path();
+		fs::create_dir_all(project.join("docs/adr")).expect("create adr");
+		fs::create_dir_all(project.join("openspec/specs/use-postgres")).expect("create specs");
+		fs::write(
+			project.join("openspec/specs/use-postgres/spec.md"),
+			"# Existing\n",
+		)
+		.expect("write existing");
+		fs::write(
+			project.join("docs/adr/0001-use-postgres.md"),
+			"# Use Postgres\n## Decision\nAdopt it.\n",
+		)
+		.expect("write adr");
+		fs::write(
+			project.join("docs/adr/0002-api-keys.md"),
+			"# API keys\nKeys must rotate monthly.\n",
+		)
+		.expect("write adr");
+		let ignore = IgnoreRules::new(DEFAULT_IGNORE_PATTERNS).expect("rules");
+
+		let mapping = MigrationMapping {
+			dry_run: true,
+			..MigrationMapping::default()
+		};
+		let report = migrate(project, "docs/adr/*.md", &mapping, &ignore).expect("dry run");
+		let statuses = report
+			.entries
+			.iter()
+			.map(|entry| (entry.target.as_str(), entry.status))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			statuses,
+			vec![
+				("specs/use-postgres/spec.md", MigrationStatus::Conflict),
+				("specs/api-keys/spec.md", MigrationStatus::Planned),
+			]
+		);
+		assert!(!project.join("openspec/specs/api-keys").exists());
+
+		let mapping = MigrationMapping {
+			target: MigrationTarget::Change,
+			..MigrationMapping::default()
+		};
+		let report = migrate(project, "docs/adr/0002-*.md", &mapping, &ignore).expect("migrate");
+		assert_eq!(report.entries[0].status, MigrationStatus::Created);
+		let spec = fs::read_to_string(
+			project.join("openspec/changes/import-api-keys/specs/api-keys/spec.md"),
+		)
+		.expect("read spec");
+		assert!(spec.starts_with("## ADDED Requirements\n\n### Requirement: Keys must rotate monthly\nKeys SHALL rotate monthly."));
+	}
+}
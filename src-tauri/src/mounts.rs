@@ -0,0 +1,257 @@
+use serde::Serialize;
+use std::{
+	collections::HashMap,
+	fs,
+	path::{Component, Path, PathBuf},
+	sync::Mutex,
+	time::Duration,
+};
+
+/// Poll interval for projects whose file system does not deliver reliable
+/// change notifications.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const SLOW_TIMEOUT_FACTOR: u32 = 3;
+
+static KINDS: Mutex<Option<HashMap<PathBuf, StorageKind>>> = Mutex::new(None);
+
+#[derive(Clone, Copy, Debug, Default, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum StorageKind {
+	#[default]
+	Local,
+	Network,
+	CloudSynced,
+}
+
+impl StorageKind {
+	/// Network shares and sync clients coalesce or drop native watch events
+	/// and answer metadata calls slowly.
+	pub fn is_slow(self) -> bool {
+		self != StorageKind::Local
+	}
+}
+
+const NETWORK_FILESYSTEMS: &[&str] = &[
+	"nfs",
+	"nfs4",
+	"cifs",
+	"smbfs",
+	"smb3",
+	"afpfs",
+	"webdav",
+	"davfs",
+	"9p",
+	"ceph",
+	"glusterfs",
+	"lustre",
+	"fuse.sshfs",
+	"fuse.rclone",
+	"fuse.glusterfs",
+];
+
+const CLOUD_FOLDERS: &[&str] = &[
+	"dropbox",
+	"onedrive",
+	"google drive",
+	"my drive",
+	"icloud drive",
+	"mobile documents",
+	"cloudstorage",
+	"box",
+	"box sync",
+	"pcloud drive",
+];
+
+fn is_network_fs(fstype: &str) -> bool {
+	NETWORK_FILESYSTEMS.contains(&fstype.to_ascii_lowercase().as_str())
+}
+
+// Business accounts get suffixed folders such as `OneDrive - Contoso` or
+// `Dropbox (Team)`.
+fn is_cloud_folder(name: &str) -> bool {
+	let name = name.to_lowercase();
+	CLOUD_FOLDERS.contains(&name.as_str())
+		|| name.starts_with("onedrive - ")
+		|| name.starts_with("dropbox (")
+}
+
+fn unescape_mount_path(path: &str) -> String {
+	path.replace("\\040", " ")
+		.replace("\\011", "\t")
+		.replace("\\134", "\\")
+}
+
+/// Reads `/proc/mounts`: `source target fstype options dump pass`.
+pub fn parse_proc_mounts(text: &str) -> Vec<(PathBuf, String)> {
+	text.lines()
+		.filter_map(|line| {
+			let mut fields = line.split_whitespace();
+			let target = fields.nth(1)?;
+			let fstype = fields.next()?;
+			Some((
+				PathBuf::from(unescape_mount_path(target)),
+				fstype.to_string(),
+			))
+		})
+		.collect()
+}
+
+/// Reads BSD `mount` output: `//user@host/share on /Volumes/share (smbfs, nodev)`.
+pub fn parse_mount_output(text: &str) -> Vec<(PathBuf, String)> {
+	text.lines()
+		.filter_map(|line| {
+			let (_, rest) = line.split_once(" on ")?;
+			let (target, options) = rest.rsplit_once(" (")?;
+			let fstype = options.trim_end_matches(')').split(',').next()?.trim();
+			Some((PathBuf::from(target), fstype.to_string()))
+		})
+		.collect()
+}
+
+#[cfg(target_os = "linux")]
+fn mount_table() -> Vec<(PathBuf, String)> {
+	fs::read_to_string("/proc/self/mounts")
+		.map(|text| parse_proc_mounts(&text))
+		.unwrap_or_default()
+}
+
+#[cfg(target_os = "macos")]
+fn mount_table() -> Vec<(PathBuf, String)> {
+	std::process::Command::new("mount")
+		.output()
+		.map(|output| parse_mount_output(&String::from_utf8_lossy(&output.stdout)))
+		.unwrap_or_default()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn mount_table() -> Vec<(PathBuf, String)> {
+	Vec::new()
+}
+
+#[cfg(windows)]
+fn is_remote_drive(path: &Path) -> bool {
+	use std::os::windows::ffi::OsStrExt;
+	use windows_sys::Win32::Storage::FileSystem::GetDriveTypeW;
+	const DRIVE_REMOTE: u32 = 4;
+	let Some(Component::Prefix(prefix)) = path.components().next() else {
+		return false;
+	};
+	let root = prefix
+		.as_os_str()
+		.encode_wide()
+		.chain("\\".encode_utf16())
+		.chain(std::iter::once(0))
+		.collect::<Vec<_>>();
+	unsafe { GetDriveTypeW(root.as_ptr()) == DRIVE_REMOTE }
+}
+
+#[cfg(not(windows))]
+fn is_remote_drive(_path: &Path) -> bool {
+	false
+}
+
+fn is_unc(path: &Path) -> bool {
+	let text = path.to_string_lossy();
+	(text.starts_with(r"\\") && !text.starts_with(r"\\?\") && !text.starts_with(r"\\.\"))
+		|| text.starts_with(r"\\?\UNC\")
+}
+
+fn mount_fstype<'a>(mounts: &'a [(PathBuf, String)], path: &Path) -> Option<&'a str> {
+	mounts
+		.iter()
+		.filter(|(target, _)| path.starts_with(target))
+		.max_by_key(|(target, _)| target.as_os_str().len())
+		.map(|(_, fstype)| fstype.as_str())
+}
+
+pub fn classify(path: &Path, mounts: &[(PathBuf, String)]) -> StorageKind {
+	if is_unc(path) || mount_fstype(mounts, path).is_some_and(is_network_fs) {
+		return StorageKind::Network;
+	}
+	let in_cloud_folder = path.components().any(|component| match component {
+		Component::Normal(name) => is_cloud_folder(&name.to_string_lossy()),
+		_ => false,
+	});
+	if in_cloud_folder {
+		StorageKind::CloudSynced
+	} else {
+		StorageKind::Local
+	}
+}
+
+/// Classifies where `path` lives. Results are remembered per path because
+/// mounts rarely move under an open project and probing is itself slow on
+/// the file systems this is meant to detect.
+pub fn storage_kind(path: &Path) -> StorageKind {
+	let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+	if let Some(kind) = KINDS
+		.lock()
+		.ok()
+		.and_then(|kinds| kinds.as_ref()?.get(&path).copied())
+	{
+		return kind;
+	}
+	let kind = if is_remote_drive(&path) {
+		StorageKind::Network
+	} else {
+		classify(&path, &mount_table())
+	};
+	if let Ok(mut kinds) = KINDS.lock() {
+		kinds.get_or_insert_with(HashMap::new).insert(path, kind);
+	}
+	kind
+}
+
+/// Stretches command timeouts for projects on slow storage, where the
+/// OpenSpec CLI spends most of its time waiting on metadata calls.
+pub fn scaled_timeout(timeout: Option<Duration>, kind: StorageKind) -> Option<Duration> {
+	if kind.is_slow() {
+		timeout.map(|timeout| timeout * SLOW_TIMEOUT_FACTOR)
+	} else {
+		timeout
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reads_mount_tables() {
+		let proc = "sysfs /sys sysfs rw 0 0\nnas:/export/home /mnt/nas\\040share nfs4 rw,relatime 0 0\n/dev/sda1 / ext4 rw 0 0\n";
+		assert_eq!(
+			parse_proc_mounts(proc)[1],
+			(PathBuf::from("/mnt/nas share"), "nfs4".to_string())
+		);
+		let bsd = "/dev/disk3s1 on / (apfs, local, journaled)\n//dev@nas/specs on /Volumes/specs (smbfs, nodev, nosuid, mounted by dev)\n";
+		assert_eq!(
+			parse_mount_output(bsd),
+			[
+				(PathBuf::from("/"), "apfs".to_string()),
+				(PathBuf::from("/Volumes/specs"), "smbfs".to_string()),
+			]
+		);
+	}
+
+	#[test]
+	fn classifies_network_and_cloud_synced_paths() {
+		let mounts = parse_proc_mounts(
+			"/dev/sda1 / ext4 rw 0 0\nnas:/export /mnt/nas nfs4 rw 0 0\n/dev/sdb1 /mnt/nas/local ext4 rw 0 0\n",
+		);
+		let kind = |path: &str| classify(Path::new(path), &mounts);
+		assert_eq!(kind("/mnt/nas/repo"), StorageKind::Network);
+		assert_eq!(kind("/mnt/nas/local/repo"), StorageKind::Local);
+		assert_eq!(kind("/home/dev/Dropbox/repo"), StorageKind::CloudSynced);
+		assert_eq!(
+			kind("/home/dev/OneDrive - Contoso/repo"),
+			StorageKind::CloudSynced
+		);
+		assert_eq!(kind("/home/dev/dropbox-tools"), StorageKind::Local);
+		assert_eq!(kind(r"\\fileserver\specs\repo"), StorageKind::Network);
+		assert_eq!(
+			scaled_timeout(Some(Duration::from_secs(60)), StorageKind::Network),
+			Some(Duration::from_secs(180))
+		);
+		assert_eq!(scaled_timeout(None, StorageKind::CloudSynced), None);
+	}
+}
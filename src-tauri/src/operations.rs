@@ -0,0 +1,516 @@
+use crate::{
+	journal::OperationStatus,
+	settings::{CommandTimeouts, SettingsStore},
+	spec::{SpecError, SpecErrorPayload},
+	storage,
+};
+use serde::Serialize;
+use std::{
+	collections::{BTreeMap, HashMap, VecDeque},
+	io::Write,
+	process::{Command, Stdio},
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		mpsc::{self, RecvTimeoutError, Sender},
+		Arc, Mutex,
+	},
+	thread::{self, JoinHandle},
+	time::{Duration, Instant},
+};
+use tauri::{Emitter, Manager, State, Window};
+
+const FINISHED_LIMIT: usize = 50;
+const CANCEL_POLL: Duration = Duration::from_millis(100);
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationSummary {
+	operation_id: String,
+	kind: String,
+	command_line: String,
+	started_at: u64,
+	finished_at: Option<u64>,
+	status: OperationStatus,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+	pub fn cancel(&self) {
+		self.0.store(true, Ordering::SeqCst);
+	}
+
+	pub fn is_cancelled(&self) -> bool {
+		self.0.load(Ordering::SeqCst)
+	}
+
+	/// Native jobs call this between units of work; the error unwinds the job
+	/// with `?` so partial output is never committed.
+	pub fn check(&self) -> Result<(), SpecError> {
+		if self.is_cancelled() {
+			Err(SpecError::OperationCancelled)
+		} else {
+			Ok(())
+		}
+	}
+
+	/// Waits out `duration`, returning early once the token trips.
+	pub fn sleep(&self, duration: Duration) -> Result<(), SpecError> {
+		let deadline = Instant::now() + duration;
+		loop {
+			self.check()?;
+			let left = deadline.saturating_duration_since(Instant::now());
+			if left.is_zero() {
+				return Ok(());
+			}
+			thread::sleep(left.min(CANCEL_POLL));
+		}
+	}
+}
+
+type OperationInput = Arc<Mutex<Box<dyn Write + Send>>>;
+
+struct RunningOperation {
+	pid: Option<u32>,
+	cancelled: CancellationToken,
+	input: Option<OperationInput>,
+	summary: OperationSummary,
+}
+
+#[derive(Default)]
+pub struct RunningOperations {
+	running: Mutex<HashMap<String, RunningOperation>>,
+	finished: Mutex<VecDeque<OperationSummary>>,
+}
+
+pub struct Watchdog {
+	done: Sender<()>,
+	handle: JoinHandle<bool>,
+}
+
+impl Watchdog {
+	pub fn start(pid: u32, timeout: Duration) -> Self {
+		let (done, finished) = mpsc::channel();
+		let handle = thread::spawn(move || match finished.recv_timeout(timeout) {
+			Err(RecvTimeoutError::Timeout) => kill_tree(pid).is_ok(),
+			_ => false,
+		});
+		Self { done, handle }
+	}
+
+	pub fn finish(self) -> bool {
+		let _ = self.done.send(());
+		self.handle.join().unwrap_or(false)
+	}
+}
+
+#[cfg(unix)]
+pub fn isolate(command: &mut Command) {
+	use std::os::unix::process::CommandExt;
+	command.process_group(0);
+}
+
+#[cfg(not(unix))]
+pub fn isolate(_command: &mut Command) {}
+
+#[cfg(unix)]
+fn kill_tree(pid: u32) -> std::io::Result<()> {
+	Command::new("kill")
+		.args(["-TERM", "--", &format!("-{pid}")])
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.status()
+		.map(|_| ())
+}
+
+#[cfg(windows)]
+fn kill_tree(pid: u32) -> std::io::Result<()> {
+	Command::new("taskkill")
+		.args(["/PID", &pid.to_string(), "/T", "/F"])
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.status()
+		.map(|_| ())
+}
+
+impl RunningOperations {
+	pub fn register(
+		&self,
+		id: &str,
+		kind: &str,
+		command_line: String,
+		pid: u32,
+	) -> CancellationToken {
+		self.insert(id, kind, command_line, Some(pid))
+	}
+
+	pub fn register_native(&self, id: &str, kind: &str, description: String) -> CancellationToken {
+		self.insert(id, kind, description, None)
+	}
+
+	fn insert(
+		&self,
+		id: &str,
+		kind: &str,
+		command_line: String,
+		pid: Option<u32>,
+	) -> CancellationToken {
+		let cancelled = CancellationToken::default();
+		if let Ok(mut running) = self.running.lock() {
+			running.insert(
+				id.to_string(),
+				RunningOperation {
+					pid,
+					cancelled: cancelled.clone(),
+					input: None,
+					summary: OperationSummary {
+						operation_id: id.to_string(),
+						kind: kind.to_string(),
+						command_line,
+						started_at: storage::now_secs(),
+						finished_at: None,
+						status: OperationStatus::Running,
+					},
+				},
+			);
+		}
+		cancelled
+	}
+
+	pub fn attach_input(&self, id: &str, input: Box<dyn Write + Send>) {
+		if let Ok(mut running) = self.running.lock() {
+			if let Some(operation) = running.get_mut(id) {
+				operation.input = Some(Arc::new(Mutex::new(input)));
+			}
+		}
+	}
+
+	/// Writes a line to the child's stdin. The write happens outside the
+	/// operations lock so a child that stops reading cannot stall listing or
+	/// cancelling.
+	pub fn send_input(&self, id: &str, input: &str) -> Result<(), SpecError> {
+		let writer = self
+			.running
+			.lock()
+			.map_err(|_| SpecError::OperationNotFound)?
+			.get(id)
+			.ok_or(SpecError::OperationNotFound)?
+			.input
+			.clone()
+			.ok_or(SpecError::OperationInputClosed)?;
+		let line = if input.ends_with('\n') {
+			input.to_string()
+		} else {
+			format!("{input}\n")
+		};
+		let written = writer.lock().is_ok_and(|mut writer| {
+			writer
+				.write_all(line.as_bytes())
+				.and_then(|_| writer.flush())
+				.is_ok()
+		});
+		if !written {
+			if let Some(operation) = self
+				.running
+				.lock()
+				.ok()
+				.as_mut()
+				.and_then(|running| running.get_mut(id))
+			{
+				operation.input = None;
+			}
+			return Err(SpecError::OperationInputClosed);
+		}
+		Ok(())
+	}
+
+	pub fn finish(&self, id: &str, status: OperationStatus) {
+		let Some(operation) = self
+			.running
+			.lock()
+			.ok()
+			.and_then(|mut running| running.remove(id))
+		else {
+			return;
+		};
+		if let Ok(mut finished) = self.finished.lock() {
+			finished.push_front(OperationSummary {
+				finished_at: Some(storage::now_secs()),
+				status,
+				..operation.summary
+			});
+			finished.truncate(FINISHED_LIMIT);
+		}
+	}
+
+	pub fn list(&self) -> Vec<OperationSummary> {
+		let mut active = self
+			.running
+			.lock()
+			.map(|running| {
+				running
+					.values()
+					.map(|operation| operation.summary.clone())
+					.collect::<Vec<_>>()
+			})
+			.unwrap_or_default();
+		active
+			.sort_by(|a, b| (a.started_at, &a.operation_id).cmp(&(b.started_at, &b.operation_id)));
+		if let Ok(finished) = self.finished.lock() {
+			active.extend(finished.iter().cloned());
+		}
+		active
+	}
+
+	pub fn cancel(&self, id: &str) -> Result<(), SpecError> {
+		let running = self
+			.running
+			.lock()
+			.map_err(|_| SpecError::OperationNotFound)?;
+		let operation = running.get(id).ok_or(SpecError::OperationNotFound)?;
+		operation.cancelled.cancel();
+		if let Some(pid) = operation.pid {
+			kill_tree(pid)?;
+		}
+		Ok(())
+	}
+}
+
+fn native_status<T>(result: &Result<T, SpecError>) -> OperationStatus {
+	match result {
+		Ok(_) => OperationStatus::Succeeded,
+		Err(SpecError::OperationCancelled) => OperationStatus::Cancelled,
+		Err(_) => OperationStatus::Failed,
+	}
+}
+
+/// Runs an in-process job as a listed operation so `cancel_operation` can stop
+/// it through the token it receives.
+pub fn run_native<T>(
+	window: &Window,
+	kind: &str,
+	description: String,
+	job: impl FnOnce(&CancellationToken) -> Result<T, SpecError>,
+) -> Result<T, SpecError> {
+	let operations = window.state::<RunningOperations>();
+	let operation_id = storage::new_id(kind);
+	let cancelled = operations.register_native(&operation_id, kind, description);
+	let _ = window.emit(
+		"openspec://operation-started",
+		crate::OperationStartedEvent {
+			operation: kind.to_string(),
+			operation_id: operation_id.clone(),
+		},
+	);
+	let result = job(&cancelled);
+	let status = native_status(&result);
+	operations.finish(&operation_id, status);
+	let _ = window.emit(
+		"openspec://operation-finished",
+		crate::OperationFinishedEvent {
+			operation: kind.to_string(),
+			operation_id,
+			status,
+		},
+	);
+	result
+}
+
+#[tauri::command]
+pub fn cancel_operation(
+	operations: State<'_, RunningOperations>,
+	operation_id: String,
+) -> Result<(), SpecErrorPayload> {
+	operations
+		.cancel(&operation_id)
+		.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn list_operations(operations: State<'_, RunningOperations>) -> Vec<OperationSummary> {
+	operations.list()
+}
+
+#[tauri::command]
+pub fn send_operation_input(
+	operations: State<'_, RunningOperations>,
+	operation_id: String,
+	input: String,
+) -> Result<(), SpecErrorPayload> {
+	operations
+		.send_input(&operation_id, &input)
+		.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn command_timeouts(settings: State<'_, SettingsStore>) -> CommandTimeouts {
+	settings.get().command_timeouts
+}
+
+#[tauri::command]
+pub fn set_command_timeouts(
+	settings: State<'_, SettingsStore>,
+	timeouts: CommandTimeouts,
+) -> Result<CommandTimeouts, SpecErrorPayload> {
+	settings
+		.update(|current| current.command_timeouts = timeouts.clone())
+		.map_err(|error| SpecErrorPayload::from(SpecError::from(error)))?;
+	Ok(timeouts)
+}
+
+#[tauri::command]
+pub fn command_environment(settings: State<'_, SettingsStore>) -> BTreeMap<String, String> {
+	settings.get().command_env
+}
+
+#[tauri::command]
+pub fn set_command_environment(
+	settings: State<'_, SettingsStore>,
+	env: BTreeMap<String, String>,
+) -> Result<BTreeMap<String, String>, SpecErrorPayload> {
+	if let Some(name) = env
+		.keys()
+		.find(|name| name.is_empty() || name.contains(['=', '\0']))
+	{
+		return Err(SpecError::InvalidEnvironmentVariable(name.clone()).into());
+	}
+	settings
+		.update(|current| current.command_env = env.clone())
+		.map_err(|error| SpecErrorPayload::from(SpecError::from(error)))?;
+	Ok(env)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[cfg(unix)]
+	#[test]
+	fn cancelling_kills_the_child_process_group() {
+		let mut command = Command::new("sh");
+		command.args(["-c", "sleep 30 & wait"]);
+		isolate(&mut command);
+		let mut child = command.spawn().expect("spawn");
+
+		let operations = RunningOperations::default();
+		let cancelled = operations.register("op-1", "init", "sh -c sleep".to_string(), child.id());
+		assert_eq!(operations.list()[0].status, OperationStatus::Running);
+		operations.cancel("op-1").expect("cancel");
+		let status = child.wait().expect("wait");
+
+		assert!(!status.success());
+		assert!(cancelled.is_cancelled());
+		operations.finish("op-1", OperationStatus::Cancelled);
+		let listed = operations.list();
+		assert_eq!(listed.len(), 1);
+		assert_eq!(listed[0].status, OperationStatus::Cancelled);
+		assert!(listed[0].finished_at.is_some());
+		assert!(matches!(
+			operations.cancel("op-1"),
+			Err(SpecError::OperationNotFound)
+		));
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn cancels_from_another_thread_while_the_runner_waits() {
+		let operations = RunningOperations::default();
+		thread::scope(|scope| {
+			let runner = scope.spawn(|| {
+				let mut command = Command::new("sh");
+				command.args(["-c", "sleep 30 & wait"]);
+				isolate(&mut command);
+				let mut child = command.spawn().expect("spawn");
+				let cancelled = operations.register("op-1", "init", "sh".to_string(), child.id());
+				let status = child.wait().expect("wait");
+				operations.finish("op-1", OperationStatus::Cancelled);
+				(status, cancelled.is_cancelled())
+			});
+			while operations.list().is_empty() {
+				thread::sleep(Duration::from_millis(5));
+			}
+			operations.cancel("op-1").expect("cancel");
+			let (status, cancelled) = runner.join().expect("runner");
+			assert!(!status.success());
+			assert!(cancelled);
+		});
+		assert_eq!(operations.list()[0].status, OperationStatus::Cancelled);
+	}
+
+	#[test]
+	fn cancelling_native_operations_trips_their_token() {
+		let operations = RunningOperations::default();
+		let cancelled = operations.register_native("op-1", "bundle-export", "export".to_string());
+		assert!(cancelled.check().is_ok());
+		operations.cancel("op-1").expect("cancel");
+		assert!(matches!(
+			cancelled.check(),
+			Err(SpecError::OperationCancelled)
+		));
+		let result: Result<(), _> = cancelled.check();
+		operations.finish("op-1", native_status(&result));
+		assert_eq!(operations.list()[0].status, OperationStatus::Cancelled);
+	}
+
+	#[test]
+	fn sleeping_wakes_up_when_cancelled() {
+		let token = CancellationToken::default();
+		assert!(token.sleep(Duration::from_millis(10)).is_ok());
+
+		let started = Instant::now();
+		thread::scope(|scope| {
+			scope.spawn(|| {
+				thread::sleep(Duration::from_millis(20));
+				token.cancel();
+			});
+			assert!(matches!(
+				token.sleep(Duration::from_secs(30)),
+				Err(SpecError::OperationCancelled)
+			));
+		});
+		assert!(started.elapsed() < Duration::from_secs(5));
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn watchdog_kills_only_commands_that_overrun() {
+		let mut command = Command::new("sh");
+		command.args(["-c", "sleep 30 & wait"]);
+		isolate(&mut command);
+		let mut child = command.spawn().expect("spawn");
+		let watchdog = Watchdog::start(child.id(), Duration::from_millis(50));
+		assert!(!child.wait().expect("wait").success());
+		assert!(watchdog.finish());
+
+		let mut child = Command::new("true").spawn().expect("spawn");
+		let watchdog = Watchdog::start(child.id(), Duration::from_secs(30));
+		assert!(child.wait().expect("wait").success());
+		assert!(!watchdog.finish());
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn forwards_input_to_the_child_stdin() {
+		let mut child = Command::new("sh")
+			.args(["-c", "read answer && echo \"got $answer\""])
+			.stdin(Stdio::piped())
+			.stdout(Stdio::piped())
+			.spawn()
+			.expect("spawn");
+
+		let operations = RunningOperations::default();
+		operations.register("op-1", "init", "sh".to_string(), child.id());
+		assert!(matches!(
+			operations.send_input("op-1", "y"),
+			Err(SpecError::OperationInputClosed)
+		));
+		let stdin = child.stdin.take().expect("stdin");
+		operations.attach_input("op-1", Box::new(stdin));
+		let runner = thread::spawn(move || child.wait_with_output());
+		operations.send_input("op-1", "y").expect("send");
+
+		let output = runner.join().expect("runner").expect("wait");
+		assert_eq!(String::from_utf8_lossy(&output.stdout), "got y\n");
+	}
+}
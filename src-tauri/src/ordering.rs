@@ -0,0 +1,270 @@
+use crate::{
+	settings::SettingsStore,
+	spec::{SpecError, SpecErrorPayload},
+};
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, iter::Peekable, path::Path, str::Chars, sync::RwLock};
+use tauri::State;
+
+static POLICY: RwLock<OrderingPolicy> = RwLock::new(OrderingPolicy {
+	mode: SortMode::Natural,
+	case_sensitive: false,
+});
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SortMode {
+	#[default]
+	Natural,
+	Lexical,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct OrderingPolicy {
+	pub mode: SortMode,
+	pub case_sensitive: bool,
+}
+
+pub fn install(policy: OrderingPolicy) {
+	*POLICY
+		.write()
+		.unwrap_or_else(|poisoned| poisoned.into_inner()) = policy;
+}
+
+pub fn policy() -> OrderingPolicy {
+	*POLICY
+		.read()
+		.unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Folds the Latin accents that show up in capability and change names so
+// "écran" sorts next to "ecran" rather than after "z".
+fn strip_accent(c: char) -> char {
+	match c {
+		'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+		'À' | 'Á' | 'Â' | 'Ã' | 'Ä' | 'Å' => 'A',
+		'ç' => 'c',
+		'Ç' => 'C',
+		'è' | 'é' | 'ê' | 'ë' => 'e',
+		'È' | 'É' | 'Ê' | 'Ë' => 'E',
+		'ì' | 'í' | 'î' | 'ï' => 'i',
+		'Ì' | 'Í' | 'Î' | 'Ï' => 'I',
+		'ñ' => 'n',
+		'Ñ' => 'N',
+		'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => 'o',
+		'Ò' | 'Ó' | 'Ô' | 'Õ' | 'Ö' | 'Ø' => 'O',
+		'ù' | 'ú' | 'û' | 'ü' => 'u',
+		'Ù' | 'Ú' | 'Û' | 'Ü' => 'U',
+		'ý' | 'ÿ' => 'y',
+		'Ý' => 'Y',
+		other => other,
+	}
+}
+
+fn fold(c: char, case_sensitive: bool) -> char {
+	let c = strip_accent(c);
+	if case_sensitive {
+		c
+	} else {
+		c.to_lowercase().next().unwrap_or(c)
+	}
+}
+
+fn digit_run(chars: &mut Peekable<Chars>) -> String {
+	let mut run = String::new();
+	while let Some(c) = chars.next_if(char::is_ascii_digit) {
+		run.push(c);
+	}
+	run
+}
+
+fn compare_numbers(a: &str, b: &str) -> Ordering {
+	let a = a.trim_start_matches('0');
+	let b = b.trim_start_matches('0');
+	a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
+fn natural(a: &str, b: &str, case_sensitive: bool) -> Ordering {
+	let mut left = a.chars().peekable();
+	let mut right = b.chars().peekable();
+	loop {
+		let ordering = match (left.peek().copied(), right.peek().copied()) {
+			(None, None) => return Ordering::Equal,
+			(None, Some(_)) => return Ordering::Less,
+			(Some(_), None) => return Ordering::Greater,
+			(Some(l), Some(r)) if l.is_ascii_digit() && r.is_ascii_digit() => {
+				compare_numbers(&digit_run(&mut left), &digit_run(&mut right))
+			}
+			(Some(l), Some(r)) => {
+				left.next();
+				right.next();
+				fold(l, case_sensitive).cmp(&fold(r, case_sensitive))
+			}
+		};
+		if ordering != Ordering::Equal {
+			return ordering;
+		}
+	}
+}
+
+fn lexical(a: &str, b: &str, case_sensitive: bool) -> Ordering {
+	a.chars()
+		.map(|c| fold(c, case_sensitive))
+		.cmp(b.chars().map(|c| fold(c, case_sensitive)))
+}
+
+pub fn compare_with(policy: OrderingPolicy, a: &str, b: &str) -> Ordering {
+	let folded = match policy.mode {
+		SortMode::Natural => natural(a, b, policy.case_sensitive),
+		SortMode::Lexical => lexical(a, b, policy.case_sensitive),
+	};
+	// Names that only differ by case, accents or zero padding still need a
+	// fixed order, otherwise listings shuffle between platforms.
+	folded.then_with(|| a.cmp(b))
+}
+
+pub fn compare(a: &str, b: &str) -> Ordering {
+	compare_with(policy(), a, b)
+}
+
+pub fn compare_paths(a: &Path, b: &Path) -> Ordering {
+	let policy = policy();
+	let components = |path: &Path| {
+		path.components()
+			.map(|component| component.as_os_str().to_string_lossy().to_string())
+			.collect::<Vec<_>>()
+	};
+	let (left, right) = (components(a), components(b));
+	left.iter()
+		.zip(&right)
+		.map(|(l, r)| compare_with(policy, l, r))
+		.find(|ordering| *ordering != Ordering::Equal)
+		.unwrap_or_else(|| left.len().cmp(&right.len()))
+}
+
+#[tauri::command]
+pub fn ordering_policy() -> OrderingPolicy {
+	policy()
+}
+
+#[tauri::command]
+pub fn set_ordering_policy(
+	settings: State<'_, SettingsStore>,
+	policy: OrderingPolicy,
+) -> Result<OrderingPolicy, SpecErrorPayload> {
+	settings
+		.update(|settings| settings.ordering = policy)
+		.map_err(|error| SpecErrorPayload::from(SpecError::from(error)))?;
+	install(policy);
+	Ok(policy)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::path::PathBuf;
+
+	fn sorted(policy: OrderingPolicy, names: &[&str]) -> Vec<String> {
+		let mut names = names
+			.iter()
+			.map(|name| name.to_string())
+			.collect::<Vec<_>>();
+		names.sort_by(|a, b| compare_with(policy, a, b));
+		names
+	}
+
+	#[test]
+	fn natural_order_compares_numbers_by_value() {
+		let names = [
+			"change-10",
+			"change-2",
+			"Change-1",
+			"change-02",
+			"écran",
+			"ecran-b",
+		];
+		assert_eq!(
+			sorted(OrderingPolicy::default(), &names),
+			[
+				"Change-1",
+				"change-02",
+				"change-2",
+				"change-10",
+				"écran",
+				"ecran-b"
+			]
+		);
+		assert_eq!(
+			sorted(
+				OrderingPolicy {
+					mode: SortMode::Lexical,
+					case_sensitive: false,
+				},
+				&names
+			),
+			[
+				"change-02",
+				"Change-1",
+				"change-10",
+				"change-2",
+				"écran",
+				"ecran-b"
+			]
+		);
+	}
+
+	#[test]
+	fn case_sensitive_policy_keeps_uppercase_first() {
+		let policy = OrderingPolicy {
+			mode: SortMode::Natural,
+			case_sensitive: true,
+		};
+		assert_eq!(
+			sorted(policy, &["beta", "Alpha", "alpha"]),
+			["Alpha", "alpha", "beta"]
+		);
+		assert_eq!(
+			sorted(policy, &["beta", "Zeta", "alpha"]),
+			["Zeta", "alpha", "beta"]
+		);
+	}
+
+	#[test]
+	fn ordering_is_total_and_stable() {
+		let names = ["a1", "a01", "A1", "a001", "á1"];
+		let first = sorted(OrderingPolicy::default(), &names);
+		let mut reversed = names;
+		reversed.reverse();
+		assert_eq!(sorted(OrderingPolicy::default(), &reversed), first);
+		for (index, name) in first.iter().enumerate() {
+			for other in &first[index + 1..] {
+				assert_eq!(
+					compare_with(OrderingPolicy::default(), name, other),
+					Ordering::Less
+				);
+			}
+		}
+	}
+
+	#[test]
+	fn paths_sort_component_by_component() {
+		let mut paths = [
+			"specs/auth-10/spec.md",
+			"specs/auth-2/spec.md",
+			"specs/auth/spec.md",
+			"specs/auth-2/notes/a.md",
+		]
+		.map(PathBuf::from);
+		paths.sort_by(|a, b| compare_paths(a, b));
+		assert_eq!(
+			paths.map(|path| path.to_string_lossy().to_string()),
+			[
+				"specs/auth/spec.md",
+				"specs/auth-2/notes/a.md",
+				"specs/auth-2/spec.md",
+				"specs/auth-10/spec.md",
+			]
+		);
+	}
+}
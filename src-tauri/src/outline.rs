@@ -0,0 +1,192 @@
+use crate::spec::{
+	parse_headings, Heading, SourceText, SpecError, SpecErrorPayload, MAX_PARSE_BYTES,
+};
+use serde::Serialize;
+use std::path::Path;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SymbolKind {
+	Capability,
+	Requirement,
+	Scenario,
+	Section,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutlineSymbol {
+	pub kind: SymbolKind,
+	pub name: String,
+	pub level: usize,
+	pub start_line: usize,
+	pub end_line: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DocumentOutline {
+	symbols: Vec<OutlineSymbol>,
+	truncated: bool,
+}
+
+fn capability_dir_name(path: &Path) -> Option<String> {
+	if path.file_name()?.to_str()? != "spec.md" {
+		return None;
+	}
+	let capability = path.parent()?;
+	if capability.parent()?.file_name()?.to_str()? != "specs" {
+		return None;
+	}
+	Some(capability.file_name()?.to_string_lossy().to_string())
+}
+
+fn classify(heading: &Heading, spec_file: bool) -> (SymbolKind, String) {
+	if let Some(name) = heading.text.strip_prefix("Requirement:") {
+		(SymbolKind::Requirement, name.trim().to_string())
+	} else if let Some(name) = heading.text.strip_prefix("Scenario:") {
+		(SymbolKind::Scenario, name.trim().to_string())
+	} else if spec_file && heading.level == 1 {
+		let name = heading
+			.text
+			.strip_suffix("Specification")
+			.unwrap_or(&heading.text)
+			.trim();
+		(SymbolKind::Capability, name.to_string())
+	} else {
+		(SymbolKind::Section, heading.text.clone())
+	}
+}
+
+fn last_content_line(lines: &[String], start: usize, end: usize) -> usize {
+	let mut last = end;
+	while last > start && lines[last].trim().is_empty() {
+		last -= 1;
+	}
+	last
+}
+
+pub fn outline_source(path: &Path, source: &SourceText) -> Vec<OutlineSymbol> {
+	let lines = &source.lines;
+	if lines.is_empty() {
+		return Vec::new();
+	}
+	let headings = parse_headings(lines);
+	let capability = capability_dir_name(path);
+	let mut symbols = Vec::with_capacity(headings.len() + 1);
+
+	let has_capability_heading = capability.is_some()
+		&& headings.iter().any(|heading| {
+			heading.level == 1 && classify(heading, true).0 == SymbolKind::Capability
+		});
+	if let (Some(name), false) = (capability.clone(), has_capability_heading) {
+		symbols.push(OutlineSymbol {
+			kind: SymbolKind::Capability,
+			name,
+			level: 0,
+			start_line: 1,
+			end_line: last_content_line(lines, 0, lines.len() - 1) + 1,
+		});
+	}
+
+	for (index, heading) in headings.iter().enumerate() {
+		let next = headings[index + 1..]
+			.iter()
+			.find(|next| next.level <= heading.level)
+			.map(|next| next.line)
+			.unwrap_or(lines.len());
+		let (kind, name) = classify(heading, capability.is_some());
+		symbols.push(OutlineSymbol {
+			kind,
+			name,
+			level: heading.level,
+			start_line: heading.line + 1,
+			end_line: last_content_line(lines, heading.line, next - 1) + 1,
+		});
+	}
+	symbols
+}
+
+fn outline_file(path: &Path) -> Result<DocumentOutline, SpecError> {
+	let source = SourceText::read_limited(path, MAX_PARSE_BYTES)?;
+	Ok(DocumentOutline {
+		symbols: outline_source(path, &source),
+		truncated: source.truncated,
+	})
+}
+
+#[tauri::command]
+pub fn document_outline(path: String) -> Result<DocumentOutline, SpecErrorPayload> {
+	outline_file(Path::new(&path)).map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn summary(symbols: &[OutlineSymbol]) -> Vec<(SymbolKind, &str, usize, usize)> {
+		symbols
+			.iter()
+			.map(|symbol| {
+				(
+					symbol.kind,
+					symbol.name.as_str(),
+					symbol.start_line,
+					symbol.end_line,
+				)
+			})
+			.collect()
+	}
+
+	#[test]
+	fn outlines_capability_spec() {
+		let text = "# auth Specification\n\n## Purpose\nAuth.\n\n## Requirements\n### Requirement: Login\nThe system SHALL log in.\n\n#### Scenario: Works\n- **WHEN** x\n\n";
+		let symbols = outline_source(
+			Path::new("/repo/openspec/specs/auth/spec.md"),
+			&SourceText::parse(text),
+		);
+		assert_eq!(
+			summary(&symbols),
+			vec![
+				(SymbolKind::Capability, "auth", 1, 11),
+				(SymbolKind::Section, "Purpose", 3, 4),
+				(SymbolKind::Section, "Requirements", 6, 11),
+				(SymbolKind::Requirement, "Login", 7, 11),
+				(SymbolKind::Scenario, "Works", 10, 11),
+			],
+		);
+	}
+
+	#[test]
+	fn synthesizes_capability_for_delta_specs() {
+		let text = "## ADDED Requirements\n### Requirement: Logout\nThe system SHALL log out.\n";
+		let symbols = outline_source(
+			Path::new("/repo/openspec/changes/add-logout/specs/auth/spec.md"),
+			&SourceText::parse(text),
+		);
+		assert_eq!(
+			summary(&symbols),
+			vec![
+				(SymbolKind::Capability, "auth", 1, 3),
+				(SymbolKind::Section, "ADDED Requirements", 1, 3),
+				(SymbolKind::Requirement, "Logout", 2, 3),
+			],
+		);
+	}
+
+	#[test]
+	fn treats_non_spec_headings_as_sections() {
+		let text = "# Change: Add logout\n## Why\nBecause.\n";
+		let symbols = outline_source(
+			Path::new("/repo/openspec/changes/add-logout/proposal.md"),
+			&SourceText::parse(text),
+		);
+		assert_eq!(
+			summary(&symbols),
+			vec![
+				(SymbolKind::Section, "Change: Add logout", 1, 3),
+				(SymbolKind::Section, "Why", 2, 3),
+			],
+		);
+	}
+}
@@ -0,0 +1,177 @@
+use crate::ansi::StyledSegment;
+use serde::Serialize;
+use std::{
+	mem,
+	sync::{
+		mpsc::{self, RecvTimeoutError, Sender},
+		Arc, Mutex,
+	},
+	thread::{self, JoinHandle},
+	time::{Duration, Instant},
+};
+use tauri::{Emitter, Window};
+
+const FLUSH_INTERVAL: Duration = Duration::from_millis(50);
+const MAX_BATCH_LINES: usize = 100;
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliOutputLine {
+	pub stream: String,
+	pub line: String,
+	pub plain: String,
+	pub segments: Vec<StyledSegment>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CliOutputEvent {
+	operation: String,
+	operation_id: String,
+	sequence: u64,
+	lines: Vec<CliOutputLine>,
+}
+
+struct Pending {
+	lines: Vec<CliOutputLine>,
+	sequence: u64,
+	last_flush: Instant,
+}
+
+impl Pending {
+	fn new() -> Self {
+		Self {
+			lines: Vec::new(),
+			sequence: 0,
+			last_flush: Instant::now(),
+		}
+	}
+
+	fn push(&mut self, line: CliOutputLine) -> Option<(u64, Vec<CliOutputLine>)> {
+		self.lines.push(line);
+		if self.lines.len() >= MAX_BATCH_LINES || self.last_flush.elapsed() >= FLUSH_INTERVAL {
+			self.take()
+		} else {
+			None
+		}
+	}
+
+	fn take(&mut self) -> Option<(u64, Vec<CliOutputLine>)> {
+		self.last_flush = Instant::now();
+		if self.lines.is_empty() {
+			return None;
+		}
+		let sequence = self.sequence;
+		self.sequence += 1;
+		Some((sequence, mem::take(&mut self.lines)))
+	}
+}
+
+pub struct OutputBatch {
+	window: Window,
+	operation: String,
+	operation_id: String,
+	pending: Mutex<Pending>,
+}
+
+impl OutputBatch {
+	pub fn new(window: &Window, operation: &str, operation_id: &str) -> Self {
+		Self {
+			window: window.clone(),
+			operation: operation.to_string(),
+			operation_id: operation_id.to_string(),
+			pending: Mutex::new(Pending::new()),
+		}
+	}
+
+	fn emit(&self, batch: Option<(u64, Vec<CliOutputLine>)>) {
+		if let Some((sequence, lines)) = batch {
+			let _ = self.window.emit(
+				"openspec://cli-output",
+				CliOutputEvent {
+					operation: self.operation.clone(),
+					operation_id: self.operation_id.clone(),
+					sequence,
+					lines,
+				},
+			);
+		}
+	}
+
+	pub fn push(&self, line: CliOutputLine) {
+		if let Ok(mut pending) = self.pending.lock() {
+			self.emit(pending.push(line));
+		}
+	}
+
+	pub fn flush(&self) {
+		if let Ok(mut pending) = self.pending.lock() {
+			self.emit(pending.take());
+		}
+	}
+}
+
+pub struct FlushTicker {
+	stop: Sender<()>,
+	handle: JoinHandle<()>,
+}
+
+impl FlushTicker {
+	pub fn start(batch: &Arc<OutputBatch>) -> Self {
+		let (stop, stopped) = mpsc::channel();
+		let batch = Arc::clone(batch);
+		let handle = thread::spawn(move || {
+			while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(FLUSH_INTERVAL) {
+				batch.flush();
+			}
+			batch.flush();
+		});
+		Self { stop, handle }
+	}
+
+	pub fn stop(self) {
+		let _ = self.stop.send(());
+		let _ = self.handle.join();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn line(text: &str) -> CliOutputLine {
+		CliOutputLine {
+			stream: "stdout".to_string(),
+			line: text.to_string(),
+			plain: text.to_string(),
+			segments: Vec::new(),
+		}
+	}
+
+	#[test]
+	fn batches_lines_by_count_and_age() {
+		let mut pending = Pending::new();
+		pending.last_flush += Duration::from_secs(60);
+		let mut batches = Vec::new();
+		for index in 0..250 {
+			batches.extend(pending.push(line(&index.to_string())));
+		}
+		assert_eq!(
+			batches
+				.iter()
+				.map(|(sequence, lines)| (*sequence, lines.len()))
+				.collect::<Vec<_>>(),
+			vec![(0, 100), (1, 100)]
+		);
+		let (sequence, rest) = pending.take().expect("remaining lines");
+		assert_eq!((sequence, rest.len()), (2, 50));
+		assert_eq!(rest[0].line, "200");
+		assert!(pending.take().is_none());
+
+		pending.last_flush = Instant::now() - FLUSH_INTERVAL;
+		assert_eq!(
+			pending.push(line("late")).map(|(sequence, _)| sequence),
+			Some(3)
+		);
+	}
+}
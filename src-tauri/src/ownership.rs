@@ -0,0 +1,262 @@
+use crate::{
+	git::git_output,
+	ordering,
+	spec::{existing_openspec_root, SpecError, SpecErrorPayload},
+};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use serde::Serialize;
+use std::{
+	collections::{BTreeMap, HashSet},
+	fs,
+	path::Path,
+};
+
+const CODEOWNERS_LOCATIONS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+const DEFAULT_RECENT_DAYS: u32 = 90;
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthorShare {
+	name: String,
+	email: String,
+	lines: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilityOwnership {
+	capability: String,
+	path: String,
+	total_lines: usize,
+	authors: Vec<AuthorShare>,
+	main_author: Option<AuthorShare>,
+	code_owners: Vec<String>,
+	main_author_active: bool,
+	flagged: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnershipReport {
+	project: String,
+	recent_days: u32,
+	codeowners_path: Option<String>,
+	capabilities: Vec<CapabilityOwnership>,
+}
+
+struct CodeOwnersRule {
+	matcher: GlobSet,
+	owners: Vec<String>,
+}
+
+fn codeowners_globs(pattern: &str) -> Vec<String> {
+	let anchored = pattern.starts_with('/');
+	let directory = pattern.ends_with('/');
+	let trimmed = pattern.trim_start_matches('/').trim_end_matches('/');
+	let base = if anchored || trimmed.contains('/') {
+		trimmed.to_string()
+	} else {
+		format!("**/{trimmed}")
+	};
+	if directory {
+		vec![format!("{base}/**")]
+	} else {
+		vec![base.clone(), format!("{base}/**")]
+	}
+}
+
+fn parse_codeowners(text: &str) -> Vec<CodeOwnersRule> {
+	text.lines()
+		.filter_map(|line| {
+			let line = line.split('#').next().unwrap_or_default().trim();
+			let mut parts = line.split_whitespace();
+			let pattern = parts.next()?;
+			let mut builder = GlobSetBuilder::new();
+			for glob in codeowners_globs(pattern) {
+				builder.add(
+					GlobBuilder::new(&glob)
+						.literal_separator(true)
+						.build()
+						.ok()?,
+				);
+			}
+			Some(CodeOwnersRule {
+				matcher: builder.build().ok()?,
+				owners: parts.map(str::to_string).collect(),
+			})
+		})
+		.collect()
+}
+
+fn owners_for(rules: &[CodeOwnersRule], relative_path: &str) -> Vec<String> {
+	rules
+		.iter()
+		.rev()
+		.find(|rule| rule.matcher.is_match(relative_path))
+		.map(|rule| rule.owners.clone())
+		.unwrap_or_default()
+}
+
+fn summarize_blame(porcelain: &str) -> Vec<AuthorShare> {
+	let mut counts = BTreeMap::<String, AuthorShare>::new();
+	let mut name = String::new();
+	for line in porcelain.lines() {
+		if let Some(author) = line.strip_prefix("author ") {
+			name = author.to_string();
+		} else if let Some(mail) = line.strip_prefix("author-mail ") {
+			let email = mail.trim_matches(['<', '>']).to_lowercase();
+			counts
+				.entry(email.clone())
+				.or_insert_with(|| AuthorShare {
+					name: name.clone(),
+					email,
+					lines: 0,
+				})
+				.lines += 1;
+		}
+	}
+	let mut authors = counts.into_values().collect::<Vec<_>>();
+	authors.sort_by(|a, b| b.lines.cmp(&a.lines).then_with(|| a.email.cmp(&b.email)));
+	authors
+}
+
+fn capability_ownership(
+	capability: String,
+	relative_path: String,
+	authors: Vec<AuthorShare>,
+	code_owners: Vec<String>,
+	recent_authors: &HashSet<String>,
+) -> CapabilityOwnership {
+	let main_author = authors.first().cloned();
+	let main_author_active = main_author
+		.as_ref()
+		.is_some_and(|author| recent_authors.contains(&author.email));
+	CapabilityOwnership {
+		capability,
+		path: relative_path,
+		total_lines: authors.iter().map(|author| author.lines).sum(),
+		flagged: main_author.is_some() && !main_author_active,
+		authors,
+		main_author,
+		code_owners,
+		main_author_active,
+	}
+}
+
+fn ownership_report_for(project: &Path, recent_days: u32) -> Result<OwnershipReport, SpecError> {
+	let specs_root = existing_openspec_root(project)?.join("specs");
+	let codeowners = CODEOWNERS_LOCATIONS.iter().find_map(|location| {
+		fs::read_to_string(project.join(location))
+			.ok()
+			.map(|text| (location, text))
+	});
+	let rules = codeowners
+		.as_ref()
+		.map(|(_, text)| parse_codeowners(text))
+		.unwrap_or_default();
+	let since = format!("--since={recent_days}.days");
+	let recent_authors = git_output(project, &["log", &since, "--format=%ae"])?
+		.lines()
+		.map(|email| email.trim().to_lowercase())
+		.collect::<HashSet<_>>();
+
+	let mut capabilities = Vec::new();
+	let mut entries = match fs::read_dir(&specs_root) {
+		Ok(entries) => entries.filter_map(Result::ok).collect::<Vec<_>>(),
+		Err(_) => Vec::new(),
+	};
+	entries.sort_by(|a, b| {
+		ordering::compare(
+			&a.file_name().to_string_lossy(),
+			&b.file_name().to_string_lossy(),
+		)
+	});
+	for entry in entries {
+		let spec_path = entry.path().join("spec.md");
+		if !spec_path.is_file() {
+			continue;
+		}
+		let relative_path = spec_path
+			.strip_prefix(project)
+			.unwrap_or(&spec_path)
+			.to_string_lossy()
+			.replace('\\', "/");
+		let authors = git_output(
+			project,
+			&["blame", "--line-porcelain", "--", &relative_path],
+		)
+		.map(|porcelain| summarize_blame(&porcelain))
+		.unwrap_or_default();
+		capabilities.push(capability_ownership(
+			entry.file_name().to_string_lossy().to_string(),
+			relative_path.clone(),
+			authors,
+			owners_for(&rules, &relative_path),
+			&recent_authors,
+		));
+	}
+
+	Ok(OwnershipReport {
+		project: project.to_string_lossy().to_string(),
+		recent_days,
+		codeowners_path: codeowners.map(|(location, _)| location.to_string()),
+		capabilities,
+	})
+}
+
+#[tauri::command]
+pub fn ownership_report(
+	project: String,
+	recent_days: Option<u32>,
+) -> Result<OwnershipReport, SpecErrorPayload> {
+	ownership_report_for(
+		Path::new(&project),
+		recent_days.unwrap_or(DEFAULT_RECENT_DAYS),
+	)
+	.map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn last_matching_codeowners_rule_wins() {
+		let rules = parse_codeowners(
+			"# owners\n* @org/everyone\nopenspec/specs/ @org/specs\n/openspec/specs/auth/*.md @alice # auth\n",
+		);
+		assert_eq!(
+			owners_for(&rules, "openspec/specs/auth/spec.md"),
+			vec!["@alice"]
+		);
+		assert_eq!(
+			owners_for(&rules, "openspec/specs/billing/spec.md"),
+			vec!["@org/specs"]
+		);
+		assert_eq!(owners_for(&rules, "README.md"), vec!["@org/everyone"]);
+	}
+
+	#[test]
+	fn aggregates_blame_and_flags_inactive_main_author() {
+		let porcelain = "\
+abc 1 1 1\nauthor Alice\nauthor-mail <Alice@Example.com>\n\tline\n\
+abc 2 2\nauthor Alice\nauthor-mail <alice@example.com>\n\tline\n\
+def 3 3 1\nauthor Bob\nauthor-mail <bob@example.com>\n\tline\n";
+		let authors = summarize_blame(porcelain);
+		assert_eq!(authors.len(), 2);
+		assert_eq!(authors[0].email, "alice@example.com");
+		assert_eq!(authors[0].lines, 2);
+
+		let recent = HashSet::from(["bob@example.com".to_string()]);
+		let ownership = capability_ownership(
+			"auth".to_string(),
+			"openspec/specs/auth/spec.md".to_string(),
+			authors,
+			Vec::new(),
+			&recent,
+		);
+		assert_eq!(ownership.total_lines, 3);
+		assert!(ownership.flagged);
+		assert!(!ownership.main_author_active);
+	}
+}
@@ -0,0 +1,204 @@
+use crate::{
+	settings::SettingsStore,
+	spec::{SpecError, SpecErrorPayload},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+use tauri::State;
+
+const PROBE_TTL: Duration = Duration::from_secs(60);
+const NORMAL_DEBOUNCE: Duration = Duration::from_millis(300);
+const LOW_POWER_DEBOUNCE: Duration = Duration::from_secs(3);
+
+static LAST_PROBE: Mutex<Option<(Instant, PowerSource)>> = Mutex::new(None);
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PowerSource {
+	Ac,
+	Battery,
+	Unknown,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum PowerMode {
+	#[default]
+	Auto,
+	AlwaysFull,
+	AlwaysLowPower,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerStatus {
+	source: PowerSource,
+	mode: PowerMode,
+	low_power: bool,
+	debounce_ms: u64,
+}
+
+#[cfg(target_os = "linux")]
+fn linux_power_source(root: &std::path::Path) -> PowerSource {
+	let read = |path: std::path::PathBuf| {
+		std::fs::read_to_string(path)
+			.map(|value| value.trim().to_string())
+			.unwrap_or_default()
+	};
+	let Ok(entries) = std::fs::read_dir(root) else {
+		return PowerSource::Unknown;
+	};
+	let mut on_battery = false;
+	for entry in entries.filter_map(Result::ok) {
+		let supply = entry.path();
+		match read(supply.join("type")).as_str() {
+			"Mains" | "USB" if read(supply.join("online")) == "1" => return PowerSource::Ac,
+			"Battery" if read(supply.join("status")) == "Discharging" => on_battery = true,
+			_ => {}
+		}
+	}
+	if on_battery {
+		PowerSource::Battery
+	} else {
+		PowerSource::Ac
+	}
+}
+
+#[cfg(target_os = "linux")]
+fn probe_power_source() -> PowerSource {
+	linux_power_source(std::path::Path::new("/sys/class/power_supply"))
+}
+
+#[cfg(target_os = "macos")]
+fn probe_power_source() -> PowerSource {
+	let Ok(output) = std::process::Command::new("pmset")
+		.args(["-g", "batt"])
+		.output()
+	else {
+		return PowerSource::Unknown;
+	};
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	if stdout.contains("'Battery Power'") {
+		PowerSource::Battery
+	} else if stdout.contains("'AC Power'") {
+		PowerSource::Ac
+	} else {
+		PowerSource::Unknown
+	}
+}
+
+#[cfg(windows)]
+fn probe_power_source() -> PowerSource {
+	use windows_sys::Win32::System::Power::{GetSystemPowerStatus, SYSTEM_POWER_STATUS};
+	let mut status = unsafe { std::mem::zeroed::<SYSTEM_POWER_STATUS>() };
+	if unsafe { GetSystemPowerStatus(&mut status) } == 0 {
+		return PowerSource::Unknown;
+	}
+	match status.ACLineStatus {
+		0 => PowerSource::Battery,
+		1 => PowerSource::Ac,
+		_ => PowerSource::Unknown,
+	}
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", windows)))]
+fn probe_power_source() -> PowerSource {
+	PowerSource::Unknown
+}
+
+pub fn power_source() -> PowerSource {
+	let Ok(mut last) = LAST_PROBE.lock() else {
+		return probe_power_source();
+	};
+	match *last {
+		Some((probed_at, source)) if probed_at.elapsed() < PROBE_TTL => source,
+		_ => {
+			let source = probe_power_source();
+			*last = Some((Instant::now(), source));
+			source
+		}
+	}
+}
+
+fn is_low_power(mode: PowerMode, source: PowerSource) -> bool {
+	match mode {
+		PowerMode::Auto => source == PowerSource::Battery,
+		PowerMode::AlwaysFull => false,
+		PowerMode::AlwaysLowPower => true,
+	}
+}
+
+pub fn low_power(settings: &SettingsStore) -> bool {
+	is_low_power(settings.get().power_mode, power_source())
+}
+
+pub fn watch_debounce(low_power: bool) -> Duration {
+	if low_power {
+		LOW_POWER_DEBOUNCE
+	} else {
+		NORMAL_DEBOUNCE
+	}
+}
+
+fn status(mode: PowerMode) -> PowerStatus {
+	let source = power_source();
+	let low_power = is_low_power(mode, source);
+	PowerStatus {
+		source,
+		mode,
+		low_power,
+		debounce_ms: watch_debounce(low_power).as_millis() as u64,
+	}
+}
+
+#[tauri::command]
+pub fn power_status(settings: State<'_, SettingsStore>) -> PowerStatus {
+	status(settings.get().power_mode)
+}
+
+#[tauri::command]
+pub fn set_power_mode(
+	settings: State<'_, SettingsStore>,
+	mode: PowerMode,
+) -> Result<PowerStatus, SpecErrorPayload> {
+	settings
+		.update(|settings| settings.power_mode = mode)
+		.map_err(|error| SpecErrorPayload::from(SpecError::from(error)))?;
+	Ok(status(mode))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn manual_override_wins_over_power_source() {
+		assert!(is_low_power(PowerMode::Auto, PowerSource::Battery));
+		assert!(!is_low_power(PowerMode::Auto, PowerSource::Unknown));
+		assert!(!is_low_power(PowerMode::AlwaysFull, PowerSource::Battery));
+		assert!(is_low_power(PowerMode::AlwaysLowPower, PowerSource::Ac));
+		assert!(watch_debounce(true) > watch_debounce(false));
+	}
+
+	#[cfg(target_os = "linux")]
+	#[test]
+	fn reads_linux_power_supplies() {
+		let temp_dir = tempfile::TempDir::new().expect("create temp dir");
+		let supply = |name: &str, files: &[(&str, &str)]| {
+			let dir = temp_dir.path().join(name);
+			std::fs::create_dir_all(&dir).expect("create supply");
+			for (file, value) in files {
+				std::fs::write(dir.join(file), format!("{value}\n")).expect("write");
+			}
+		};
+		supply("BAT0", &[("type", "Battery"), ("status", "Discharging")]);
+		supply("AC", &[("type", "Mains"), ("online", "0")]);
+		assert_eq!(linux_power_source(temp_dir.path()), PowerSource::Battery);
+
+		supply("AC", &[("type", "Mains"), ("online", "1")]);
+		assert_eq!(linux_power_source(temp_dir.path()), PowerSource::Ac);
+	}
+}
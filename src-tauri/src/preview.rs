@@ -0,0 +1,359 @@
+use crate::spec::{
+	existing_openspec_root, is_fence, parse_heading, SourceText, SpecError, SpecErrorPayload,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::Serialize;
+use std::{
+	fs,
+	path::{Path, PathBuf},
+	sync::OnceLock,
+};
+use syntect::{highlighting::ThemeSet, html::highlighted_html_for_string, parsing::SyntaxSet};
+
+const MAX_TEXT_BYTES: usize = 1024 * 1024;
+const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
+const THEME: &str = "InspiredGitHub";
+const IMAGE_TYPES: &[(&str, &str)] = &[
+	("png", "image/png"),
+	("jpg", "image/jpeg"),
+	("jpeg", "image/jpeg"),
+	("gif", "image/gif"),
+	("webp", "image/webp"),
+	("svg", "image/svg+xml"),
+	("bmp", "image/bmp"),
+	("ico", "image/x-icon"),
+];
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum MarkdownBlock {
+	Heading {
+		level: usize,
+		text: String,
+	},
+	Paragraph {
+		text: String,
+	},
+	ListItem {
+		text: String,
+		ordered: bool,
+		checked: Option<bool>,
+	},
+	Code {
+		language: Option<String>,
+		text: String,
+	},
+	Quote {
+		text: String,
+	},
+	Rule,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum FilePreview {
+	Markdown {
+		blocks: Vec<MarkdownBlock>,
+		truncated: bool,
+	},
+	Code {
+		language: String,
+		html: String,
+	},
+	#[serde(rename_all = "camelCase")]
+	Image {
+		mime: String,
+		data_url: String,
+	},
+	Binary {
+		size: u64,
+		truncated: bool,
+	},
+}
+
+pub fn image_mime(extension: &str) -> Option<&'static str> {
+	IMAGE_TYPES
+		.iter()
+		.find_map(|(ext, mime)| (*ext == extension).then_some(*mime))
+}
+
+fn highlighter() -> &'static (SyntaxSet, ThemeSet) {
+	static HIGHLIGHTER: OnceLock<(SyntaxSet, ThemeSet)> = OnceLock::new();
+	HIGHLIGHTER.get_or_init(|| {
+		(
+			SyntaxSet::load_defaults_newlines(),
+			ThemeSet::load_defaults(),
+		)
+	})
+}
+
+fn list_item(line: &str) -> Option<MarkdownBlock> {
+	let trimmed = line.trim_start();
+	let (rest, ordered) = if let Some(rest) = trimmed
+		.strip_prefix("- ")
+		.or_else(|| trimmed.strip_prefix("* "))
+		.or_else(|| trimmed.strip_prefix("+ "))
+	{
+		(rest, false)
+	} else {
+		let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+		let rest = trimmed[digits..]
+			.strip_prefix(". ")
+			.filter(|_| digits > 0)?;
+		(rest, true)
+	};
+	let (checked, text) = match rest.get(..4) {
+		Some("[ ] ") => (Some(false), &rest[4..]),
+		Some("[x] " | "[X] ") => (Some(true), &rest[4..]),
+		_ => (None, rest),
+	};
+	Some(MarkdownBlock::ListItem {
+		text: text.trim().to_string(),
+		ordered,
+		checked,
+	})
+}
+
+pub fn parse_markdown(text: &str) -> Vec<MarkdownBlock> {
+	let mut blocks = Vec::new();
+	let mut paragraph = Vec::<&str>::new();
+	let mut fence = None::<(Option<String>, Vec<&str>)>;
+	let flush = |paragraph: &mut Vec<&str>, blocks: &mut Vec<MarkdownBlock>| {
+		if !paragraph.is_empty() {
+			blocks.push(MarkdownBlock::Paragraph {
+				text: paragraph.join(" "),
+			});
+			paragraph.clear();
+		}
+	};
+	for line in text.lines() {
+		if is_fence(line) {
+			match fence.take() {
+				Some((language, lines)) => blocks.push(MarkdownBlock::Code {
+					language,
+					text: lines.join("\n"),
+				}),
+				None => {
+					flush(&mut paragraph, &mut blocks);
+					let info = line.trim_start().trim_start_matches(['`', '~']).trim();
+					fence = Some(((!info.is_empty()).then(|| info.to_string()), Vec::new()));
+				}
+			}
+			continue;
+		}
+		if let Some((_, lines)) = fence.as_mut() {
+			lines.push(line);
+			continue;
+		}
+		let trimmed = line.trim();
+		if trimmed.is_empty() {
+			flush(&mut paragraph, &mut blocks);
+		} else if let Some((level, heading)) = parse_heading(line) {
+			flush(&mut paragraph, &mut blocks);
+			blocks.push(MarkdownBlock::Heading {
+				level,
+				text: heading.trim().to_string(),
+			});
+		} else if matches!(trimmed, "---" | "***" | "___") {
+			flush(&mut paragraph, &mut blocks);
+			blocks.push(MarkdownBlock::Rule);
+		} else if let Some(quote) = trimmed.strip_prefix('>') {
+			flush(&mut paragraph, &mut blocks);
+			blocks.push(MarkdownBlock::Quote {
+				text: quote.trim().to_string(),
+			});
+		} else if let Some(item) = list_item(line) {
+			flush(&mut paragraph, &mut blocks);
+			blocks.push(item);
+		} else {
+			paragraph.push(trimmed);
+		}
+	}
+	if let Some((language, lines)) = fence {
+		blocks.push(MarkdownBlock::Code {
+			language,
+			text: lines.join("\n"),
+		});
+	}
+	flush(&mut paragraph, &mut blocks);
+	blocks
+}
+
+fn highlight(extension: &str, text: &str) -> Result<FilePreview, SpecError> {
+	let (syntaxes, themes) = highlighter();
+	let syntax = syntaxes
+		.find_syntax_by_extension(extension)
+		.unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+	let html = highlighted_html_for_string(text, syntaxes, syntax, &themes.themes[THEME])
+		.map_err(|error| SpecError::Io(std::io::Error::other(error)))?;
+	Ok(FilePreview::Code {
+		language: syntax.name.clone(),
+		html,
+	})
+}
+
+fn preview(path: &Path) -> Result<FilePreview, SpecError> {
+	let size = fs::metadata(path)?.len();
+	let extension = path
+		.extension()
+		.map(|extension| extension.to_string_lossy().to_lowercase())
+		.unwrap_or_default();
+	if let Some(mime) = image_mime(&extension) {
+		if size as usize > MAX_IMAGE_BYTES {
+			return Ok(FilePreview::Binary {
+				size,
+				truncated: true,
+			});
+		}
+		return Ok(FilePreview::Image {
+			mime: mime.to_string(),
+			data_url: format!("data:{mime};base64,{}", STANDARD.encode(fs::read(path)?)),
+		});
+	}
+	if matches!(extension.as_str(), "md" | "markdown") {
+		let source = SourceText::read_limited(path, MAX_TEXT_BYTES as u64)?;
+		return Ok(FilePreview::Markdown {
+			blocks: parse_markdown(&source.lines.join("\n")),
+			truncated: source.truncated,
+		});
+	}
+	if size as usize > MAX_TEXT_BYTES {
+		return Ok(FilePreview::Binary {
+			size,
+			truncated: true,
+		});
+	}
+	let bytes = fs::read(path)?;
+	let Some(text) = String::from_utf8(bytes)
+		.ok()
+		.filter(|text| !text.contains('\0'))
+	else {
+		return Ok(FilePreview::Binary {
+			size,
+			truncated: false,
+		});
+	};
+	highlight(&extension, &text)
+}
+
+/// Resolves a project-relative path, following symlinks, and refuses
+/// anything that lands outside the OpenSpec folder. Preview windows can call
+/// this, so it must not become a way to read arbitrary files.
+pub fn resolve(project: &Path, path: impl AsRef<Path>) -> Result<PathBuf, SpecError> {
+	let root = fs::canonicalize(existing_openspec_root(project)?)?;
+	let resolved = fs::canonicalize(project.join(path))?;
+	if resolved.starts_with(&root) {
+		Ok(resolved)
+	} else {
+		Err(SpecError::PathOutsideOpenSpec)
+	}
+}
+
+#[tauri::command]
+pub fn preview_file(project: String, path: String) -> Result<FilePreview, SpecErrorPayload> {
+	let path = resolve(Path::new(&project), &path)?;
+	preview(&path).map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn parses_markdown_blocks() {
+		let blocks = parse_markdown(
+			"# Design\nFirst line\ncontinues.\n\n- [x] done\n2. second\n```json\n{\"a\": 1}\n```\n> note\n---\n",
+		);
+		assert_eq!(
+			blocks,
+			vec![
+				MarkdownBlock::Heading {
+					level: 1,
+					text: "Design".to_string()
+				},
+				MarkdownBlock::Paragraph {
+					text: "First line continues.".to_string()
+				},
+				MarkdownBlock::ListItem {
+					text: "done".to_string(),
+					ordered: false,
+					checked: Some(true)
+				},
+				MarkdownBlock::ListItem {
+					text: "second".to_string(),
+					ordered: true,
+					checked: None
+				},
+				MarkdownBlock::Code {
+					language: Some("json".to_string()),
+					text: "{\"a\": 1}".to_string()
+				},
+				MarkdownBlock::Quote {
+					text: "note".to_string()
+				},
+				MarkdownBlock::Rule,
+			]
+		);
+	}
+
+	#[test]
+	fn previews_files_by_type() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let dir = temp_dir.path();
+		fs::write(dir.join("example.yaml"), "key: value\n").expect("write yaml");
+		fs::write(dir.join("diagram.png"), [0x89, b'P', b'N', b'G']).expect("write png");
+		fs::write(dir.join("blob.bin"), [0, 159, 146, 150]).expect("write bin");
+
+		match preview(&dir.join("example.yaml")).expect("yaml") {
+			FilePreview::Code { language, html } => {
+				assert_eq!(language, "YAML");
+				assert!(html.contains("<span"));
+			}
+			other => panic!("unexpected preview {other:?}"),
+		}
+		match preview(&dir.join("diagram.png")).expect("png") {
+			FilePreview::Image { data_url, .. } => {
+				assert_eq!(data_url, "data:image/png;base64,iVBORw==")
+			}
+			other => panic!("unexpected preview {other:?}"),
+		}
+		assert!(matches!(
+			preview(&dir.join("blob.bin")).expect("bin"),
+			FilePreview::Binary {
+				size: 4,
+				truncated: false
+			}
+		));
+
+		fs::write(dir.join("giant.md"), "# Heading\n".repeat(200_000)).expect("write md");
+		match preview(&dir.join("giant.md")).expect("md") {
+			FilePreview::Markdown { blocks, truncated } => {
+				assert!(truncated);
+				assert_eq!(blocks.len(), MAX_TEXT_BYTES / "# Heading\n".len());
+			}
+			other => panic!("unexpected preview {other:?}"),
+		}
+	}
+
+	#[test]
+	fn only_resolves_files_inside_the_openspec_folder() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let project = temp_dir.path();
+		fs::create_dir_all(project.join("openspec/specs/auth")).expect("create specs");
+		fs::write(project.join("openspec/specs/auth/spec.md"), "# Auth\n").expect("write spec");
+		fs::write(project.join("secret.txt"), "token").expect("write secret");
+
+		assert!(resolve(project, "openspec/specs/auth/spec.md").is_ok());
+		for path in [
+			"secret.txt",
+			"openspec/../secret.txt",
+			&project.join("secret.txt").display().to_string(),
+		] {
+			assert!(
+				matches!(resolve(project, path), Err(SpecError::PathOutsideOpenSpec)),
+				"{path} should be rejected"
+			);
+		}
+	}
+}
@@ -0,0 +1,360 @@
+use crate::{
+	settings::SettingsStore,
+	spec::{SpecError, SpecErrorPayload},
+	storage,
+};
+use serde::Serialize;
+use std::{
+	path::{Path, PathBuf},
+	sync::{Condvar, Mutex},
+};
+use tauri::{Emitter, Manager, State, Window};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OperationScope {
+	Global,
+	Project(PathBuf),
+}
+
+impl OperationScope {
+	pub fn for_project(project: Option<&Path>) -> Self {
+		match project {
+			Some(project) => Self::Project(
+				project
+					.canonicalize()
+					.unwrap_or_else(|_| project.to_path_buf()),
+			),
+			None => Self::Global,
+		}
+	}
+
+	fn conflicts_with(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Self::Project(a), Self::Project(b)) => a == b,
+			_ => true,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuePositionEvent {
+	operation: String,
+	operation_id: String,
+	position: usize,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedOperation {
+	operation_id: String,
+	operation: String,
+	position: usize,
+	running: bool,
+	queued_at: u64,
+}
+
+struct Ticket {
+	id: String,
+	operation: String,
+	scope: OperationScope,
+	queued_at: u64,
+	started: bool,
+}
+
+#[derive(Default)]
+pub struct OperationQueue {
+	tickets: Mutex<Vec<Ticket>>,
+	changed: Condvar,
+}
+
+pub struct QueueGuard<'a> {
+	queue: &'a OperationQueue,
+	id: String,
+}
+
+impl Drop for QueueGuard<'_> {
+	fn drop(&mut self) {
+		if let Ok(mut tickets) = self.queue.tickets.lock() {
+			tickets.retain(|ticket| ticket.id != self.id);
+		}
+		self.queue.changed.notify_all();
+	}
+}
+
+fn position(tickets: &[Ticket], id: &str) -> usize {
+	let Some(index) = tickets.iter().position(|ticket| ticket.id == id) else {
+		return 0;
+	};
+	let scope = &tickets[index].scope;
+	tickets[..index]
+		.iter()
+		.filter(|ticket| ticket.scope.conflicts_with(scope))
+		.count()
+}
+
+impl OperationQueue {
+	pub fn enter(
+		&self,
+		id: &str,
+		operation: &str,
+		scope: OperationScope,
+		wait: bool,
+		mut on_position: impl FnMut(usize),
+	) -> Result<QueueGuard<'_>, SpecError> {
+		let guard = QueueGuard {
+			queue: self,
+			id: id.to_string(),
+		};
+		let Ok(mut tickets) = self.tickets.lock() else {
+			return Ok(guard);
+		};
+		tickets.push(Ticket {
+			id: id.to_string(),
+			operation: operation.to_string(),
+			scope,
+			queued_at: storage::now_secs(),
+			started: false,
+		});
+		let mut reported = None;
+		loop {
+			let Some(index) = tickets.iter().position(|ticket| ticket.id == id) else {
+				return Err(SpecError::OperationDequeued);
+			};
+			let ahead = position(&tickets, id);
+			if ahead == 0 {
+				tickets[index].started = true;
+				return Ok(guard);
+			}
+			if !wait {
+				return Err(SpecError::OperationBusy);
+			}
+			if reported != Some(ahead) {
+				reported = Some(ahead);
+				on_position(ahead);
+			}
+			tickets = match self.changed.wait(tickets) {
+				Ok(tickets) => tickets,
+				Err(_) => return Ok(guard),
+			};
+		}
+	}
+
+	pub fn status(&self, scope: &OperationScope) -> Vec<QueuedOperation> {
+		let Ok(tickets) = self.tickets.lock() else {
+			return Vec::new();
+		};
+		tickets
+			.iter()
+			.filter(|ticket| ticket.scope.conflicts_with(scope))
+			.map(|ticket| QueuedOperation {
+				operation_id: ticket.id.clone(),
+				operation: ticket.operation.clone(),
+				position: position(&tickets, &ticket.id),
+				running: ticket.started,
+				queued_at: ticket.queued_at,
+			})
+			.collect()
+	}
+
+	pub fn move_to(&self, id: &str, index: usize) -> Result<(), SpecError> {
+		let mut tickets = self
+			.tickets
+			.lock()
+			.map_err(|_| SpecError::OperationNotFound)?;
+		let from = tickets
+			.iter()
+			.position(|ticket| ticket.id == id && !ticket.started)
+			.ok_or(SpecError::OperationNotFound)?;
+		let ticket = tickets.remove(from);
+		let conflicting = tickets
+			.iter()
+			.enumerate()
+			.filter(|(_, other)| other.scope.conflicts_with(&ticket.scope))
+			.map(|(at, _)| at)
+			.collect::<Vec<_>>();
+		let first_pending = conflicting
+			.iter()
+			.rposition(|&at| tickets[at].started)
+			.map_or(0, |last| last + 1);
+		let at = conflicting
+			.get(index.max(first_pending))
+			.copied()
+			.unwrap_or(tickets.len());
+		tickets.insert(at, ticket);
+		drop(tickets);
+		self.changed.notify_all();
+		Ok(())
+	}
+
+	pub fn cancel(&self, id: &str) -> Result<(), SpecError> {
+		let mut tickets = self
+			.tickets
+			.lock()
+			.map_err(|_| SpecError::OperationNotFound)?;
+		let index = tickets
+			.iter()
+			.position(|ticket| ticket.id == id && !ticket.started)
+			.ok_or(SpecError::OperationNotFound)?;
+		tickets.remove(index);
+		drop(tickets);
+		self.changed.notify_all();
+		Ok(())
+	}
+}
+
+/// Parks the calling thread until no conflicting operation is ahead. Callers
+/// must be async commands: waiting on the main thread would freeze the UI and
+/// leave `move_queued_operation` and `cancel_queued_operation` unreachable.
+pub fn wait_for_turn<'a>(
+	window: &'a Window,
+	operation: &str,
+	operation_id: &str,
+	project: Option<&Path>,
+) -> Result<QueueGuard<'a>, SpecError> {
+	let wait = !window
+		.state::<SettingsStore>()
+		.get()
+		.reject_concurrent_operations;
+	window.state::<OperationQueue>().inner().enter(
+		operation_id,
+		operation,
+		OperationScope::for_project(project),
+		wait,
+		|position| {
+			let _ = window.emit(
+				"openspec://operation-queued",
+				QueuePositionEvent {
+					operation: operation.to_string(),
+					operation_id: operation_id.to_string(),
+					position,
+				},
+			);
+		},
+	)
+}
+
+#[tauri::command]
+pub fn queue_status(queue: State<'_, OperationQueue>, project: String) -> Vec<QueuedOperation> {
+	queue.status(&OperationScope::for_project(Some(Path::new(&project))))
+}
+
+#[tauri::command]
+pub fn move_queued_operation(
+	queue: State<'_, OperationQueue>,
+	operation_id: String,
+	position: usize,
+) -> Result<(), SpecErrorPayload> {
+	queue
+		.move_to(&operation_id, position)
+		.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn cancel_queued_operation(
+	queue: State<'_, OperationQueue>,
+	operation_id: String,
+) -> Result<(), SpecErrorPayload> {
+	queue.cancel(&operation_id).map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::{sync::mpsc, thread, time::Duration};
+
+	#[test]
+	fn serializes_conflicting_operations_per_project() {
+		let queue = OperationQueue::default();
+		let project = OperationScope::Project(PathBuf::from("/repo"));
+		let first = queue
+			.enter("op-1", "init", project.clone(), true, |_| {
+				panic!("should not wait")
+			})
+			.expect("enter");
+		let other = queue
+			.enter(
+				"op-2",
+				"init",
+				OperationScope::Project(PathBuf::from("/other")),
+				true,
+				|_| panic!("should not wait"),
+			)
+			.expect("enter");
+		drop(other);
+
+		let (sender, positions) = mpsc::channel();
+		thread::scope(|scope| {
+			scope.spawn(|| {
+				let _guard = queue
+					.enter("op-3", "update", project.clone(), true, |position| {
+						let _ = sender.send(position);
+					})
+					.expect("enter");
+				let _ = sender.send(0);
+			});
+			assert_eq!(positions.recv().expect("queued"), 1);
+			assert!(positions.recv_timeout(Duration::from_millis(50)).is_err());
+			drop(first);
+			assert_eq!(positions.recv().expect("started"), 0);
+		});
+
+		let install = queue
+			.enter("op-4", "install", OperationScope::Global, true, |_| {
+				panic!("should not wait")
+			})
+			.expect("enter");
+		drop(install);
+	}
+
+	#[test]
+	fn reorders_and_cancels_pending_operations() {
+		let queue = OperationQueue::default();
+		let project = OperationScope::Project(PathBuf::from("/repo"));
+		let _running = queue
+			.enter("op-1", "init", project.clone(), true, |_| {})
+			.expect("enter");
+		assert!(matches!(
+			queue.enter("op-x", "update", project.clone(), false, |_| {}),
+			Err(SpecError::OperationBusy)
+		));
+
+		let (sender, events) = mpsc::channel();
+		thread::scope(|scope| {
+			for (queued, id) in [(2, "op-2"), (3, "op-3")] {
+				let sender = sender.clone();
+				let scope_project = project.clone();
+				let queue = &queue;
+				scope.spawn(move || {
+					let result = queue.enter(id, "update", scope_project, true, |_| {});
+					let _ = sender.send((id, result.is_ok()));
+				});
+				while queue.status(&project).len() < queued {
+					thread::sleep(Duration::from_millis(5));
+				}
+			}
+			queue.move_to("op-3", 0).expect("move");
+			let order = queue
+				.status(&project)
+				.into_iter()
+				.map(|entry| (entry.operation_id, entry.position, entry.running))
+				.collect::<Vec<_>>();
+			assert_eq!(
+				order,
+				vec![
+					("op-1".to_string(), 0, true),
+					("op-3".to_string(), 1, false),
+					("op-2".to_string(), 2, false),
+				]
+			);
+			assert!(matches!(
+				queue.cancel("op-1"),
+				Err(SpecError::OperationNotFound)
+			));
+			queue.cancel("op-2").expect("cancel");
+			assert_eq!(events.recv().expect("dequeued"), ("op-2", false));
+			queue.cancel("op-3").expect("cancel");
+			assert_eq!(events.recv().expect("dequeued"), ("op-3", false));
+		});
+	}
+}
@@ -0,0 +1,223 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use tauri::{Emitter, Window};
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum OperationPhase {
+	Resolving,
+	Downloading,
+	Linking,
+	WritingToolConfigs,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationProgress {
+	phase: OperationPhase,
+	percent: Option<u8>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OperationProgressEvent {
+	operation: String,
+	operation_id: String,
+	#[serde(flatten)]
+	progress: OperationProgress,
+}
+
+fn progress(phase: OperationPhase, percent: Option<u8>) -> Option<OperationProgress> {
+	Some(OperationProgress { phase, percent })
+}
+
+fn ratio(done: u64, total: u64) -> Option<u8> {
+	(total > 0).then(|| (done.min(total) * 100 / total) as u8)
+}
+
+fn pnpm_counts(rest: &str) -> Vec<(String, u64)> {
+	rest.split(',')
+		.filter_map(|part| {
+			let (label, count) = part.trim().split_once(' ')?;
+			Some((label.to_string(), count.trim().parse().ok()?))
+		})
+		.collect()
+}
+
+fn parse_pnpm(rest: &str) -> Option<OperationProgress> {
+	let counts = pnpm_counts(rest);
+	let count = |label: &str| {
+		counts
+			.iter()
+			.find_map(|(name, count)| (name == label).then_some(*count))
+			.unwrap_or(0)
+	};
+	let (resolved, downloaded, added) = (count("resolved"), count("downloaded"), count("added"));
+	if added > 0 {
+		progress(OperationPhase::Linking, ratio(added, resolved))
+	} else if downloaded > 0 {
+		progress(
+			OperationPhase::Downloading,
+			ratio(downloaded + count("reused"), resolved),
+		)
+	} else {
+		progress(OperationPhase::Resolving, None)
+	}
+}
+
+fn parse_yarn_step(line: &str) -> Option<OperationProgress> {
+	let (step, label) = line.strip_prefix('[')?.split_once(']')?;
+	let (current, total) = step.split_once('/')?;
+	let (current, total) = (current.parse().ok()?, total.parse().ok()?);
+	let label = label.trim().to_lowercase();
+	let phase = if label.starts_with("resolving") {
+		OperationPhase::Resolving
+	} else if label.starts_with("fetching") {
+		OperationPhase::Downloading
+	} else if label.starts_with("linking") || label.starts_with("building") {
+		OperationPhase::Linking
+	} else {
+		return None;
+	};
+	progress(phase, ratio(current, total))
+}
+
+pub fn parse_phase(line: &str) -> Option<OperationProgress> {
+	let line = line.trim();
+	if let Some(rest) = line.strip_prefix("Progress:") {
+		return parse_pnpm(rest);
+	}
+	if line.starts_with('[') {
+		return parse_yarn_step(line);
+	}
+	let lower = line.to_lowercase();
+	let contains = |needles: &[&str]| needles.iter().any(|needle| lower.contains(needle));
+	if contains(&["idealtree", "resolving dependencies", "resolving packages"]) {
+		progress(OperationPhase::Resolving, None)
+	} else if contains(&["http fetch", "downloaded and extracted", "downloading"]) {
+		progress(OperationPhase::Downloading, None)
+	} else if contains(&["reify", "linking", "saved lockfile"]) {
+		progress(OperationPhase::Linking, None)
+	} else if (lower.starts_with("added ") && lower.contains("package"))
+		|| lower.starts_with("installed ")
+	{
+		progress(OperationPhase::Linking, Some(100))
+	} else if contains(&[
+		"configuring",
+		"ai tools configured",
+		"agents.md",
+		"claude.md",
+		"slash command",
+	]) {
+		progress(OperationPhase::WritingToolConfigs, None)
+	} else {
+		None
+	}
+}
+
+#[derive(Default)]
+struct Tracker {
+	last: Option<OperationProgress>,
+}
+
+impl Tracker {
+	fn advance(&mut self, line: &str) -> Option<OperationProgress> {
+		let next = parse_phase(line)?;
+		if self
+			.last
+			.is_some_and(|last| next.phase < last.phase || next == last)
+		{
+			return None;
+		}
+		self.last = Some(next);
+		Some(next)
+	}
+}
+
+pub struct ProgressReporter {
+	window: Window,
+	operation: String,
+	operation_id: String,
+	tracker: Mutex<Tracker>,
+}
+
+impl ProgressReporter {
+	pub fn new(window: &Window, operation: &str, operation_id: &str) -> Self {
+		Self {
+			window: window.clone(),
+			operation: operation.to_string(),
+			operation_id: operation_id.to_string(),
+			tracker: Mutex::default(),
+		}
+	}
+
+	pub fn observe(&self, line: &str) {
+		let Some(progress) = self
+			.tracker
+			.lock()
+			.ok()
+			.and_then(|mut tracker| tracker.advance(line))
+		else {
+			return;
+		};
+		let _ = self.window.emit(
+			"openspec://operation-progress",
+			OperationProgressEvent {
+				operation: self.operation.clone(),
+				operation_id: self.operation_id.clone(),
+				progress,
+			},
+		);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn detects_package_manager_and_init_phases() {
+		assert_eq!(
+			parse_phase("Progress: resolved 40, reused 10, downloaded 10, added 0"),
+			progress(OperationPhase::Downloading, Some(50))
+		);
+		assert_eq!(
+			parse_phase("Progress: resolved 40, reused 30, downloaded 10, added 40, done"),
+			progress(OperationPhase::Linking, Some(100))
+		);
+		assert_eq!(
+			parse_phase("[2/4] Fetching packages..."),
+			progress(OperationPhase::Downloading, Some(50))
+		);
+		assert_eq!(
+			parse_phase("added 42 packages in 3s"),
+			progress(OperationPhase::Linking, Some(100))
+		);
+		assert_eq!(
+			parse_phase("✔ AI tools configured"),
+			progress(OperationPhase::WritingToolConfigs, None)
+		);
+		assert_eq!(parse_phase("npm notice New minor version"), None);
+
+		let mut tracker = Tracker::default();
+		let phases = [
+			"Resolving dependencies",
+			"Resolving dependencies",
+			"Resolved, downloaded and extracted [12]",
+			"resolving peer dependencies",
+			"Saved lockfile",
+		]
+		.into_iter()
+		.filter_map(|line| tracker.advance(line))
+		.map(|progress| progress.phase)
+		.collect::<Vec<_>>();
+		assert_eq!(
+			phases,
+			vec![
+				OperationPhase::Resolving,
+				OperationPhase::Downloading,
+				OperationPhase::Linking,
+			]
+		);
+	}
+}
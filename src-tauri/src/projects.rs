@@ -0,0 +1,461 @@
+use crate::{
+	changes::{list_changes, ChangeState},
+	lint::{lint_project_files, Severity},
+	spec::{openspec_root, SpecError, SpecErrorPayload},
+	storage::{self, JsonStore},
+	ProjectDiscovery,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+	collections::BTreeSet,
+	path::Path,
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		Mutex,
+	},
+	thread,
+};
+use tauri::{Emitter, State, Window};
+
+const ARCHIVED_TAG: &str = "archived";
+const STATUS_WORKERS: usize = 4;
+// Unmounted drives and stopped WSL distros come back, so a missing folder
+// only costs an unlabelled entry its place after it stays gone this long.
+const MISSING_GRACE_SECS: u64 = 30 * 24 * 60 * 60;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentProject {
+	pub path: String,
+	pub name: String,
+	pub openspec_present: bool,
+	pub last_opened: u64,
+	#[serde(default)]
+	pub tags: Vec<String>,
+	#[serde(default)]
+	pub group: Option<String>,
+	/// When the folder was first found missing; cleared once it is back.
+	#[serde(default)]
+	pub missing_since: Option<u64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProjectFilter {
+	tags: Vec<String>,
+	group: Option<String>,
+	search: Option<String>,
+	include_archived: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectLabels {
+	tags: Vec<String>,
+	groups: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStatus {
+	path: String,
+	openspec_present: bool,
+	active_changes: usize,
+	archived_changes: usize,
+	errors: usize,
+	warnings: usize,
+	valid: bool,
+	failure: Option<String>,
+}
+
+pub type RecentProjects = JsonStore<Vec<RecentProject>>;
+
+pub fn load_recent_projects() -> RecentProjects {
+	RecentProjects::in_home("projects.json")
+}
+
+pub fn record_discovery(store: &RecentProjects, discovery: &ProjectDiscovery) {
+	let _ = store.update(|projects| {
+		let previous = projects
+			.iter()
+			.position(|project| project.path == discovery.repo_path)
+			.map(|index| projects.remove(index))
+			.unwrap_or_default();
+		projects.insert(
+			0,
+			RecentProject {
+				path: discovery.repo_path.clone(),
+				name: discovery.repo_name.clone(),
+				openspec_present: discovery.openspec_present,
+				last_opened: storage::now_secs(),
+				tags: previous.tags,
+				group: previous.group,
+				missing_since: None,
+			},
+		);
+	});
+}
+
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+	tags.into_iter()
+		.map(|tag| tag.trim().to_lowercase())
+		.filter(|tag| !tag.is_empty())
+		.collect::<BTreeSet<_>>()
+		.into_iter()
+		.collect()
+}
+
+fn update_project(
+	store: &RecentProjects,
+	path: &str,
+	mutate: impl FnOnce(&mut RecentProject),
+) -> Result<RecentProject, SpecError> {
+	store
+		.update(|projects| {
+			let project = projects.iter_mut().find(|project| project.path == path)?;
+			mutate(project);
+			Some(project.clone())
+		})?
+		.ok_or(SpecError::ProjectNotFound)
+}
+
+/// Marks projects whose folder is gone as unavailable rather than dropping
+/// them. Only entries without tags or a group that stayed missing past the
+/// grace period are pruned; anything else waits for an explicit removal.
+fn mark_missing(store: &RecentProjects, now: u64) -> Vec<RecentProject> {
+	store
+		.update(|projects| {
+			for project in projects.iter_mut() {
+				if Path::new(&project.path).is_dir() {
+					project.missing_since = None;
+				} else {
+					project.missing_since.get_or_insert(now);
+				}
+			}
+			projects.retain(|project| {
+				let labelled = !project.tags.is_empty() || project.group.is_some();
+				labelled
+					|| project
+						.missing_since
+						.is_none_or(|since| now.saturating_sub(since) < MISSING_GRACE_SECS)
+			});
+			projects.clone()
+		})
+		.unwrap_or_else(|_| store.get())
+}
+
+fn remove_project(store: &RecentProjects, path: &str) -> Result<Vec<RecentProject>, SpecError> {
+	store
+		.update(|projects| {
+			let index = projects.iter().position(|project| project.path == path)?;
+			projects.remove(index);
+			Some(projects.clone())
+		})?
+		.ok_or(SpecError::ProjectNotFound)
+}
+
+fn matches_filter(project: &RecentProject, filter: &ProjectFilter) -> bool {
+	let search = filter
+		.search
+		.as_deref()
+		.map(|search| search.trim().to_lowercase())
+		.unwrap_or_default();
+	(filter.include_archived
+		|| filter.tags.iter().any(|tag| tag == ARCHIVED_TAG)
+		|| !project.tags.iter().any(|tag| tag == ARCHIVED_TAG))
+		&& filter.tags.iter().all(|tag| project.tags.contains(tag))
+		&& filter
+			.group
+			.as_ref()
+			.is_none_or(|group| project.group.as_ref() == Some(group))
+		&& (search.is_empty()
+			|| project.name.to_lowercase().contains(&search)
+			|| project.path.to_lowercase().contains(&search))
+}
+
+fn project_status(path: &str) -> ProjectStatus {
+	let project = Path::new(path);
+	let mut status = ProjectStatus {
+		path: path.to_string(),
+		openspec_present: openspec_root(project).is_dir(),
+		..ProjectStatus::default()
+	};
+	if !status.openspec_present {
+		return status;
+	}
+	let result = list_changes(project, true).and_then(|changes| {
+		let diagnostics = lint_project_files(project)?;
+		Ok((changes, diagnostics))
+	});
+	match result {
+		Ok((changes, diagnostics)) => {
+			let archived = changes
+				.iter()
+				.filter(|change| change.state == ChangeState::Archived)
+				.count();
+			status.archived_changes = archived;
+			status.active_changes = changes.len() - archived;
+			status.errors = diagnostics
+				.iter()
+				.filter(|diagnostic| diagnostic.severity == Severity::Error)
+				.count();
+			status.warnings = diagnostics
+				.iter()
+				.filter(|diagnostic| diagnostic.severity == Severity::Warning)
+				.count();
+			status.valid = status.errors == 0;
+		}
+		Err(error) => status.failure = Some(error.to_string()),
+	}
+	status
+}
+
+fn bulk_status(
+	paths: &[String],
+	workers: usize,
+	on_status: impl Fn(&ProjectStatus) + Sync,
+) -> Vec<ProjectStatus> {
+	let next = AtomicUsize::new(0);
+	let results = Mutex::new(vec![ProjectStatus::default(); paths.len()]);
+	thread::scope(|scope| {
+		for _ in 0..workers.clamp(1, paths.len().max(1)) {
+			scope.spawn(|| loop {
+				let index = next.fetch_add(1, Ordering::SeqCst);
+				let Some(path) = paths.get(index) else {
+					break;
+				};
+				let status = project_status(path);
+				on_status(&status);
+				if let Ok(mut results) = results.lock() {
+					results[index] = status;
+				}
+			});
+		}
+	});
+	results.into_inner().unwrap_or_default()
+}
+
+#[tauri::command]
+pub fn bulk_project_status(window: Window, paths: Vec<String>) -> Vec<ProjectStatus> {
+	bulk_status(&paths, STATUS_WORKERS, |status| {
+		let _ = window.emit("openspec://project-status", status);
+	})
+}
+
+#[tauri::command]
+pub fn set_project_tags(
+	recent: State<'_, RecentProjects>,
+	path: String,
+	tags: Vec<String>,
+) -> Result<RecentProject, SpecErrorPayload> {
+	update_project(&recent, &path, |project| {
+		project.tags = normalize_tags(tags)
+	})
+	.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn set_project_group(
+	recent: State<'_, RecentProjects>,
+	path: String,
+	group: Option<String>,
+) -> Result<RecentProject, SpecErrorPayload> {
+	let group = group
+		.map(|group| group.trim().to_string())
+		.filter(|group| !group.is_empty());
+	update_project(&recent, &path, |project| project.group = group).map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn list_recent_projects(recent: State<'_, RecentProjects>) -> Vec<RecentProject> {
+	mark_missing(&recent, storage::now_secs())
+}
+
+#[tauri::command]
+pub fn remove_recent_project(
+	recent: State<'_, RecentProjects>,
+	path: String,
+) -> Result<Vec<RecentProject>, SpecErrorPayload> {
+	remove_project(&recent, &path).map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn filter_recent_projects(
+	recent: State<'_, RecentProjects>,
+	filter: Option<ProjectFilter>,
+) -> Vec<RecentProject> {
+	let filter = filter.unwrap_or_default();
+	mark_missing(&recent, storage::now_secs())
+		.into_iter()
+		.filter(|project| matches_filter(project, &filter))
+		.collect()
+}
+
+#[tauri::command]
+pub fn project_labels(recent: State<'_, RecentProjects>) -> ProjectLabels {
+	let projects = recent.get();
+	ProjectLabels {
+		tags: projects
+			.iter()
+			.flat_map(|project| project.tags.iter().cloned())
+			.collect::<BTreeSet<_>>()
+			.into_iter()
+			.collect(),
+		groups: projects
+			.iter()
+			.filter_map(|project| project.group.clone())
+			.collect::<BTreeSet<_>>()
+			.into_iter()
+			.collect(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs;
+	use tempfile::TempDir;
+
+	fn discovery(path: &str) -> ProjectDiscovery {
+		ProjectDiscovery {
+			repo_path: path.to_string(),
+			repo_name: path.trim_start_matches('/').to_string(),
+			openspec_present: true,
+			vcs: crate::vcs::VcsKind::Git,
+			vcs_read_only: false,
+			storage: crate::mounts::StorageKind::Local,
+			polling_watcher: false,
+			remote: None,
+		}
+	}
+
+	#[test]
+	fn records_most_recent_first_without_duplicates() {
+		let store = RecentProjects::load(None);
+		for path in ["/a", "/b", "/a"] {
+			record_discovery(&store, &discovery(path));
+		}
+		let paths = store
+			.get()
+			.into_iter()
+			.map(|project| project.path)
+			.collect::<Vec<_>>();
+		assert_eq!(paths, vec!["/a", "/b"]);
+	}
+
+	#[test]
+	fn marks_missing_projects_and_prunes_only_after_the_grace_period() {
+		let kept = TempDir::new().expect("create temp dir");
+		let kept_path = kept.path().to_string_lossy().to_string();
+		let store = RecentProjects::load(None);
+		for path in [
+			kept_path.as_str(),
+			"/specops-missing-project",
+			"/specops-unmounted-drive",
+		] {
+			record_discovery(&store, &discovery(path));
+		}
+		update_project(&store, "/specops-unmounted-drive", |project| {
+			project.group = Some("Clients".to_string())
+		})
+		.expect("group");
+
+		let missing = |projects: Vec<RecentProject>| {
+			projects
+				.into_iter()
+				.map(|project| (project.path, project.missing_since))
+				.collect::<Vec<_>>()
+		};
+		let now = 1_000;
+		assert_eq!(
+			missing(mark_missing(&store, now)),
+			vec![
+				("/specops-unmounted-drive".to_string(), Some(now)),
+				("/specops-missing-project".to_string(), Some(now)),
+				(kept_path.clone(), None),
+			]
+		);
+
+		let later = now + MISSING_GRACE_SECS;
+		assert_eq!(
+			missing(mark_missing(&store, later)),
+			vec![
+				("/specops-unmounted-drive".to_string(), Some(now)),
+				(kept_path.clone(), None),
+			]
+		);
+		assert_eq!(store.get()[0].group.as_deref(), Some("Clients"));
+	}
+
+	#[test]
+	fn removing_an_unknown_project_is_an_error() {
+		let store = RecentProjects::load(None);
+		record_discovery(&store, &discovery("/a"));
+		assert!(matches!(
+			remove_project(&store, "/missing"),
+			Err(SpecError::ProjectNotFound)
+		));
+		assert!(remove_project(&store, "/a").expect("remove").is_empty());
+	}
+
+	#[test]
+	fn keeps_labels_across_visits_and_filters_archived() {
+		let store = RecentProjects::load(None);
+		for path in ["/client-a", "/client-b", "/old"] {
+			record_discovery(&store, &discovery(path));
+		}
+		update_project(&store, "/client-a", |project| {
+			project.tags = normalize_tags(vec![" Client ".to_string(), "client".to_string()]);
+			project.group = Some("Acme".to_string());
+		})
+		.expect("tag");
+		update_project(&store, "/old", |project| {
+			project.tags = vec![ARCHIVED_TAG.to_string()]
+		})
+		.expect("archive");
+		record_discovery(&store, &discovery("/client-a"));
+
+		let projects = store.get();
+		assert_eq!(projects[0].tags, vec!["client"]);
+		let visible = projects
+			.iter()
+			.filter(|project| matches_filter(project, &ProjectFilter::default()))
+			.count();
+		assert_eq!(visible, 2);
+		let filter = ProjectFilter {
+			group: Some("Acme".to_string()),
+			..ProjectFilter::default()
+		};
+		assert!(matches_filter(&projects[0], &filter));
+		assert!(!matches_filter(&projects[1], &filter));
+		assert!(matches!(
+			update_project(&store, "/missing", |_| {}),
+			Err(SpecError::ProjectNotFound)
+		));
+	}
+
+	#[test]
+	fn bulk_status_reports_every_project_in_input_order() {
+		let with_specs = TempDir::new().expect("create temp dir");
+		let changes = with_specs.path().join("openspec/changes");
+		fs::create_dir_all(changes.join("add-login")).expect("create change");
+		fs::create_dir_all(changes.join("archive/2024-01-01-old")).expect("create archive");
+		let without_specs = TempDir::new().expect("create temp dir");
+
+		let paths = vec![
+			with_specs.path().to_string_lossy().to_string(),
+			without_specs.path().to_string_lossy().to_string(),
+		];
+		let seen = AtomicUsize::new(0);
+		let statuses = bulk_status(&paths, 2, |_| {
+			seen.fetch_add(1, Ordering::SeqCst);
+		});
+
+		assert_eq!(seen.load(Ordering::SeqCst), 2);
+		assert_eq!(statuses[0].path, paths[0]);
+		assert_eq!(statuses[0].active_changes, 1);
+		assert_eq!(statuses[0].archived_changes, 1);
+		assert!(statuses[0].valid);
+		assert!(!statuses[1].openspec_present);
+	}
+}
@@ -0,0 +1,159 @@
+use crate::exit::Termination;
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
+use std::{
+	collections::BTreeMap,
+	io::{self, Read, Write},
+	path::Path,
+};
+
+const PTY_SIZE: PtySize = PtySize {
+	rows: 40,
+	cols: 120,
+	pixel_width: 0,
+	pixel_height: 0,
+};
+
+pub type PtyReader = Box<dyn Read + Send>;
+pub type PtyWriter = Box<dyn Write + Send>;
+
+pub struct PtyChild {
+	child: Box<dyn Child + Send + Sync>,
+	_master: Box<dyn MasterPty + Send>,
+}
+
+pub fn spawn(
+	command: &str,
+	args: &[String],
+	current_dir: Option<&Path>,
+	env: &BTreeMap<String, String>,
+) -> io::Result<(PtyChild, PtyReader, PtyWriter)> {
+	let pair = native_pty_system()
+		.openpty(PTY_SIZE)
+		.map_err(io::Error::other)?;
+	let mut builder = CommandBuilder::new(command);
+	builder.args(args);
+	for (key, value) in env {
+		builder.env(key, value);
+	}
+	match current_dir {
+		Some(dir) => builder.cwd(dir),
+		None => {
+			if let Ok(dir) = std::env::current_dir() {
+				builder.cwd(dir);
+			}
+		}
+	}
+	let child = pair.slave.spawn_command(builder);
+	drop(pair.slave);
+	let child = child.map_err(|error| match error.downcast::<io::Error>() {
+		Ok(error) => error,
+		Err(error) => io::Error::other(error),
+	})?;
+	let reader = pair.master.try_clone_reader().map_err(io::Error::other)?;
+	let writer = pair.master.take_writer().map_err(io::Error::other)?;
+	Ok((
+		PtyChild {
+			child,
+			_master: pair.master,
+		},
+		reader,
+		writer,
+	))
+}
+
+impl PtyChild {
+	pub fn id(&self) -> Option<u32> {
+		self.child.process_id()
+	}
+
+	pub fn wait(&mut self) -> io::Result<(bool, Termination)> {
+		let status = self.child.wait()?;
+		Ok((status.success(), Termination::from_pty(&status)))
+	}
+}
+
+pub fn read_output(
+	mut reader: impl Read,
+	mut on_chunk: impl FnMut(&str),
+	mut on_line: impl FnMut(String),
+) {
+	let mut buffer = [0; 4096];
+	let mut pending = Vec::new();
+	let mut line = String::new();
+	loop {
+		let read = match reader.read(&mut buffer) {
+			Ok(0) | Err(_) => break,
+			Ok(read) => read,
+		};
+		pending.extend_from_slice(&buffer[..read]);
+		let valid = match std::str::from_utf8(&pending) {
+			Ok(text) => text.len(),
+			Err(error) if error.error_len().is_none() => error.valid_up_to(),
+			Err(_) => pending.len(),
+		};
+		let chunk = String::from_utf8_lossy(&pending[..valid]).to_string();
+		pending.drain(..valid);
+		if chunk.is_empty() {
+			continue;
+		}
+		on_chunk(&chunk);
+		for c in chunk.chars() {
+			match c {
+				'\n' => on_line(std::mem::take(&mut line)),
+				'\r' => {}
+				_ => line.push(c),
+			}
+		}
+	}
+	if !line.is_empty() {
+		on_line(line);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn reassembles_lines_across_split_utf8_chunks() {
+		struct Chunks(Vec<Vec<u8>>);
+		impl Read for Chunks {
+			fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+				if self.0.is_empty() {
+					return Err(io::Error::other("EIO"));
+				}
+				let chunk = self.0.remove(0);
+				buf[..chunk.len()].copy_from_slice(&chunk);
+				Ok(chunk.len())
+			}
+		}
+		let bytes = "héllo\r\nworld".as_bytes();
+		let reader = Chunks(vec![bytes[..2].to_vec(), bytes[2..].to_vec()]);
+
+		let mut chunks = Vec::new();
+		let mut lines = Vec::new();
+		read_output(
+			reader,
+			|chunk| chunks.push(chunk.to_string()),
+			|line| lines.push(line),
+		);
+		assert_eq!(chunks, vec!["h", "éllo\r\nworld"]);
+		assert_eq!(lines, vec!["héllo", "world"]);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn commands_see_a_terminal() {
+		let (mut child, reader, _writer) = spawn(
+			"sh",
+			&["-c".to_string(), "test -t 1 && echo $GREETING".to_string()],
+			None,
+			&BTreeMap::from([("GREETING".to_string(), "tty".to_string())]),
+		)
+		.expect("spawn");
+		let mut lines = Vec::new();
+		read_output(reader, |_| {}, |line| lines.push(line));
+		assert_eq!(child.wait().expect("wait"), (true, Termination::exited(0)));
+		assert_eq!(lines, vec!["tty"]);
+	}
+}
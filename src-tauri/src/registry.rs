@@ -0,0 +1,1501 @@
+use serde::{Deserialize, Serialize};
+use ArgKind::{Boolean, Number, Object, Path, String as Text, StringList};
+use Availability::{Always, Change, OpenSpec, Project};
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ArgKind {
+	String,
+	Boolean,
+	Number,
+	Path,
+	StringList,
+	Choice,
+	Object,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Availability {
+	Always,
+	Project,
+	OpenSpec,
+	Change,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Access {
+	ReadOnly,
+	Mutating,
+	Process,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandArg {
+	name: &'static str,
+	kind: ArgKind,
+	required: bool,
+	#[serde(skip_serializing_if = "<[_]>::is_empty")]
+	options: &'static [&'static str],
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppCommand {
+	id: &'static str,
+	title: &'static str,
+	category: &'static str,
+	args: &'static [CommandArg],
+	requires: Availability,
+	access: Access,
+	available: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CommandContext {
+	project: Option<String>,
+	openspec_present: bool,
+	change_id: Option<String>,
+}
+
+struct CommandSpec {
+	id: &'static str,
+	title: &'static str,
+	category: &'static str,
+	args: &'static [CommandArg],
+	requires: Availability,
+}
+
+const fn arg(name: &'static str, kind: ArgKind) -> CommandArg {
+	CommandArg {
+		name,
+		kind,
+		required: true,
+		options: &[],
+	}
+}
+
+const fn optional(name: &'static str, kind: ArgKind) -> CommandArg {
+	CommandArg {
+		name,
+		kind,
+		required: false,
+		options: &[],
+	}
+}
+
+const fn choice(name: &'static str, options: &'static [&'static str]) -> CommandArg {
+	CommandArg {
+		name,
+		kind: ArgKind::Choice,
+		required: true,
+		options,
+	}
+}
+
+const fn command(
+	id: &'static str,
+	title: &'static str,
+	category: &'static str,
+	args: &'static [CommandArg],
+	requires: Availability,
+) -> CommandSpec {
+	CommandSpec {
+		id,
+		title,
+		category,
+		args,
+		requires,
+	}
+}
+
+const PACKAGE_MANAGERS: &[&str] = &["npm", "bun", "yarn", "pnpm"];
+const SHOW_KINDS: &[&str] = &["change", "spec"];
+const METRICS_FORMATS: &[&str] = &["prometheus", "json"];
+
+const TRUSTED_WINDOWS: &[&str] = &["main"];
+
+// Preview windows render project content, so they get the few reads that
+// stay inside a project's specs. Read-only is not enough on its own: logs,
+// history, crash reports and cross-project search expose far more.
+const PREVIEW_WINDOW_COMMANDS: &[&str] = &[
+	"preview_file",
+	"read_tasks",
+	"burndown",
+	"list_review_comments",
+	"openspec_list",
+	"spec_layout",
+	"ordering_policy",
+	"export_branding",
+	"list_app_commands",
+];
+
+const READ_ONLY_COMMANDS: &[&str] = &[
+	"openspec_cli_status",
+	"openspec_cli_update_check",
+	"openspec_tools_detailed",
+	"package_manager_statuses",
+	"validate_file",
+	"read_operation_log_page",
+	"journal_retention",
+	"export_audit_log",
+	"document_outline",
+	"search_project",
+	"search_all_projects",
+	"list_saved_searches",
+	"external_edit_sessions",
+	"preview_ci_config",
+	"git_hook_status",
+	"ownership_report",
+	"list_snapshots",
+	"snapshot_usage",
+	"project_encryption_status",
+	"has_secret",
+	"openspec_list",
+	"webhook_settings",
+	"filter_recent_projects",
+	"list_recent_projects",
+	"project_labels",
+	"list_app_commands",
+	"read_tasks",
+	"burndown",
+	"list_review_comments",
+	"export_review_comments",
+	"list_edit_sessions",
+	"approval_status",
+	"get_ignore_patterns",
+	"list_suppressions",
+	"severity_gate",
+	"tool_launchers",
+	"generate_kickoff_prompt",
+	"command_timeouts",
+	"bulk_project_status",
+	"spec_layout",
+	"spec_tree",
+	"list_crash_reports",
+	"export_crash_report",
+	"subsystem_status",
+	"list_operations",
+	"queue_status",
+	"preview_file",
+	"command_environment",
+	"export_branding",
+	"time_summary",
+	"upcoming_deadlines",
+	"deadline_reminders",
+	"list_operation_history",
+	"get_operation_output",
+	"preview_operation",
+	"ordering_policy",
+	"history_privacy",
+	"repair_plan",
+	"scan_code_todos",
+	"capability_code_map",
+	"affected_capabilities",
+	"execution_backend",
+	"on_demand_cli",
+];
+
+const PROCESS_COMMANDS: &[&str] = &[
+	"install_openspec_cli",
+	"upgrade_openspec_cli",
+	"uninstall_openspec_cli",
+	"openspec_init",
+	"cancel_operation",
+	"preview_tool_update",
+	"check_instruction_drift",
+	"git_clone",
+	"git_fetch",
+	"git_push",
+	"launch_tool",
+	"send_operation_input",
+	"move_queued_operation",
+	"cancel_queued_operation",
+	"openspec_list_changes",
+	"openspec_show",
+	"openspec_change_deltas",
+	"openspec_validate",
+	"openspec_archive",
+	"openspec_update",
+	"sync_remote_project",
+];
+
+const COMMANDS: &[CommandSpec] = &[
+	command(
+		"discover_project",
+		"Open Project",
+		"project",
+		&[arg("path", Path)],
+		Always,
+	),
+	command(
+		"invalidate_discovery",
+		"Refresh Project Discovery",
+		"project",
+		&[arg("path", Path)],
+		Always,
+	),
+	command(
+		"sync_remote_project",
+		"Sync Remote Project",
+		"project",
+		&[arg("project", Path)],
+		Project,
+	),
+	command(
+		"openspec_cli_status",
+		"Check OpenSpec CLI",
+		"cli",
+		&[optional("project", Path)],
+		Always,
+	),
+	command(
+		"openspec_cli_update_check",
+		"Check for OpenSpec CLI Updates",
+		"cli",
+		&[optional("force", Boolean)],
+		Always,
+	),
+	command(
+		"execution_backend",
+		"Execution Backend",
+		"cli",
+		&[arg("project", Path)],
+		Project,
+	),
+	command(
+		"set_execution_backend",
+		"Set Execution Backend",
+		"cli",
+		&[arg("project", Path), arg("backend", Object)],
+		Project,
+	),
+	command(
+		"package_manager_statuses",
+		"Check Package Managers",
+		"cli",
+		&[],
+		Always,
+	),
+	command(
+		"openspec_tools",
+		"List OpenSpec Tools",
+		"cli",
+		&[optional("project", Path)],
+		Always,
+	),
+	command(
+		"openspec_tools_detailed",
+		"List OpenSpec Tools with Details",
+		"cli",
+		&[optional("project", Path)],
+		Always,
+	),
+	command(
+		"install_openspec_cli",
+		"Install OpenSpec CLI",
+		"cli",
+		&[
+			choice("packageManager", PACKAGE_MANAGERS),
+			optional("env", Object),
+			optional("project", Path),
+		],
+		Always,
+	),
+	command(
+		"uninstall_openspec_cli",
+		"Uninstall OpenSpec CLI",
+		"cli",
+		&[
+			choice("packageManager", PACKAGE_MANAGERS),
+			optional("env", Object),
+		],
+		Always,
+	),
+	command(
+		"upgrade_openspec_cli",
+		"Upgrade OpenSpec CLI",
+		"cli",
+		&[
+			optional("version", Text),
+			optional("packageManager", Text),
+			optional("env", Object),
+		],
+		Always,
+	),
+	command(
+		"openspec_init",
+		"Initialize OpenSpec",
+		"cli",
+		&[arg("request", Object)],
+		Project,
+	),
+	command(
+		"preview_operation",
+		"Preview Operation",
+		"cli",
+		&[arg("request", Object)],
+		Always,
+	),
+	command(
+		"openspec_archive",
+		"Archive Change",
+		"changes",
+		&[
+			arg("project", Path),
+			arg("changeId", Text),
+			optional("yes", Boolean),
+			optional("env", Object),
+		],
+		Change,
+	),
+	command(
+		"rollback_failed_init",
+		"Roll Back Failed Init",
+		"cli",
+		&[arg("operationId", Text)],
+		Project,
+	),
+	command(
+		"cancel_operation",
+		"Cancel Operation",
+		"cli",
+		&[arg("operationId", Text)],
+		Always,
+	),
+	command(
+		"read_operation_log_page",
+		"Read Operation Log",
+		"cli",
+		&[
+			arg("operationId", Text),
+			arg("offset", Number),
+			optional("limit", Number),
+		],
+		Always,
+	),
+	command(
+		"journal_retention",
+		"Show Journal Retention",
+		"settings",
+		&[],
+		Always,
+	),
+	command(
+		"set_journal_retention",
+		"Set Journal Retention",
+		"settings",
+		&[arg("retention", Object)],
+		Always,
+	),
+	command(
+		"history_privacy",
+		"Show History Privacy",
+		"settings",
+		&[],
+		Always,
+	),
+	command(
+		"set_history_privacy",
+		"Set History Privacy",
+		"settings",
+		&[arg("privacy", Object)],
+		Always,
+	),
+	command(
+		"purge_history",
+		"Purge Operation History",
+		"settings",
+		&[optional("range", Object)],
+		Always,
+	),
+	command(
+		"export_audit_log",
+		"Export Audit Log",
+		"reports",
+		&[
+			arg("project", Path),
+			optional("range", Object),
+			choice("format", &["csv", "json"]),
+		],
+		Project,
+	),
+	command(
+		"adopt_project",
+		"Adopt Existing OpenSpec Folder",
+		"project",
+		&[arg("project", Path), optional("dryRun", Boolean)],
+		OpenSpec,
+	),
+	command(
+		"preview_tool_update",
+		"Preview Tool Update",
+		"cli",
+		&[arg("project", Path), arg("tools", StringList)],
+		OpenSpec,
+	),
+	command(
+		"openspec_update",
+		"Update Tool Instructions",
+		"cli",
+		&[arg("project", Path), optional("env", Object)],
+		OpenSpec,
+	),
+	command(
+		"lint_project",
+		"Lint Specs",
+		"quality",
+		&[arg("path", Path)],
+		OpenSpec,
+	),
+	command(
+		"validate_file",
+		"Validate File",
+		"quality",
+		&[arg("path", Path), optional("content", Text)],
+		Always,
+	),
+	command(
+		"apply_quick_fix",
+		"Apply Quick Fix",
+		"quality",
+		&[arg("project", Path), arg("diagnosticId", Text)],
+		OpenSpec,
+	),
+	command(
+		"export_diagnostics_sarif",
+		"Export Diagnostics as SARIF",
+		"quality",
+		&[arg("project", Path), arg("dest", Path)],
+		OpenSpec,
+	),
+	command(
+		"export_metrics",
+		"Export Project Metrics",
+		"quality",
+		&[
+			arg("project", Path),
+			choice("format", METRICS_FORMATS),
+			arg("dest", Path),
+		],
+		OpenSpec,
+	),
+	command(
+		"repair_plan",
+		"Plan Tree Repair",
+		"quality",
+		&[arg("project", Path)],
+		OpenSpec,
+	),
+	command(
+		"apply_repair",
+		"Apply Tree Repair",
+		"quality",
+		&[arg("planId", Text), arg("selections", StringList)],
+		Project,
+	),
+	command(
+		"format_spec",
+		"Format Spec",
+		"quality",
+		&[arg("path", Path), arg("check", Boolean)],
+		OpenSpec,
+	),
+	command(
+		"format_project",
+		"Format All Specs",
+		"quality",
+		&[arg("path", Path), arg("check", Boolean)],
+		OpenSpec,
+	),
+	command(
+		"document_outline",
+		"Show Outline",
+		"navigation",
+		&[arg("path", Path)],
+		OpenSpec,
+	),
+	command(
+		"spec_tree",
+		"Spec Tree",
+		"navigation",
+		&[arg("project", Path)],
+		OpenSpec,
+	),
+	command(
+		"search_project",
+		"Search Project",
+		"search",
+		&[
+			arg("path", Path),
+			arg("query", Text),
+			optional("filters", Object),
+		],
+		OpenSpec,
+	),
+	command(
+		"rebuild_search_index",
+		"Rebuild Search Index",
+		"search",
+		&[arg("path", Path)],
+		OpenSpec,
+	),
+	command(
+		"search_all_projects",
+		"Search All Projects",
+		"search",
+		&[arg("query", Text), optional("filters", Object)],
+		Always,
+	),
+	command(
+		"save_search",
+		"Save Search",
+		"search",
+		&[arg("input", Object)],
+		OpenSpec,
+	),
+	command(
+		"list_saved_searches",
+		"List Saved Searches",
+		"search",
+		&[],
+		Always,
+	),
+	command(
+		"delete_saved_search",
+		"Delete Saved Search",
+		"search",
+		&[arg("id", Text)],
+		Always,
+	),
+	command(
+		"run_saved_search",
+		"Run Saved Search",
+		"search",
+		&[arg("id", Text)],
+		Always,
+	),
+	command(
+		"watch_project",
+		"Watch Project",
+		"project",
+		&[arg("path", Path)],
+		OpenSpec,
+	),
+	command(
+		"unwatch_project",
+		"Stop Watching Project",
+		"project",
+		&[arg("path", Path)],
+		Project,
+	),
+	command(
+		"external_edit_sessions",
+		"Show External Edit Sessions",
+		"project",
+		&[arg("path", Path)],
+		OpenSpec,
+	),
+	command(
+		"preview_ci_config",
+		"Preview CI Config",
+		"integrations",
+		&[
+			arg("project", Path),
+			choice("provider", &["github-actions", "gitlab-ci"]),
+		],
+		OpenSpec,
+	),
+	command(
+		"generate_ci_config",
+		"Generate CI Config",
+		"integrations",
+		&[
+			arg("project", Path),
+			choice("provider", &["github-actions", "gitlab-ci"]),
+			optional("overwrite", Boolean),
+		],
+		OpenSpec,
+	),
+	command(
+		"install_git_hook",
+		"Install Git Hook",
+		"integrations",
+		&[
+			arg("project", Path),
+			choice("hook", &["pre-commit", "pre-push"]),
+		],
+		Project,
+	),
+	command(
+		"uninstall_git_hook",
+		"Uninstall Git Hook",
+		"integrations",
+		&[
+			arg("project", Path),
+			choice("hook", &["pre-commit", "pre-push"]),
+		],
+		Project,
+	),
+	command(
+		"git_hook_status",
+		"Show Git Hooks",
+		"integrations",
+		&[arg("project", Path)],
+		Project,
+	),
+	command(
+		"ownership_report",
+		"Spec Ownership Report",
+		"reports",
+		&[arg("project", Path), optional("recentDays", Number)],
+		OpenSpec,
+	),
+	command(
+		"capability_code_map",
+		"Capability Code Map",
+		"reports",
+		&[arg("project", Path)],
+		Project,
+	),
+	command(
+		"set_capability_code_map",
+		"Set Capability Code Map",
+		"reports",
+		&[arg("project", Path), arg("map", Object)],
+		Project,
+	),
+	command(
+		"affected_capabilities",
+		"Capabilities Affected by Diff",
+		"reports",
+		&[arg("project", Path), optional("diff", Text)],
+		OpenSpec,
+	),
+	command(
+		"create_snapshot",
+		"Create Snapshot",
+		"snapshots",
+		&[arg("project", Path), optional("label", Text)],
+		OpenSpec,
+	),
+	command(
+		"list_snapshots",
+		"List Snapshots",
+		"snapshots",
+		&[arg("project", Path)],
+		Project,
+	),
+	command(
+		"snapshot_usage",
+		"Snapshot Disk Usage",
+		"snapshots",
+		&[],
+		Always,
+	),
+	command(
+		"project_encryption_status",
+		"Show Encryption Status",
+		"security",
+		&[arg("project", Path)],
+		Project,
+	),
+	command(
+		"set_project_encryption",
+		"Toggle Encryption",
+		"security",
+		&[arg("project", Path), arg("enabled", Boolean)],
+		Project,
+	),
+	command(
+		"store_secret",
+		"Store Secret",
+		"security",
+		&[arg("name", Text), arg("value", Text)],
+		Always,
+	),
+	command(
+		"has_secret",
+		"Check Secret",
+		"security",
+		&[arg("name", Text)],
+		Always,
+	),
+	command(
+		"delete_secret",
+		"Delete Secret",
+		"security",
+		&[arg("name", Text)],
+		Always,
+	),
+	command(
+		"openspec_list",
+		"List Changes",
+		"changes",
+		&[arg("project", Path), optional("query", Object)],
+		OpenSpec,
+	),
+	command(
+		"openspec_list_changes",
+		"List Changes with CLI",
+		"changes",
+		&[arg("project", Path)],
+		OpenSpec,
+	),
+	command(
+		"openspec_show",
+		"Show Change or Spec",
+		"changes",
+		&[
+			arg("project", Path),
+			arg("itemId", Text),
+			choice("kind", SHOW_KINDS),
+		],
+		OpenSpec,
+	),
+	command(
+		"openspec_change_deltas",
+		"Show Change Deltas",
+		"changes",
+		&[arg("project", Path), arg("changeId", Text)],
+		Change,
+	),
+	command(
+		"openspec_validate",
+		"Validate with CLI",
+		"changes",
+		&[
+			arg("project", Path),
+			optional("changeId", Text),
+			optional("strict", Boolean),
+		],
+		OpenSpec,
+	),
+	command(
+		"openspec_create_change",
+		"Create Change",
+		"changes",
+		&[arg("project", Path), arg("change", Object)],
+		OpenSpec,
+	),
+	command(
+		"scan_code_todos",
+		"Find Code TODOs for Change",
+		"changes",
+		&[arg("project", Path), arg("changeId", Text)],
+		Change,
+	),
+	command(
+		"link_change_to_issue",
+		"Link Issue",
+		"changes",
+		&[
+			arg("project", Path),
+			arg("changeId", Text),
+			arg("issueUrl", Text),
+		],
+		Change,
+	),
+	command(
+		"unlink_change_issue",
+		"Unlink Issue",
+		"changes",
+		&[arg("project", Path), arg("changeId", Text)],
+		Change,
+	),
+	command(
+		"fetch_issue_status",
+		"Refresh Issue Status",
+		"changes",
+		&[arg("project", Path), arg("changeId", Text)],
+		Change,
+	),
+	command(
+		"webhook_settings",
+		"Show Webhook",
+		"integrations",
+		&[],
+		Always,
+	),
+	command(
+		"configure_webhook",
+		"Configure Webhook",
+		"integrations",
+		&[arg("input", Object)],
+		Always,
+	),
+	command(
+		"test_webhook",
+		"Send Test Webhook",
+		"integrations",
+		&[],
+		Always,
+	),
+	command(
+		"export_openspec_bundle",
+		"Export Bundle",
+		"project",
+		&[arg("project", Path), arg("dest", Path)],
+		OpenSpec,
+	),
+	command(
+		"import_openspec_bundle",
+		"Import Bundle",
+		"project",
+		&[
+			arg("project", Path),
+			arg("bundlePath", Path),
+			choice("strategy", &["replace", "merge"]),
+		],
+		Project,
+	),
+	command("power_status", "Show Power Status", "settings", &[], Always),
+	command(
+		"set_power_mode",
+		"Set Power Mode",
+		"settings",
+		&[choice("mode", &["auto", "alwaysFull", "alwaysLowPower"])],
+		Always,
+	),
+	command(
+		"ordering_policy",
+		"Show Ordering Policy",
+		"settings",
+		&[],
+		Always,
+	),
+	command(
+		"set_ordering_policy",
+		"Set Ordering Policy",
+		"settings",
+		&[arg("policy", Object)],
+		Always,
+	),
+	command(
+		"set_project_tags",
+		"Tag Project",
+		"project",
+		&[arg("path", Path), arg("tags", StringList)],
+		Always,
+	),
+	command(
+		"set_project_group",
+		"Group Project",
+		"project",
+		&[arg("path", Path), optional("group", Text)],
+		Always,
+	),
+	command(
+		"list_recent_projects",
+		"List Recent Projects",
+		"project",
+		&[],
+		Always,
+	),
+	command(
+		"remove_recent_project",
+		"Remove Recent Project",
+		"project",
+		&[arg("path", Path)],
+		Always,
+	),
+	command(
+		"filter_recent_projects",
+		"Filter Projects",
+		"project",
+		&[optional("filter", Object)],
+		Always,
+	),
+	command(
+		"project_labels",
+		"List Project Labels",
+		"project",
+		&[],
+		Always,
+	),
+	command(
+		"list_app_commands",
+		"List Commands",
+		"app",
+		&[optional("context", Object)],
+		Always,
+	),
+	command(
+		"read_tasks",
+		"Show Task Plan",
+		"changes",
+		&[arg("project", Path), arg("changeId", Text)],
+		Change,
+	),
+	command(
+		"burndown",
+		"Show Burndown",
+		"changes",
+		&[arg("project", Path), arg("changeId", Text)],
+		Change,
+	),
+	command(
+		"add_review_comment",
+		"Add Review Comment",
+		"review",
+		&[
+			arg("project", Path),
+			arg("changeId", Text),
+			arg("comment", Object),
+		],
+		Change,
+	),
+	command(
+		"list_review_comments",
+		"List Review Comments",
+		"review",
+		&[arg("project", Path), arg("changeId", Text)],
+		Change,
+	),
+	command(
+		"export_review_comments",
+		"Export Review Comments",
+		"review",
+		&[
+			arg("project", Path),
+			arg("changeId", Text),
+			choice("format", &["markdown", "gh-review"]),
+		],
+		Change,
+	),
+	command(
+		"open_edit_session",
+		"Open Editing Session",
+		"editor",
+		&[arg("path", Path)],
+		OpenSpec,
+	),
+	command(
+		"save_edit_session",
+		"Save Editing Session",
+		"editor",
+		&[arg("sessionId", Text), arg("contents", Text)],
+		OpenSpec,
+	),
+	command(
+		"close_edit_session",
+		"Close Editing Session",
+		"editor",
+		&[arg("sessionId", Text)],
+		OpenSpec,
+	),
+	command(
+		"list_edit_sessions",
+		"List Editing Sessions",
+		"editor",
+		&[],
+		Always,
+	),
+	command(
+		"export_specs_html",
+		"Export Specs as HTML",
+		"reports",
+		&[
+			arg("project", Path),
+			arg("dest", Path),
+			optional("options", Object),
+		],
+		OpenSpec,
+	),
+	command(
+		"approve_change",
+		"Approve Change",
+		"review",
+		&[
+			arg("project", Path),
+			arg("changeId", Text),
+			arg("approver", Text),
+			optional("note", Text),
+		],
+		Change,
+	),
+	command(
+		"approval_status",
+		"Show Approvals",
+		"review",
+		&[arg("project", Path), arg("changeId", Text)],
+		Change,
+	),
+	command(
+		"set_required_approvals",
+		"Set Required Approvals",
+		"settings",
+		&[arg("count", Number)],
+		Always,
+	),
+	command(
+		"get_ignore_patterns",
+		"Show Ignore Patterns",
+		"settings",
+		&[arg("project", Path)],
+		Project,
+	),
+	command(
+		"set_ignore_patterns",
+		"Set Ignore Patterns",
+		"settings",
+		&[arg("project", Path), arg("patterns", StringList)],
+		Project,
+	),
+	command(
+		"reset_ignore_patterns",
+		"Reset Ignore Patterns",
+		"settings",
+		&[arg("project", Path)],
+		Project,
+	),
+	command(
+		"list_suppressions",
+		"List Lint Suppressions",
+		"quality",
+		&[arg("project", Path)],
+		OpenSpec,
+	),
+	command(
+		"severity_gate",
+		"Show Severity Gate",
+		"quality",
+		&[arg("project", Path)],
+		Project,
+	),
+	command(
+		"set_severity_gate",
+		"Set Severity Gate",
+		"quality",
+		&[
+			arg("project", Path),
+			choice("severity", &["info", "warning", "error"]),
+		],
+		Project,
+	),
+	command(
+		"git_clone",
+		"Clone Repository",
+		"project",
+		&[arg("url", Text), arg("dest", Path)],
+		Always,
+	),
+	command(
+		"git_fetch",
+		"Git Fetch",
+		"project",
+		&[arg("project", Path)],
+		Project,
+	),
+	command(
+		"git_push",
+		"Git Push",
+		"project",
+		&[arg("project", Path)],
+		Project,
+	),
+	command(
+		"tool_launchers",
+		"List Tool Launchers",
+		"integrations",
+		&[],
+		Always,
+	),
+	command(
+		"launch_tool",
+		"Launch AI Tool",
+		"integrations",
+		&[arg("project", Path), arg("toolId", Text)],
+		Project,
+	),
+	command(
+		"generate_kickoff_prompt",
+		"Generate Kickoff Prompt",
+		"changes",
+		&[
+			arg("project", Path),
+			arg("changeId", Text),
+			arg("toolId", Text),
+		],
+		Change,
+	),
+	command(
+		"check_instruction_drift",
+		"Check Instruction Drift",
+		"integrations",
+		&[arg("project", Path)],
+		OpenSpec,
+	),
+	command("command_timeouts", "Command Timeouts", "cli", &[], Always),
+	command(
+		"set_command_timeouts",
+		"Set Command Timeouts",
+		"cli",
+		&[arg("timeouts", Object)],
+		Always,
+	),
+	command(
+		"bulk_project_status",
+		"Bulk Project Status",
+		"project",
+		&[arg("paths", StringList)],
+		Always,
+	),
+	command(
+		"spec_layout",
+		"Spec Layout",
+		"project",
+		&[arg("project", Path)],
+		Project,
+	),
+	command(
+		"set_spec_layout",
+		"Set Spec Layout",
+		"project",
+		&[arg("project", Path), optional("root", Text)],
+		Project,
+	),
+	command(
+		"migrate_external_docs",
+		"Migrate External Docs",
+		"project",
+		&[
+			arg("project", Path),
+			arg("sourceGlob", Text),
+			optional("mapping", Object),
+		],
+		Project,
+	),
+	command(
+		"set_crash_reporting",
+		"Set Crash Reporting",
+		"settings",
+		&[arg("enabled", Boolean)],
+		Always,
+	),
+	command(
+		"on_demand_cli",
+		"On-Demand OpenSpec CLI",
+		"settings",
+		&[],
+		Always,
+	),
+	command(
+		"set_on_demand_cli",
+		"Set On-Demand OpenSpec CLI",
+		"settings",
+		&[arg("enabled", Boolean)],
+		Always,
+	),
+	command(
+		"list_crash_reports",
+		"List Crash Reports",
+		"settings",
+		&[],
+		Always,
+	),
+	command(
+		"export_crash_report",
+		"Export Crash Report",
+		"settings",
+		&[arg("id", Text)],
+		Always,
+	),
+	command(
+		"send_operation_input",
+		"Send Operation Input",
+		"cli",
+		&[arg("operationId", Text), arg("input", Text)],
+		Always,
+	),
+	command(
+		"subsystem_status",
+		"Subsystem Status",
+		"settings",
+		&[],
+		Always,
+	),
+	command("list_operations", "List Operations", "cli", &[], Always),
+	command(
+		"queue_status",
+		"Queue Status",
+		"cli",
+		&[arg("project", Path)],
+		Always,
+	),
+	command(
+		"move_queued_operation",
+		"Move Queued Operation",
+		"cli",
+		&[arg("operationId", Text), arg("position", Number)],
+		Always,
+	),
+	command(
+		"cancel_queued_operation",
+		"Cancel Queued Operation",
+		"cli",
+		&[arg("operationId", Text)],
+		Always,
+	),
+	command(
+		"preview_file",
+		"Preview File",
+		"project",
+		&[arg("project", Path), arg("path", Path)],
+		OpenSpec,
+	),
+	command(
+		"command_environment",
+		"Command Environment",
+		"cli",
+		&[],
+		Always,
+	),
+	command(
+		"set_command_environment",
+		"Set Command Environment",
+		"cli",
+		&[arg("env", Object)],
+		Always,
+	),
+	command(
+		"export_branding",
+		"Export Branding",
+		"reports",
+		&[arg("project", Path)],
+		Always,
+	),
+	command(
+		"set_export_branding",
+		"Set Export Branding",
+		"reports",
+		&[arg("project", Path), arg("branding", Object)],
+		Always,
+	),
+	command(
+		"review_checklist",
+		"Review Checklist",
+		"review",
+		&[arg("project", Path), arg("changeId", Text)],
+		Always,
+	),
+	command(
+		"tick_checklist_item",
+		"Tick Checklist Item",
+		"review",
+		&[
+			arg("project", Path),
+			arg("changeId", Text),
+			arg("itemId", Text),
+			arg("reviewer", Text),
+			arg("checked", Boolean),
+		],
+		Always,
+	),
+	command(
+		"start_timer",
+		"Start Timer",
+		"project",
+		&[arg("project", Path), arg("changeId", Text)],
+		Always,
+	),
+	command(
+		"stop_timer",
+		"Stop Timer",
+		"project",
+		&[arg("project", Path), arg("changeId", Text)],
+		Always,
+	),
+	command(
+		"time_summary",
+		"Time Summary",
+		"project",
+		&[arg("project", Path), optional("changeId", Text)],
+		Always,
+	),
+	command(
+		"set_target_date",
+		"Set Target Date",
+		"project",
+		&[
+			arg("project", Path),
+			arg("changeId", Text),
+			optional("targetDate", Text),
+		],
+		Always,
+	),
+	command(
+		"upcoming_deadlines",
+		"Upcoming Deadlines",
+		"project",
+		&[arg("project", Path), optional("withinDays", Number)],
+		Always,
+	),
+	command(
+		"deadline_reminders",
+		"Deadline Reminders",
+		"settings",
+		&[],
+		Always,
+	),
+	command(
+		"set_deadline_reminders",
+		"Set Deadline Reminders",
+		"settings",
+		&[arg("reminders", Object)],
+		Always,
+	),
+	command(
+		"list_operation_history",
+		"Operation History",
+		"cli",
+		&[optional("query", Object)],
+		Always,
+	),
+	command(
+		"get_operation_output",
+		"Operation Output",
+		"cli",
+		&[arg("operationId", Text)],
+		Always,
+	),
+	command(
+		"take_launch_intent",
+		"Take Launch Intent",
+		"project",
+		&[],
+		Always,
+	),
+];
+
+pub fn access(id: &str) -> Access {
+	if READ_ONLY_COMMANDS.contains(&id) {
+		Access::ReadOnly
+	} else if PROCESS_COMMANDS.contains(&id) {
+		Access::Process
+	} else {
+		Access::Mutating
+	}
+}
+
+pub fn is_permitted(window: &str, id: &str) -> bool {
+	TRUSTED_WINDOWS.contains(&window)
+		|| (PREVIEW_WINDOW_COMMANDS.contains(&id) && access(id) == Access::ReadOnly)
+}
+
+fn is_available(requires: Availability, context: &CommandContext) -> bool {
+	let project = context.project.is_some();
+	match requires {
+		Always => true,
+		Project => project,
+		OpenSpec => project && context.openspec_present,
+		Change => project && context.openspec_present && context.change_id.is_some(),
+	}
+}
+
+#[tauri::command]
+pub fn list_app_commands(context: Option<CommandContext>) -> Vec<AppCommand> {
+	let context = context.unwrap_or_default();
+	COMMANDS
+		.iter()
+		.map(|spec| AppCommand {
+			id: spec.id,
+			title: spec.title,
+			category: spec.category,
+			args: spec.args,
+			requires: spec.requires,
+			access: access(spec.id),
+			available: is_available(spec.requires, &context),
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::collections::BTreeSet;
+
+	fn registered_handlers() -> BTreeSet<String> {
+		let source = include_str!("lib.rs");
+		let start = source.find("generate_handler![").expect("handler list");
+		let end = start + source[start..].find("])").expect("handler list end");
+		source[start + "generate_handler![".len()..end]
+			.split(',')
+			.map(|entry| {
+				entry
+					.trim()
+					.rsplit("::")
+					.next()
+					.unwrap_or_default()
+					.to_string()
+			})
+			.filter(|entry| !entry.is_empty())
+			.collect()
+	}
+
+	#[test]
+	fn registry_covers_every_registered_command() {
+		let registry = COMMANDS
+			.iter()
+			.map(|spec| spec.id.to_string())
+			.collect::<BTreeSet<_>>();
+		assert_eq!(registry.len(), COMMANDS.len());
+		assert_eq!(registry, registered_handlers());
+	}
+
+	#[test]
+	fn untrusted_windows_only_reach_read_only_commands() {
+		let known = |id: &&str| COMMANDS.iter().any(|spec| spec.id == *id);
+		assert!(READ_ONLY_COMMANDS.iter().all(known));
+		assert!(PROCESS_COMMANDS.iter().all(known));
+		assert!(PREVIEW_WINDOW_COMMANDS
+			.iter()
+			.all(|id| access(id) == Access::ReadOnly));
+
+		assert!(is_permitted("main", "openspec_init"));
+		assert!(is_permitted("preview-1", "read_tasks"));
+		assert!(!is_permitted("preview-1", "openspec_init"));
+		assert!(!is_permitted("preview-1", "save_edit_session"));
+		for sensitive in [
+			"get_operation_output",
+			"read_operation_log_page",
+			"list_operation_history",
+			"export_audit_log",
+			"export_crash_report",
+			"search_all_projects",
+			"has_secret",
+			"document_outline",
+		] {
+			assert!(is_permitted("main", sensitive));
+			assert!(
+				!is_permitted("preview-1", sensitive),
+				"{sensitive} must not reach preview windows"
+			);
+		}
+		assert_eq!(access("not_a_command"), Access::Mutating);
+	}
+
+	#[test]
+	fn evaluates_availability_against_context() {
+		let commands = list_app_commands(Some(CommandContext {
+			project: Some("/repo".to_string()),
+			openspec_present: true,
+			change_id: None,
+		}));
+		let available = |id: &str| {
+			commands
+				.iter()
+				.find(|command| command.id == id)
+				.expect("command")
+				.available
+		};
+		assert!(available("lint_project"));
+		assert!(!available("link_change_to_issue"));
+		assert!(!list_app_commands(None)
+			.iter()
+			.any(|command| command.id == "lint_project" && command.available));
+	}
+}
@@ -0,0 +1,476 @@
+use crate::{
+	cache::cache_root,
+	checklist::CHECKLIST_CONFIG,
+	codemap::CODE_MAP_CONFIG,
+	container::container_path,
+	journal::collect_paths,
+	launcher::MANIFEST_FILES,
+	layout::{self, DETECTED_ROOTS, LAYOUT_CONFIG},
+	lint::LINT_CONFIG,
+	spec::{SpecError, SpecErrorPayload},
+	storage, DiscoveryError,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+	collections::BTreeMap,
+	fs::{self, File},
+	io,
+	path::{Path, PathBuf},
+	process::{Command, Stdio},
+};
+
+/// Written into each local mirror so later runs know where it came from.
+pub const REMOTE_MARKER: &str = ".specops-remote.json";
+// Digests of the mirrored files as of the last sync, so edits made in the
+// mirror can be told apart from what the sync itself wrote.
+const SYNC_STATE: &str = ".specops-remote-sync.json";
+const SCHEME: &str = "ssh://";
+// ssh reserves exit status 255 for its own connection failures.
+const SSH_FAILURE: i32 = 255;
+const MIRRORED_PATHS: &[&str] = &[
+	"AGENTS.md",
+	"CLAUDE.md",
+	LAYOUT_CONFIG,
+	LINT_CONFIG,
+	CODE_MAP_CONFIG,
+	CHECKLIST_CONFIG,
+];
+
+/// An OpenSpec project on another machine, written as
+/// `ssh://[user@]host[:port]/absolute/path`.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RemoteTarget {
+	pub destination: String,
+	pub port: Option<u16>,
+	pub path: String,
+}
+
+pub fn parse_url(text: &str) -> Option<RemoteTarget> {
+	let rest = text.strip_prefix(SCHEME)?;
+	let (authority, path) = rest.split_once('/')?;
+	let (destination, port) = match authority.rsplit_once(':') {
+		Some((destination, port)) => (destination, Some(port.parse().ok()?)),
+		None => (authority, None),
+	};
+	// The destination reaches `ssh` as a positional argument.
+	if destination.is_empty()
+		|| destination.starts_with('-')
+		|| destination.contains(char::is_whitespace)
+	{
+		return None;
+	}
+	Some(RemoteTarget {
+		destination: destination.to_string(),
+		port,
+		path: format!("/{}", path.trim_end_matches('/')),
+	})
+}
+
+fn shell_quote(text: &str) -> String {
+	format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+fn remote_command(dir: &str, program: &str, args: &[String]) -> String {
+	let command = std::iter::once(program)
+		.chain(args.iter().map(String::as_str))
+		.map(shell_quote)
+		.collect::<Vec<_>>()
+		.join(" ");
+	format!("cd {} && exec {command}", shell_quote(dir))
+}
+
+impl RemoteTarget {
+	pub fn url(&self) -> String {
+		let port = self.port.map(|port| format!(":{port}")).unwrap_or_default();
+		format!("{SCHEME}{}{port}{}", self.destination, self.path)
+	}
+
+	// Batch mode fails fast instead of hanging on a password prompt nobody
+	// can answer; keys and agents still work.
+	fn ssh_args(&self, script: String) -> Vec<String> {
+		let mut args = vec!["-o".to_string(), "BatchMode=yes".to_string()];
+		if let Some(port) = self.port {
+			args.extend(["-p".to_string(), port.to_string()]);
+		}
+		args.extend([self.destination.clone(), script]);
+		args
+	}
+}
+
+fn mirror_dir(target: &RemoteTarget) -> Option<PathBuf> {
+	cache_root().map(|root| {
+		root.join("remote")
+			.join(storage::project_key(Path::new(&target.url())))
+	})
+}
+
+/// The remote project a local mirror stands in for.
+pub fn target_for(project: &Path) -> Option<RemoteTarget> {
+	serde_json::from_slice(&fs::read(project.join(REMOTE_MARKER)).ok()?).ok()
+}
+
+/// Runs a shell script on the remote host; the tests swap in a local shell.
+type RemoteShell<'a> = dyn Fn(String) -> Command + 'a;
+
+fn ssh(target: &RemoteTarget) -> impl Fn(String) -> Command + '_ {
+	move |script| {
+		let mut command = Command::new("ssh");
+		command.args(target.ssh_args(script));
+		command
+	}
+}
+
+fn quoted_list(paths: &[String]) -> String {
+	paths
+		.iter()
+		.map(|path| shell_quote(path))
+		.collect::<Vec<_>>()
+		.join(" ")
+}
+
+fn pull(
+	shell: &RemoteShell<'_>,
+	remote_dir: &str,
+	staging: &Path,
+	paths: &[String],
+) -> Result<(), SpecError> {
+	let script = format!(
+		"cd {} && ls -d {} 2>/dev/null | tar -cf - -T -",
+		shell_quote(remote_dir),
+		quoted_list(paths)
+	);
+	let mut remote = shell(script)
+		.stdout(Stdio::piped())
+		.stderr(Stdio::null())
+		.spawn()?;
+	let archive = remote.stdout.take().ok_or(SpecError::RemoteSyncFailed)?;
+	let extracted = Command::new("tar")
+		.arg("-xf")
+		.arg("-")
+		.arg("-C")
+		.arg(staging)
+		.stdin(archive)
+		.status()?;
+	if remote.wait()?.success() && extracted.success() {
+		Ok(())
+	} else {
+		Err(SpecError::RemoteSyncFailed)
+	}
+}
+
+fn push(
+	shell: &RemoteShell<'_>,
+	remote_dir: &str,
+	mirror: &Path,
+	changed: &[String],
+	removed: &[String],
+) -> Result<(), SpecError> {
+	if !removed.is_empty() {
+		let script = format!(
+			"cd {} && rm -f {}",
+			shell_quote(remote_dir),
+			quoted_list(removed)
+		);
+		if !shell(script)
+			.stdout(Stdio::null())
+			.stderr(Stdio::null())
+			.status()?
+			.success()
+		{
+			return Err(SpecError::RemoteSyncFailed);
+		}
+	}
+	if changed.is_empty() {
+		return Ok(());
+	}
+	let mut tar = Command::new("tar")
+		.arg("-cf")
+		.arg("-")
+		.arg("-C")
+		.arg(mirror)
+		.args(changed)
+		.stdout(Stdio::piped())
+		.spawn()?;
+	let archive = tar.stdout.take().ok_or(SpecError::RemoteSyncFailed)?;
+	let extracted = shell(format!("cd {} && tar -xf -", shell_quote(remote_dir)))
+		.stdin(archive)
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.status()?;
+	if tar.wait()?.success() && extracted.success() {
+		Ok(())
+	} else {
+		Err(SpecError::RemoteSyncFailed)
+	}
+}
+
+fn known_paths() -> Vec<String> {
+	MIRRORED_PATHS
+		.iter()
+		.chain(MANIFEST_FILES)
+		.chain(DETECTED_ROOTS)
+		.map(|path| path.to_string())
+		.collect()
+}
+
+fn mirrored_paths(dir: &Path) -> Vec<String> {
+	let mut paths = known_paths();
+	let root = layout::resolve(dir).root;
+	if !paths.contains(&root) {
+		paths.push(root);
+	}
+	paths
+}
+
+fn digests(mirror: &Path) -> Result<BTreeMap<String, String>, SpecError> {
+	let (files, _) = collect_paths(mirror, &mirrored_paths(mirror))?;
+	files
+		.into_iter()
+		.map(|file| {
+			let mut hasher = Sha256::new();
+			io::copy(&mut File::open(mirror.join(&file))?, &mut hasher)?;
+			let digest = hasher
+				.finalize()
+				.iter()
+				.map(|byte| format!("{byte:02x}"))
+				.collect();
+			Ok((file, digest))
+		})
+		.collect()
+}
+
+fn record_state(mirror: &Path) -> Result<(), SpecError> {
+	let state = serde_json::to_vec_pretty(&digests(mirror)?).map_err(io::Error::from)?;
+	storage::write_atomic(&mirror.join(SYNC_STATE), &state)?;
+	Ok(())
+}
+
+// Edits saved in the mirror win over concurrent remote changes to the same
+// file; anything untouched locally is left for the pull to refresh.
+fn push_edits_with(
+	shell: &RemoteShell<'_>,
+	remote_dir: &str,
+	mirror: &Path,
+) -> Result<(), SpecError> {
+	let Some(synced) = fs::read(mirror.join(SYNC_STATE))
+		.ok()
+		.and_then(|bytes| serde_json::from_slice::<BTreeMap<String, String>>(&bytes).ok())
+	else {
+		return Ok(());
+	};
+	let current = digests(mirror)?;
+	let changed = current
+		.iter()
+		.filter(|(file, digest)| synced.get(*file) != Some(digest))
+		.map(|(file, _)| file.clone())
+		.collect::<Vec<_>>();
+	let removed = synced
+		.keys()
+		.filter(|file| !current.contains_key(*file))
+		.cloned()
+		.collect::<Vec<_>>();
+	if changed.is_empty() && removed.is_empty() {
+		return Ok(());
+	}
+	push(shell, remote_dir, mirror, &changed, &removed)?;
+	record_state(mirror)
+}
+
+/// Sends files edited in the mirror since the last sync back to the remote
+/// checkout, so commands that run there see them.
+pub fn push_edits(target: &RemoteTarget, mirror: &Path) -> Result<(), SpecError> {
+	push_edits_with(&ssh(target), &target.path, mirror)
+}
+
+/// Refreshes the local copy of the remote specs, configs and package
+/// manifests after pushing back local edits. Everything lands in a staging
+/// folder first so a dropped connection leaves the previous mirror intact.
+pub fn sync(target: &RemoteTarget, mirror: &Path) -> Result<(), SpecError> {
+	sync_with(&ssh(target), &target.path, mirror)
+}
+
+fn sync_with(shell: &RemoteShell<'_>, remote_dir: &str, mirror: &Path) -> Result<(), SpecError> {
+	push_edits_with(shell, remote_dir, mirror)?;
+	let staging = mirror.with_extension("sync");
+	let _ = fs::remove_dir_all(&staging);
+	fs::create_dir_all(&staging)?;
+	let mut paths = known_paths();
+	pull(shell, remote_dir, &staging, &paths)?;
+	let root = layout::resolve(&staging).root;
+	if !paths.contains(&root) {
+		pull(shell, remote_dir, &staging, std::slice::from_ref(&root))?;
+		paths.push(root);
+	}
+
+	for path in &paths {
+		let destination = mirror.join(path);
+		if destination.is_dir() {
+			fs::remove_dir_all(&destination)?;
+		} else if destination.exists() {
+			fs::remove_file(&destination)?;
+		}
+		let staged = staging.join(path);
+		if staged.exists() {
+			if let Some(parent) = destination.parent() {
+				fs::create_dir_all(parent)?;
+			}
+			fs::rename(staged, destination)?;
+		}
+	}
+	let _ = fs::remove_dir_all(&staging);
+	record_state(mirror)
+}
+
+/// Resolves the repository root on the remote host and mirrors it locally.
+pub fn discover(target: RemoteTarget) -> Result<(RemoteTarget, PathBuf), DiscoveryError> {
+	let script = remote_command(
+		&target.path,
+		"git",
+		&["rev-parse".to_string(), "--show-toplevel".to_string()],
+	);
+	let output = match Command::new("ssh").args(target.ssh_args(script)).output() {
+		Ok(output) => output,
+		Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+			return Err(DiscoveryError::SshUnavailable)
+		}
+		Err(error) => return Err(error.into()),
+	};
+	if output.status.code() == Some(SSH_FAILURE) {
+		return Err(DiscoveryError::RemoteUnreachable);
+	}
+	if !output.status.success() {
+		return Err(DiscoveryError::NotGitWorkTree);
+	}
+	let root = String::from_utf8_lossy(&output.stdout).trim().to_string();
+	if root.is_empty() {
+		return Err(DiscoveryError::RepoRootUnavailable);
+	}
+
+	let root = RemoteTarget {
+		path: root,
+		..target
+	};
+	let mirror = mirror_dir(&root).ok_or(DiscoveryError::RemoteSyncFailed)?;
+	fs::create_dir_all(&mirror)?;
+	let marker = serde_json::to_vec_pretty(&root).map_err(|_| DiscoveryError::RemoteSyncFailed)?;
+	storage::write_atomic(&mirror.join(REMOTE_MARKER), &marker)
+		.map_err(|_| DiscoveryError::RemoteSyncFailed)?;
+	sync(&root, &mirror).map_err(|_| DiscoveryError::RemoteSyncFailed)?;
+	Ok((root, mirror))
+}
+
+/// The `ssh` invocation that runs `program` in the remote checkout a mirror
+/// stands in for. Paths inside the mirror are rewritten to the remote side.
+pub fn invocation(
+	program: &str,
+	args: &[impl AsRef<str>],
+	project: Option<&Path>,
+) -> Option<(String, Vec<String>)> {
+	let project = project?;
+	let target = target_for(project)?;
+	let args = args
+		.iter()
+		.map(|arg| {
+			let arg = arg.as_ref();
+			match Path::new(arg).strip_prefix(project) {
+				Ok(rest) if Path::new(arg).is_absolute() => container_path(&target.path, rest),
+				_ => arg.to_string(),
+			}
+		})
+		.collect::<Vec<_>>();
+	let script = remote_command(&target.path, program, &args);
+	Some(("ssh".to_string(), target.ssh_args(script)))
+}
+
+#[tauri::command(async)]
+pub fn sync_remote_project(project: String) -> Result<(), SpecErrorPayload> {
+	let project = Path::new(&project);
+	let target = target_for(project).ok_or(SpecError::NotRemoteProject)?;
+	sync(&target, project).map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn parses_ssh_project_urls() {
+		let target = parse_url("ssh://dev@build-01:2222/srv/repos/app/").expect("ssh url");
+		assert_eq!(target.destination, "dev@build-01");
+		assert_eq!(target.port, Some(2222));
+		assert_eq!(target.path, "/srv/repos/app");
+		assert_eq!(target.url(), "ssh://dev@build-01:2222/srv/repos/app");
+		assert_eq!(
+			parse_url("ssh://build-01/srv/app").map(|target| target.port),
+			Some(None)
+		);
+		assert_eq!(parse_url("ssh://-oProxyCommand=x/srv/app"), None);
+		assert_eq!(parse_url("/srv/app"), None);
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn local_edits_survive_a_sync() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let remote = temp_dir.path().join("remote");
+		let mirror = temp_dir.path().join("mirror");
+		fs::create_dir_all(remote.join("openspec/specs/auth")).expect("create specs");
+		fs::write(remote.join("openspec/specs/auth/spec.md"), "# Auth\n").expect("write spec");
+		fs::write(remote.join("openspec/project.md"), "# Project\n").expect("write project");
+		let shell = |script: String| {
+			let mut command = Command::new("sh");
+			command.arg("-c").arg(script);
+			command
+		};
+		let remote_dir = remote.to_string_lossy();
+		sync_with(&shell, &remote_dir, &mirror).expect("first sync");
+		let spec = mirror.join("openspec/specs/auth/spec.md");
+		assert_eq!(fs::read_to_string(&spec).expect("read mirror"), "# Auth\n");
+
+		fs::write(&spec, "# Auth\n\nEdited locally.\n").expect("edit mirror");
+		fs::remove_file(mirror.join("openspec/project.md")).expect("remove from mirror");
+		fs::write(remote.join("AGENTS.md"), "# Agents\n").expect("write remote agents");
+		sync_with(&shell, &remote_dir, &mirror).expect("second sync");
+
+		for side in [&remote, &mirror] {
+			assert_eq!(
+				fs::read_to_string(side.join("openspec/specs/auth/spec.md")).expect("read spec"),
+				"# Auth\n\nEdited locally.\n"
+			);
+			assert!(!side.join("openspec/project.md").exists());
+		}
+		assert!(mirror.join("AGENTS.md").exists());
+	}
+
+	#[test]
+	fn runs_commands_in_the_remote_checkout() {
+		let mirror = TempDir::new().expect("create mirror dir");
+		assert_eq!(invocation("openspec", &["list"], Some(mirror.path())), None);
+		let target = parse_url("ssh://build-01/srv/it's").expect("ssh url");
+		fs::write(
+			mirror.path().join(REMOTE_MARKER),
+			serde_json::to_vec(&target).expect("serialize target"),
+		)
+		.expect("write marker");
+		let change = mirror.path().join("openspec/changes/add-x");
+		let (program, args) = invocation(
+			"openspec",
+			&["show".to_string(), change.display().to_string()],
+			Some(mirror.path()),
+		)
+		.expect("ssh invocation");
+		assert_eq!(program, "ssh");
+		assert_eq!(
+			args,
+			[
+				"-o",
+				"BatchMode=yes",
+				"build-01",
+				r"cd '/srv/it'\''s' && exec 'openspec' 'show' '/srv/it'\''s/openspec/changes/add-x'"
+			]
+		);
+	}
+}
@@ -0,0 +1,636 @@
+use crate::{
+	crypto::ArtifactCipher,
+	deadlines::{format_date, parse_date},
+	editing::EditSessions,
+	journal::{self, OperationStatus},
+	ordering,
+	settings::SettingsStore,
+	spec::{
+		existing_openspec_root, is_fence, openspec_root, parse_heading, SourceText, SpecError,
+		SpecErrorPayload,
+	},
+	storage,
+};
+use serde::Serialize;
+use std::{
+	collections::HashMap,
+	fs,
+	path::{Path, PathBuf},
+	sync::Mutex,
+	time::UNIX_EPOCH,
+};
+use tauri::State;
+
+const DAY_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum RepairKind {
+	MissingSpec,
+	OrphanedDelta,
+	MalformedArchive,
+}
+
+impl RepairKind {
+	fn id(self) -> &'static str {
+		match self {
+			RepairKind::MissingSpec => "missing-spec",
+			RepairKind::OrphanedDelta => "orphaned-delta",
+			RepairKind::MalformedArchive => "malformed-archive",
+		}
+	}
+}
+
+#[derive(Clone, Debug)]
+enum RepairAction {
+	CreateFile {
+		path: PathBuf,
+		contents: String,
+	},
+	RewriteFile {
+		path: PathBuf,
+		original: String,
+		contents: String,
+	},
+	RemoveFile {
+		path: PathBuf,
+		original: String,
+	},
+	Rename {
+		from: PathBuf,
+		to: PathBuf,
+	},
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairFix {
+	id: String,
+	kind: RepairKind,
+	path: String,
+	description: String,
+	#[serde(skip)]
+	action: RepairAction,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairPlan {
+	id: String,
+	project: String,
+	created_at: u64,
+	fixes: Vec<RepairFix>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairReport {
+	operation_id: String,
+	applied: Vec<String>,
+	skipped: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct RepairPlans(Mutex<HashMap<String, RepairPlan>>);
+
+fn relative(project: &Path, path: &Path) -> String {
+	path.strip_prefix(project)
+		.unwrap_or(path)
+		.to_string_lossy()
+		.replace('\\', "/")
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+	fs::read_dir(dir)
+		.map(|entries| {
+			entries
+				.filter_map(Result::ok)
+				.map(|entry| entry.path())
+				.filter(|path| {
+					path.is_dir()
+						&& path
+							.file_name()
+							.and_then(|name| name.to_str())
+							.is_some_and(|name| !name.starts_with('.'))
+				})
+				.collect()
+		})
+		.unwrap_or_default()
+}
+
+fn dir_name(path: &Path) -> String {
+	path.file_name()
+		.map(|name| name.to_string_lossy().to_string())
+		.unwrap_or_default()
+}
+
+fn spec_stub(capability: &str) -> String {
+	format!("# {capability} Specification\n\n## Purpose\nTBD.\n\n## Requirements\n")
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DeltaSection {
+	Kept,
+	Added,
+	Dropped,
+}
+
+// A delta against a capability that does not exist can only add
+// requirements: MODIFIED blocks become additions and REMOVED/RENAMED blocks,
+// which have nothing to act on, are dropped. `None` means nothing to fix.
+fn as_additions(text: &str) -> Option<Option<String>> {
+	let mut source = SourceText::parse(text);
+	let mut kept = Vec::new();
+	let mut added = Vec::new();
+	let mut added_at = None;
+	let mut changed = false;
+	let mut section = DeltaSection::Kept;
+	let mut in_fence = false;
+	for line in &source.lines {
+		if is_fence(line) {
+			in_fence = !in_fence;
+		}
+		let heading = parse_heading(line)
+			.filter(|(level, _)| !in_fence && *level <= 2)
+			.map(|(_, title)| title);
+		if let Some(title) = heading {
+			section = match title {
+				"ADDED Requirements" | "MODIFIED Requirements" => {
+					changed |= title.starts_with("MODIFIED");
+					added_at.get_or_insert(kept.len());
+					DeltaSection::Added
+				}
+				"REMOVED Requirements" | "RENAMED Requirements" => {
+					changed = true;
+					DeltaSection::Dropped
+				}
+				_ => DeltaSection::Kept,
+			};
+			if section != DeltaSection::Kept {
+				continue;
+			}
+		}
+		match section {
+			DeltaSection::Kept => kept.push(line.clone()),
+			DeltaSection::Added => added.push(line.clone()),
+			DeltaSection::Dropped => {}
+		}
+	}
+	if !changed {
+		return None;
+	}
+	if added.iter().all(|line| line.trim().is_empty()) {
+		return Some(None);
+	}
+	let mut section = vec!["## ADDED Requirements".to_string()];
+	section.extend(added);
+	let at = added_at.unwrap_or(kept.len());
+	kept.splice(at..at, section);
+	source.lines = kept;
+	Some(Some(source.render()))
+}
+
+fn is_archive_name(name: &str) -> bool {
+	name.get(..10).and_then(parse_date).is_some()
+		&& name[10..]
+			.strip_prefix('-')
+			.is_some_and(|id| !id.is_empty())
+}
+
+fn leading_date(name: &str) -> Option<(String, &str)> {
+	if let Some(prefix) = name.get(..10) {
+		let date = prefix.replace(['_', '.'], "-");
+		if parse_date(&date).is_some() {
+			return Some((date, &name[10..]));
+		}
+	}
+	let digits = name
+		.get(..8)
+		.filter(|digits| digits.chars().all(|c| c.is_ascii_digit()))?;
+	let date = format!("{}-{}-{}", &digits[..4], &digits[4..6], &digits[6..]);
+	parse_date(&date).map(|_| (date, &name[8..]))
+}
+
+fn modified_days(path: &Path) -> i64 {
+	let secs = fs::metadata(path)
+		.and_then(|metadata| metadata.modified())
+		.ok()
+		.and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+		.map(|age| age.as_secs())
+		.unwrap_or_else(storage::now_secs);
+	(secs / DAY_SECS) as i64
+}
+
+// Archive folders are `YYYY-MM-DD-<id>`. Dates written another way are
+// normalised; folders without one fall back to their modification date.
+fn archive_name(dir: &Path) -> Option<String> {
+	let name = dir_name(dir);
+	let (date, rest) =
+		leading_date(&name).unwrap_or_else(|| (format_date(modified_days(dir)), name.as_str()));
+	let id = rest.trim_start_matches(['-', '_', '.', ' ']);
+	(!id.is_empty()).then(|| format!("{date}-{id}"))
+}
+
+fn missing_specs(project: &Path, root: &Path) -> Vec<RepairFix> {
+	subdirs(&root.join("specs"))
+		.into_iter()
+		.filter(|dir| !dir.join("spec.md").is_file())
+		.map(|dir| {
+			let capability = dir_name(&dir);
+			let path = dir.join("spec.md");
+			RepairFix {
+				id: String::new(),
+				kind: RepairKind::MissingSpec,
+				path: relative(project, &path),
+				description: format!("Create an empty spec.md for capability `{capability}`"),
+				action: RepairAction::CreateFile {
+					path,
+					contents: spec_stub(&capability),
+				},
+			}
+		})
+		.collect()
+}
+
+fn orphaned_deltas(project: &Path, root: &Path) -> Vec<RepairFix> {
+	let mut fixes = Vec::new();
+	for change in subdirs(&root.join("changes")) {
+		if dir_name(&change) == "archive" {
+			continue;
+		}
+		for delta in subdirs(&change.join("specs")) {
+			let capability = dir_name(&delta);
+			let path = delta.join("spec.md");
+			if root.join("specs").join(&capability).is_dir() {
+				continue;
+			}
+			let Ok(original) = SourceText::read_complete(&path).map(|source| source.render())
+			else {
+				continue;
+			};
+			let Some(rewritten) = as_additions(&original) else {
+				continue;
+			};
+			let change_id = dir_name(&change);
+			let (description, action) = match rewritten {
+				Some(contents) => (
+					format!(
+						"Turn `{change_id}` changes to missing capability `{capability}` into additions"
+					),
+					RepairAction::RewriteFile {
+						path: path.clone(),
+						original,
+						contents,
+					},
+				),
+				None => (
+					format!(
+						"Remove `{change_id}` delta that only removes requirements from missing capability `{capability}`"
+					),
+					RepairAction::RemoveFile {
+						path: path.clone(),
+						original,
+					},
+				),
+			};
+			fixes.push(RepairFix {
+				id: String::new(),
+				kind: RepairKind::OrphanedDelta,
+				path: relative(project, &path),
+				description,
+				action,
+			});
+		}
+	}
+	fixes
+}
+
+fn malformed_archives(project: &Path, root: &Path) -> Vec<RepairFix> {
+	subdirs(&root.join("changes").join("archive"))
+		.into_iter()
+		.filter(|dir| !is_archive_name(&dir_name(dir)))
+		.filter_map(|dir| {
+			let name = archive_name(&dir)?;
+			let to = dir.with_file_name(&name);
+			if to.exists() {
+				return None;
+			}
+			Some(RepairFix {
+				id: String::new(),
+				kind: RepairKind::MalformedArchive,
+				path: relative(project, &dir),
+				description: format!("Rename archived change `{}` to `{name}`", dir_name(&dir)),
+				action: RepairAction::Rename { from: dir, to },
+			})
+		})
+		.collect()
+}
+
+pub fn repair_fixes(project: &Path) -> Result<Vec<RepairFix>, SpecError> {
+	let root = existing_openspec_root(project)?;
+	let mut fixes = missing_specs(project, &root);
+	fixes.extend(orphaned_deltas(project, &root));
+	fixes.extend(malformed_archives(project, &root));
+	fixes.sort_by(|a, b| {
+		a.kind
+			.cmp(&b.kind)
+			.then_with(|| ordering::compare_paths(Path::new(&a.path), Path::new(&b.path)))
+	});
+	for fix in &mut fixes {
+		fix.id = format!("{}:{}", fix.kind.id(), fix.path);
+	}
+	Ok(fixes)
+}
+
+impl RepairAction {
+	fn paths(&self) -> Vec<&Path> {
+		match self {
+			RepairAction::CreateFile { path, .. }
+			| RepairAction::RewriteFile { path, .. }
+			| RepairAction::RemoveFile { path, .. } => vec![path.as_path()],
+			RepairAction::Rename { from, to } => vec![from.as_path(), to.as_path()],
+		}
+	}
+
+	/// Returns `false` when the tree no longer matches what the plan saw.
+	fn apply(&self) -> Result<bool, SpecError> {
+		let unchanged = |path: &Path, original: &str| {
+			SourceText::read_complete(path).is_ok_and(|current| current.render() == original)
+		};
+		match self {
+			RepairAction::CreateFile { path, contents } => {
+				if path.exists() {
+					return Ok(false);
+				}
+				storage::write_atomic(path, contents.as_bytes())?;
+			}
+			RepairAction::RewriteFile {
+				path,
+				original,
+				contents,
+			} => {
+				if !unchanged(path, original) {
+					return Ok(false);
+				}
+				storage::write_atomic(path, contents.as_bytes())?;
+			}
+			RepairAction::RemoveFile { path, original } => {
+				if !unchanged(path, original) {
+					return Ok(false);
+				}
+				fs::remove_file(path)?;
+				if let Some(parent) = path.parent() {
+					let _ = fs::remove_dir(parent);
+				}
+			}
+			RepairAction::Rename { from, to } => {
+				if !from.is_dir() || to.exists() {
+					return Ok(false);
+				}
+				fs::rename(from, to)?;
+			}
+		}
+		Ok(true)
+	}
+}
+
+pub fn apply_plan_in(
+	root: &Path,
+	plan: &RepairPlan,
+	selections: &[String],
+	cipher: Option<&ArtifactCipher>,
+) -> Result<RepairReport, SpecError> {
+	let project = Path::new(&plan.project);
+	let selected = plan
+		.fixes
+		.iter()
+		.filter(|fix| selections.contains(&fix.id))
+		.collect::<Vec<_>>();
+	let mut skipped = selections
+		.iter()
+		.filter(|id| !plan.fixes.iter().any(|fix| fix.id == **id))
+		.cloned()
+		.collect::<Vec<_>>();
+	let backup_paths = selected
+		.iter()
+		.flat_map(|fix| fix.action.paths())
+		.map(|path| relative(project, path))
+		.collect::<Vec<_>>();
+	let backup_paths = backup_paths.iter().map(String::as_str).collect::<Vec<_>>();
+	let ids = selected
+		.iter()
+		.map(|fix| fix.id.as_str())
+		.collect::<Vec<_>>();
+	let record = journal::begin_scrubbed_in(
+		root,
+		"repair",
+		&format!("repair {}", ids.join(" ")),
+		Some(project),
+		&backup_paths,
+		&journal::Scrubber::default(),
+		cipher,
+	)?;
+	let log = journal::OperationLog::create_in(root, &record.id)
+		.ok()
+		.map(|log| log.sealed(cipher.cloned()));
+
+	let mut applied = Vec::new();
+	let mut failure = None;
+	for fix in selected {
+		match fix.action.apply() {
+			Ok(true) => {
+				if let Some(log) = &log {
+					log.append("stdout", &fix.description);
+				}
+				applied.push(fix.id.clone());
+			}
+			Ok(false) => {
+				if let Some(log) = &log {
+					log.append("stderr", &format!("skipped {}: tree changed", fix.id));
+				}
+				skipped.push(fix.id.clone());
+			}
+			Err(error) => {
+				if let Some(log) = &log {
+					log.append("stderr", &format!("failed {}: {error}", fix.id));
+				}
+				failure = Some(error);
+				break;
+			}
+		}
+	}
+	let status = if failure.is_some() {
+		OperationStatus::Failed
+	} else {
+		OperationStatus::Succeeded
+	};
+	journal::finish_in(root, &record.id, status, None)?;
+	if let Some(error) = failure {
+		return Err(error);
+	}
+	Ok(RepairReport {
+		operation_id: record.id,
+		applied,
+		skipped,
+	})
+}
+
+#[tauri::command]
+pub fn repair_plan(
+	plans: State<'_, RepairPlans>,
+	project: String,
+) -> Result<RepairPlan, SpecErrorPayload> {
+	let plan = RepairPlan {
+		id: storage::new_id("repair"),
+		fixes: repair_fixes(Path::new(&project))?,
+		project,
+		created_at: storage::now_secs(),
+	};
+	plans
+		.0
+		.lock()
+		.unwrap_or_else(|poisoned| poisoned.into_inner())
+		.insert(plan.id.clone(), plan.clone());
+	Ok(plan)
+}
+
+#[tauri::command]
+pub fn apply_repair(
+	plans: State<'_, RepairPlans>,
+	sessions: State<'_, EditSessions>,
+	settings: State<'_, SettingsStore>,
+	plan_id: String,
+	selections: Vec<String>,
+) -> Result<RepairReport, SpecErrorPayload> {
+	let mut pending = plans
+		.0
+		.lock()
+		.unwrap_or_else(|poisoned| poisoned.into_inner());
+	let project = &pending
+		.get(&plan_id)
+		.ok_or(SpecError::RepairPlanNotFound)?
+		.project;
+	// The plan stays pending so it can be applied once the editor closes.
+	if sessions.is_locked_within(&openspec_root(Path::new(project))) {
+		return Err(SpecError::FileLocked.into());
+	}
+	let cipher = journal::project_journal_cipher(&settings, Some(Path::new(project)))?;
+	let plan = pending
+		.remove(&plan_id)
+		.ok_or(SpecError::RepairPlanNotFound)?;
+	drop(pending);
+	let root = journal::journal_root()?;
+	apply_plan_in(&root, &plan, &selections, cipher.as_ref()).map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	fn broken_tree() -> TempDir {
+		let project = TempDir::new().expect("create project dir");
+		let openspec = project.path().join("openspec");
+		fs::create_dir_all(openspec.join("specs/auth")).expect("create auth");
+		fs::create_dir_all(openspec.join("specs/billing")).expect("create billing");
+		fs::write(openspec.join("specs/billing/spec.md"), "# billing\n").expect("write billing");
+		let delta = openspec.join("changes/add-session/specs/session");
+		fs::create_dir_all(&delta).expect("create delta");
+		fs::write(
+			delta.join("spec.md"),
+			"## ADDED Requirements\n### Requirement: Start\nThe system SHALL start.\n\n## MODIFIED Requirements\n### Requirement: Expire\nThe system SHALL expire.\n\n## REMOVED Requirements\n### Requirement: Legacy\n",
+		)
+		.expect("write delta");
+		let removal = openspec.join("changes/drop-legacy/specs/legacy");
+		fs::create_dir_all(&removal).expect("create removal");
+		fs::write(
+			removal.join("spec.md"),
+			"## REMOVED Requirements\n### Requirement: Old\n",
+		)
+		.expect("write removal");
+		let archive = openspec.join("changes/archive");
+		for name in [
+			"2026-03-02-add-auth",
+			"20260405-add-billing",
+			"2026_05_06_fix-io",
+		] {
+			fs::create_dir_all(archive.join(name)).expect("create archived change");
+		}
+		project
+	}
+
+	#[test]
+	fn plans_fixes_in_order() {
+		let project = broken_tree();
+		let fixes = repair_fixes(project.path()).expect("plan");
+		let ids = fixes.iter().map(|fix| fix.id.as_str()).collect::<Vec<_>>();
+		assert_eq!(
+			ids,
+			[
+				"missing-spec:openspec/specs/auth/spec.md",
+				"orphaned-delta:openspec/changes/add-session/specs/session/spec.md",
+				"orphaned-delta:openspec/changes/drop-legacy/specs/legacy/spec.md",
+				"malformed-archive:openspec/changes/archive/2026_05_06_fix-io",
+				"malformed-archive:openspec/changes/archive/20260405-add-billing",
+			]
+		);
+		assert!(fixes[3].description.ends_with("`2026-05-06-fix-io`"));
+		assert_eq!(
+			as_additions(
+				"## MODIFIED Requirements\n### Requirement: A\n```md\n## REMOVED Requirements\n```\n"
+			),
+			Some(Some(
+				"## ADDED Requirements\n### Requirement: A\n```md\n## REMOVED Requirements\n```\n"
+					.to_string()
+			))
+		);
+		assert_eq!(
+			as_additions("## ADDED Requirements\n### Requirement: A\n"),
+			None
+		);
+	}
+
+	#[test]
+	fn applies_selected_fixes_with_journal() {
+		let project = broken_tree();
+		let journal = TempDir::new().expect("create journal dir");
+		let plan = RepairPlan {
+			id: "repair-test".to_string(),
+			project: project.path().to_string_lossy().to_string(),
+			created_at: 0,
+			fixes: repair_fixes(project.path()).expect("plan"),
+		};
+		let selections = [
+			"orphaned-delta:openspec/changes/add-session/specs/session/spec.md",
+			"orphaned-delta:openspec/changes/drop-legacy/specs/legacy/spec.md",
+			"malformed-archive:openspec/changes/archive/20260405-add-billing",
+			"missing-spec:openspec/specs/unknown/spec.md",
+		]
+		.map(str::to_string);
+
+		let report = apply_plan_in(journal.path(), &plan, &selections, None).expect("apply");
+		assert_eq!(report.applied, selections[..3]);
+		assert_eq!(report.skipped, selections[3..]);
+		let openspec = project.path().join("openspec");
+		assert_eq!(
+			fs::read_to_string(openspec.join("changes/add-session/specs/session/spec.md"))
+				.expect("read delta"),
+			"## ADDED Requirements\n### Requirement: Start\nThe system SHALL start.\n\n### Requirement: Expire\nThe system SHALL expire.\n\n"
+		);
+		assert!(!openspec.join("changes/drop-legacy/specs/legacy").exists());
+		assert!(openspec
+			.join("changes/archive/2026-04-05-add-billing")
+			.is_dir());
+		assert!(!openspec.join("specs/auth/spec.md").exists());
+
+		let record = journal::load_in(journal.path(), &report.operation_id).expect("record");
+		assert_eq!(record.operation, "repair");
+		assert_eq!(record.status, OperationStatus::Succeeded);
+		assert!(record.backup.is_some());
+
+		let report =
+			apply_plan_in(journal.path(), &plan, &selections[2..3], None).expect("reapply");
+		assert!(report.applied.is_empty());
+		assert_eq!(report.skipped, selections[2..3]);
+	}
+}
@@ -0,0 +1,65 @@
+use serde::Serialize;
+use std::time::Duration;
+
+pub const MAX_ATTEMPTS: u32 = 3;
+pub const BASE_DELAY: Duration = Duration::from_secs(2);
+
+const TRANSIENT_MARKERS: &[&str] = &[
+	"econnreset",
+	"etimedout",
+	"econnrefused",
+	"eai_again",
+	"epipe",
+	"socket hang up",
+	"network timeout",
+	"err_socket_timeout",
+	"fetch failed",
+	"connection reset",
+	"503 service unavailable",
+	"502 bad gateway",
+	"504 gateway timeout",
+];
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryEvent {
+	pub operation: String,
+	pub operation_id: Option<String>,
+	pub attempt: u32,
+	pub max_attempts: u32,
+	pub delay_secs: u64,
+	pub reason: String,
+}
+
+pub fn transient_reason(output: &str) -> Option<&'static str> {
+	let output = output.to_lowercase();
+	TRANSIENT_MARKERS
+		.iter()
+		.copied()
+		.find(|marker| output.contains(marker))
+}
+
+pub fn delay(base: Duration, attempt: u32) -> Duration {
+	base * 2u32.pow(attempt.saturating_sub(1))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn classifies_network_errors_and_backs_off() {
+		assert_eq!(
+			transient_reason("npm ERR! code ECONNRESET\nnpm ERR! network aborted"),
+			Some("econnreset")
+		);
+		assert_eq!(
+			transient_reason("error: GET https://registry.npmjs.org - 503 Service Unavailable"),
+			Some("503 service unavailable")
+		);
+		assert_eq!(transient_reason("npm ERR! code E404"), None);
+		assert_eq!(transient_reason("npm ERR! code EACCES"), None);
+		assert_eq!(delay(BASE_DELAY, 1), Duration::from_secs(2));
+		assert_eq!(delay(BASE_DELAY, 3), Duration::from_secs(8));
+	}
+}
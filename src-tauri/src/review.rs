@@ -0,0 +1,294 @@
+use crate::{
+	changes::{read_metadata, update_metadata},
+	spec::{parse_headings, SourceText, SpecError, SpecErrorPayload, MAX_PARSE_BYTES},
+	storage::{self, StorageError},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::{collections::BTreeMap, path::Path};
+
+const REQUIREMENT_PREFIX: &str = "Requirement:";
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewComment {
+	pub id: String,
+	pub path: String,
+	pub line: usize,
+	pub requirement: Option<String>,
+	pub author: String,
+	pub body: String,
+	pub created_at: u64,
+	#[serde(default)]
+	pub reply_to: Option<String>,
+	#[serde(default)]
+	pub resolved: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewReviewComment {
+	path: String,
+	line: usize,
+	author: String,
+	body: String,
+	reply_to: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReviewExportFormat {
+	Markdown,
+	GhReview,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewExport {
+	format: ReviewExportFormat,
+	thread_count: usize,
+	content: String,
+}
+
+struct Thread<'a> {
+	root: &'a ReviewComment,
+	replies: Vec<&'a ReviewComment>,
+}
+
+fn requirement_at(project: &Path, path: &str, line: usize) -> Option<String> {
+	let source = SourceText::read_limited(&project.join(path), MAX_PARSE_BYTES).ok()?;
+	parse_headings(&source.lines)
+		.into_iter()
+		.rev()
+		.filter(|heading| heading.line < line)
+		.find_map(|heading| {
+			heading
+				.text
+				.strip_prefix(REQUIREMENT_PREFIX)
+				.map(|name| name.trim().to_string())
+		})
+}
+
+fn add_comment(
+	project: &Path,
+	change_id: &str,
+	comment: NewReviewComment,
+) -> Result<ReviewComment, SpecError> {
+	let comment = ReviewComment {
+		id: storage::new_id("comment"),
+		requirement: requirement_at(project, &comment.path, comment.line),
+		path: comment.path,
+		line: comment.line,
+		author: comment.author,
+		body: comment.body,
+		created_at: storage::now_secs(),
+		reply_to: comment.reply_to,
+		resolved: false,
+	};
+	update_metadata(project, change_id, |metadata| {
+		metadata.comments.push(comment.clone())
+	})?;
+	Ok(comment)
+}
+
+fn threads(comments: &[ReviewComment]) -> Vec<Thread<'_>> {
+	let mut threads = comments
+		.iter()
+		.filter(|comment| comment.reply_to.is_none())
+		.map(|root| Thread {
+			root,
+			replies: Vec::new(),
+		})
+		.collect::<Vec<_>>();
+	for reply in comments.iter().filter(|comment| comment.reply_to.is_some()) {
+		if let Some(thread) = threads
+			.iter_mut()
+			.find(|thread| reply.reply_to.as_ref() == Some(&thread.root.id))
+		{
+			thread.replies.push(reply);
+		}
+	}
+	threads.sort_by(|a, b| {
+		(&a.root.path, a.root.line, a.root.created_at).cmp(&(
+			&b.root.path,
+			b.root.line,
+			b.root.created_at,
+		))
+	});
+	threads
+}
+
+fn quote(comment: &ReviewComment) -> String {
+	format!("**@{}**: {}", comment.author, comment.body.trim())
+}
+
+fn render_markdown(change_id: &str, threads: &[Thread]) -> String {
+	let mut by_requirement = BTreeMap::<(&str, Option<&str>), Vec<&Thread>>::new();
+	for thread in threads {
+		by_requirement
+			.entry((&thread.root.path, thread.root.requirement.as_deref()))
+			.or_default()
+			.push(thread);
+	}
+	let mut out = format!("## Review: `{change_id}`\n");
+	for ((path, requirement), threads) in by_requirement {
+		match requirement {
+			Some(requirement) => out.push_str(&format!("\n### {requirement}\n`{path}`\n")),
+			None => out.push_str(&format!("\n### `{path}`\n")),
+		}
+		for thread in threads {
+			let status = if thread.root.resolved {
+				" (resolved)"
+			} else {
+				""
+			};
+			out.push_str(&format!(
+				"\n- Line {}{status}: {}\n",
+				thread.root.line,
+				quote(thread.root)
+			));
+			for reply in &thread.replies {
+				out.push_str(&format!("  - {}\n", quote(reply)));
+			}
+		}
+	}
+	out
+}
+
+fn render_gh_review(change_id: &str, threads: &[Thread]) -> Result<String, SpecError> {
+	let comments = threads
+		.iter()
+		.filter(|thread| !thread.root.resolved)
+		.map(|thread| {
+			let body = std::iter::once(thread.root)
+				.chain(thread.replies.iter().copied())
+				.map(quote)
+				.collect::<Vec<_>>()
+				.join("\n\n");
+			json!({
+				"path": thread.root.path,
+				"line": thread.root.line,
+				"side": "RIGHT",
+				"body": body,
+			})
+		})
+		.collect::<Vec<_>>();
+	let draft = json!({
+		"event": "COMMENT",
+		"body": format!("Spec review for `{change_id}` exported from SpecOps."),
+		"comments": comments,
+	});
+	Ok(serde_json::to_string_pretty(&draft).map_err(StorageError::from)?)
+}
+
+fn export_comments(
+	project: &Path,
+	change_id: &str,
+	format: ReviewExportFormat,
+) -> Result<ReviewExport, SpecError> {
+	let comments = read_metadata(project, change_id)?.comments;
+	let threads = threads(&comments);
+	let content = match format {
+		ReviewExportFormat::Markdown => render_markdown(change_id, &threads),
+		ReviewExportFormat::GhReview => render_gh_review(change_id, &threads)?,
+	};
+	Ok(ReviewExport {
+		format,
+		thread_count: threads.len(),
+		content,
+	})
+}
+
+#[tauri::command]
+pub fn add_review_comment(
+	project: String,
+	change_id: String,
+	comment: NewReviewComment,
+) -> Result<ReviewComment, SpecErrorPayload> {
+	add_comment(Path::new(&project), &change_id, comment).map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn list_review_comments(
+	project: String,
+	change_id: String,
+) -> Result<Vec<ReviewComment>, SpecErrorPayload> {
+	read_metadata(Path::new(&project), &change_id)
+		.map(|metadata| metadata.comments)
+		.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn export_review_comments(
+	project: String,
+	change_id: String,
+	format: ReviewExportFormat,
+) -> Result<ReviewExport, SpecErrorPayload> {
+	export_comments(Path::new(&project), &change_id, format).map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::Value;
+	use std::fs;
+	use tempfile::TempDir;
+
+	#[test]
+	fn exports_threads_grouped_by_requirement() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let spec = "openspec/changes/add-login/specs/auth/spec.md";
+		fs::create_dir_all(
+			temp_dir
+				.path()
+				.join("openspec/changes/add-login/specs/auth"),
+		)
+		.expect("create change");
+		fs::write(
+			temp_dir.path().join(spec),
+			"## ADDED Requirements\n\n### Requirement: Login\nUsers SHALL log in.\n",
+		)
+		.expect("write spec");
+
+		let root = add_comment(
+			temp_dir.path(),
+			"add-login",
+			NewReviewComment {
+				path: spec.to_string(),
+				line: 4,
+				author: "alice".to_string(),
+				body: "Which providers?".to_string(),
+				reply_to: None,
+			},
+		)
+		.expect("add comment");
+		assert_eq!(root.requirement.as_deref(), Some("Login"));
+		add_comment(
+			temp_dir.path(),
+			"add-login",
+			NewReviewComment {
+				path: spec.to_string(),
+				line: 4,
+				author: "bob".to_string(),
+				body: "GitHub only".to_string(),
+				reply_to: Some(root.id.clone()),
+			},
+		)
+		.expect("add reply");
+
+		let markdown = export_comments(temp_dir.path(), "add-login", ReviewExportFormat::Markdown)
+			.expect("export markdown");
+		assert_eq!(markdown.thread_count, 1);
+		assert!(markdown.content.contains("### Login"));
+		assert!(markdown
+			.content
+			.contains("- Line 4: **@alice**: Which providers?\n  - **@bob**: GitHub only"));
+
+		let draft = export_comments(temp_dir.path(), "add-login", ReviewExportFormat::GhReview)
+			.expect("export draft");
+		let draft: Value = serde_json::from_str(&draft.content).expect("parse draft");
+		assert_eq!(draft["event"], "COMMENT");
+		assert_eq!(draft["comments"][0]["path"], spec);
+		assert_eq!(draft["comments"][0]["line"], 4);
+	}
+}
@@ -0,0 +1,142 @@
+use crate::{
+	lint::{lint_project_files, Diagnostic, Severity, RULES},
+	spec::{SpecError, SpecErrorPayload},
+	storage,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::path::Path;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifExport {
+	path: String,
+	result_count: usize,
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+	match severity {
+		Severity::Error => "error",
+		Severity::Warning => "warning",
+		Severity::Info => "note",
+	}
+}
+
+fn relative_uri(project: &Path, path: &str) -> String {
+	Path::new(path)
+		.strip_prefix(project)
+		.unwrap_or(Path::new(path))
+		.to_string_lossy()
+		.replace('\\', "/")
+}
+
+pub fn sarif_log(project: &Path, diagnostics: &[Diagnostic]) -> Value {
+	let rules = RULES
+		.iter()
+		.map(|rule| {
+			json!({
+				"id": rule.id(),
+				"shortDescription": { "text": rule.description() },
+				"defaultConfiguration": { "level": sarif_level(rule.severity()) },
+			})
+		})
+		.collect::<Vec<_>>();
+	let results = diagnostics
+		.iter()
+		.map(|diagnostic| {
+			json!({
+				"ruleId": diagnostic.rule.id(),
+				"ruleIndex": RULES.iter().position(|rule| *rule == diagnostic.rule),
+				"level": sarif_level(diagnostic.severity),
+				"message": { "text": diagnostic.message },
+				"locations": [{
+					"physicalLocation": {
+						"artifactLocation": {
+							"uri": relative_uri(project, &diagnostic.path),
+							"uriBaseId": "%SRCROOT%",
+						},
+						"region": { "startLine": diagnostic.line },
+					},
+				}],
+			})
+		})
+		.collect::<Vec<_>>();
+
+	json!({
+		"$schema": SARIF_SCHEMA,
+		"version": "2.1.0",
+		"runs": [{
+			"tool": {
+				"driver": {
+					"name": "SpecOps",
+					"version": env!("CARGO_PKG_VERSION"),
+					"rules": rules,
+				},
+			},
+			"results": results,
+		}],
+	})
+}
+
+fn export_sarif(project: &Path, dest: &Path) -> Result<SarifExport, SpecError> {
+	let diagnostics = lint_project_files(project)?;
+	let log = sarif_log(project, &diagnostics);
+	let bytes = serde_json::to_vec_pretty(&log).map_err(std::io::Error::other)?;
+	storage::write_atomic(dest, &bytes)?;
+	Ok(SarifExport {
+		path: dest.to_string_lossy().to_string(),
+		result_count: diagnostics.len(),
+	})
+}
+
+#[tauri::command]
+pub fn export_diagnostics_sarif(
+	project: String,
+	dest: String,
+) -> Result<SarifExport, SpecErrorPayload> {
+	export_sarif(Path::new(&project), Path::new(&dest)).map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs;
+	use tempfile::TempDir;
+
+	#[test]
+	fn exports_lint_results_as_sarif() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let spec_dir = temp_dir.path().join("openspec/specs/auth");
+		fs::create_dir_all(&spec_dir).expect("create specs");
+		fs::write(
+			spec_dir.join("spec.md"),
+			"### Requirement: Login\nThe system logs in.\n",
+		)
+		.expect("write spec");
+
+		let dest = temp_dir.path().join("out/specops.sarif");
+		let export = export_sarif(temp_dir.path(), &dest).expect("export");
+		assert_eq!(export.result_count, 2);
+
+		let log: Value =
+			serde_json::from_slice(&fs::read(&dest).expect("read sarif")).expect("parse sarif");
+		assert_eq!(log["version"], "2.1.0");
+		let results = log["runs"][0]["results"].as_array().expect("results");
+		assert_eq!(results[0]["ruleId"], "requirement-missing-scenario");
+		assert_eq!(results[0]["level"], "error");
+		assert_eq!(
+			results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+			"openspec/specs/auth/spec.md"
+		);
+		assert_eq!(results[1]["level"], "warning");
+		assert_eq!(
+			log["runs"][0]["tool"]["driver"]["rules"]
+				.as_array()
+				.expect("rules")
+				.len(),
+			RULES.len()
+		);
+	}
+}
@@ -0,0 +1,710 @@
+use crate::{
+	cache::{self, FileStamp, ProjectCache},
+	crypto::project_cipher,
+	ignore::{self, IgnoreRules},
+	operations::{self, CancellationToken},
+	projects::RecentProjects,
+	settings::SettingsStore,
+	spec::{
+		existing_openspec_root, markdown_files_except, SourceText, SpecError, SpecErrorPayload,
+		MAX_PARSE_BYTES,
+	},
+	storage,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+	collections::{hash_map::DefaultHasher, HashMap},
+	hash::{Hash, Hasher},
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex},
+	thread,
+};
+use tauri::{AppHandle, Emitter, Manager, State, Window};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SearchScope {
+	#[default]
+	All,
+	Specs,
+	Changes,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SearchFilters {
+	pub scope: SearchScope,
+	pub change_id: Option<String>,
+	pub case_sensitive: bool,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SavedSearch {
+	pub id: String,
+	pub name: String,
+	pub project: String,
+	pub query: String,
+	pub filters: SearchFilters,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SavedSearchInput {
+	name: String,
+	project: String,
+	query: String,
+	#[serde(default)]
+	filters: SearchFilters,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SearchHit {
+	pub path: String,
+	pub relative_path: String,
+	pub line: usize,
+	pub column: usize,
+	pub preview: String,
+	/// The file is past the parse limit and only its start was indexed.
+	pub partial: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSearchResults {
+	project: String,
+	name: String,
+	stale: bool,
+	hits: Vec<SearchHit>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SavedSearchChangedEvent {
+	id: String,
+	name: String,
+	project: String,
+	hit_count: usize,
+}
+
+#[derive(Clone, Deserialize, Serialize)]
+struct IndexedFile {
+	path: PathBuf,
+	relative_path: String,
+	stamp: FileStamp,
+	lines: Vec<String>,
+	#[serde(default)]
+	truncated: bool,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct ProjectIndex {
+	files: Vec<IndexedFile>,
+}
+
+/// The project's markdown files under `root`, in path order, with the stamps
+/// cached entries are compared against.
+pub fn file_stamps(
+	project: &Path,
+	root: &Path,
+	ignore: &IgnoreRules,
+) -> Result<Vec<(PathBuf, FileStamp)>, SpecError> {
+	let mut paths = markdown_files_except(root, &|path| ignore.is_ignored(project, path))?;
+	paths.sort();
+	Ok(paths
+		.into_iter()
+		.map(|path| {
+			let stamp = FileStamp::of(&path);
+			(path, stamp)
+		})
+		.collect())
+}
+
+fn file_key(file: &IndexedFile) -> (&Path, &FileStamp) {
+	(&file.path, &file.stamp)
+}
+
+fn index_file(root: &Path, path: PathBuf, stamp: FileStamp) -> Result<IndexedFile, SpecError> {
+	let source = SourceText::read_limited(&path, MAX_PARSE_BYTES)?;
+	let relative_path = path
+		.strip_prefix(root)
+		.unwrap_or(&path)
+		.to_string_lossy()
+		.replace('\\', "/");
+	Ok(IndexedFile {
+		path,
+		relative_path,
+		stamp,
+		lines: source.lines,
+		truncated: source.truncated,
+	})
+}
+
+impl ProjectIndex {
+	pub fn build_cancellable(
+		project: &Path,
+		ignore: &IgnoreRules,
+		cancel: &CancellationToken,
+	) -> Result<Self, SpecError> {
+		let root = existing_openspec_root(project)?;
+		let mut files = Vec::new();
+		for (path, stamp) in file_stamps(project, &root, ignore)? {
+			cancel.check()?;
+			files.push(index_file(&root, path, stamp)?);
+		}
+		Ok(Self { files })
+	}
+
+	pub fn search(&self, query: &str, filters: &SearchFilters) -> Vec<SearchHit> {
+		let query = query.trim();
+		if query.is_empty() {
+			return Vec::new();
+		}
+		let needle = if filters.case_sensitive {
+			query.to_string()
+		} else {
+			query.to_lowercase()
+		};
+
+		let mut hits = Vec::new();
+		for file in self.files.iter().filter(|file| in_scope(file, filters)) {
+			for (index, line) in file.lines.iter().enumerate() {
+				let haystack = if filters.case_sensitive {
+					line.clone()
+				} else {
+					line.to_lowercase()
+				};
+				if let Some(offset) = haystack.find(&needle) {
+					hits.push(SearchHit {
+						path: file.path.to_string_lossy().to_string(),
+						relative_path: file.relative_path.clone(),
+						line: index + 1,
+						column: haystack[..offset].chars().count() + 1,
+						preview: line.trim().to_string(),
+						partial: file.truncated,
+					});
+				}
+			}
+		}
+		hits
+	}
+}
+
+impl ProjectCache for ProjectIndex {
+	const NAME: &'static str = "search-index";
+
+	fn is_stale(&self, project: &Path, ignore: &IgnoreRules) -> bool {
+		let Ok(root) = existing_openspec_root(project) else {
+			return true;
+		};
+		file_stamps(project, &root, ignore).map_or(true, |stamps| {
+			cache::entries_changed(&stamps, &self.files, file_key)
+		})
+	}
+
+	fn refresh(
+		previous: Option<&Self>,
+		project: &Path,
+		ignore: &IgnoreRules,
+	) -> Result<Self, SpecError> {
+		let root = existing_openspec_root(project)?;
+		let files = cache::refresh_entries(
+			file_stamps(project, &root, ignore)?,
+			previous
+				.map(|index| index.files.as_slice())
+				.unwrap_or_default(),
+			file_key,
+			|path, stamp| index_file(&root, path, stamp),
+		)?;
+		Ok(Self { files })
+	}
+}
+
+fn in_scope(file: &IndexedFile, filters: &SearchFilters) -> bool {
+	let path = file.relative_path.as_str();
+	let scoped = match filters.scope {
+		SearchScope::All => true,
+		SearchScope::Specs => path.starts_with("specs/"),
+		SearchScope::Changes => path.starts_with("changes/"),
+	};
+	let in_change = match &filters.change_id {
+		Some(change_id) => path.starts_with(&format!("changes/{change_id}/")),
+		None => true,
+	};
+	scoped && in_change
+}
+
+fn hits_digest(hits: &[SearchHit]) -> u64 {
+	let mut hasher = DefaultHasher::new();
+	hits.hash(&mut hasher);
+	hasher.finish()
+}
+
+#[derive(Default)]
+pub struct SearchState {
+	indexes: Mutex<HashMap<PathBuf, Arc<ProjectIndex>>>,
+	digests: Mutex<HashMap<String, u64>>,
+	cache_root: Option<PathBuf>,
+}
+
+impl SearchState {
+	pub fn persistent() -> Self {
+		Self {
+			cache_root: cache::cache_root(),
+			..Self::default()
+		}
+	}
+
+	pub fn index(
+		&self,
+		project: &Path,
+		settings: &SettingsStore,
+	) -> Result<Arc<ProjectIndex>, SpecError> {
+		let ignore = ignore::rules_for(settings, project);
+		let previous = self
+			.indexes
+			.lock()
+			.ok()
+			.and_then(|indexes| indexes.get(project).cloned());
+		if let Some(index) = &previous {
+			if !index.is_stale(project, &ignore) {
+				return Ok(Arc::clone(index));
+			}
+		}
+
+		let index = Arc::new(cache::warm(
+			self.cache_root.as_deref(),
+			project,
+			settings,
+			&ignore,
+			previous.as_deref(),
+		)?);
+		if let Ok(mut indexes) = self.indexes.lock() {
+			indexes.insert(project.to_path_buf(), Arc::clone(&index));
+		}
+		Ok(index)
+	}
+
+	fn cached_index(
+		&self,
+		project: &Path,
+		settings: &SettingsStore,
+	) -> Result<(Arc<ProjectIndex>, bool), SpecError> {
+		let cached = self
+			.indexes
+			.lock()
+			.ok()
+			.and_then(|indexes| indexes.get(project).cloned());
+		match cached {
+			Some(index) => {
+				let stale = index.is_stale(project, &ignore::rules_for(settings, project));
+				Ok((index, stale))
+			}
+			None => Ok((self.index(project, settings)?, false)),
+		}
+	}
+
+	fn cached_projects(&self) -> Vec<PathBuf> {
+		self.indexes
+			.lock()
+			.map(|indexes| indexes.keys().cloned().collect())
+			.unwrap_or_default()
+	}
+
+	pub fn rebuild(
+		&self,
+		project: &Path,
+		settings: &SettingsStore,
+		cancel: &CancellationToken,
+	) -> Result<Arc<ProjectIndex>, SpecError> {
+		let ignore = ignore::rules_for(settings, project);
+		let index = Arc::new(ProjectIndex::build_cancellable(project, &ignore, cancel)?);
+		if let Some(root) = &self.cache_root {
+			if let Ok(cipher) = project_cipher(settings, project) {
+				let _ = cache::store(
+					root,
+					project,
+					ProjectIndex::NAME,
+					index.as_ref(),
+					cipher.as_ref(),
+				);
+			}
+		}
+		if let Ok(mut indexes) = self.indexes.lock() {
+			indexes.insert(project.to_path_buf(), Arc::clone(&index));
+		}
+		Ok(index)
+	}
+
+	/// Drops the in-memory index. The copy on disk stays: it is checked file
+	/// by file on the next load and only changed files are read again.
+	pub fn invalidate(&self, project: &Path) {
+		if let Ok(mut indexes) = self.indexes.lock() {
+			indexes.remove(project);
+		}
+	}
+
+	fn record_digest(&self, id: &str, hits: &[SearchHit]) -> bool {
+		let digest = hits_digest(hits);
+		let Ok(mut digests) = self.digests.lock() else {
+			return false;
+		};
+		match digests.insert(id.to_string(), digest) {
+			Some(previous) => previous != digest,
+			None => false,
+		}
+	}
+
+	fn run_saved(
+		&self,
+		search: &SavedSearch,
+		settings: &SettingsStore,
+	) -> Result<Vec<SearchHit>, SpecError> {
+		let index = self.index(Path::new(&search.project), settings)?;
+		let hits = index.search(&search.query, &search.filters);
+		self.record_digest(&search.id, &hits);
+		Ok(hits)
+	}
+}
+
+fn search_projects(
+	state: &SearchState,
+	settings: &SettingsStore,
+	projects: &[(PathBuf, String)],
+	query: &str,
+	filters: &SearchFilters,
+) -> Vec<ProjectSearchResults> {
+	thread::scope(|scope| {
+		let handles = projects
+			.iter()
+			.map(|(project, name)| {
+				scope.spawn(move || {
+					let (index, stale) = state.cached_index(project, settings).ok()?;
+					let hits = index.search(query, filters);
+					(!hits.is_empty()).then(|| ProjectSearchResults {
+						project: project.to_string_lossy().to_string(),
+						name: name.clone(),
+						stale,
+						hits,
+					})
+				})
+			})
+			.collect::<Vec<_>>();
+		handles
+			.into_iter()
+			.filter_map(|handle| handle.join().ok().flatten())
+			.collect()
+	})
+}
+
+pub fn refresh_saved_searches(app: &AppHandle, project: &Path) {
+	let settings = app.state::<SettingsStore>();
+	let search = app.state::<SearchState>();
+	for saved in settings
+		.get()
+		.saved_searches
+		.iter()
+		.filter(|saved| Path::new(&saved.project) == project)
+	{
+		let Ok(index) = search.index(project, &settings) else {
+			continue;
+		};
+		let hits = index.search(&saved.query, &saved.filters);
+		if search.record_digest(&saved.id, &hits) {
+			let _ = app.emit(
+				"openspec://saved-search-changed",
+				SavedSearchChangedEvent {
+					id: saved.id.clone(),
+					name: saved.name.clone(),
+					project: saved.project.clone(),
+					hit_count: hits.len(),
+				},
+			);
+		}
+	}
+}
+
+#[tauri::command]
+pub fn search_project(
+	settings: State<'_, SettingsStore>,
+	search: State<'_, SearchState>,
+	path: String,
+	query: String,
+	filters: Option<SearchFilters>,
+) -> Result<Vec<SearchHit>, SpecErrorPayload> {
+	let index = search
+		.index(Path::new(&path), &settings)
+		.map_err(SpecErrorPayload::from)?;
+	Ok(index.search(&query, &filters.unwrap_or_default()))
+}
+
+#[tauri::command(async)]
+pub fn rebuild_search_index(
+	window: Window,
+	settings: State<'_, SettingsStore>,
+	search: State<'_, SearchState>,
+	path: String,
+) -> Result<(), SpecErrorPayload> {
+	operations::run_native(&window, "index", format!("index {path}"), |cancel| {
+		search.rebuild(Path::new(&path), &settings, cancel)
+	})
+	.map(|_| ())
+	.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn search_all_projects(
+	settings: State<'_, SettingsStore>,
+	recent: State<'_, RecentProjects>,
+	search: State<'_, SearchState>,
+	query: String,
+	filters: Option<SearchFilters>,
+) -> Vec<ProjectSearchResults> {
+	let mut projects = recent
+		.get()
+		.into_iter()
+		.filter(|project| project.openspec_present)
+		.map(|project| (PathBuf::from(project.path), project.name))
+		.collect::<Vec<_>>();
+	for project in search.cached_projects() {
+		if projects.iter().all(|(path, _)| *path != project) {
+			let name = project
+				.file_name()
+				.map(|name| name.to_string_lossy().to_string())
+				.unwrap_or_default();
+			projects.push((project, name));
+		}
+	}
+	search_projects(
+		&search,
+		&settings,
+		&projects,
+		&query,
+		&filters.unwrap_or_default(),
+	)
+}
+
+#[tauri::command]
+pub fn save_search(
+	settings: State<'_, SettingsStore>,
+	input: SavedSearchInput,
+) -> Result<SavedSearch, SpecErrorPayload> {
+	let saved = SavedSearch {
+		id: storage::new_id("search"),
+		name: input.name.trim().to_string(),
+		project: input.project,
+		query: input.query,
+		filters: input.filters,
+	};
+	settings
+		.update(|current| current.saved_searches.push(saved.clone()))
+		.map_err(|error| SpecErrorPayload::from(SpecError::from(error)))?;
+	Ok(saved)
+}
+
+#[tauri::command]
+pub fn list_saved_searches(settings: State<'_, SettingsStore>) -> Vec<SavedSearch> {
+	settings.get().saved_searches
+}
+
+#[tauri::command]
+pub fn delete_saved_search(
+	settings: State<'_, SettingsStore>,
+	id: String,
+) -> Result<(), SpecErrorPayload> {
+	let removed = settings
+		.update(|current| {
+			let before = current.saved_searches.len();
+			current.saved_searches.retain(|saved| saved.id != id);
+			before != current.saved_searches.len()
+		})
+		.map_err(|error| SpecErrorPayload::from(SpecError::from(error)))?;
+	if removed {
+		Ok(())
+	} else {
+		Err(SpecError::SavedSearchNotFound.into())
+	}
+}
+
+#[tauri::command]
+pub fn run_saved_search(
+	settings: State<'_, SettingsStore>,
+	search: State<'_, SearchState>,
+	id: String,
+) -> Result<Vec<SearchHit>, SpecErrorPayload> {
+	let saved = settings
+		.get()
+		.saved_searches
+		.into_iter()
+		.find(|saved| saved.id == id)
+		.ok_or(SpecError::SavedSearchNotFound)?;
+	search
+		.run_saved(&saved, &settings)
+		.map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs;
+	use tempfile::TempDir;
+
+	fn project_with_specs() -> TempDir {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let root = temp_dir.path().join("openspec");
+		fs::create_dir_all(root.join("specs/auth")).expect("create specs");
+		fs::create_dir_all(root.join("changes/add-logout")).expect("create change");
+		fs::write(
+			root.join("specs/auth/spec.md"),
+			"### Requirement: Login\nThe system SHALL log users in.\n",
+		)
+		.expect("write spec");
+		fs::write(
+			root.join("changes/add-logout/proposal.md"),
+			"# Change: Logout\nUsers can log out.\n",
+		)
+		.expect("write proposal");
+		temp_dir
+	}
+
+	#[test]
+	fn finds_case_insensitive_hits_within_scope() {
+		let project = project_with_specs();
+		let index =
+			ProjectIndex::refresh(None, project.path(), &IgnoreRules::default()).expect("index");
+
+		let hits = index.search("LOG", &SearchFilters::default());
+		assert_eq!(hits.len(), 4);
+
+		let filters = SearchFilters {
+			scope: SearchScope::Specs,
+			..SearchFilters::default()
+		};
+		let hits = index.search("log", &filters);
+		assert_eq!(hits.len(), 2);
+		assert!(hits
+			.iter()
+			.all(|hit| hit.relative_path == "specs/auth/spec.md"));
+		assert_eq!(hits[1].column, 18);
+
+		let filters = SearchFilters {
+			change_id: Some("add-logout".to_string()),
+			case_sensitive: true,
+			..SearchFilters::default()
+		};
+		let hits = index.search("Logout", &filters);
+		assert_eq!(hits.len(), 1);
+	}
+
+	#[test]
+	fn cancelled_rebuild_keeps_the_previous_index() {
+		let project = project_with_specs();
+		let state = SearchState::default();
+		let settings = SettingsStore::load(None);
+		let index = state.index(project.path(), &settings).expect("index");
+		let cancel = CancellationToken::default();
+		cancel.cancel();
+		assert!(matches!(
+			state.rebuild(project.path(), &settings, &cancel),
+			Err(SpecError::OperationCancelled)
+		));
+		let (cached, _) = state
+			.cached_index(project.path(), &settings)
+			.expect("cached");
+		assert!(Arc::ptr_eq(&index, &cached));
+	}
+
+	#[test]
+	fn searches_projects_concurrently_and_flags_stale_indexes() {
+		let first = project_with_specs();
+		let second = project_with_specs();
+		let missing = TempDir::new().expect("create temp dir");
+		let state = SearchState::default();
+		let settings = SettingsStore::load(None);
+		state.index(first.path(), &settings).expect("index");
+		fs::write(first.path().join("openspec/specs/auth/new.md"), "login\n").expect("write");
+
+		let projects = [first.path(), second.path(), missing.path()]
+			.into_iter()
+			.map(|path| (path.to_path_buf(), "project".to_string()))
+			.collect::<Vec<_>>();
+		let mut results = search_projects(
+			&state,
+			&settings,
+			&projects,
+			"logout",
+			&SearchFilters::default(),
+		);
+		results.sort_by_key(|result| result.stale);
+		assert_eq!(results.len(), 2);
+		assert!(!results[0].stale);
+		assert!(results[1].stale);
+		assert_eq!(results[1].project, first.path().to_string_lossy());
+	}
+
+	#[test]
+	fn detects_stale_index_and_saved_search_changes() {
+		let project = project_with_specs();
+		let state = SearchState::default();
+		let settings = SettingsStore::load(None);
+		let saved = SavedSearch {
+			id: "search-1".to_string(),
+			name: "Login".to_string(),
+			project: project.path().to_string_lossy().to_string(),
+			query: "login".to_string(),
+			filters: SearchFilters::default(),
+		};
+		assert_eq!(state.run_saved(&saved, &settings).expect("run").len(), 1);
+
+		let index = state.index(project.path(), &settings).expect("index");
+		assert!(!index.is_stale(project.path(), &IgnoreRules::default()));
+		fs::write(
+			project.path().join("openspec/specs/auth/login.md"),
+			"Login flows\n",
+		)
+		.expect("write file");
+		assert!(index.is_stale(project.path(), &IgnoreRules::default()));
+
+		let hits = state
+			.index(project.path(), &settings)
+			.expect("index")
+			.search("login", &saved.filters);
+		assert!(state.record_digest(&saved.id, &hits));
+		assert!(!state.record_digest(&saved.id, &hits));
+	}
+
+	#[test]
+	fn reuses_persisted_index_until_files_change() {
+		let project = project_with_specs();
+		let cache_dir = TempDir::new().expect("create temp dir");
+		let settings = SettingsStore::load(None);
+		let warm = || SearchState {
+			cache_root: Some(cache_dir.path().to_path_buf()),
+			..SearchState::default()
+		};
+		let persisted = || {
+			cache::load::<ProjectIndex>(cache_dir.path(), project.path(), ProjectIndex::NAME, None)
+				.expect("persisted index")
+		};
+		warm().index(project.path(), &settings).expect("index");
+		assert!(!persisted().is_stale(project.path(), &IgnoreRules::default()));
+
+		// The new text has a different length, so the change is seen even when
+		// the write lands within the filesystem's timestamp granularity.
+		let spec = project.path().join("openspec/specs/auth/spec.md");
+		fs::write(&spec, "Sessions expire.\n").expect("write spec");
+		assert!(persisted().is_stale(project.path(), &IgnoreRules::default()));
+		let state = warm();
+		let hits = state
+			.index(project.path(), &settings)
+			.expect("index")
+			.search("expire", &SearchFilters::default());
+		assert_eq!(hits.len(), 1);
+
+		state.invalidate(project.path());
+		let index = persisted();
+		assert!(!index.is_stale(project.path(), &IgnoreRules::default()));
+		assert_eq!(index.search("expire", &SearchFilters::default()), hits);
+	}
+}
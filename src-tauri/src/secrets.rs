@@ -0,0 +1,65 @@
+use crate::spec::{SpecError, SpecErrorPayload};
+use keyring::Entry;
+
+const KEYCHAIN_SERVICE: &str = "com.chainyo.specops";
+
+pub fn keychain_entry(account: &str) -> Result<Entry, SpecError> {
+	Entry::new(KEYCHAIN_SERVICE, account).map_err(|_| SpecError::KeychainUnavailable)
+}
+
+fn valid_name(name: &str) -> bool {
+	!name.is_empty()
+		&& name.len() <= 128
+		&& name
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+}
+
+fn secret_entry(name: &str) -> Result<Entry, SpecError> {
+	if !valid_name(name) {
+		return Err(SpecError::InvalidSecretName);
+	}
+	keychain_entry(&format!("secret:{name}"))
+}
+
+pub fn read_secret(name: &str) -> Result<Option<String>, SpecError> {
+	match secret_entry(name)?.get_password() {
+		Ok(value) => Ok(Some(value)),
+		Err(keyring::Error::NoEntry) => Ok(None),
+		Err(_) => Err(SpecError::KeychainUnavailable),
+	}
+}
+
+#[tauri::command]
+pub fn store_secret(name: String, value: String) -> Result<(), SpecErrorPayload> {
+	secret_entry(&name)?
+		.set_password(&value)
+		.map_err(|_| SpecErrorPayload::from(SpecError::KeychainUnavailable))
+}
+
+#[tauri::command]
+pub fn has_secret(name: String) -> Result<bool, SpecErrorPayload> {
+	Ok(read_secret(&name)?.is_some())
+}
+
+#[tauri::command]
+pub fn delete_secret(name: String) -> Result<(), SpecErrorPayload> {
+	match secret_entry(&name)?.delete_credential() {
+		Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+		Err(_) => Err(SpecError::KeychainUnavailable.into()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn validates_secret_names() {
+		assert!(valid_name("github.token"));
+		assert!(valid_name("npm_registry-token"));
+		assert!(!valid_name(""));
+		assert!(!valid_name("../token"));
+		assert!(!valid_name("with space"));
+	}
+}
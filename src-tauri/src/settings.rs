@@ -0,0 +1,132 @@
+use crate::{
+	container::ExecutionBackend, lint::Severity, ordering::OrderingPolicy, power::PowerMode,
+	search::SavedSearch, storage::JsonStore, webhook::WebhookSettings,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	time::Duration,
+};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RetentionPolicy {
+	pub keep_daily: usize,
+	pub keep_weekly: usize,
+}
+
+impl Default for RetentionPolicy {
+	fn default() -> Self {
+		Self {
+			keep_daily: 7,
+			keep_weekly: 4,
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct JournalRetention {
+	pub max_entries: usize,
+	pub max_age_days: u64,
+	pub output_max_age_days: u64,
+}
+
+impl Default for JournalRetention {
+	fn default() -> Self {
+		Self {
+			max_entries: 500,
+			max_age_days: 30,
+			output_max_age_days: 30,
+		}
+	}
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct HistoryPrivacy {
+	pub scrub_environment: bool,
+	pub scrub_home_paths: bool,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CommandTimeouts {
+	pub default_secs: u64,
+	pub operations: BTreeMap<String, u64>,
+}
+
+impl Default for CommandTimeouts {
+	fn default() -> Self {
+		Self {
+			default_secs: 600,
+			operations: BTreeMap::new(),
+		}
+	}
+}
+
+impl CommandTimeouts {
+	pub fn for_operation(&self, operation: &str) -> Option<Duration> {
+		let secs = self
+			.operations
+			.get(operation)
+			.copied()
+			.unwrap_or(self.default_secs);
+		(secs > 0).then(|| Duration::from_secs(secs))
+	}
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ExportBranding {
+	pub name: Option<String>,
+	pub logo: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DeadlineReminders {
+	pub enabled: bool,
+	pub lead_days: u32,
+}
+
+impl Default for DeadlineReminders {
+	fn default() -> Self {
+		Self {
+			enabled: true,
+			lead_days: 3,
+		}
+	}
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppSettings {
+	pub saved_searches: Vec<SavedSearch>,
+	pub snapshot_retention: RetentionPolicy,
+	pub encrypted_projects: Vec<String>,
+	pub webhook: WebhookSettings,
+	pub power_mode: PowerMode,
+	pub required_approvals: usize,
+	pub ignore_patterns: BTreeMap<String, Vec<String>>,
+	pub journal_retention: JournalRetention,
+	pub severity_gates: BTreeMap<String, Severity>,
+	pub command_timeouts: CommandTimeouts,
+	pub pty_operations: BTreeSet<String>,
+	pub crash_reporting: bool,
+	pub reject_concurrent_operations: bool,
+	pub command_env: BTreeMap<String, String>,
+	pub export_branding: BTreeMap<String, ExportBranding>,
+	pub deadline_reminders: DeadlineReminders,
+	pub ordering: OrderingPolicy,
+	pub history_privacy: HistoryPrivacy,
+	pub on_demand_cli: bool,
+	pub trusted_issue_hosts: BTreeSet<String>,
+	pub execution_backends: BTreeMap<String, ExecutionBackend>,
+}
+
+pub type SettingsStore = JsonStore<AppSettings>;
+
+pub fn load_settings() -> SettingsStore {
+	SettingsStore::in_home("settings.json")
+}
@@ -0,0 +1,467 @@
+use crate::{
+	crypto::{project_cipher, ArtifactCipher},
+	power,
+	projects::RecentProjects,
+	settings::{RetentionPolicy, SettingsStore},
+	spec::{existing_openspec_root, SpecError, SpecErrorPayload},
+	storage::{self, StorageError},
+	supervisor,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+	collections::HashSet,
+	fs,
+	path::{Path, PathBuf},
+	thread,
+	time::Duration,
+};
+use tauri::{AppHandle, Manager, State};
+
+const MANIFEST: &str = "snapshot.json";
+const FILES_DIR: &str = "files";
+const DAY_SECS: u64 = 24 * 60 * 60;
+const MAINTENANCE_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SnapshotKind {
+	Manual,
+	Automatic,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Snapshot {
+	pub id: String,
+	pub project: String,
+	pub kind: SnapshotKind,
+	pub label: Option<String>,
+	pub created_at: u64,
+	pub file_count: usize,
+	pub bytes: u64,
+	#[serde(default)]
+	pub encrypted: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectSnapshotUsage {
+	project: String,
+	snapshot_count: usize,
+	bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotUsage {
+	total_bytes: u64,
+	projects: Vec<ProjectSnapshotUsage>,
+}
+
+pub fn snapshots_root() -> Result<PathBuf, SpecError> {
+	storage::specops_home()
+		.map(|home| home.join("snapshots"))
+		.ok_or(SpecError::Storage(StorageError::Unavailable))
+}
+
+fn project_dir(root: &Path, project: &Path) -> PathBuf {
+	root.join(storage::project_key(project))
+}
+
+fn copy_tree(
+	from: &Path,
+	to: &Path,
+	cipher: Option<&ArtifactCipher>,
+) -> Result<(usize, u64), SpecError> {
+	fs::create_dir_all(to)?;
+	let mut totals = (0, 0);
+	for entry in fs::read_dir(from)? {
+		let entry = entry?;
+		let target = to.join(entry.file_name());
+		if entry.file_type()?.is_dir() {
+			let (files, bytes) = copy_tree(&entry.path(), &target, cipher)?;
+			totals = (totals.0 + files, totals.1 + bytes);
+			continue;
+		}
+		let bytes = match cipher {
+			Some(cipher) => {
+				let sealed = cipher.seal(&fs::read(entry.path())?)?;
+				fs::write(&target, &sealed)?;
+				sealed.len() as u64
+			}
+			None => fs::copy(entry.path(), &target)?,
+		};
+		totals = (totals.0 + 1, totals.1 + bytes);
+	}
+	Ok(totals)
+}
+
+fn rewrite_tree(
+	dir: &Path,
+	rewrite: &impl Fn(&[u8]) -> Result<Vec<u8>, SpecError>,
+) -> Result<u64, SpecError> {
+	let mut total = 0;
+	for entry in fs::read_dir(dir)? {
+		let entry = entry?;
+		if entry.file_type()?.is_dir() {
+			total += rewrite_tree(&entry.path(), rewrite)?;
+		} else {
+			let contents = rewrite(&fs::read(entry.path())?)?;
+			storage::write_atomic(&entry.path(), &contents)?;
+			total += contents.len() as u64;
+		}
+	}
+	Ok(total)
+}
+
+fn dir_size(path: &Path) -> u64 {
+	fs::read_dir(path)
+		.map(|entries| {
+			entries
+				.filter_map(Result::ok)
+				.map(|entry| match entry.metadata() {
+					Ok(metadata) if metadata.is_dir() => dir_size(&entry.path()),
+					Ok(metadata) => metadata.len(),
+					Err(_) => 0,
+				})
+				.sum()
+		})
+		.unwrap_or_default()
+}
+
+pub fn create_snapshot_in(
+	root: &Path,
+	project: &Path,
+	kind: SnapshotKind,
+	label: Option<String>,
+	cipher: Option<&ArtifactCipher>,
+) -> Result<Snapshot, SpecError> {
+	let source = existing_openspec_root(project)?;
+	let id = storage::new_id("snapshot");
+	let dir = project_dir(root, project).join(&id);
+	let (file_count, bytes) =
+		copy_tree(&source, &dir.join(FILES_DIR), cipher).inspect_err(|_| {
+			let _ = fs::remove_dir_all(&dir);
+		})?;
+	let snapshot = Snapshot {
+		id,
+		project: project.to_string_lossy().to_string(),
+		kind,
+		label,
+		created_at: storage::now_secs(),
+		file_count,
+		bytes,
+		encrypted: cipher.is_some(),
+	};
+	write_manifest(&dir, &snapshot)?;
+	Ok(snapshot)
+}
+
+fn write_manifest(dir: &Path, snapshot: &Snapshot) -> Result<(), SpecError> {
+	storage::write_atomic(
+		&dir.join(MANIFEST),
+		&serde_json::to_vec_pretty(snapshot).map_err(StorageError::from)?,
+	)?;
+	Ok(())
+}
+
+fn read_snapshots(dir: &Path) -> Vec<Snapshot> {
+	let mut snapshots = fs::read_dir(dir)
+		.map(|entries| {
+			entries
+				.filter_map(Result::ok)
+				.filter_map(|entry| fs::read(entry.path().join(MANIFEST)).ok())
+				.filter_map(|bytes| serde_json::from_slice::<Snapshot>(&bytes).ok())
+				.collect::<Vec<_>>()
+		})
+		.unwrap_or_default();
+	snapshots.sort_by(|a, b| {
+		b.created_at
+			.cmp(&a.created_at)
+			.then_with(|| b.id.cmp(&a.id))
+	});
+	snapshots
+}
+
+pub fn list_snapshots_in(root: &Path, project: &Path) -> Vec<Snapshot> {
+	read_snapshots(&project_dir(root, project))
+}
+
+pub fn transcode_snapshots_in(
+	root: &Path,
+	project: &Path,
+	from: Option<&ArtifactCipher>,
+	to: Option<&ArtifactCipher>,
+) -> Result<(), SpecError> {
+	let dir = project_dir(root, project);
+	for mut snapshot in read_snapshots(&dir) {
+		if snapshot.encrypted == to.is_some() {
+			continue;
+		}
+		// Snapshots taken before encryption was enabled are plaintext.
+		let from = if snapshot.encrypted {
+			Some(from.ok_or(SpecError::Encryption)?)
+		} else {
+			None
+		};
+		let snapshot_dir = dir.join(&snapshot.id);
+		snapshot.bytes = rewrite_tree(&snapshot_dir.join(FILES_DIR), &|contents| {
+			let plaintext = match from {
+				Some(cipher) => cipher.open(contents)?,
+				None => contents.to_vec(),
+			};
+			match to {
+				Some(cipher) => cipher.seal(&plaintext),
+				None => Ok(plaintext),
+			}
+		})?;
+		snapshot.encrypted = to.is_some();
+		write_manifest(&snapshot_dir, &snapshot)?;
+	}
+	Ok(())
+}
+
+fn newest_per_bucket(
+	snapshots: &[&Snapshot],
+	bucket: impl Fn(u64) -> u64,
+	limit: usize,
+) -> Vec<String> {
+	let mut seen = HashSet::new();
+	snapshots
+		.iter()
+		.filter(|snapshot| seen.insert(bucket(snapshot.created_at)))
+		.take(limit)
+		.map(|snapshot| snapshot.id.clone())
+		.collect()
+}
+
+pub fn retained_ids(snapshots: &[Snapshot], policy: RetentionPolicy) -> HashSet<String> {
+	let automatic = snapshots
+		.iter()
+		.filter(|snapshot| snapshot.kind == SnapshotKind::Automatic)
+		.collect::<Vec<_>>();
+	let mut keep = snapshots
+		.iter()
+		.filter(|snapshot| snapshot.kind == SnapshotKind::Manual)
+		.map(|snapshot| snapshot.id.clone())
+		.collect::<HashSet<_>>();
+	keep.extend(newest_per_bucket(
+		&automatic,
+		|created_at| created_at / DAY_SECS,
+		policy.keep_daily,
+	));
+	keep.extend(newest_per_bucket(
+		&automatic,
+		|created_at| (created_at / DAY_SECS + 3) / 7,
+		policy.keep_weekly,
+	));
+	keep
+}
+
+pub fn prune_in(
+	root: &Path,
+	project: &Path,
+	policy: RetentionPolicy,
+) -> Result<Vec<String>, SpecError> {
+	let dir = project_dir(root, project);
+	let snapshots = read_snapshots(&dir);
+	let keep = retained_ids(&snapshots, policy);
+	let mut removed = Vec::new();
+	for snapshot in snapshots {
+		if !keep.contains(&snapshot.id) {
+			fs::remove_dir_all(dir.join(&snapshot.id))?;
+			removed.push(snapshot.id);
+		}
+	}
+	Ok(removed)
+}
+
+pub fn usage_in(root: &Path) -> SnapshotUsage {
+	let mut projects = fs::read_dir(root)
+		.map(|entries| {
+			entries
+				.filter_map(Result::ok)
+				.filter(|entry| entry.path().is_dir())
+				.map(|entry| {
+					let snapshots = read_snapshots(&entry.path());
+					ProjectSnapshotUsage {
+						project: snapshots
+							.first()
+							.map(|snapshot| snapshot.project.clone())
+							.unwrap_or_else(|| entry.file_name().to_string_lossy().to_string()),
+						snapshot_count: snapshots.len(),
+						bytes: dir_size(&entry.path()),
+					}
+				})
+				.collect::<Vec<_>>()
+		})
+		.unwrap_or_default();
+	projects.sort_by(|a, b| {
+		b.bytes
+			.cmp(&a.bytes)
+			.then_with(|| a.project.cmp(&b.project))
+	});
+	SnapshotUsage {
+		total_bytes: projects.iter().map(|project| project.bytes).sum(),
+		projects,
+	}
+}
+
+fn run_maintenance(root: &Path, settings: &SettingsStore, projects: &[PathBuf]) {
+	let today = storage::now_secs() / DAY_SECS;
+	let policy = settings.get().snapshot_retention;
+	for project in projects {
+		let has_today = list_snapshots_in(root, project).iter().any(|snapshot| {
+			snapshot.kind == SnapshotKind::Automatic && snapshot.created_at / DAY_SECS == today
+		});
+		if !has_today {
+			if let Ok(cipher) = project_cipher(settings, project) {
+				let _ = create_snapshot_in(
+					root,
+					project,
+					SnapshotKind::Automatic,
+					None,
+					cipher.as_ref(),
+				);
+			}
+		}
+		let _ = prune_in(root, project, policy);
+	}
+}
+
+pub fn spawn_maintenance(app: AppHandle) {
+	supervisor::supervise(app.clone(), "snapshots".to_string(), move || loop {
+		if power::low_power(&app.state::<SettingsStore>()) {
+			thread::sleep(MAINTENANCE_INTERVAL);
+			continue;
+		}
+		if let Ok(root) = snapshots_root() {
+			let projects = app
+				.state::<RecentProjects>()
+				.get()
+				.into_iter()
+				.filter(|project| project.openspec_present)
+				.map(|project| PathBuf::from(project.path))
+				.collect::<Vec<_>>();
+			run_maintenance(&root, &app.state::<SettingsStore>(), &projects);
+		}
+		thread::sleep(MAINTENANCE_INTERVAL);
+	});
+}
+
+#[tauri::command]
+pub fn create_snapshot(
+	settings: State<'_, SettingsStore>,
+	project: String,
+	label: Option<String>,
+) -> Result<Snapshot, SpecErrorPayload> {
+	let project = Path::new(&project);
+	let cipher = project_cipher(&settings, project)?;
+	create_snapshot_in(
+		&snapshots_root()?,
+		project,
+		SnapshotKind::Manual,
+		label,
+		cipher.as_ref(),
+	)
+	.map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn list_snapshots(project: String) -> Result<Vec<Snapshot>, SpecErrorPayload> {
+	Ok(list_snapshots_in(&snapshots_root()?, Path::new(&project)))
+}
+
+#[tauri::command]
+pub fn snapshot_usage() -> Result<SnapshotUsage, SpecErrorPayload> {
+	Ok(usage_in(&snapshots_root()?))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	fn snapshot(id: &str, kind: SnapshotKind, day: u64) -> Snapshot {
+		Snapshot {
+			id: id.to_string(),
+			project: "/project".to_string(),
+			kind,
+			label: None,
+			created_at: day * DAY_SECS + 60,
+			file_count: 0,
+			bytes: 0,
+			encrypted: false,
+		}
+	}
+
+	#[test]
+	fn keeps_newest_daily_and_weekly_automatic_snapshots() {
+		let mut snapshots = (0..21)
+			.rev()
+			.map(|day| snapshot(&format!("auto-{day}"), SnapshotKind::Automatic, day))
+			.collect::<Vec<_>>();
+		snapshots.push(snapshot("manual", SnapshotKind::Manual, 0));
+		let keep = retained_ids(
+			&snapshots,
+			RetentionPolicy {
+				keep_daily: 2,
+				keep_weekly: 3,
+			},
+		);
+		let mut kept = keep.into_iter().collect::<Vec<_>>();
+		kept.sort();
+		assert_eq!(
+			kept,
+			vec!["auto-10", "auto-17", "auto-19", "auto-20", "manual"]
+		);
+	}
+
+	#[test]
+	fn creates_prunes_and_reports_usage() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let project = temp_dir.path().join("project");
+		fs::create_dir_all(project.join("openspec/specs/auth")).expect("create specs");
+		fs::write(project.join("openspec/specs/auth/spec.md"), "# Auth\n").expect("write spec");
+		let root = temp_dir.path().join("snapshots");
+
+		let manual = create_snapshot_in(&root, &project, SnapshotKind::Manual, None, None)
+			.expect("manual snapshot");
+		assert_eq!((manual.file_count, manual.bytes), (1, 7));
+		create_snapshot_in(&root, &project, SnapshotKind::Automatic, None, None).expect("auto");
+		create_snapshot_in(&root, &project, SnapshotKind::Automatic, None, None).expect("auto");
+
+		let removed = prune_in(&root, &project, RetentionPolicy::default()).expect("prune");
+		assert_eq!(removed.len(), 1);
+		assert_eq!(list_snapshots_in(&root, &project).len(), 2);
+
+		let usage = usage_in(&root);
+		assert_eq!(usage.projects.len(), 1);
+		assert_eq!(usage.projects[0].snapshot_count, 2);
+		assert!(usage.total_bytes >= 14);
+	}
+
+	#[test]
+	fn encrypts_snapshot_files_and_transcodes_back() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let project = temp_dir.path().join("project");
+		fs::create_dir_all(project.join("openspec")).expect("create openspec");
+		fs::write(project.join("openspec/project.md"), "secret").expect("write");
+		let root = temp_dir.path().join("snapshots");
+		let cipher = ArtifactCipher::from_key(&[1u8; 32]).expect("cipher");
+
+		let snapshot =
+			create_snapshot_in(&root, &project, SnapshotKind::Manual, None, Some(&cipher))
+				.expect("snapshot");
+		assert!(snapshot.encrypted);
+		let stored = project_dir(&root, &project)
+			.join(&snapshot.id)
+			.join(FILES_DIR)
+			.join("project.md");
+		assert_ne!(fs::read(&stored).expect("read"), b"secret");
+
+		transcode_snapshots_in(&root, &project, Some(&cipher), None).expect("decrypt");
+		assert_eq!(fs::read(&stored).expect("read"), b"secret");
+		assert!(!list_snapshots_in(&root, &project)[0].encrypted);
+	}
+}
@@ -0,0 +1,599 @@
+use crate::{git::GitAuthFailure, layout, ordering, storage::StorageError};
+use serde::Serialize;
+use std::{
+	fs::{self, File},
+	io::{BufRead, BufReader, Read},
+	path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+pub const MAX_PARSE_BYTES: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecErrorPayload {
+	code: String,
+	message: String,
+}
+
+#[derive(Debug, Error)]
+pub enum SpecError {
+	#[error("OpenSpec folder is missing")]
+	MissingOpenSpec,
+	#[error("Diagnostic could not be found")]
+	UnknownDiagnostic,
+	#[error("No quick fix is available for this diagnostic")]
+	FixUnavailable,
+	#[error("Saved search could not be found")]
+	SavedSearchNotFound,
+	#[error("Target file already exists")]
+	TargetExists,
+	#[error("Project is not a git repository")]
+	NotGitRepository,
+	#[error("Git command failed")]
+	GitCommandFailed,
+	#[error("Encrypted data could not be processed")]
+	Encryption,
+	#[error("OS keychain is unavailable")]
+	KeychainUnavailable,
+	#[error("Secret name is invalid")]
+	InvalidSecretName,
+	#[error("Change could not be found")]
+	ChangeNotFound,
+	#[error("Change id must be lowercase kebab-case")]
+	InvalidChangeId,
+	#[error("A change with this id already exists")]
+	ChangeExists,
+	#[error("Spec could not be found")]
+	SpecNotFound,
+	#[error("Issue URL is not a GitHub issue link")]
+	InvalidIssueUrl,
+	#[error("Change has no linked issue")]
+	IssueNotLinked,
+	#[error("Issue status could not be fetched")]
+	IssueLookupFailed,
+	#[error("Change metadata is invalid")]
+	InvalidChangeMetadata,
+	#[error("Webhook URL is invalid")]
+	InvalidWebhookUrl,
+	#[error("Webhook delivery failed")]
+	WebhookDeliveryFailed,
+	#[error("Bundle is not a valid OpenSpec export")]
+	InvalidBundle,
+	#[error("Project is not in the recent projects list")]
+	ProjectNotFound,
+	#[error("Operation could not be found")]
+	OperationNotFound,
+	#[error("Operation cannot be rolled back")]
+	RollbackUnavailable,
+	#[error("OpenSpec CLI is not available")]
+	CliUnavailable,
+	#[error("Node.js is not available")]
+	NodeUnavailable,
+	#[error("OpenSpec command failed")]
+	CliCommandFailed,
+	#[error("OpenSpec CLI output could not be parsed")]
+	CliOutputUnparseable,
+	#[error("File is open in an editing session")]
+	FileLocked,
+	#[error("Editing session could not be found")]
+	EditSessionNotFound,
+	#[error("Approver name is required")]
+	InvalidApprover,
+	#[error("Ignore pattern is invalid: {0}")]
+	InvalidIgnorePattern(String),
+	#[error("Lint config is invalid")]
+	InvalidLintConfig,
+	#[error("Capability code map is invalid")]
+	InvalidCodeMap,
+	#[error("Execution backend config is invalid")]
+	InvalidExecutionBackend,
+	#[error("Project container is not running")]
+	ContainerNotRunning,
+	#[error("Remote project could not be synced")]
+	RemoteSyncFailed,
+	#[error("Project is not a remote project")]
+	NotRemoteProject,
+	#[error("Git authentication failed")]
+	GitAuthFailed(GitAuthFailure),
+	#[error("Tool has no known launcher")]
+	ToolNotSupported,
+	#[error("Tool launcher is not installed")]
+	ToolNotInstalled,
+	#[error("Spec layout root is invalid: {0}")]
+	InvalidLayout(String),
+	#[error("Source glob is invalid: {0}")]
+	InvalidSourceGlob(String),
+	#[error("Crash report could not be found")]
+	CrashReportNotFound,
+	#[error("Operation is not accepting input")]
+	OperationInputClosed,
+	#[error("Another operation is already running for this project")]
+	OperationBusy,
+	#[error("Operation was removed from the queue")]
+	OperationDequeued,
+	#[error("Operation was cancelled")]
+	OperationCancelled,
+	#[error("Repair plan could not be found")]
+	RepairPlanNotFound,
+	#[error("Environment variable name is invalid: {0}")]
+	InvalidEnvironmentVariable(String),
+	#[error("Review checklist config is invalid")]
+	InvalidChecklistConfig,
+	#[error("Checklist item could not be found")]
+	ChecklistItemNotFound,
+	#[error("A timer is already running for this change")]
+	TimerAlreadyRunning,
+	#[error("No timer is running for this change")]
+	TimerNotRunning,
+	#[error("Target date must use the YYYY-MM-DD format")]
+	InvalidTargetDate,
+	#[error("Path is outside the OpenSpec folder")]
+	PathOutsideOpenSpec,
+	#[error("File is too large to parse: {0}")]
+	FileTooLarge(String),
+	#[error(transparent)]
+	Storage(#[from] StorageError),
+	#[error(transparent)]
+	Io(#[from] std::io::Error),
+}
+
+impl From<SpecError> for SpecErrorPayload {
+	fn from(error: SpecError) -> Self {
+		let (code, message) = match error {
+			SpecError::MissingOpenSpec => ("openspec_missing", "OpenSpec folder is missing"),
+			SpecError::UnknownDiagnostic => (
+				"diagnostic_not_found",
+				"Diagnostic no longer applies to the file",
+			),
+			SpecError::FixUnavailable => (
+				"fix_unavailable",
+				"No quick fix is available for this diagnostic",
+			),
+			SpecError::SavedSearchNotFound => {
+				("saved_search_not_found", "Saved search could not be found")
+			}
+			SpecError::TargetExists => (
+				"target_exists",
+				"A different file already exists at the target path",
+			),
+			SpecError::NotGitRepository => ("not_git_repo", "Project is not a git repository"),
+			SpecError::GitCommandFailed => ("git_failed", "Git command failed"),
+			SpecError::Encryption => ("encryption_failed", "Encrypted data could not be processed"),
+			SpecError::KeychainUnavailable => {
+				("keychain_unavailable", "OS keychain is unavailable")
+			}
+			SpecError::InvalidSecretName => ("invalid_secret_name", "Secret name is invalid"),
+			SpecError::ChangeNotFound => ("change_not_found", "Change could not be found"),
+			SpecError::InvalidChangeId => {
+				("invalid_change_id", "Change id must be lowercase kebab-case")
+			}
+			SpecError::ChangeExists => ("change_exists", "A change with this id already exists"),
+			SpecError::SpecNotFound => ("spec_not_found", "Spec could not be found"),
+			SpecError::InvalidIssueUrl => {
+				("invalid_issue_url", "Issue URL is not a GitHub issue link")
+			}
+			SpecError::IssueNotLinked => ("issue_not_linked", "Change has no linked issue"),
+			SpecError::IssueLookupFailed => {
+				("issue_lookup_failed", "Issue status could not be fetched")
+			}
+			SpecError::InvalidChangeMetadata => (
+				"invalid_change_metadata",
+				"Change metadata (.specops.json) is not valid for this version of SpecOps",
+			),
+			SpecError::InvalidWebhookUrl => ("invalid_webhook_url", "Webhook URL is invalid"),
+			SpecError::WebhookDeliveryFailed => {
+				("webhook_delivery_failed", "Webhook delivery failed")
+			}
+			SpecError::InvalidBundle => ("invalid_bundle", "Bundle is not a valid OpenSpec export"),
+			SpecError::ProjectNotFound => (
+				"project_not_found",
+				"Project is not in the recent projects list",
+			),
+			SpecError::OperationNotFound => ("operation_not_found", "Operation could not be found"),
+			SpecError::RollbackUnavailable => (
+				"rollback_unavailable",
+				"Only failed or cancelled operations with a backup can be rolled back",
+			),
+			SpecError::CliUnavailable => ("openspec_unavailable", "OpenSpec CLI is not available"),
+			SpecError::NodeUnavailable => (
+				"node_unavailable",
+				"Node.js is required to run OpenSpec on demand. Install Node.js or install the OpenSpec CLI",
+			),
+			SpecError::CliCommandFailed => ("command_failed", "OpenSpec command failed"),
+			SpecError::CliOutputUnparseable => (
+				"cli_output_unparseable",
+				"OpenSpec CLI output was not in a format SpecOps understands",
+			),
+			SpecError::FileLocked => (
+				"file_locked",
+				"File has unsaved edits in an open editing session",
+			),
+			SpecError::EditSessionNotFound => (
+				"edit_session_not_found",
+				"Editing session could not be found",
+			),
+			SpecError::InvalidApprover => ("invalid_approver", "Approver name is required"),
+			SpecError::InvalidIgnorePattern(_) => (
+				"invalid_ignore_pattern",
+				"Ignore pattern is not a valid glob",
+			),
+			SpecError::InvalidLintConfig => (
+				"invalid_lint_config",
+				"Project lint config (.specops-lint.json) is not valid JSON",
+			),
+			SpecError::InvalidCodeMap => (
+				"invalid_code_map",
+				"Capability code map (.specops-code-map.json) is not valid",
+			),
+			SpecError::InvalidExecutionBackend => (
+				"invalid_execution_backend",
+				"Execution backend is not valid",
+			),
+			SpecError::ContainerNotRunning => (
+				"container_not_running",
+				"The project's devcontainer is not running. Start it and try again",
+			),
+			SpecError::RemoteSyncFailed => (
+				"remote_sync_failed",
+				"Could not copy the project's specs from the remote host",
+			),
+			SpecError::NotRemoteProject => (
+				"not_remote_project",
+				"Project was not opened over SSH",
+			),
+			SpecError::GitAuthFailed(GitAuthFailure::PublicKeyDenied) => (
+				"git_auth_publickey",
+				"The remote rejected your SSH key. Add your public key to the host or check `ssh -T` for the right identity",
+			),
+			SpecError::GitAuthFailed(GitAuthFailure::AgentUnavailable) => (
+				"git_auth_agent",
+				"The SSH agent is not running or refused to sign. Start it and run `ssh-add` to load your key",
+			),
+			SpecError::GitAuthFailed(GitAuthFailure::HostKeyVerification) => (
+				"git_auth_host_key",
+				"The host key is unknown or changed. Verify the fingerprint and add it to known_hosts",
+			),
+			SpecError::GitAuthFailed(GitAuthFailure::CredentialHelperMissing) => (
+				"git_auth_credential_helper",
+				"Git could not ask for credentials. Install or configure a credential helper, or use an SSH remote",
+			),
+			SpecError::GitAuthFailed(GitAuthFailure::CredentialsRejected) => (
+				"git_auth_rejected",
+				"The remote rejected your credentials. Update the stored token or password",
+			),
+			SpecError::ToolNotSupported => ("tool_not_supported", "Tool has no known launcher"),
+			SpecError::ToolNotInstalled => (
+				"tool_not_installed",
+				"Tool launcher is not installed or not on PATH",
+			),
+			SpecError::OperationBusy => (
+				"operation_busy",
+				"Another operation is already running for this project",
+			),
+			SpecError::OperationDequeued => (
+				"operation_dequeued",
+				"Operation was removed from the queue",
+			),
+			SpecError::OperationCancelled => ("operation_cancelled", "Operation was cancelled"),
+			SpecError::RepairPlanNotFound => {
+				("repair_plan_not_found", "Repair plan could not be found")
+			}
+			SpecError::InvalidChecklistConfig => (
+				"invalid_checklist_config",
+				"Project review checklist (.specops-review.json) is not valid JSON",
+			),
+			SpecError::ChecklistItemNotFound => (
+				"checklist_item_not_found",
+				"Checklist item does not exist or is checked automatically",
+			),
+			SpecError::InvalidEnvironmentVariable(_) => (
+				"invalid_environment_variable",
+				"Environment variable names must be non-empty and cannot contain '=' or NUL",
+			),
+			SpecError::TimerAlreadyRunning => (
+				"timer_already_running",
+				"A timer is already running for this change",
+			),
+			SpecError::TimerNotRunning => {
+				("timer_not_running", "No timer is running for this change")
+			}
+			SpecError::InvalidTargetDate => (
+				"invalid_target_date",
+				"Target date must use the YYYY-MM-DD format",
+			),
+			SpecError::PathOutsideOpenSpec => (
+				"path_outside_openspec",
+				"Path is outside the OpenSpec folder",
+			),
+			SpecError::FileTooLarge(_) => ("file_too_large", "File is too large to parse"),
+			SpecError::OperationInputClosed => (
+				"operation_input_closed",
+				"Operation is not accepting input",
+			),
+			SpecError::CrashReportNotFound => (
+				"crash_report_not_found",
+				"Crash report could not be found",
+			),
+			SpecError::InvalidSourceGlob(_) => {
+				("invalid_source_glob", "Source pattern is not a valid glob")
+			}
+			SpecError::InvalidLayout(_) => (
+				"invalid_layout",
+				"Spec root must be a relative path inside the project",
+			),
+			SpecError::Storage(_) => ("storage_unavailable", "App data could not be saved"),
+			SpecError::Io(_) => ("io_error", "File system error"),
+		};
+
+		Self {
+			code: code.to_string(),
+			message: message.to_string(),
+		}
+	}
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Heading {
+	pub level: usize,
+	pub text: String,
+	pub line: usize,
+}
+
+pub fn openspec_root(project: &Path) -> PathBuf {
+	layout::root_path(project)
+}
+
+pub fn existing_openspec_root(project: &Path) -> Result<PathBuf, SpecError> {
+	let root = openspec_root(project);
+	if root.is_dir() {
+		Ok(root)
+	} else {
+		Err(SpecError::MissingOpenSpec)
+	}
+}
+
+pub fn markdown_files(root: &Path) -> Result<Vec<PathBuf>, SpecError> {
+	markdown_files_except(root, &|_| false)
+}
+
+pub fn markdown_files_except(
+	root: &Path,
+	skip: &dyn Fn(&Path) -> bool,
+) -> Result<Vec<PathBuf>, SpecError> {
+	let mut files = Vec::new();
+	collect_markdown_files(root, skip, &mut files)?;
+	files.sort_by(|a, b| ordering::compare_paths(a, b));
+	Ok(files)
+}
+
+fn collect_markdown_files(
+	dir: &Path,
+	skip: &dyn Fn(&Path) -> bool,
+	files: &mut Vec<PathBuf>,
+) -> Result<(), SpecError> {
+	for entry in fs::read_dir(dir)? {
+		let path = entry?.path();
+		if skip(&path) {
+			continue;
+		}
+		if path.is_dir() {
+			if is_archive_dir(&path) {
+				continue;
+			}
+			collect_markdown_files(&path, skip, files)?;
+		} else if path.extension().and_then(|ext| ext.to_str()) == Some("md") {
+			files.push(path);
+		}
+	}
+	Ok(())
+}
+
+fn is_archive_dir(path: &Path) -> bool {
+	path.file_name().and_then(|name| name.to_str()) == Some("archive")
+		&& path
+			.parent()
+			.and_then(|parent| parent.file_name())
+			.and_then(|name| name.to_str())
+			== Some("changes")
+}
+
+pub fn is_delta_spec(path: &Path) -> bool {
+	let components = path
+		.components()
+		.filter_map(|component| component.as_os_str().to_str())
+		.collect::<Vec<_>>();
+	components
+		.iter()
+		.position(|component| *component == "changes")
+		.is_some_and(|index| components[index..].contains(&"specs"))
+}
+
+pub fn parse_heading(line: &str) -> Option<(usize, &str)> {
+	let trimmed = line.trim_start();
+	let level = trimmed.chars().take_while(|ch| *ch == '#').count();
+	if level == 0 || level > 6 {
+		return None;
+	}
+	let rest = &trimmed[level..];
+	if !rest.is_empty() && !rest.starts_with(' ') {
+		return None;
+	}
+	Some((level, rest.trim()))
+}
+
+pub fn parse_headings(lines: &[String]) -> Vec<Heading> {
+	let mut headings = Vec::new();
+	let mut in_fence = false;
+	for (index, line) in lines.iter().enumerate() {
+		if is_fence(line) {
+			in_fence = !in_fence;
+			continue;
+		}
+		if in_fence {
+			continue;
+		}
+		if let Some((level, text)) = parse_heading(line) {
+			headings.push(Heading {
+				level,
+				text: text.to_string(),
+				line: index,
+			});
+		}
+	}
+	headings
+}
+
+pub fn is_fence(line: &str) -> bool {
+	let trimmed = line.trim_start();
+	trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+pub struct SourceText {
+	pub lines: Vec<String>,
+	newline: &'static str,
+	pub trailing_newline: bool,
+	pub truncated: bool,
+}
+
+impl SourceText {
+	pub fn parse(text: &str) -> Self {
+		let newline = if text.contains("\r\n") { "\r\n" } else { "\n" };
+		let trailing_newline = text.ends_with('\n');
+		let lines = text
+			.lines()
+			.map(|line| line.trim_end_matches('\r').to_string())
+			.collect();
+		Self {
+			lines,
+			newline,
+			trailing_newline,
+			truncated: false,
+		}
+	}
+
+	/// Streams `path` line by line and stops at the last complete line within
+	/// `limit` bytes, so oversized specs yield a partial parse instead of being
+	/// loaded whole. Truncated sources must not be rendered back to disk.
+	pub fn read_limited(path: &Path, limit: u64) -> Result<Self, SpecError> {
+		let mut reader = BufReader::new(File::open(path)?);
+		let mut source = Self {
+			lines: Vec::new(),
+			newline: "\n",
+			trailing_newline: false,
+			truncated: false,
+		};
+		let mut remaining = limit;
+		let mut buffer = Vec::new();
+		loop {
+			buffer.clear();
+			let read = (&mut reader)
+				.take(remaining.saturating_add(1))
+				.read_until(b'\n', &mut buffer)? as u64;
+			if read == 0 {
+				break;
+			}
+			if read > remaining {
+				source.truncated = true;
+				break;
+			}
+			remaining -= read;
+			if buffer.ends_with(b"\r\n") {
+				source.newline = "\r\n";
+			}
+			source.trailing_newline = buffer.ends_with(b"\n");
+			let line = String::from_utf8_lossy(&buffer);
+			source.lines.push(
+				line.strip_suffix('\n')
+					.unwrap_or(&line)
+					.trim_end_matches('\r')
+					.to_string(),
+			);
+		}
+		Ok(source)
+	}
+
+	/// Like `read_limited` at `MAX_PARSE_BYTES`, for callers that rewrite or
+	/// tally the whole file and so cannot act on a partial parse.
+	pub fn read_complete(path: &Path) -> Result<Self, SpecError> {
+		let source = Self::read_limited(path, MAX_PARSE_BYTES)?;
+		if source.truncated {
+			return Err(SpecError::FileTooLarge(path.to_string_lossy().to_string()));
+		}
+		Ok(source)
+	}
+
+	pub fn render(&self) -> String {
+		let mut text = self.lines.join(self.newline);
+		if self.trailing_newline && !self.lines.is_empty() {
+			text.push_str(self.newline);
+		}
+		text
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn streams_sources_up_to_the_size_guard() {
+		let dir = TempDir::new().expect("create temp dir");
+		let path = dir.path().join("spec.md");
+		fs::write(&path, "# Title\r\n## Purpose\r\nText\r\n").expect("write spec");
+
+		let full = SourceText::read_limited(&path, MAX_PARSE_BYTES).expect("read full");
+		assert!(!full.truncated);
+		assert_eq!(full.lines, ["# Title", "## Purpose", "Text"]);
+		assert_eq!(full.render(), "# Title\r\n## Purpose\r\nText\r\n");
+
+		let partial = SourceText::read_limited(&path, 24).expect("read partial");
+		assert!(partial.truncated);
+		assert_eq!(partial.lines, ["# Title", "## Purpose"]);
+
+		assert!(SourceText::read_complete(&path).is_ok());
+		fs::write(&path, "- [ ] task\n".repeat(MAX_PARSE_BYTES as usize / 10)).expect("write");
+		assert!(matches!(
+			SourceText::read_complete(&path),
+			Err(SpecError::FileTooLarge(_))
+		));
+	}
+
+	#[test]
+	fn parses_headings_outside_code_fences() {
+		let source = SourceText::parse("# Title\n```\n## Not a heading\n```\n### Requirement: A\n");
+		let headings = parse_headings(&source.lines);
+		assert_eq!(
+			headings,
+			vec![
+				Heading {
+					level: 1,
+					text: "Title".to_string(),
+					line: 0,
+				},
+				Heading {
+					level: 3,
+					text: "Requirement: A".to_string(),
+					line: 4,
+				},
+			],
+		);
+	}
+
+	#[test]
+	fn preserves_crlf_line_endings() {
+		let text = "# Title\r\nBody\r\n";
+		assert_eq!(SourceText::parse(text).render(), text);
+	}
+
+	#[test]
+	fn detects_delta_spec_paths() {
+		assert!(is_delta_spec(Path::new(
+			"/repo/openspec/changes/add-x/specs/cap/spec.md"
+		)));
+		assert!(!is_delta_spec(Path::new(
+			"/repo/openspec/specs/cap/spec.md"
+		)));
+	}
+}
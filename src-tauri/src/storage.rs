@@ -0,0 +1,190 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+	env, fs,
+	path::{Path, PathBuf},
+	sync::{
+		atomic::{AtomicU64, Ordering},
+		Mutex,
+	},
+	time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use thiserror::Error;
+
+static ID_COUNTER: AtomicU64 = AtomicU64::new(0);
+static APP_WRITES: Mutex<Vec<(PathBuf, Instant)>> = Mutex::new(Vec::new());
+const APP_WRITE_WINDOW: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Error)]
+pub enum StorageError {
+	#[error("App storage is unavailable")]
+	Unavailable,
+	#[error(transparent)]
+	Serialize(#[from] serde_json::Error),
+	#[error(transparent)]
+	Io(#[from] std::io::Error),
+}
+
+pub fn specops_home() -> Option<PathBuf> {
+	if cfg!(windows) {
+		env::var_os("LOCALAPPDATA").map(|dir| PathBuf::from(dir).join("SpecOps"))
+	} else {
+		env::var_os("HOME").map(|dir| PathBuf::from(dir).join(".specops"))
+	}
+}
+
+pub fn now_secs() -> u64 {
+	SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|duration| duration.as_secs())
+		.unwrap_or_default()
+}
+
+pub fn new_id(prefix: &str) -> String {
+	let millis = SystemTime::now()
+		.duration_since(UNIX_EPOCH)
+		.map(|duration| duration.as_millis())
+		.unwrap_or_default();
+	let counter = ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+	format!("{prefix}-{millis:x}-{counter}")
+}
+
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+	bytes.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+		(hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+	})
+}
+
+pub fn project_key(project: &Path) -> String {
+	let hash = fnv1a(project.to_string_lossy().as_bytes());
+	let name = project
+		.file_name()
+		.map(|name| name.to_string_lossy().to_string())
+		.unwrap_or_default()
+		.chars()
+		.map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+		.collect::<String>();
+	format!("{name}-{hash:016x}")
+}
+
+pub fn record_app_write(path: &Path) {
+	if let Ok(mut writes) = APP_WRITES.lock() {
+		let now = Instant::now();
+		writes.retain(|(_, at)| now.duration_since(*at) < APP_WRITE_WINDOW);
+		writes.push((path.to_path_buf(), now));
+	}
+}
+
+pub fn is_recent_app_write(path: &Path) -> bool {
+	APP_WRITES.lock().is_ok_and(|writes| {
+		writes
+			.iter()
+			.any(|(written, at)| written == path && at.elapsed() < APP_WRITE_WINDOW)
+	})
+}
+
+pub fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+	if let Some(parent) = path.parent() {
+		fs::create_dir_all(parent)?;
+	}
+	let temp_path = path.with_extension("tmp");
+	record_app_write(&temp_path);
+	record_app_write(path);
+	fs::write(&temp_path, contents)?;
+	fs::rename(&temp_path, path)
+}
+
+pub struct JsonStore<T> {
+	path: Option<PathBuf>,
+	current: Mutex<T>,
+}
+
+impl<T: Clone + Default + Serialize + DeserializeOwned> JsonStore<T> {
+	pub fn load(path: Option<PathBuf>) -> Self {
+		let current = path
+			.as_ref()
+			.and_then(|path| fs::read(path).ok())
+			.and_then(|bytes| serde_json::from_slice(&bytes).ok())
+			.unwrap_or_default();
+		Self {
+			path,
+			current: Mutex::new(current),
+		}
+	}
+
+	pub fn in_home(file_name: &str) -> Self {
+		Self::load(specops_home().map(|home| home.join(file_name)))
+	}
+
+	pub fn get(&self) -> T {
+		self.current
+			.lock()
+			.map(|current| current.clone())
+			.unwrap_or_default()
+	}
+
+	pub fn update<R>(&self, mutate: impl FnOnce(&mut T) -> R) -> Result<R, StorageError> {
+		let mut current = self.current.lock().map_err(|_| StorageError::Unavailable)?;
+		let mut next = current.clone();
+		let result = mutate(&mut next);
+		if let Some(path) = &self.path {
+			write_atomic(path, &serde_json::to_vec_pretty(&next)?)?;
+		}
+		*current = next;
+		Ok(result)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn generates_unique_ids() {
+		let first = new_id("search");
+		let second = new_id("search");
+		assert!(first.starts_with("search-"));
+		assert_ne!(first, second);
+	}
+
+	#[test]
+	fn project_keys_are_stable_and_distinct() {
+		let key = project_key(Path::new("/work/my app"));
+		assert!(key.starts_with("my-app-"));
+		assert_eq!(key, project_key(Path::new("/work/my app")));
+		assert_ne!(key, project_key(Path::new("/other/my app")));
+	}
+
+	#[test]
+	fn writes_atomically_and_creates_parents() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let path = temp_dir.path().join("nested").join("settings.json");
+		write_atomic(&path, b"{}").expect("write");
+		assert_eq!(fs::read_to_string(&path).expect("read"), "{}");
+		assert!(!path.with_extension("tmp").exists());
+		assert!(is_recent_app_write(&path));
+		assert!(!is_recent_app_write(&temp_dir.path().join("other.json")));
+	}
+
+	#[test]
+	fn json_store_persists_updates_and_reloads() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let path = temp_dir.path().join("values.json");
+		let store = JsonStore::<Vec<String>>::load(Some(path.clone()));
+		store
+			.update(|values| values.push("one".to_string()))
+			.expect("update");
+
+		let reloaded = JsonStore::<Vec<String>>::load(Some(path));
+		assert_eq!(reloaded.get(), vec!["one".to_string()]);
+	}
+
+	#[test]
+	fn json_store_falls_back_to_default_for_corrupt_file() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let path = temp_dir.path().join("values.json");
+		fs::write(&path, "not json").expect("write");
+		let store = JsonStore::<Vec<String>>::load(Some(path));
+		assert!(store.get().is_empty());
+	}
+}
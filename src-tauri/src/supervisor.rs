@@ -0,0 +1,167 @@
+use crate::storage;
+use serde::Serialize;
+use std::{
+	collections::BTreeMap,
+	panic::{self, AssertUnwindSafe},
+	sync::Mutex,
+	thread,
+	time::Duration,
+};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+const MAX_RESTARTS: u32 = 5;
+const RESTART_BACKOFF: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubsystemStatus {
+	name: String,
+	healthy: bool,
+	restarts: u32,
+	last_error: Option<String>,
+	last_failure_at: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubsystemErrorEvent {
+	subsystem: String,
+	message: String,
+	restarts: u32,
+	restarting: bool,
+}
+
+#[derive(Default)]
+pub struct Subsystems(Mutex<BTreeMap<String, SubsystemStatus>>);
+
+impl Subsystems {
+	fn update(&self, name: &str, mutate: impl FnOnce(&mut SubsystemStatus)) {
+		if let Ok(mut subsystems) = self.0.lock() {
+			let status = subsystems
+				.entry(name.to_string())
+				.or_insert_with(|| SubsystemStatus {
+					name: name.to_string(),
+					healthy: true,
+					restarts: 0,
+					last_error: None,
+					last_failure_at: None,
+				});
+			mutate(status);
+		}
+	}
+
+	fn remove(&self, name: &str) {
+		if let Ok(mut subsystems) = self.0.lock() {
+			subsystems.remove(name);
+		}
+	}
+
+	pub fn list(&self) -> Vec<SubsystemStatus> {
+		self.0
+			.lock()
+			.map(|subsystems| subsystems.values().cloned().collect())
+			.unwrap_or_default()
+	}
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+	payload
+		.downcast_ref::<&str>()
+		.map(|message| message.to_string())
+		.or_else(|| payload.downcast_ref::<String>().cloned())
+		.unwrap_or_else(|| "background task panicked".to_string())
+}
+
+fn run_supervised(
+	subsystems: &Subsystems,
+	name: &str,
+	backoff: Duration,
+	task: impl Fn(),
+	on_error: impl Fn(SubsystemErrorEvent),
+) {
+	subsystems.update(name, |status| status.healthy = true);
+	let mut restarts = 0;
+	loop {
+		let Err(payload) = panic::catch_unwind(AssertUnwindSafe(&task)) else {
+			subsystems.remove(name);
+			return;
+		};
+		let message = panic_message(payload.as_ref());
+		let restarting = restarts < MAX_RESTARTS;
+		subsystems.update(name, |status| {
+			status.healthy = restarting;
+			status.last_error = Some(message.clone());
+			status.last_failure_at = Some(storage::now_secs());
+		});
+		on_error(SubsystemErrorEvent {
+			subsystem: name.to_string(),
+			message,
+			restarts,
+			restarting,
+		});
+		if !restarting {
+			return;
+		}
+		thread::sleep(backoff * 2u32.pow(restarts));
+		restarts += 1;
+		subsystems.update(name, |status| status.restarts = restarts);
+	}
+}
+
+pub fn supervise(app: AppHandle, name: String, task: impl Fn() + Send + 'static) {
+	let _ = thread::Builder::new().name(name.clone()).spawn(move || {
+		run_supervised(
+			&app.state::<Subsystems>(),
+			&name,
+			RESTART_BACKOFF,
+			task,
+			|event| {
+				let _ = app.emit("openspec://subsystem-error", event);
+			},
+		);
+	});
+}
+
+#[tauri::command]
+pub fn subsystem_status(subsystems: State<'_, Subsystems>) -> Vec<SubsystemStatus> {
+	subsystems.list()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::sync::atomic::{AtomicU32, Ordering};
+
+	#[test]
+	fn restarts_panicking_tasks_until_the_limit() {
+		let subsystems = Subsystems::default();
+		let runs = AtomicU32::new(0);
+		let events = Mutex::new(Vec::new());
+		run_supervised(
+			&subsystems,
+			"watcher",
+			Duration::ZERO,
+			|| {
+				if runs.fetch_add(1, Ordering::SeqCst) < 2 {
+					panic!("lost inotify handle");
+				}
+			},
+			|event| events.lock().expect("events").push(event),
+		);
+		assert_eq!(runs.load(Ordering::SeqCst), 3);
+		assert_eq!(events.lock().expect("events").len(), 2);
+		assert!(subsystems.list().is_empty());
+
+		run_supervised(
+			&subsystems,
+			"snapshots",
+			Duration::ZERO,
+			|| panic!("disk full"),
+			|_| {},
+		);
+		let status = subsystems.list().remove(0);
+		assert!(!status.healthy);
+		assert_eq!(status.restarts, MAX_RESTARTS);
+		assert_eq!(status.last_error.as_deref(), Some("disk full"));
+	}
+}
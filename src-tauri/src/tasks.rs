@@ -0,0 +1,426 @@
+use crate::{
+	changes::change_dir,
+	git::git_output,
+	spec::{parse_heading, SourceText, SpecError, SpecErrorPayload},
+	storage,
+};
+use serde::Serialize;
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	path::Path,
+};
+
+const DEPENDENCY_MARKERS: &[&str] = &["blocked by", "depends on"];
+const MINUTES_PER_DAY: f64 = 8.0 * 60.0;
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Task {
+	pub id: String,
+	pub title: String,
+	pub section: Option<String>,
+	pub line: usize,
+	pub done: bool,
+	pub depends_on: Vec<String>,
+	pub estimate_minutes: Option<u32>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingDependency {
+	task: String,
+	dependency: String,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskPlan {
+	order: Vec<String>,
+	actionable: Vec<String>,
+	blocked: Vec<String>,
+	cycles: Vec<Vec<String>>,
+	missing: Vec<MissingDependency>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BurndownPoint {
+	commit: Option<String>,
+	timestamp: u64,
+	tasks_total: usize,
+	tasks_done: usize,
+	estimated_minutes: u32,
+	remaining_minutes: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Burndown {
+	change_id: String,
+	points: Vec<BurndownPoint>,
+	unestimated: usize,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskList {
+	change_id: String,
+	tasks: Vec<Task>,
+	plan: TaskPlan,
+}
+
+fn checkbox(line: &str) -> Option<(bool, &str)> {
+	let item = line.trim_start().strip_prefix("- [")?;
+	let mut chars = item.chars();
+	let done = match chars.next()? {
+		' ' => false,
+		'x' | 'X' => true,
+		_ => return None,
+	};
+	let rest = chars.as_str().strip_prefix(']')?;
+	Some((done, rest.trim()))
+}
+
+fn is_task_id(word: &str) -> bool {
+	!word.is_empty()
+		&& word
+			.split('.')
+			.all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn split_number(text: &str) -> Option<(&str, &str)> {
+	let (word, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+	let id = word.trim_end_matches('.');
+	is_task_id(id).then(|| (id, rest.trim_start()))
+}
+
+pub fn parse_dependencies(text: &str) -> Vec<String> {
+	let lower = text.to_lowercase();
+	let mut dependencies = Vec::new();
+	for marker in DEPENDENCY_MARKERS {
+		let mut rest = lower.as_str();
+		while let Some(index) = rest.find(marker) {
+			rest = &rest[index + marker.len()..];
+			let mut words = rest
+				.split(|c: char| c.is_whitespace() || c == ',')
+				.filter(|word| !word.is_empty() && *word != "and")
+				.map(|word| word.trim_matches(|c: char| !c.is_ascii_digit()));
+			while let Some(word) = words.next().filter(|word| is_task_id(word)) {
+				if !dependencies.iter().any(|existing| existing == word) {
+					dependencies.push(word.to_string());
+				}
+			}
+		}
+	}
+	dependencies
+}
+
+pub fn parse_estimate(text: &str) -> Option<u32> {
+	text.split('[').skip(1).find_map(|segment| {
+		let (value, _) = segment.split_once(']')?;
+		let value = value.trim().to_lowercase();
+		let unit = value.chars().last()?;
+		let amount = value[..value.len() - unit.len_utf8()].trim().parse::<f64>().ok()?;
+		let minutes = match unit {
+			'm' => amount,
+			'h' => amount * 60.0,
+			'd' => amount * MINUTES_PER_DAY,
+			_ => return None,
+		};
+		(minutes > 0.0).then(|| minutes.round() as u32)
+	})
+}
+
+pub fn parse_tasks(text: &str) -> Vec<Task> {
+	let mut section = None;
+	let mut tasks = Vec::new();
+	for (index, line) in text.lines().enumerate() {
+		if let Some((_, heading)) = parse_heading(line) {
+			section = Some(heading.to_string());
+			continue;
+		}
+		let Some((done, text)) = checkbox(line) else {
+			continue;
+		};
+		let (id, title) = match split_number(text) {
+			Some((id, title)) => (id.to_string(), title),
+			None => (format!("line-{}", index + 1), text),
+		};
+		tasks.push(Task {
+			depends_on: parse_dependencies(title),
+			estimate_minutes: parse_estimate(title),
+			id,
+			title: title.to_string(),
+			section: section.clone(),
+			line: index + 1,
+			done,
+		});
+	}
+	tasks
+}
+
+fn reachable(from: &str, edges: &BTreeMap<&str, Vec<&str>>) -> BTreeSet<String> {
+	let mut seen = BTreeSet::new();
+	let mut stack = edges.get(from).cloned().unwrap_or_default();
+	while let Some(next) = stack.pop() {
+		if seen.insert(next.to_string()) {
+			stack.extend(edges.get(next).cloned().unwrap_or_default());
+		}
+	}
+	seen
+}
+
+pub fn plan_tasks(tasks: &[Task]) -> TaskPlan {
+	let known = tasks
+		.iter()
+		.map(|task| task.id.as_str())
+		.collect::<BTreeSet<_>>();
+	let mut plan = TaskPlan::default();
+	let mut edges = BTreeMap::new();
+	for task in tasks {
+		let mut dependencies = Vec::new();
+		for dependency in &task.depends_on {
+			if known.contains(dependency.as_str()) {
+				dependencies.push(dependency.as_str());
+			} else {
+				plan.missing.push(MissingDependency {
+					task: task.id.clone(),
+					dependency: dependency.clone(),
+				});
+			}
+		}
+		edges.insert(task.id.as_str(), dependencies);
+	}
+
+	let mut placed = BTreeSet::new();
+	while let Some(task) = tasks.iter().find(|task| {
+		!placed.contains(task.id.as_str())
+			&& edges[task.id.as_str()]
+				.iter()
+				.all(|dependency| placed.contains(dependency))
+	}) {
+		placed.insert(task.id.as_str());
+		plan.order.push(task.id.clone());
+	}
+
+	let mut in_cycle = BTreeSet::new();
+	for task in tasks
+		.iter()
+		.filter(|task| !placed.contains(task.id.as_str()))
+	{
+		plan.order.push(task.id.clone());
+		if in_cycle.contains(&task.id) {
+			continue;
+		}
+		let forward = reachable(&task.id, &edges);
+		if !forward.contains(&task.id) {
+			continue;
+		}
+		let cycle = tasks
+			.iter()
+			.filter(|other| {
+				forward.contains(&other.id) && reachable(&other.id, &edges).contains(&task.id)
+			})
+			.map(|other| other.id.clone())
+			.collect::<Vec<_>>();
+		in_cycle.extend(cycle.iter().cloned());
+		plan.cycles.push(cycle);
+	}
+
+	let done = tasks
+		.iter()
+		.filter(|task| task.done)
+		.map(|task| task.id.as_str())
+		.collect::<BTreeSet<_>>();
+	for id in &plan.order {
+		let Some(task) = tasks.iter().find(|task| &task.id == id) else {
+			continue;
+		};
+		if task.done {
+			continue;
+		}
+		if !in_cycle.contains(id)
+			&& edges[id.as_str()]
+				.iter()
+				.all(|dependency| done.contains(dependency))
+		{
+			plan.actionable.push(id.clone());
+		} else {
+			plan.blocked.push(id.clone());
+		}
+	}
+	plan
+}
+
+fn burndown_point(text: &str, commit: Option<String>, timestamp: u64) -> BurndownPoint {
+	let tasks = parse_tasks(text);
+	let estimate = |task: &&Task| task.estimate_minutes.unwrap_or_default();
+	BurndownPoint {
+		commit,
+		timestamp,
+		tasks_total: tasks.len(),
+		tasks_done: tasks.iter().filter(|task| task.done).count(),
+		estimated_minutes: tasks.iter().map(|task| estimate(&task)).sum(),
+		remaining_minutes: tasks
+			.iter()
+			.filter(|task| !task.done)
+			.map(|task| estimate(&task))
+			.sum(),
+	}
+}
+
+fn burndown_points(
+	history: &[(String, u64, String)],
+	current: Option<&str>,
+	now: u64,
+) -> Vec<BurndownPoint> {
+	let mut points = history
+		.iter()
+		.map(|(commit, timestamp, text)| burndown_point(text, Some(commit.clone()), *timestamp))
+		.collect::<Vec<_>>();
+	if let Some(text) = current {
+		let point = burndown_point(text, None, now);
+		let unchanged = points.last().is_some_and(|last| {
+			(last.tasks_done, last.tasks_total, last.remaining_minutes)
+				== (point.tasks_done, point.tasks_total, point.remaining_minutes)
+		});
+		if !unchanged {
+			points.push(point);
+		}
+	}
+	points
+}
+
+fn tasks_history(project: &Path, relative: &str) -> Vec<(String, u64, String)> {
+	let log = git_output(
+		project,
+		&["log", "--reverse", "--format=%H %ct", "--", relative],
+	)
+	.unwrap_or_default();
+	log.lines()
+		.filter_map(|line| {
+			let (commit, timestamp) = line.split_once(' ')?;
+			let text = git_output(project, &["show", &format!("{commit}:./{relative}")]).ok()?;
+			Some((commit.to_string(), timestamp.parse().ok()?, text))
+		})
+		.collect()
+}
+
+fn change_burndown(project: &Path, change_id: &str) -> Result<Burndown, SpecError> {
+	let path = change_dir(project, change_id)?.join("tasks.md");
+	let relative = path
+		.strip_prefix(project)
+		.unwrap_or(&path)
+		.to_string_lossy()
+		.replace('\\', "/");
+	let current = SourceText::read_complete(&path)
+		.ok()
+		.map(|source| source.render());
+	let history = tasks_history(project, &relative);
+	Ok(Burndown {
+		change_id: change_id.to_string(),
+		unestimated: parse_tasks(current.as_deref().unwrap_or_default())
+			.iter()
+			.filter(|task| task.estimate_minutes.is_none())
+			.count(),
+		points: burndown_points(&history, current.as_deref(), storage::now_secs()),
+	})
+}
+
+fn read_change_tasks(project: &Path, change_id: &str) -> Result<TaskList, SpecError> {
+	let path = change_dir(project, change_id)?.join("tasks.md");
+	let text = match SourceText::read_complete(&path) {
+		Ok(source) => source.render(),
+		Err(SpecError::Io(error)) if error.kind() == std::io::ErrorKind::NotFound => String::new(),
+		Err(error) => return Err(error),
+	};
+	let tasks = parse_tasks(&text);
+	Ok(TaskList {
+		change_id: change_id.to_string(),
+		plan: plan_tasks(&tasks),
+		tasks,
+	})
+}
+
+#[tauri::command]
+pub fn read_tasks(project: String, change_id: String) -> Result<TaskList, SpecErrorPayload> {
+	read_change_tasks(Path::new(&project), &change_id).map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn burndown(project: String, change_id: String) -> Result<Burndown, SpecErrorPayload> {
+	change_burndown(Path::new(&project), &change_id).map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_numbered_tasks_with_dependency_annotations() {
+		let tasks = parse_tasks(
+			"## 1. Backend\n- [x] 1.1 Add schema\n- [ ] 1.2 Add endpoint (blocked by 1.1)\n\n## 2. Frontend\n- [ ] 2.1 Build form, depends on 1.2 and 1.1\n- [ ] Write docs\n",
+		);
+		assert_eq!(tasks.len(), 4);
+		assert_eq!(tasks[1].id, "1.2");
+		assert_eq!(tasks[1].depends_on, vec!["1.1"]);
+		assert_eq!(tasks[2].section.as_deref(), Some("2. Frontend"));
+		assert_eq!(tasks[2].depends_on, vec!["1.2", "1.1"]);
+		assert_eq!(tasks[3].id, "line-7");
+		assert!(tasks[0].done);
+	}
+
+	#[test]
+	fn orders_by_dependencies_and_flags_cycles() {
+		let tasks = parse_tasks(
+			"- [ ] 1 Ship (blocked by 3)\n- [x] 2 Design\n- [ ] 3 Build (blocked by 2)\n- [ ] 4 Loop a (blocked by 5)\n- [ ] 5 Loop b (blocked by 4, 9)\n",
+		);
+		let plan = plan_tasks(&tasks);
+		assert_eq!(plan.order, vec!["2", "3", "1", "4", "5"]);
+		assert_eq!(plan.actionable, vec!["3"]);
+		assert_eq!(plan.blocked, vec!["1", "4", "5"]);
+		assert_eq!(plan.cycles, vec![vec!["4".to_string(), "5".to_string()]]);
+		assert_eq!(
+			plan.missing,
+			vec![MissingDependency {
+				task: "5".to_string(),
+				dependency: "9".to_string(),
+			}]
+		);
+	}
+
+	#[test]
+	fn parses_estimates_and_builds_burndown_from_history() {
+		assert_eq!(parse_estimate("1.1 Add schema [2h]"), Some(120));
+		assert_eq!(parse_estimate("Docs [ 30m ] (blocked by 1.1)"), Some(30));
+		assert_eq!(parse_estimate("Rollout [0.5d]"), Some(240));
+		assert_eq!(parse_estimate("See [link] and [x]"), None);
+
+		let history = vec![
+			(
+				"a1".to_string(),
+				100,
+				"- [ ] 1 Schema [2h]\n- [ ] 2 Endpoint [1h]\n".to_string(),
+			),
+			(
+				"b2".to_string(),
+				200,
+				"- [x] 1 Schema [2h]\n- [ ] 2 Endpoint [1h]\n- [ ] 3 Docs\n".to_string(),
+			),
+		];
+		let current = "- [x] 1 Schema [2h]\n- [ ] 2 Endpoint [1h]\n- [ ] 3 Docs\n";
+		let points = burndown_points(&history, Some(current), 300);
+		assert_eq!(points.len(), 2);
+		assert_eq!(points[0].remaining_minutes, 180);
+		assert_eq!(points[1].remaining_minutes, 60);
+		assert_eq!(points[1].tasks_total, 3);
+
+		let points = burndown_points(
+			&history,
+			Some("- [x] 1 Schema [2h]\n- [x] 2 Endpoint [1h]\n"),
+			300,
+		);
+		assert_eq!(points.last().map(|point| point.commit.clone()), Some(None));
+		assert_eq!(points[2].remaining_minutes, 0);
+	}
+}
@@ -0,0 +1,219 @@
+use crate::{
+	changes::change_dir,
+	spec::{SpecError, SpecErrorPayload},
+	storage::{self, JsonStore},
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+use tauri::State;
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeEntry {
+	project: String,
+	change_id: String,
+	started_at: u64,
+	stopped_at: Option<u64>,
+}
+
+impl TimeEntry {
+	fn duration_secs(&self, now: u64) -> u64 {
+		self.stopped_at
+			.unwrap_or(now)
+			.saturating_sub(self.started_at)
+	}
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeTime {
+	change_id: String,
+	total_secs: u64,
+	sessions: usize,
+	running_since: Option<u64>,
+}
+
+#[derive(Clone, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeSummary {
+	project: String,
+	total_secs: u64,
+	changes: Vec<ChangeTime>,
+}
+
+pub type TimeEntries = JsonStore<Vec<TimeEntry>>;
+
+pub fn load_time_entries() -> TimeEntries {
+	TimeEntries::in_home("time-tracking.json")
+}
+
+fn is_running(entry: &TimeEntry, project: &str, change_id: &str) -> bool {
+	entry.stopped_at.is_none() && entry.project == project && entry.change_id == change_id
+}
+
+fn start(
+	store: &TimeEntries,
+	project: &str,
+	change_id: &str,
+	now: u64,
+) -> Result<TimeEntry, SpecError> {
+	change_dir(Path::new(project), change_id)?;
+	store
+		.update(|entries| {
+			if entries
+				.iter()
+				.any(|entry| is_running(entry, project, change_id))
+			{
+				return None;
+			}
+			let entry = TimeEntry {
+				project: project.to_string(),
+				change_id: change_id.to_string(),
+				started_at: now,
+				stopped_at: None,
+			};
+			entries.push(entry.clone());
+			Some(entry)
+		})?
+		.ok_or(SpecError::TimerAlreadyRunning)
+}
+
+fn stop(
+	store: &TimeEntries,
+	project: &str,
+	change_id: &str,
+	now: u64,
+) -> Result<TimeEntry, SpecError> {
+	store
+		.update(|entries| {
+			let entry = entries
+				.iter_mut()
+				.find(|entry| is_running(entry, project, change_id))?;
+			entry.stopped_at = Some(now.max(entry.started_at));
+			Some(entry.clone())
+		})?
+		.ok_or(SpecError::TimerNotRunning)
+}
+
+fn summarize(
+	entries: &[TimeEntry],
+	project: &str,
+	change_id: Option<&str>,
+	now: u64,
+) -> TimeSummary {
+	let mut changes = BTreeMap::<&str, ChangeTime>::new();
+	for entry in entries.iter().filter(|entry| {
+		entry.project == project && change_id.is_none_or(|change_id| entry.change_id == change_id)
+	}) {
+		let change = changes
+			.entry(entry.change_id.as_str())
+			.or_insert_with(|| ChangeTime {
+				change_id: entry.change_id.clone(),
+				total_secs: 0,
+				sessions: 0,
+				running_since: None,
+			});
+		change.total_secs += entry.duration_secs(now);
+		change.sessions += 1;
+		if entry.stopped_at.is_none() {
+			change.running_since = Some(entry.started_at);
+		}
+	}
+	let changes = changes.into_values().collect::<Vec<_>>();
+	TimeSummary {
+		project: project.to_string(),
+		total_secs: changes.iter().map(|change| change.total_secs).sum(),
+		changes,
+	}
+}
+
+#[tauri::command]
+pub fn start_timer(
+	entries: State<'_, TimeEntries>,
+	project: String,
+	change_id: String,
+) -> Result<TimeEntry, SpecErrorPayload> {
+	start(&entries, &project, &change_id, storage::now_secs()).map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn stop_timer(
+	entries: State<'_, TimeEntries>,
+	project: String,
+	change_id: String,
+) -> Result<TimeEntry, SpecErrorPayload> {
+	stop(&entries, &project, &change_id, storage::now_secs()).map_err(SpecErrorPayload::from)
+}
+
+#[tauri::command]
+pub fn time_summary(
+	entries: State<'_, TimeEntries>,
+	project: String,
+	change_id: Option<String>,
+) -> TimeSummary {
+	summarize(
+		&entries.get(),
+		&project,
+		change_id.as_deref(),
+		storage::now_secs(),
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs;
+	use tempfile::TempDir;
+
+	#[test]
+	fn tracks_sessions_per_change() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let project = temp_dir.path();
+		for change in ["add-login", "add-billing"] {
+			fs::create_dir_all(project.join("openspec/changes").join(change))
+				.expect("create change");
+		}
+		let project = project.to_string_lossy().to_string();
+		let store = TimeEntries::load(None);
+
+		start(&store, &project, "add-login", 100).expect("start");
+		assert!(matches!(
+			start(&store, &project, "add-login", 110),
+			Err(SpecError::TimerAlreadyRunning)
+		));
+		stop(&store, &project, "add-login", 160).expect("stop");
+		assert!(matches!(
+			stop(&store, &project, "add-login", 170),
+			Err(SpecError::TimerNotRunning)
+		));
+		start(&store, &project, "add-login", 200).expect("restart");
+		start(&store, &project, "add-billing", 300).expect("start billing");
+		stop(&store, &project, "add-billing", 330).expect("stop billing");
+		assert!(matches!(
+			start(&store, &project, "missing", 400),
+			Err(SpecError::ChangeNotFound)
+		));
+
+		let summary = summarize(&store.get(), &project, None, 260);
+		assert_eq!(summary.total_secs, 60 + 60 + 30);
+		assert_eq!(
+			summary.changes,
+			vec![
+				ChangeTime {
+					change_id: "add-billing".to_string(),
+					total_secs: 30,
+					sessions: 1,
+					running_since: None,
+				},
+				ChangeTime {
+					change_id: "add-login".to_string(),
+					total_secs: 120,
+					sessions: 2,
+					running_since: Some(200),
+				},
+			]
+		);
+		let single = summarize(&store.get(), &project, Some("add-billing"), 260);
+		assert_eq!(single.total_secs, 30);
+	}
+}
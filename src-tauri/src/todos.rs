@@ -0,0 +1,258 @@
+use crate::{
+	changes::change_dir,
+	ignore::{self, IgnoreRules},
+	ordering,
+	settings::SettingsStore,
+	spec::{openspec_root, SourceText, SpecError, SpecErrorPayload, MAX_PARSE_BYTES},
+	tasks::parse_tasks,
+};
+use serde::Serialize;
+use std::{
+	collections::BTreeSet,
+	fs,
+	path::{Path, PathBuf},
+};
+use tauri::State;
+
+const MARKERS: [&str; 2] = ["TODO", "FIXME"];
+const MAX_SCAN_BYTES: u64 = 1024 * 1024;
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeTodo {
+	path: String,
+	line: usize,
+	marker: &'static str,
+	text: String,
+	task_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodeTodoScan {
+	change_id: String,
+	files_scanned: usize,
+	todos: Vec<CodeTodo>,
+}
+
+fn is_word(c: char) -> bool {
+	c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+// Positions where `needle` appears as a whole token, so `add-login` does not
+// match inside `add-login-form`.
+fn token_positions<'a>(haystack: &'a str, needle: &'a str) -> impl Iterator<Item = usize> + 'a {
+	haystack
+		.match_indices(needle)
+		.filter_map(move |(start, _)| {
+			let before = haystack[..start].chars().next_back();
+			let after = haystack[start + needle.len()..].chars().next();
+			(!before.is_some_and(is_word) && !after.is_some_and(is_word)).then_some(start)
+		})
+}
+
+fn find_marker(line: &str) -> Option<(usize, &'static str)> {
+	MARKERS
+		.iter()
+		.filter_map(|marker| {
+			token_positions(line, marker)
+				.next()
+				.map(|start| (start, *marker))
+		})
+		.min()
+}
+
+fn comment_text(text: &str) -> String {
+	text.trim()
+		.trim_end_matches("*/")
+		.trim_end_matches("-->")
+		.trim()
+		.to_string()
+}
+
+fn linked_task(text: &str, task_ids: &BTreeSet<String>) -> Option<String> {
+	text.split(|c: char| !c.is_ascii_digit() && c != '.')
+		.map(|word| word.trim_matches('.'))
+		.find(|word| task_ids.contains(*word))
+		.map(str::to_string)
+}
+
+pub fn todos_in_source(
+	text: &str,
+	change_id: &str,
+	task_ids: &BTreeSet<String>,
+) -> Vec<(usize, &'static str, String, Option<String>)> {
+	text.lines()
+		.enumerate()
+		.filter_map(|(index, line)| {
+			let (start, marker) = find_marker(line)?;
+			let comment = &line[start..];
+			token_positions(comment, change_id).next()?;
+			let text = comment_text(comment);
+			let task_id = linked_task(&text, task_ids);
+			Some((index + 1, marker, text, task_id))
+		})
+		.collect()
+}
+
+fn source_files(
+	dir: &Path,
+	skip: &dyn Fn(&Path) -> bool,
+	files: &mut Vec<PathBuf>,
+) -> Result<(), SpecError> {
+	for entry in fs::read_dir(dir)? {
+		let entry = entry?;
+		let path = entry.path();
+		if skip(&path) {
+			continue;
+		}
+		// Symlinked directories are not followed so a link back up the tree
+		// cannot loop the scan.
+		let file_type = entry.file_type()?;
+		if file_type.is_dir() {
+			source_files(&path, skip, files)?;
+		} else if file_type.is_file() {
+			files.push(path);
+		}
+	}
+	Ok(())
+}
+
+fn read_source(path: &Path) -> Option<String> {
+	let metadata = fs::metadata(path).ok()?;
+	if metadata.len() > MAX_SCAN_BYTES {
+		return None;
+	}
+	fs::read_to_string(path)
+		.ok()
+		.filter(|text| !text.contains('\0'))
+}
+
+pub fn scan_todos(
+	project: &Path,
+	change_id: &str,
+	ignore: &IgnoreRules,
+) -> Result<CodeTodoScan, SpecError> {
+	let change = change_dir(project, change_id)?;
+	let task_ids = SourceText::read_limited(&change.join("tasks.md"), MAX_PARSE_BYTES)
+		.map(|tasks| parse_tasks(&tasks.render()))
+		.unwrap_or_default()
+		.into_iter()
+		.map(|task| task.id)
+		.filter(|id| !id.starts_with("line-"))
+		.collect::<BTreeSet<_>>();
+	// The change's own proposal and tasks mention its id everywhere; only
+	// implementation code is of interest.
+	let spec_root = openspec_root(project);
+	let skip = |path: &Path| path.starts_with(&spec_root) || ignore.is_ignored(project, path);
+	let mut files = Vec::new();
+	source_files(project, &skip, &mut files)?;
+	files.sort_by(|a, b| ordering::compare_paths(a, b));
+
+	let mut todos = Vec::new();
+	let mut files_scanned = 0;
+	for path in files {
+		let Some(text) = read_source(&path) else {
+			continue;
+		};
+		files_scanned += 1;
+		let relative = path
+			.strip_prefix(project)
+			.unwrap_or(&path)
+			.to_string_lossy()
+			.replace('\\', "/");
+		todos.extend(
+			todos_in_source(&text, change_id, &task_ids)
+				.into_iter()
+				.map(|(line, marker, text, task_id)| CodeTodo {
+					path: relative.clone(),
+					line,
+					marker,
+					text,
+					task_id,
+				}),
+		);
+	}
+	Ok(CodeTodoScan {
+		change_id: change_id.to_string(),
+		files_scanned,
+		todos,
+	})
+}
+
+#[tauri::command]
+pub fn scan_code_todos(
+	settings: State<'_, SettingsStore>,
+	project: String,
+	change_id: String,
+) -> Result<CodeTodoScan, SpecErrorPayload> {
+	let project = Path::new(&project);
+	scan_todos(project, &change_id, &ignore::rules_for(&settings, project))
+		.map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn matches_markers_that_reference_the_change() {
+		let task_ids = BTreeSet::from(["1.1", "1.2"].map(str::to_string));
+		let source = "fn login() {\n\t// TODO(add-login): persist the session, see 1.2\n\t// TODO(add-login-form): style\n\t/* FIXME add-login */\n\t// todo add-login\n\tlet autodone = \"add-login\"; // TODOS add-login\n}\n";
+		assert_eq!(
+			todos_in_source(source, "add-login", &task_ids),
+			[
+				(
+					2,
+					"TODO",
+					"TODO(add-login): persist the session, see 1.2".to_string(),
+					Some("1.2".to_string())
+				),
+				(4, "FIXME", "FIXME add-login".to_string(), None),
+			]
+		);
+	}
+
+	#[test]
+	fn scans_working_tree_outside_openspec() {
+		let project = TempDir::new().expect("create project dir");
+		let change = project.path().join("openspec/changes/add-login");
+		fs::create_dir_all(&change).expect("create change");
+		fs::write(
+			change.join("tasks.md"),
+			"## 1. Implementation\n- [ ] 1.1 Form\n- [ ] 1.2 Session\n",
+		)
+		.expect("write tasks");
+		fs::write(change.join("proposal.md"), "TODO add-login\n").expect("write proposal");
+		let src = project.path().join("src");
+		fs::create_dir_all(&src).expect("create src");
+		fs::write(src.join("b.rs"), "// FIXME(add-login) 1.1 validate input\n").expect("write b");
+		fs::write(
+			src.join("a.py"),
+			"x = 1\n# TODO add-login: expire sessions\n",
+		)
+		.expect("write a");
+		fs::create_dir_all(project.path().join("node_modules/dep")).expect("create deps");
+		fs::write(
+			project.path().join("node_modules/dep/index.js"),
+			"// TODO add-login\n",
+		)
+		.expect("write dep");
+		fs::write(src.join("blob.bin"), b"TODO add-login\0").expect("write blob");
+
+		let scan = scan_todos(project.path(), "add-login", &IgnoreRules::default()).expect("scan");
+		assert_eq!(scan.files_scanned, 2);
+		assert_eq!(
+			scan.todos
+				.iter()
+				.map(|todo| (todo.path.as_str(), todo.line, todo.task_id.as_deref()))
+				.collect::<Vec<_>>(),
+			[("src/a.py", 2, None), ("src/b.rs", 1, Some("1.1"))]
+		);
+		assert!(matches!(
+			scan_todos(project.path(), "missing", &IgnoreRules::default()),
+			Err(SpecError::ChangeNotFound)
+		));
+	}
+}
@@ -0,0 +1,925 @@
+use crate::{
+	changes::change_dir,
+	diff::unified_diff,
+	journal::collect_paths,
+	launcher, ordering, run_journaled_command,
+	spec::{
+		markdown_files, openspec_root, parse_heading, SourceText, SpecError, SpecErrorPayload,
+		MAX_PARSE_BYTES,
+	},
+	storage,
+	tasks::parse_tasks,
+	CommandRunOutput, OpenSpecCommandError, OpenSpecCommandErrorPayload,
+};
+use serde::Serialize;
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	env,
+	ffi::OsStr,
+	fs,
+	path::{Path, PathBuf},
+	process::{Command, Stdio},
+};
+use tauri::Window;
+
+pub const CONFIG_PATHS: &[&str] = &[
+	"openspec",
+	"AGENTS.md",
+	"CLAUDE.md",
+	".claude",
+	".cursor",
+	".windsurf",
+	".opencode",
+	".gemini",
+	".kilocode",
+	".roo",
+	".clinerules",
+	".amazonq",
+	".qwen",
+	".augment",
+	".codebuddy",
+	".crush",
+	".factory",
+	".github/prompts",
+];
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum FileChangeKind {
+	Added,
+	Modified,
+	Removed,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolFileChange {
+	path: String,
+	change: FileChangeKind,
+	diff: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolUpdatePreview {
+	tools: Vec<String>,
+	changes: Vec<ToolFileChange>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstructionUpdate {
+	#[serde(flatten)]
+	output: CommandRunOutput,
+	files: Vec<ToolFileChange>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstructionDrift {
+	path: String,
+	drifted: bool,
+	diff: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstructionDriftReport {
+	cli_version: Option<String>,
+	files: Vec<InstructionDrift>,
+	remediation: Option<String>,
+}
+
+const INSTRUCTION_FILES: &[&str] = &["AGENTS.md", "CLAUDE.md", "openspec/AGENTS.md"];
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum LaunchStyle {
+	Editor,
+	Terminal,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolLauncher {
+	tool_id: &'static str,
+	command: &'static str,
+	style: LaunchStyle,
+	installed: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolLaunch {
+	tool_id: String,
+	command: String,
+	style: LaunchStyle,
+}
+
+const LAUNCHERS: &[(&str, &str, LaunchStyle)] = &[
+	("cursor", "cursor", LaunchStyle::Editor),
+	("windsurf", "windsurf", LaunchStyle::Editor),
+	("github-copilot", "code", LaunchStyle::Editor),
+	("kilocode", "code", LaunchStyle::Editor),
+	("roocode", "code", LaunchStyle::Editor),
+	("cline", "code", LaunchStyle::Editor),
+	("claude", "claude", LaunchStyle::Terminal),
+	("codex", "codex", LaunchStyle::Terminal),
+	("gemini", "gemini", LaunchStyle::Terminal),
+	("opencode", "opencode", LaunchStyle::Terminal),
+	("qwen", "qwen", LaunchStyle::Terminal),
+	("amazon-q", "q", LaunchStyle::Terminal),
+	("auggie", "auggie", LaunchStyle::Terminal),
+	("codebuddy", "codebuddy", LaunchStyle::Terminal),
+	("crush", "crush", LaunchStyle::Terminal),
+	("factory", "droid", LaunchStyle::Terminal),
+];
+
+fn launcher(tool_id: &str) -> Option<(&'static str, &'static str, LaunchStyle)> {
+	LAUNCHERS.iter().copied().find(|(id, _, _)| *id == tool_id)
+}
+
+struct ToolDefinition {
+	id: &'static str,
+	name: &'static str,
+	config_files: &'static [&'static str],
+	docs_url: &'static str,
+}
+
+const fn tool(
+	id: &'static str,
+	name: &'static str,
+	config_files: &'static [&'static str],
+	docs_url: &'static str,
+) -> ToolDefinition {
+	ToolDefinition {
+		id,
+		name,
+		config_files,
+		docs_url,
+	}
+}
+
+// Tools `openspec init --tools` accepts and where each one's instructions
+// land. Keep in step with OpenSpec releases; the CLI's own list still decides
+// what the installed version supports.
+const TOOL_REGISTRY: &[ToolDefinition] = &[
+	tool("agents", "AGENTS.md", &["AGENTS.md"], "https://agents.md"),
+	tool(
+		"amazon-q",
+		"Amazon Q Developer",
+		&[".amazonq/prompts"],
+		"https://docs.aws.amazon.com/amazonq/",
+	),
+	tool(
+		"auggie",
+		"Auggie (Augment CLI)",
+		&[".augment/commands"],
+		"https://docs.augmentcode.com",
+	),
+	tool(
+		"claude",
+		"Claude Code",
+		&["CLAUDE.md", ".claude/commands/openspec"],
+		"https://docs.anthropic.com/en/docs/claude-code",
+	),
+	tool("cline", "Cline", &[".clinerules"], "https://docs.cline.bot"),
+	tool(
+		"codebuddy",
+		"CodeBuddy",
+		&[".codebuddy/commands/openspec"],
+		"https://www.codebuddy.ai",
+	),
+	tool(
+		"codex",
+		"Codex",
+		&["$CODEX_HOME/prompts"],
+		"https://github.com/openai/codex",
+	),
+	tool(
+		"crush",
+		"Crush",
+		&[".crush/commands/openspec"],
+		"https://github.com/charmbracelet/crush",
+	),
+	tool(
+		"cursor",
+		"Cursor",
+		&[".cursor/commands"],
+		"https://docs.cursor.com",
+	),
+	tool(
+		"factory",
+		"Factory Droid",
+		&[".factory/commands"],
+		"https://docs.factory.ai",
+	),
+	tool(
+		"gemini",
+		"Gemini CLI",
+		&[".gemini/commands/openspec"],
+		"https://github.com/google-gemini/gemini-cli",
+	),
+	tool(
+		"github-copilot",
+		"GitHub Copilot",
+		&[".github/prompts"],
+		"https://docs.github.com/copilot",
+	),
+	tool(
+		"kilocode",
+		"Kilo Code",
+		&[".kilocode/workflows"],
+		"https://kilocode.ai/docs",
+	),
+	tool(
+		"opencode",
+		"OpenCode",
+		&[".opencode/command"],
+		"https://opencode.ai/docs",
+	),
+	tool(
+		"qwen",
+		"Qwen Code",
+		&[".qwen/commands"],
+		"https://github.com/QwenLM/qwen-code",
+	),
+	tool(
+		"roocode",
+		"RooCode",
+		&[".roo/commands"],
+		"https://docs.roocode.com",
+	),
+	tool(
+		"windsurf",
+		"Windsurf",
+		&[".windsurf/workflows"],
+		"https://docs.windsurf.com",
+	),
+];
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolDetails {
+	id: String,
+	name: String,
+	config_files: Vec<String>,
+	docs_url: Option<String>,
+	known: bool,
+	supported: bool,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetailedTools {
+	tools: Vec<ToolDetails>,
+	cli_reported: bool,
+}
+
+pub fn registry_tool_ids() -> Vec<String> {
+	TOOL_REGISTRY
+		.iter()
+		.map(|tool| tool.id.to_string())
+		.collect()
+}
+
+/// Joins the registry with the tools the installed CLI reports. Tools the CLI
+/// has not caught up with are marked unsupported, and ids the registry does
+/// not know yet still appear with bare metadata. Without a CLI list every
+/// registry entry is assumed supported.
+pub fn reconcile(reported: Option<&[String]>) -> DetailedTools {
+	let supported =
+		|id: &str| reported.is_none_or(|reported| reported.iter().any(|tool| tool == id));
+	let mut tools = TOOL_REGISTRY
+		.iter()
+		.map(|tool| ToolDetails {
+			id: tool.id.to_string(),
+			name: tool.name.to_string(),
+			config_files: tool
+				.config_files
+				.iter()
+				.map(|path| path.to_string())
+				.collect(),
+			docs_url: Some(tool.docs_url.to_string()),
+			known: true,
+			supported: supported(tool.id),
+		})
+		.collect::<Vec<_>>();
+	for id in reported.unwrap_or_default() {
+		if !TOOL_REGISTRY.iter().any(|tool| tool.id == id) {
+			tools.push(ToolDetails {
+				id: id.clone(),
+				name: id.clone(),
+				config_files: Vec::new(),
+				docs_url: None,
+				known: false,
+				supported: true,
+			});
+		}
+	}
+	tools.sort_by(|a, b| ordering::compare(&a.id, &b.id));
+	DetailedTools {
+		tools,
+		cli_reported: reported.is_some(),
+	}
+}
+
+pub fn find_on_path(command: &str, path_var: &OsStr) -> Option<PathBuf> {
+	let extensions = if cfg!(windows) {
+		vec!["exe", "cmd", "bat"]
+	} else {
+		vec![""]
+	};
+	env::split_paths(path_var).find_map(|dir| {
+		extensions.iter().find_map(|extension| {
+			let candidate = dir.join(command).with_extension(extension);
+			candidate.is_file().then_some(candidate)
+		})
+	})
+}
+
+fn is_installed(command: &str) -> bool {
+	env::var_os("PATH").is_some_and(|path| find_on_path(command, &path).is_some())
+}
+
+#[cfg(target_os = "macos")]
+fn terminal_command(project: &Path, command: &str) -> Command {
+	let script = format!(
+		"tell application \"Terminal\" to do script \"cd '{}' && {command}\"",
+		project.to_string_lossy().replace('\'', "'\\\\''")
+	);
+	let mut terminal = Command::new("osascript");
+	terminal.arg("-e").arg(script);
+	terminal
+}
+
+#[cfg(windows)]
+fn terminal_command(project: &Path, command: &str) -> Command {
+	let mut terminal = Command::new("cmd");
+	terminal
+		.args(["/C", "start", "", "/D"])
+		.arg(project)
+		.args(["cmd", "/K", command]);
+	terminal
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn terminal_command(project: &Path, command: &str) -> Command {
+	let emulator = ["x-terminal-emulator", "gnome-terminal", "konsole", "xterm"]
+		.into_iter()
+		.find(|emulator| is_installed(emulator))
+		.unwrap_or("xterm");
+	let mut terminal = Command::new(emulator);
+	if emulator == "gnome-terminal" {
+		terminal.arg("--").arg(command);
+	} else {
+		terminal.arg("-e").arg(command);
+	}
+	terminal.current_dir(project);
+	terminal
+}
+
+fn launch(project: &Path, tool_id: &str) -> Result<ToolLaunch, SpecError> {
+	let (tool_id, command, style) = launcher(tool_id).ok_or(SpecError::ToolNotSupported)?;
+	if !is_installed(command) {
+		return Err(SpecError::ToolNotInstalled);
+	}
+	let mut process = match style {
+		LaunchStyle::Editor => {
+			let mut editor = Command::new(command);
+			editor.arg(project);
+			editor
+		}
+		LaunchStyle::Terminal => terminal_command(project, command),
+	};
+	process
+		.stdin(Stdio::null())
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.spawn()?;
+	Ok(ToolLaunch {
+		tool_id: tool_id.to_string(),
+		command: command.to_string(),
+		style,
+	})
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KickoffPrompt {
+	change_id: String,
+	tool_id: String,
+	prompt: String,
+}
+
+fn mention(tool_id: &str, path: &str) -> String {
+	match tool_id {
+		"claude" | "cursor" | "windsurf" | "gemini" | "opencode" | "qwen" => format!("@{path}"),
+		"github-copilot" => format!("#file:{path}"),
+		_ => format!("`{path}`"),
+	}
+}
+
+fn apply_command(tool_id: &str, change_id: &str) -> Option<String> {
+	match tool_id {
+		"claude" => Some(format!("/openspec:apply {change_id}")),
+		"cursor" | "windsurf" | "codex" | "opencode" => {
+			Some(format!("/openspec-apply {change_id}"))
+		}
+		_ => None,
+	}
+}
+
+fn conventions(project_md: &str) -> Option<String> {
+	let mut section = None;
+	let mut lines = Vec::new();
+	for line in project_md.lines() {
+		match (parse_heading(line), section) {
+			(Some((level, text)), None) if text.contains("Conventions") => section = Some(level),
+			(Some((level, _)), Some(open)) if level <= open => break,
+			(_, Some(_)) => lines.push(line),
+			_ => {}
+		}
+	}
+	let text = lines.join("\n").trim().to_string();
+	(!text.is_empty()).then_some(text)
+}
+
+fn relative(project: &Path, path: &Path) -> String {
+	path.strip_prefix(project)
+		.unwrap_or(path)
+		.to_string_lossy()
+		.replace('\\', "/")
+}
+
+fn kickoff_prompt(project: &Path, change_id: &str, tool_id: &str) -> Result<String, SpecError> {
+	let dir = change_dir(project, change_id)?;
+	let mut out = format!("# Implement OpenSpec change `{change_id}`\n\n");
+	if let Some(command) = apply_command(tool_id, change_id) {
+		out.push_str(&format!(
+			"Start with `{command}`, then follow the steps below.\n\n"
+		));
+	}
+
+	out.push_str("## Read first\n");
+	let mut references = ["proposal.md", "design.md", "tasks.md"]
+		.iter()
+		.map(|name| dir.join(name))
+		.filter(|path| path.is_file())
+		.collect::<Vec<_>>();
+	if dir.join("specs").is_dir() {
+		references.extend(markdown_files(&dir.join("specs"))?);
+	}
+	let project_md = openspec_root(project).join("project.md");
+	if project_md.is_file() {
+		references.push(project_md.clone());
+	}
+	for path in &references {
+		out.push_str(&format!(
+			"- {}\n",
+			mention(tool_id, &relative(project, path))
+		));
+	}
+
+	if let Some(conventions) = SourceText::read_limited(&project_md, MAX_PARSE_BYTES)
+		.ok()
+		.and_then(|source| conventions(&source.render()))
+	{
+		out.push_str(&format!("\n## Project conventions\n{conventions}\n"));
+	}
+
+	let open_tasks = SourceText::read_limited(&dir.join("tasks.md"), MAX_PARSE_BYTES)
+		.map(|source| parse_tasks(&source.render()))
+		.unwrap_or_default()
+		.into_iter()
+		.filter(|task| !task.done)
+		.collect::<Vec<_>>();
+	if !open_tasks.is_empty() {
+		out.push_str("\n## Open tasks\n");
+		for task in open_tasks {
+			out.push_str(&format!("- [ ] {} {}\n", task.id, task.title));
+		}
+	}
+
+	out.push_str(&format!(
+		"\n## Working agreement\n- Work through the tasks in order and tick each one off in `tasks.md` as soon as it is done.\n- Stay within the scope of the proposal; ask before changing requirements.\n- Run `openspec validate {change_id} --strict` before you finish.\n"
+	));
+	Ok(out)
+}
+
+fn config_paths() -> Vec<String> {
+	CONFIG_PATHS.iter().map(|path| path.to_string()).collect()
+}
+
+fn copy_config(project: &Path, dest: &Path) -> Result<(), SpecError> {
+	let (files, _) = collect_paths(project, &config_paths())?;
+	for file in files {
+		let target = dest.join(&file);
+		if let Some(parent) = target.parent() {
+			fs::create_dir_all(parent)?;
+		}
+		fs::copy(project.join(&file), target)?;
+	}
+	Ok(())
+}
+
+fn update_args(workspace: &Path, tools: &[String]) -> Vec<String> {
+	let workspace = workspace.to_string_lossy().to_string();
+	if tools.is_empty() {
+		vec!["update".to_string(), workspace]
+	} else {
+		vec![
+			"init".to_string(),
+			workspace,
+			"--tools".to_string(),
+			tools.join(","),
+		]
+	}
+}
+
+// Runs the CLI the project itself would use, so previews match what
+// `openspec update` would write there.
+fn run_openspec(project: &Path, args: &[String]) -> Result<(), SpecError> {
+	let launcher = launcher::resolve(Some(project));
+	let output = launcher
+		.command(Some(project), args)?
+		.output()
+		.map_err(|error| launcher.spawn_error(error))?;
+	if output.status.success() {
+		Ok(())
+	} else {
+		Err(SpecError::CliCommandFailed)
+	}
+}
+
+fn read_lossy(path: &Path) -> String {
+	fs::read(path)
+		.map(|bytes| String::from_utf8_lossy(&bytes).to_string())
+		.unwrap_or_default()
+}
+
+fn read_config(root: &Path) -> Result<BTreeMap<String, String>, SpecError> {
+	let (files, _) = collect_paths(root, &config_paths())?;
+	Ok(files
+		.into_iter()
+		.map(|path| {
+			let text = read_lossy(&root.join(&path));
+			(path, text)
+		})
+		.collect())
+}
+
+fn config_changes(
+	before: &BTreeMap<String, String>,
+	after: &BTreeMap<String, String>,
+) -> Vec<ToolFileChange> {
+	let paths = before.keys().chain(after.keys()).collect::<BTreeSet<_>>();
+	let mut changes = Vec::new();
+	for path in paths {
+		let (old, new) = (before.get(path), after.get(path));
+		let change = match (old, new) {
+			(None, _) => FileChangeKind::Added,
+			(_, None) => FileChangeKind::Removed,
+			_ if old == new => continue,
+			_ => FileChangeKind::Modified,
+		};
+		changes.push(ToolFileChange {
+			diff: unified_diff(
+				old.map_or("", String::as_str),
+				new.map_or("", String::as_str),
+				path,
+			),
+			path: path.clone(),
+			change,
+		});
+	}
+	changes
+}
+
+fn compare_config(project: &Path, workspace: &Path) -> Result<Vec<ToolFileChange>, SpecError> {
+	Ok(config_changes(
+		&read_config(project)?,
+		&read_config(workspace)?,
+	))
+}
+
+fn preview_in(
+	project: &Path,
+	workspace: &Path,
+	tools: &[String],
+	run: impl FnOnce(&[String]) -> Result<(), SpecError>,
+) -> Result<Vec<ToolFileChange>, SpecError> {
+	copy_config(project, workspace)?;
+	run(&update_args(workspace, tools))?;
+	compare_config(project, workspace)
+}
+
+fn drift_in(
+	project: &Path,
+	workspace: &Path,
+	run: impl FnOnce(&[String]) -> Result<(), SpecError>,
+) -> Result<Vec<InstructionDrift>, SpecError> {
+	let changes = preview_in(project, workspace, &[], run)?;
+	Ok(INSTRUCTION_FILES
+		.iter()
+		.filter(|path| project.join(path).is_file())
+		.map(|path| {
+			let change = changes
+				.iter()
+				.find(|change| change.path == *path && change.change == FileChangeKind::Modified);
+			InstructionDrift {
+				path: path.to_string(),
+				drifted: change.is_some(),
+				diff: change.map(|change| change.diff.clone()),
+			}
+		})
+		.collect())
+}
+
+fn preview_workspace() -> PathBuf {
+	env::temp_dir().join(storage::new_id("specops-tool-preview"))
+}
+
+#[tauri::command(async)]
+pub fn preview_tool_update(
+	project: String,
+	tools: Vec<String>,
+) -> Result<ToolUpdatePreview, SpecErrorPayload> {
+	let tools = tools
+		.into_iter()
+		.map(|tool| tool.trim().to_string())
+		.filter(|tool| !tool.is_empty())
+		.collect::<Vec<_>>();
+	let workspace = preview_workspace();
+	let project = Path::new(&project);
+	let changes = preview_in(project, &workspace, &tools, |args| {
+		run_openspec(project, args)
+	});
+	let _ = fs::remove_dir_all(&workspace);
+	Ok(ToolUpdatePreview {
+		tools,
+		changes: changes.map_err(SpecErrorPayload::from)?,
+	})
+}
+
+#[tauri::command(async)]
+pub fn openspec_update(
+	window: Window,
+	project: String,
+	env: Option<BTreeMap<String, String>>,
+) -> Result<InstructionUpdate, OpenSpecCommandErrorPayload> {
+	let project = Path::new(&project);
+	let before = read_config(project).map_err(OpenSpecCommandError::from)?;
+	let output = run_journaled_command(
+		&window,
+		&env.unwrap_or_default(),
+		"update",
+		Some(project),
+		CONFIG_PATHS,
+		"openspec",
+		&update_args(project, &[]),
+	)?;
+	let after = read_config(project).map_err(OpenSpecCommandError::from)?;
+	Ok(InstructionUpdate {
+		output,
+		files: config_changes(&before, &after),
+	})
+}
+
+#[tauri::command(async)]
+pub fn check_instruction_drift(
+	project: String,
+) -> Result<InstructionDriftReport, SpecErrorPayload> {
+	let workspace = preview_workspace();
+	let project = Path::new(&project);
+	let files = drift_in(project, &workspace, |args| run_openspec(project, args));
+	let _ = fs::remove_dir_all(&workspace);
+	let files = files.map_err(SpecErrorPayload::from)?;
+	Ok(InstructionDriftReport {
+		cli_version: launcher::version(Some(project)),
+		remediation: files
+			.iter()
+			.any(|file| file.drifted)
+			.then(|| "openspec update".to_string()),
+		files,
+	})
+}
+
+#[tauri::command]
+pub fn generate_kickoff_prompt(
+	project: String,
+	change_id: String,
+	tool_id: String,
+) -> Result<KickoffPrompt, SpecErrorPayload> {
+	let prompt = kickoff_prompt(Path::new(&project), &change_id, tool_id.trim())?;
+	Ok(KickoffPrompt {
+		change_id,
+		tool_id,
+		prompt,
+	})
+}
+
+#[tauri::command]
+pub fn tool_launchers() -> Vec<ToolLauncher> {
+	LAUNCHERS
+		.iter()
+		.map(|(tool_id, command, style)| ToolLauncher {
+			tool_id,
+			command,
+			style: *style,
+			installed: is_installed(command),
+		})
+		.collect()
+}
+
+#[tauri::command]
+pub fn launch_tool(project: String, tool_id: String) -> Result<ToolLaunch, SpecErrorPayload> {
+	let project = Path::new(&project);
+	if !project.is_dir() {
+		return Err(SpecError::ProjectNotFound.into());
+	}
+	launch(project, tool_id.trim()).map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn reconciles_registry_with_cli_reported_tools() {
+		let reported = ["claude", "cursor", "newtool"].map(String::from);
+		let detailed = reconcile(Some(&reported[..]));
+		assert!(detailed.cli_reported);
+		let find = |id: &str| detailed.tools.iter().find(|tool| tool.id == id);
+		let claude = find("claude").expect("claude");
+		assert!(claude.known && claude.supported);
+		assert_eq!(
+			claude.config_files,
+			["CLAUDE.md", ".claude/commands/openspec"]
+		);
+		assert!(!find("windsurf").expect("windsurf").supported);
+		let unknown = find("newtool").expect("newtool");
+		assert!(!unknown.known && unknown.supported && unknown.docs_url.is_none());
+
+		let offline = reconcile(None);
+		assert!(!offline.cli_reported);
+		assert_eq!(offline.tools.len(), TOOL_REGISTRY.len());
+		assert!(offline.tools.iter().all(|tool| tool.supported));
+	}
+
+	#[test]
+	fn diffs_tool_files_written_in_the_workspace_copy() {
+		let project = TempDir::new().expect("create project dir");
+		let workspace = TempDir::new().expect("create workspace dir");
+		fs::write(project.path().join("CLAUDE.md"), "old\n").expect("write claude");
+		fs::write(project.path().join("AGENTS.md"), "same\n").expect("write agents");
+		fs::write(project.path().join("README.md"), "ignored\n").expect("write readme");
+
+		let changes = preview_in(
+			project.path(),
+			workspace.path(),
+			&["cursor".to_string()],
+			|args| {
+				assert_eq!(args[0], "init");
+				assert_eq!(args[3], "cursor");
+				let root = Path::new(&args[1]);
+				fs::write(root.join("CLAUDE.md"), "new\n")?;
+				fs::create_dir_all(root.join(".cursor/commands"))?;
+				fs::write(root.join(".cursor/commands/openspec.md"), "stub\n")?;
+				Ok(())
+			},
+		)
+		.expect("preview");
+
+		let summary = changes
+			.iter()
+			.map(|change| (change.path.as_str(), change.change))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			summary,
+			vec![
+				(".cursor/commands/openspec.md", FileChangeKind::Added),
+				("CLAUDE.md", FileChangeKind::Modified),
+			]
+		);
+		assert!(changes[1].diff.contains("+new"));
+		assert_eq!(
+			fs::read_to_string(project.path().join("CLAUDE.md")).expect("read claude"),
+			"old\n"
+		);
+	}
+
+	#[test]
+	fn reports_files_rewritten_in_place() {
+		let project = TempDir::new().expect("create project dir");
+		fs::write(project.path().join("AGENTS.md"), "old\n").expect("write agents");
+		fs::write(project.path().join("CLAUDE.md"), "same\n").expect("write claude");
+		let before = read_config(project.path()).expect("read before");
+
+		fs::write(project.path().join("AGENTS.md"), "new\n").expect("rewrite agents");
+		fs::create_dir_all(project.path().join(".cursor")).expect("create cursor dir");
+		fs::write(project.path().join(".cursor/openspec.md"), "cursor\n").expect("write cursor");
+		let after = read_config(project.path()).expect("read after");
+
+		let changes = config_changes(&before, &after);
+		assert_eq!(
+			changes
+				.iter()
+				.map(|change| (change.path.as_str(), change.change))
+				.collect::<Vec<_>>(),
+			[
+				(".cursor/openspec.md", FileChangeKind::Added),
+				("AGENTS.md", FileChangeKind::Modified),
+			]
+		);
+		assert!(changes[1].diff.contains("+new"));
+	}
+
+	#[test]
+	fn detects_launchers_on_the_search_path() {
+		let bin = TempDir::new().expect("create bin dir");
+		let name = if cfg!(windows) {
+			"cursor.exe"
+		} else {
+			"cursor"
+		};
+		fs::write(bin.path().join(name), "").expect("write launcher");
+		let path_var = env::join_paths([bin.path()]).expect("join paths");
+
+		assert!(find_on_path("cursor", &path_var).is_some());
+		assert!(find_on_path("claude", &path_var).is_none());
+		assert_eq!(
+			launcher("claude").map(|(_, command, style)| (command, style)),
+			Some(("claude", LaunchStyle::Terminal))
+		);
+		assert!(matches!(
+			launch(bin.path(), "notepad"),
+			Err(SpecError::ToolNotSupported)
+		));
+	}
+
+	#[test]
+	fn kickoff_prompt_references_change_files_and_conventions() {
+		let project = TempDir::new().expect("create project dir");
+		let change = project.path().join("openspec/changes/add-login");
+		fs::create_dir_all(change.join("specs/auth")).expect("create change");
+		fs::write(change.join("proposal.md"), "# Add login\n").expect("write proposal");
+		fs::write(
+			change.join("tasks.md"),
+			"## 1. Build\n- [x] 1.1 Schema\n- [ ] 1.2 Endpoint\n",
+		)
+		.expect("write tasks");
+		fs::write(change.join("specs/auth/spec.md"), "## ADDED Requirements\n")
+			.expect("write delta");
+		fs::write(
+			project.path().join("openspec/project.md"),
+			"# Project\n\n## Project Conventions\n### Code Style\nUse tabs.\n\n## Domain Context\nBanking.\n",
+		)
+		.expect("write project");
+
+		let prompt = kickoff_prompt(project.path(), "add-login", "claude").expect("prompt");
+		assert!(prompt.contains("`/openspec:apply add-login`"));
+		assert!(prompt.contains("- @openspec/changes/add-login/proposal.md\n"));
+		assert!(prompt.contains("- @openspec/changes/add-login/specs/auth/spec.md\n"));
+		assert!(prompt.contains("### Code Style\nUse tabs.\n"));
+		assert!(!prompt.contains("Banking"));
+		assert!(prompt.contains("- [ ] 1.2 Endpoint\n"));
+		assert!(!prompt.contains("1.1 Schema"));
+
+		let prompt = kickoff_prompt(project.path(), "add-login", "github-copilot").expect("prompt");
+		assert!(prompt.contains("- #file:openspec/changes/add-login/tasks.md\n"));
+		assert!(!prompt.contains("/openspec"));
+	}
+
+	#[test]
+	fn reports_drift_only_for_rewritten_instruction_stubs() {
+		let project = TempDir::new().expect("create project dir");
+		let workspace = TempDir::new().expect("create workspace dir");
+		fs::create_dir_all(project.path().join("openspec")).expect("create openspec");
+		fs::write(
+			project.path().join("AGENTS.md"),
+			"<!-- OPENSPEC:START -->\nold\n",
+		)
+		.expect("write agents");
+		fs::write(project.path().join("openspec/AGENTS.md"), "current\n")
+			.expect("write openspec agents");
+
+		let files = drift_in(project.path(), workspace.path(), |args| {
+			assert_eq!(args[0], "update");
+			let root = Path::new(&args[1]);
+			fs::write(root.join("AGENTS.md"), "<!-- OPENSPEC:START -->\nnew\n")?;
+			Ok(())
+		})
+		.expect("drift");
+
+		assert_eq!(files.len(), 2);
+		assert_eq!(files[0].path, "AGENTS.md");
+		assert!(files[0].drifted);
+		assert!(files[0]
+			.diff
+			.as_deref()
+			.is_some_and(|diff| diff.contains("+new")));
+		assert!(!files[1].drifted);
+	}
+}
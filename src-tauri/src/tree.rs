@@ -0,0 +1,209 @@
+use crate::{
+	cache::{self, FileStamp, ProjectCache},
+	ignore::{self, IgnoreRules},
+	search::file_stamps,
+	settings::SettingsStore,
+	spec::{
+		existing_openspec_root, parse_headings, SourceText, SpecError, SpecErrorPayload,
+		MAX_PARSE_BYTES,
+	},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+	collections::HashMap,
+	path::{Path, PathBuf},
+	sync::{Arc, Mutex},
+};
+use tauri::State;
+
+/// One markdown file under the OpenSpec folder, with what the sidebar shows
+/// for it.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecTreeEntry {
+	pub path: PathBuf,
+	pub relative_path: String,
+	pub title: Option<String>,
+	pub requirements: usize,
+	stamp: FileStamp,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct SpecTree {
+	pub entries: Vec<SpecTreeEntry>,
+}
+
+fn entry_key(entry: &SpecTreeEntry) -> (&Path, &FileStamp) {
+	(&entry.path, &entry.stamp)
+}
+
+fn read_entry(root: &Path, path: PathBuf, stamp: FileStamp) -> Result<SpecTreeEntry, SpecError> {
+	let source = SourceText::read_limited(&path, MAX_PARSE_BYTES)?;
+	let headings = parse_headings(&source.lines);
+	let relative_path = path
+		.strip_prefix(root)
+		.unwrap_or(&path)
+		.to_string_lossy()
+		.replace('\\', "/");
+	Ok(SpecTreeEntry {
+		title: headings.first().map(|heading| heading.text.clone()),
+		requirements: headings
+			.iter()
+			.filter(|heading| heading.text.starts_with("Requirement:"))
+			.count(),
+		path,
+		relative_path,
+		stamp,
+	})
+}
+
+impl ProjectCache for SpecTree {
+	const NAME: &'static str = "spec-tree";
+
+	fn is_stale(&self, project: &Path, ignore: &IgnoreRules) -> bool {
+		let Ok(root) = existing_openspec_root(project) else {
+			return true;
+		};
+		file_stamps(project, &root, ignore).map_or(true, |stamps| {
+			cache::entries_changed(&stamps, &self.entries, entry_key)
+		})
+	}
+
+	fn refresh(
+		previous: Option<&Self>,
+		project: &Path,
+		ignore: &IgnoreRules,
+	) -> Result<Self, SpecError> {
+		let root = existing_openspec_root(project)?;
+		let entries = cache::refresh_entries(
+			file_stamps(project, &root, ignore)?,
+			previous
+				.map(|tree| tree.entries.as_slice())
+				.unwrap_or_default(),
+			entry_key,
+			|path, stamp| read_entry(&root, path, stamp),
+		)?;
+		Ok(Self { entries })
+	}
+}
+
+#[derive(Default)]
+pub struct SpecTrees {
+	trees: Mutex<HashMap<PathBuf, Arc<SpecTree>>>,
+	cache_root: Option<PathBuf>,
+}
+
+impl SpecTrees {
+	pub fn persistent() -> Self {
+		Self {
+			cache_root: cache::cache_root(),
+			..Self::default()
+		}
+	}
+
+	pub fn tree(
+		&self,
+		project: &Path,
+		settings: &SettingsStore,
+	) -> Result<Arc<SpecTree>, SpecError> {
+		let ignore = ignore::rules_for(settings, project);
+		let previous = self
+			.trees
+			.lock()
+			.ok()
+			.and_then(|trees| trees.get(project).cloned());
+		if let Some(tree) = &previous {
+			if !tree.is_stale(project, &ignore) {
+				return Ok(Arc::clone(tree));
+			}
+		}
+
+		let tree = Arc::new(cache::warm(
+			self.cache_root.as_deref(),
+			project,
+			settings,
+			&ignore,
+			previous.as_deref(),
+		)?);
+		if let Ok(mut trees) = self.trees.lock() {
+			trees.insert(project.to_path_buf(), Arc::clone(&tree));
+		}
+		Ok(tree)
+	}
+}
+
+#[tauri::command]
+pub fn spec_tree(
+	settings: State<'_, SettingsStore>,
+	trees: State<'_, SpecTrees>,
+	project: String,
+) -> Result<Vec<SpecTreeEntry>, SpecErrorPayload> {
+	let tree = trees
+		.tree(Path::new(&project), &settings)
+		.map_err(SpecErrorPayload::from)?;
+	Ok(tree.entries.clone())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs;
+	use tempfile::TempDir;
+
+	#[test]
+	fn persists_the_tree_and_rereads_only_changed_files() {
+		let project = TempDir::new().expect("create project dir");
+		let root = project.path().join("openspec");
+		fs::create_dir_all(root.join("specs/auth")).expect("create specs");
+		fs::create_dir_all(root.join("specs/billing")).expect("create specs");
+		fs::write(
+			root.join("specs/auth/spec.md"),
+			"# Auth\n\n### Requirement: Login\n",
+		)
+		.expect("write spec");
+		fs::write(root.join("specs/billing/spec.md"), "# Billing\n").expect("write spec");
+		let cache_dir = TempDir::new().expect("create cache dir");
+		let settings = SettingsStore::load(None);
+		let trees = || SpecTrees {
+			cache_root: Some(cache_dir.path().to_path_buf()),
+			..SpecTrees::default()
+		};
+
+		let tree = trees().tree(project.path(), &settings).expect("tree");
+		assert_eq!(
+			tree.entries
+				.iter()
+				.map(|entry| (entry.relative_path.as_str(), entry.requirements))
+				.collect::<Vec<_>>(),
+			vec![("specs/auth/spec.md", 1), ("specs/billing/spec.md", 0)]
+		);
+
+		// A cached title that no file says any more proves the entry was reused.
+		let mut persisted =
+			cache::load::<SpecTree>(cache_dir.path(), project.path(), SpecTree::NAME, None)
+				.expect("persisted tree");
+		persisted.entries[0].title = Some("Cached".to_string());
+		cache::store(
+			cache_dir.path(),
+			project.path(),
+			SpecTree::NAME,
+			&persisted,
+			None,
+		)
+		.expect("store tree");
+		fs::write(
+			root.join("specs/billing/spec.md"),
+			"# Billing and invoices\n",
+		)
+		.expect("write spec");
+
+		let tree = trees().tree(project.path(), &settings).expect("tree");
+		assert_eq!(
+			tree.entries
+				.iter()
+				.map(|entry| entry.title.as_deref())
+				.collect::<Vec<_>>(),
+			vec![Some("Cached"), Some("Billing and invoices")]
+		);
+	}
+}
@@ -0,0 +1,330 @@
+use crate::{
+	cache::cache_root, ci::parse_cli_version, command_version, global_install_args,
+	openspec_package, package_manager_status, run_journaled_command, storage, tools::find_on_path,
+	CommandRunOutput, OpenSpecCommandError, OpenSpecCommandErrorPayload, OPENSPEC_PACKAGE,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+	cmp::Ordering,
+	collections::BTreeMap,
+	env, fs,
+	path::{Component, Path, PathBuf},
+	time::Duration,
+};
+use tauri::Window;
+
+const DEFAULT_REGISTRY: &str = "https://registry.npmjs.org";
+const REGISTRY_TIMEOUT: Duration = Duration::from_secs(10);
+const UPDATE_CHECK_TTL_SECS: u64 = 6 * 60 * 60;
+const UPDATE_CHECK_FILE: &str = "cli-update-check.json";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliUpgrade {
+	#[serde(flatten)]
+	output: CommandRunOutput,
+	package_manager: String,
+	previous_version: String,
+	version: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct CachedLatest {
+	version: String,
+	checked_at: u64,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct CliUpdateCheck {
+	installed_version: Option<String>,
+	latest_version: Option<String>,
+	update_available: bool,
+	checked_at: Option<u64>,
+	cached: bool,
+}
+
+fn installed_version() -> Option<String> {
+	command_version("openspec").map(|output| parse_cli_version(&output).unwrap_or(output))
+}
+
+// Each manager keeps its global bin directory (or the package store the
+// shim links into) under a recognisable folder; anything else came from npm.
+pub fn installer_for(binary: &Path) -> &'static str {
+	let targets = [Some(binary.to_path_buf()), fs::canonicalize(binary).ok()];
+	let components = targets
+		.iter()
+		.flatten()
+		.flat_map(|path| path.components())
+		.filter_map(|component| match component {
+			Component::Normal(name) => Some(name.to_string_lossy().to_lowercase()),
+			_ => None,
+		})
+		.collect::<Vec<_>>();
+	let has = |names: &[&str]| components.iter().any(|name| names.contains(&name.as_str()));
+	if has(&[".bun"]) {
+		"bun"
+	} else if has(&["pnpm", ".pnpm", "pnpm-global"]) {
+		"pnpm"
+	} else if has(&["yarn", ".yarn"]) {
+		"yarn"
+	} else {
+		"npm"
+	}
+}
+
+fn installed_by() -> Option<&'static str> {
+	let path = env::var_os("PATH")?;
+	find_on_path("openspec", &path).map(|binary| installer_for(&binary))
+}
+
+// Accepts release numbers (`0.16.0`, `1.0.0-beta.1`) and dist-tags such as
+// `latest` or `next`; anything else could smuggle extra arguments.
+fn valid_version(version: &str) -> bool {
+	!version.is_empty()
+		&& version.len() <= 64
+		&& version
+			.chars()
+			.next()
+			.is_some_and(|c| c.is_ascii_alphanumeric())
+		&& version
+			.chars()
+			.all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'))
+}
+
+fn version_parts(version: &str) -> (Vec<u64>, Option<&str>) {
+	let version = version.trim().trim_start_matches('v');
+	let version = version.split_once('+').map_or(version, |(core, _)| core);
+	let (core, pre) = match version.split_once('-') {
+		Some((core, pre)) => (core, Some(pre)),
+		None => (version, None),
+	};
+	let numbers = core
+		.split('.')
+		.map(|part| part.parse().unwrap_or(0))
+		.collect();
+	(numbers, pre)
+}
+
+// Enough of semver ordering for npm releases: numeric cores, and a
+// pre-release sorts before the release it leads up to.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+	let (a_core, a_pre) = version_parts(a);
+	let (b_core, b_pre) = version_parts(b);
+	let width = a_core.len().max(b_core.len());
+	let padded = |core: &[u64]| {
+		(0..width)
+			.map(|index| core.get(index).copied().unwrap_or(0))
+			.collect::<Vec<_>>()
+	};
+	padded(&a_core)
+		.cmp(&padded(&b_core))
+		.then_with(|| match (a_pre, b_pre) {
+			(None, None) => Ordering::Equal,
+			(None, Some(_)) => Ordering::Greater,
+			(Some(_), None) => Ordering::Less,
+			(Some(a), Some(b)) => a.cmp(b),
+		})
+}
+
+fn registry_url() -> String {
+	let registry = env::var("npm_config_registry")
+		.ok()
+		.filter(|registry| !registry.trim().is_empty())
+		.unwrap_or_else(|| DEFAULT_REGISTRY.to_string());
+	format!(
+		"{}/{OPENSPEC_PACKAGE}/latest",
+		registry.trim_end_matches('/')
+	)
+}
+
+fn fetch_latest() -> Option<String> {
+	let agent = ureq::AgentBuilder::new().timeout(REGISTRY_TIMEOUT).build();
+	let value = agent
+		.get(&registry_url())
+		.set("Accept", "application/json")
+		.set("User-Agent", "SpecOps")
+		.call()
+		.ok()?
+		.into_json::<Value>()
+		.ok()?;
+	value["version"].as_str().map(str::to_string)
+}
+
+fn update_check_path() -> Option<PathBuf> {
+	cache_root().map(|root| root.join(UPDATE_CHECK_FILE))
+}
+
+fn read_cached(path: &Path) -> Option<CachedLatest> {
+	serde_json::from_slice(&fs::read(path).ok()?).ok()
+}
+
+/// Consults the registry only when the cached answer is older than the TTL
+/// (or `force` is set); a failed lookup falls back to the stale answer.
+fn check_for_update_in(
+	path: Option<&Path>,
+	installed: Option<String>,
+	force: bool,
+	now: u64,
+	fetch: impl FnOnce() -> Option<String>,
+) -> CliUpdateCheck {
+	let cached = path.and_then(read_cached);
+	let fresh = cached
+		.as_ref()
+		.is_some_and(|cached| now.saturating_sub(cached.checked_at) < UPDATE_CHECK_TTL_SECS);
+	let fetched = if fresh && !force { None } else { fetch() };
+	let (latest, from_cache) = match fetched {
+		Some(version) => {
+			let latest = CachedLatest {
+				version,
+				checked_at: now,
+			};
+			if let (Some(path), Ok(bytes)) = (path, serde_json::to_vec(&latest)) {
+				let _ = storage::write_atomic(path, &bytes);
+			}
+			(Some(latest), false)
+		}
+		None => (cached, true),
+	};
+	let update_available = match (&installed, &latest) {
+		(Some(installed), Some(latest)) => {
+			compare_versions(&latest.version, installed) == Ordering::Greater
+		}
+		_ => false,
+	};
+	CliUpdateCheck {
+		installed_version: installed,
+		checked_at: latest.as_ref().map(|latest| latest.checked_at),
+		latest_version: latest.map(|latest| latest.version),
+		update_available,
+		cached: from_cache,
+	}
+}
+
+#[tauri::command]
+pub fn openspec_cli_update_check(force: Option<bool>) -> CliUpdateCheck {
+	check_for_update_in(
+		update_check_path().as_deref(),
+		installed_version(),
+		force.unwrap_or(false),
+		storage::now_secs(),
+		fetch_latest,
+	)
+}
+
+#[tauri::command(async)]
+pub fn upgrade_openspec_cli(
+	window: Window,
+	version: Option<String>,
+	package_manager: Option<String>,
+	env: Option<BTreeMap<String, String>>,
+) -> Result<CliUpgrade, OpenSpecCommandErrorPayload> {
+	let version = version.unwrap_or_else(|| "latest".to_string());
+	if !valid_version(&version) {
+		return Err(OpenSpecCommandError::InvalidVersion.into());
+	}
+	let previous_version = installed_version().ok_or(OpenSpecCommandError::CliUnavailable)?;
+	let package_manager = package_manager
+		.or_else(|| installed_by().map(str::to_string))
+		.unwrap_or_else(|| "npm".to_string());
+	let args = global_install_args(&package_manager, &openspec_package(&version))
+		.ok_or(OpenSpecCommandError::UnsupportedPackageManager)?;
+	if !package_manager_status(&package_manager).installed {
+		return Err(OpenSpecCommandError::PackageManagerUnavailable.into());
+	}
+
+	let output = run_journaled_command(
+		&window,
+		&env.unwrap_or_default(),
+		"upgrade",
+		None,
+		&[],
+		&package_manager,
+		&args,
+	)?;
+	Ok(CliUpgrade {
+		output,
+		package_manager,
+		previous_version,
+		version: installed_version(),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	#[test]
+	fn detects_installer_from_binary_location() {
+		assert_eq!(
+			installer_for(Path::new("/home/dev/.bun/bin/openspec")),
+			"bun"
+		);
+		assert_eq!(
+			installer_for(Path::new("/home/dev/.local/share/pnpm/openspec")),
+			"pnpm"
+		);
+		assert_eq!(
+			installer_for(Path::new("/home/dev/.yarn/bin/openspec")),
+			"yarn"
+		);
+		assert_eq!(installer_for(Path::new("/usr/local/bin/openspec")), "npm");
+	}
+
+	#[test]
+	fn accepts_release_numbers_and_dist_tags() {
+		for version in ["latest", "next", "0.16.0", "1.0.0-beta.1"] {
+			assert!(valid_version(version), "{version}");
+		}
+		for version in ["", "-g", "0.16.0 --force", "../openspec", "^1.0.0"] {
+			assert!(!valid_version(version), "{version}");
+		}
+	}
+
+	#[test]
+	fn orders_versions_like_semver() {
+		assert_eq!(compare_versions("0.16.0", "0.9.2"), Ordering::Greater);
+		assert_eq!(compare_versions("v1.0", "1.0.0"), Ordering::Equal);
+		assert_eq!(compare_versions("1.0.0-beta.1", "1.0.0"), Ordering::Less);
+		assert_eq!(compare_versions("1.0.0+build.5", "1.0.0"), Ordering::Equal);
+	}
+
+	#[test]
+	fn caches_latest_version_until_ttl_expires() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		let path = temp_dir.path().join(UPDATE_CHECK_FILE);
+		let installed = || Some("0.15.0".to_string());
+
+		let check = check_for_update_in(Some(&path), installed(), false, 1_000, || {
+			Some("0.16.0".to_string())
+		});
+		assert!(check.update_available && !check.cached);
+
+		let check = check_for_update_in(Some(&path), installed(), false, 2_000, || {
+			panic!("registry queried inside the ttl")
+		});
+		assert_eq!(check.latest_version.as_deref(), Some("0.16.0"));
+		assert!(check.cached);
+
+		let later = 1_000 + UPDATE_CHECK_TTL_SECS;
+		let check = check_for_update_in(Some(&path), installed(), false, later, || None);
+		assert_eq!(
+			(
+				check.latest_version.as_deref(),
+				check.checked_at,
+				check.cached
+			),
+			(Some("0.16.0"), Some(1_000), true)
+		);
+
+		let check =
+			check_for_update_in(Some(&path), Some("0.16.0".to_string()), true, 2_000, || {
+				Some("0.16.0".to_string())
+			});
+		assert!(!check.update_available && !check.cached);
+		assert_eq!(check.checked_at, Some(2_000));
+	}
+}
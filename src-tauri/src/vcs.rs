@@ -0,0 +1,132 @@
+use crate::{wsl, DiscoveryError};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, Debug, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum VcsKind {
+	Git,
+	Jujutsu,
+	Sapling,
+}
+
+impl VcsKind {
+	fn marker(self) -> &'static str {
+		match self {
+			VcsKind::Git => ".git",
+			VcsKind::Jujutsu => ".jj",
+			VcsKind::Sapling => ".sl",
+		}
+	}
+
+	fn root_command(self) -> (&'static str, &'static [&'static str]) {
+		match self {
+			VcsKind::Git => ("git", &["rev-parse", "--show-toplevel"]),
+			VcsKind::Jujutsu => ("jj", &["root"]),
+			VcsKind::Sapling => ("sl", &["root"]),
+		}
+	}
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct VcsRoot {
+	pub kind: VcsKind,
+	pub root: PathBuf,
+	pub read_only: bool,
+}
+
+enum RootLookup {
+	Found(PathBuf),
+	NotARepo,
+	Unavailable,
+}
+
+fn cli_root(kind: VcsKind, path: &Path) -> RootLookup {
+	let (program, args) = kind.root_command();
+	let output = match wsl::command(program, args, Some(path)).output() {
+		Ok(output) => output,
+		Err(_) => return RootLookup::Unavailable,
+	};
+	let stdout = String::from_utf8_lossy(&output.stdout);
+	let root = stdout.trim();
+	if output.status.success() && !root.is_empty() {
+		RootLookup::Found(wsl::host_path(path, root))
+	} else {
+		RootLookup::NotARepo
+	}
+}
+
+fn marker_root(path: &Path) -> Option<(VcsKind, PathBuf)> {
+	path.ancestors().find_map(|dir| {
+		[VcsKind::Jujutsu, VcsKind::Sapling]
+			.into_iter()
+			.find(|kind| dir.join(kind.marker()).is_dir())
+			.map(|kind| (kind, dir.to_path_buf()))
+	})
+}
+
+fn colocated_kind(root: &Path) -> VcsKind {
+	[VcsKind::Jujutsu, VcsKind::Sapling]
+		.into_iter()
+		.find(|kind| root.join(kind.marker()).is_dir())
+		.unwrap_or(VcsKind::Git)
+}
+
+pub fn resolve_repo(path: &Path) -> Result<VcsRoot, DiscoveryError> {
+	let git = cli_root(VcsKind::Git, path);
+	if let RootLookup::Found(root) = git {
+		return Ok(VcsRoot {
+			kind: colocated_kind(&root),
+			root,
+			read_only: false,
+		});
+	}
+
+	if let Some((kind, marker_dir)) = marker_root(path) {
+		let root = match cli_root(kind, path) {
+			RootLookup::Found(root) => root,
+			_ => marker_dir,
+		};
+		return Ok(VcsRoot {
+			kind,
+			root,
+			read_only: true,
+		});
+	}
+
+	match git {
+		RootLookup::Unavailable => Err(DiscoveryError::GitUnavailable),
+		_ => Err(DiscoveryError::NotGitWorkTree),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::fs;
+	use tempfile::TempDir;
+
+	#[test]
+	fn falls_back_to_jj_marker_without_git() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		fs::create_dir_all(temp_dir.path().join(".jj/repo")).expect("create jj");
+		let nested = temp_dir.path().join("openspec/specs");
+		fs::create_dir_all(&nested).expect("create nested");
+
+		assert_eq!(
+			marker_root(&nested),
+			Some((VcsKind::Jujutsu, temp_dir.path().to_path_buf()))
+		);
+		let resolved = resolve_repo(&nested).expect("resolve");
+		assert_eq!(resolved.kind, VcsKind::Jujutsu);
+		assert!(resolved.read_only || resolved.root.join(".git").exists());
+	}
+
+	#[test]
+	fn detects_colocated_sapling_checkout() {
+		let temp_dir = TempDir::new().expect("create temp dir");
+		assert_eq!(colocated_kind(temp_dir.path()), VcsKind::Git);
+		fs::create_dir_all(temp_dir.path().join(".sl")).expect("create sl");
+		assert_eq!(colocated_kind(temp_dir.path()), VcsKind::Sapling);
+	}
+}
@@ -0,0 +1,354 @@
+use crate::{
+	ignore, mounts, power,
+	search::{refresh_saved_searches, SearchState},
+	settings::SettingsStore,
+	spec::{existing_openspec_root, SpecError, SpecErrorPayload},
+	storage, supervisor,
+};
+use notify::{PollWatcher, RecursiveMode, Watcher};
+use serde::Serialize;
+use std::{
+	collections::{BTreeSet, HashMap},
+	path::{Path, PathBuf},
+	sync::{mpsc, Mutex, PoisonError},
+	time::{Duration, Instant},
+};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct FilesChangedEvent {
+	project: String,
+	paths: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExternalEditEvent {
+	project: String,
+	change_id: String,
+	active: bool,
+	paths: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExternalEditSession {
+	change_id: String,
+	started_at: u64,
+	paths: Vec<String>,
+}
+
+const SESSION_THRESHOLD: usize = 3;
+const SESSION_WINDOW: Duration = Duration::from_secs(20);
+const SESSION_IDLE: Duration = Duration::from_secs(30);
+
+#[derive(Default)]
+struct ChangeActivity {
+	writes: Vec<(Instant, PathBuf)>,
+	session: Option<ExternalEditSession>,
+}
+
+#[derive(Default)]
+struct EditTracker {
+	changes: HashMap<String, ChangeActivity>,
+}
+
+impl EditTracker {
+	fn observe(
+		&mut self,
+		changes_root: &Path,
+		paths: &BTreeSet<PathBuf>,
+		now: Instant,
+	) -> Vec<ExternalEditSession> {
+		let mut started = Vec::new();
+		for path in paths {
+			let Some(change_id) = path
+				.strip_prefix(changes_root)
+				.ok()
+				.and_then(|relative| relative.components().next())
+				.map(|component| component.as_os_str().to_string_lossy().to_string())
+				.filter(|change_id| change_id != "archive" && !change_id.starts_with('.'))
+			else {
+				continue;
+			};
+			let activity = self.changes.entry(change_id.clone()).or_default();
+			activity
+				.writes
+				.retain(|(at, _)| now.duration_since(*at) < SESSION_WINDOW);
+			activity.writes.push((now, path.clone()));
+			let written = activity
+				.writes
+				.iter()
+				.map(|(_, path)| path.to_string_lossy().to_string())
+				.collect::<BTreeSet<_>>();
+			match &mut activity.session {
+				Some(session) => session.paths = written.into_iter().collect(),
+				None if written.len() >= SESSION_THRESHOLD => {
+					let session = ExternalEditSession {
+						change_id,
+						started_at: storage::now_secs(),
+						paths: written.into_iter().collect(),
+					};
+					activity.session = Some(session.clone());
+					started.push(session);
+				}
+				None => {}
+			}
+		}
+		started
+	}
+
+	fn expire(&mut self, now: Instant) -> Vec<ExternalEditSession> {
+		let mut ended = Vec::new();
+		self.changes.retain(|_, activity| {
+			let idle = activity
+				.writes
+				.last()
+				.is_none_or(|(at, _)| now.duration_since(*at) >= SESSION_IDLE);
+			if idle {
+				ended.extend(activity.session.take());
+			}
+			!idle
+		});
+		ended
+	}
+
+	fn sessions(&self) -> Vec<ExternalEditSession> {
+		self.changes
+			.values()
+			.filter_map(|activity| activity.session.clone())
+			.collect()
+	}
+}
+
+#[derive(Default)]
+pub struct ProjectWatchers(Mutex<HashMap<PathBuf, Box<dyn Watcher + Send>>>);
+
+#[derive(Default)]
+pub struct ExternalEdits(Mutex<HashMap<PathBuf, EditTracker>>);
+
+impl ExternalEdits {
+	fn track<R>(&self, project: &Path, update: impl FnOnce(&mut EditTracker) -> R) -> Option<R> {
+		let mut trackers = self.0.lock().ok()?;
+		Some(update(trackers.entry(project.to_path_buf()).or_default()))
+	}
+
+	pub fn sessions(&self, project: &Path) -> Vec<ExternalEditSession> {
+		self.track(project, |tracker| tracker.sessions())
+			.unwrap_or_default()
+	}
+}
+
+fn emit_sessions(
+	app: &AppHandle,
+	project: &Path,
+	sessions: Vec<ExternalEditSession>,
+	active: bool,
+) {
+	for session in sessions {
+		let _ = app.emit(
+			"openspec://external-edit-session",
+			ExternalEditEvent {
+				project: project.to_string_lossy().to_string(),
+				change_id: session.change_id,
+				active,
+				paths: session.paths,
+			},
+		);
+	}
+}
+
+fn collect_burst(
+	receiver: &mpsc::Receiver<Vec<PathBuf>>,
+	first: Vec<PathBuf>,
+	debounce: Duration,
+) -> BTreeSet<PathBuf> {
+	let mut paths = first.into_iter().collect::<BTreeSet<_>>();
+	while let Ok(more) = receiver.recv_timeout(debounce) {
+		paths.extend(more);
+	}
+	paths
+}
+
+fn start_watcher(app: AppHandle, project: PathBuf) -> Result<Box<dyn Watcher + Send>, SpecError> {
+	let root = existing_openspec_root(&project)?;
+	let (sender, receiver) = mpsc::channel::<Vec<PathBuf>>();
+	let handler = move |result: notify::Result<notify::Event>| {
+		if let Ok(event) = result {
+			let _ = sender.send(event.paths);
+		}
+	};
+	// Native events are unreliable on network shares and inside sync
+	// clients, so those projects are polled instead.
+	let mut watcher: Box<dyn Watcher + Send> = if mounts::storage_kind(&project).is_slow() {
+		Box::new(
+			PollWatcher::new(
+				handler,
+				notify::Config::default().with_poll_interval(mounts::POLL_INTERVAL),
+			)
+			.map_err(|error| SpecError::Io(std::io::Error::other(error)))?,
+		)
+	} else {
+		Box::new(
+			notify::recommended_watcher(handler)
+				.map_err(|error| SpecError::Io(std::io::Error::other(error)))?,
+		)
+	};
+	watcher
+		.watch(&root, RecursiveMode::Recursive)
+		.map_err(|error| SpecError::Io(std::io::Error::other(error)))?;
+
+	let receiver = Mutex::new(receiver);
+	let name = format!("watcher:{}", project.to_string_lossy());
+	supervisor::supervise(app.clone(), name, move || {
+		let receiver = receiver.lock().unwrap_or_else(PoisonError::into_inner);
+		let changes_root = root.join("changes");
+		loop {
+			let first = match receiver.recv_timeout(SESSION_IDLE) {
+				Ok(first) => first,
+				Err(mpsc::RecvTimeoutError::Timeout) => {
+					let ended = app
+						.state::<ExternalEdits>()
+						.track(&project, |tracker| tracker.expire(Instant::now()))
+						.unwrap_or_default();
+					emit_sessions(&app, &project, ended, false);
+					continue;
+				}
+				Err(mpsc::RecvTimeoutError::Disconnected) => break,
+			};
+			let low_power = power::low_power(&app.state::<SettingsStore>());
+			let ignore = ignore::rules_for(&app.state::<SettingsStore>(), &project);
+			let paths = collect_burst(&receiver, first, power::watch_debounce(low_power))
+				.into_iter()
+				.filter(|path| !ignore.is_ignored(&project, path))
+				.collect::<BTreeSet<_>>();
+			if paths.is_empty() {
+				continue;
+			}
+			let external = paths
+				.iter()
+				.filter(|path| !storage::is_recent_app_write(path))
+				.cloned()
+				.collect::<BTreeSet<_>>();
+			let (ended, started) = app
+				.state::<ExternalEdits>()
+				.track(&project, |tracker| {
+					let now = Instant::now();
+					(
+						tracker.expire(now),
+						tracker.observe(&changes_root, &external, now),
+					)
+				})
+				.unwrap_or_default();
+			emit_sessions(&app, &project, ended, false);
+			emit_sessions(&app, &project, started, true);
+			let _ = app.emit(
+				"openspec://files-changed",
+				FilesChangedEvent {
+					project: project.to_string_lossy().to_string(),
+					paths: paths
+						.iter()
+						.map(|path| path.to_string_lossy().to_string())
+						.collect(),
+				},
+			);
+			if low_power {
+				app.state::<SearchState>().invalidate(&project);
+			} else {
+				refresh_saved_searches(&app, &project);
+			}
+		}
+	});
+
+	Ok(watcher)
+}
+
+#[tauri::command]
+pub fn watch_project(
+	app: AppHandle,
+	watchers: State<'_, ProjectWatchers>,
+	path: String,
+) -> Result<(), SpecErrorPayload> {
+	let project = Path::new(&path).to_path_buf();
+	let mut watchers = watchers.0.lock().map_err(|_| {
+		SpecErrorPayload::from(SpecError::Io(std::io::Error::other("watcher lock")))
+	})?;
+	if watchers.contains_key(&project) {
+		return Ok(());
+	}
+	let watcher = start_watcher(app, project.clone()).map_err(SpecErrorPayload::from)?;
+	watchers.insert(project, watcher);
+	Ok(())
+}
+
+#[tauri::command]
+pub fn external_edit_sessions(
+	edits: State<'_, ExternalEdits>,
+	path: String,
+) -> Vec<ExternalEditSession> {
+	edits.sessions(Path::new(&path))
+}
+
+#[tauri::command]
+pub fn unwatch_project(watchers: State<'_, ProjectWatchers>, path: String) {
+	if let Ok(mut watchers) = watchers.0.lock() {
+		watchers.remove(Path::new(&path));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn collects_burst_until_quiet() {
+		let (sender, receiver) = mpsc::channel();
+		sender
+			.send(vec![PathBuf::from("b.md"), PathBuf::from("a.md")])
+			.expect("send");
+		sender.send(vec![PathBuf::from("a.md")]).expect("send");
+		drop(sender);
+		let first = receiver.recv().expect("recv");
+		let paths = collect_burst(&receiver, first, Duration::from_millis(10));
+		assert_eq!(
+			paths.into_iter().collect::<Vec<_>>(),
+			vec![PathBuf::from("a.md"), PathBuf::from("b.md")],
+		);
+	}
+
+	#[test]
+	fn opens_and_expires_external_edit_sessions() {
+		let root = PathBuf::from("/repo/openspec/changes");
+		let mut tracker = EditTracker::default();
+		let start = Instant::now();
+		let burst = |names: &[&str]| {
+			names
+				.iter()
+				.map(|name| root.join(name))
+				.collect::<BTreeSet<_>>()
+		};
+
+		assert!(tracker
+			.observe(&root, &burst(&["add-login/proposal.md"]), start)
+			.is_empty());
+		let started = tracker.observe(
+			&root,
+			&burst(&[
+				"add-login/tasks.md",
+				"add-login/specs/auth/spec.md",
+				"archive/old/tasks.md",
+			]),
+			start + Duration::from_secs(2),
+		);
+		assert_eq!(started.len(), 1);
+		assert_eq!(started[0].change_id, "add-login");
+		assert_eq!(started[0].paths.len(), 3);
+		assert_eq!(tracker.sessions().len(), 1);
+
+		assert!(tracker.expire(start + Duration::from_secs(10)).is_empty());
+		let ended = tracker.expire(start + Duration::from_secs(40));
+		assert_eq!(ended.len(), 1);
+		assert!(tracker.sessions().is_empty());
+	}
+}
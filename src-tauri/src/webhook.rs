@@ -0,0 +1,211 @@
+use crate::{
+	secrets::read_secret,
+	settings::SettingsStore,
+	spec::{SpecError, SpecErrorPayload},
+	storage,
+};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::{thread, time::Duration};
+use tauri::State;
+
+const SECRET_NAME: &str = "webhook.secret";
+const MAX_ATTEMPTS: u32 = 3;
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum WebhookEvent {
+	ArchiveCompleted,
+	ValidationFailed,
+	Ping,
+}
+
+impl WebhookEvent {
+	fn name(self) -> &'static str {
+		match self {
+			WebhookEvent::ArchiveCompleted => "archive-completed",
+			WebhookEvent::ValidationFailed => "validation-failed",
+			WebhookEvent::Ping => "ping",
+		}
+	}
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WebhookSettings {
+	pub enabled: bool,
+	pub url: String,
+	pub events: Vec<WebhookEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookInput {
+	enabled: bool,
+	url: String,
+	events: Vec<WebhookEvent>,
+	secret: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WebhookDelivery {
+	delivery_id: String,
+	attempts: u32,
+	status: Option<u16>,
+}
+
+pub fn signature(secret: &str, body: &[u8]) -> String {
+	let mut mac =
+		Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("hmac accepts any key length");
+	mac.update(body);
+	let digest = mac
+		.finalize()
+		.into_bytes()
+		.iter()
+		.map(|byte| format!("{byte:02x}"))
+		.collect::<String>();
+	format!("sha256={digest}")
+}
+
+fn event_body(event: WebhookEvent, delivery_id: &str, payload: Value) -> Vec<u8> {
+	serde_json::to_vec(&json!({
+		"id": delivery_id,
+		"event": event.name(),
+		"sentAt": storage::now_secs(),
+		"payload": payload,
+	}))
+	.unwrap_or_default()
+}
+
+fn retryable(error: &ureq::Error) -> bool {
+	match error {
+		ureq::Error::Status(status, _) => *status == 429 || *status >= 500,
+		ureq::Error::Transport(_) => true,
+	}
+}
+
+fn deliver(
+	url: &str,
+	secret: Option<&str>,
+	event: WebhookEvent,
+	payload: Value,
+) -> Result<WebhookDelivery, SpecError> {
+	let delivery_id = storage::new_id("delivery");
+	let body = event_body(event, &delivery_id, payload);
+	let agent = ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build();
+	let mut backoff = INITIAL_BACKOFF;
+	for attempt in 1..=MAX_ATTEMPTS {
+		let mut request = agent
+			.post(url)
+			.set("Content-Type", "application/json")
+			.set("User-Agent", "SpecOps")
+			.set("X-SpecOps-Event", event.name())
+			.set("X-SpecOps-Delivery", &delivery_id);
+		if let Some(secret) = secret {
+			request = request.set("X-SpecOps-Signature", &signature(secret, &body));
+		}
+		match request.send_bytes(&body) {
+			Ok(response) => {
+				return Ok(WebhookDelivery {
+					delivery_id,
+					attempts: attempt,
+					status: Some(response.status()),
+				})
+			}
+			Err(error) if attempt < MAX_ATTEMPTS && retryable(&error) => {
+				thread::sleep(backoff);
+				backoff *= 2;
+			}
+			Err(_) => break,
+		}
+	}
+	Err(SpecError::WebhookDeliveryFailed)
+}
+
+pub fn emit_webhook(settings: &SettingsStore, event: WebhookEvent, payload: Value) {
+	let webhook = settings.get().webhook;
+	if !webhook.enabled || webhook.url.is_empty() || !webhook.events.contains(&event) {
+		return;
+	}
+	thread::spawn(move || {
+		let secret = read_secret(SECRET_NAME).ok().flatten();
+		let _ = deliver(&webhook.url, secret.as_deref(), event, payload);
+	});
+}
+
+#[tauri::command]
+pub fn webhook_settings(settings: State<'_, SettingsStore>) -> WebhookSettings {
+	settings.get().webhook
+}
+
+#[tauri::command]
+pub fn configure_webhook(
+	settings: State<'_, SettingsStore>,
+	input: WebhookInput,
+) -> Result<WebhookSettings, SpecErrorPayload> {
+	let url = input.url.trim().to_string();
+	if input.enabled && !(url.starts_with("https://") || url.starts_with("http://")) {
+		return Err(SpecError::InvalidWebhookUrl.into());
+	}
+	if let Some(secret) = input.secret {
+		crate::secrets::store_secret(SECRET_NAME.to_string(), secret)?;
+	}
+	let webhook = WebhookSettings {
+		enabled: input.enabled,
+		url,
+		events: input.events,
+	};
+	settings
+		.update(|settings| settings.webhook = webhook.clone())
+		.map_err(|error| SpecErrorPayload::from(SpecError::from(error)))?;
+	Ok(webhook)
+}
+
+#[tauri::command]
+pub async fn test_webhook(
+	settings: State<'_, SettingsStore>,
+) -> Result<WebhookDelivery, SpecErrorPayload> {
+	let webhook = settings.get().webhook;
+	if webhook.url.is_empty() {
+		return Err(SpecError::InvalidWebhookUrl.into());
+	}
+	let secret = read_secret(SECRET_NAME)?;
+	deliver(
+		&webhook.url,
+		secret.as_deref(),
+		WebhookEvent::Ping,
+		json!({ "message": "SpecOps webhook test" }),
+	)
+	.map_err(SpecErrorPayload::from)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn signs_body_with_hmac_sha256() {
+		assert_eq!(
+			signature("It's a Secret to Everybody", b"Hello, World!"),
+			"sha256=757107ea0eb2509fc211221cce984b8a37570b6d7586c22c46f4379c8b043e17"
+		);
+	}
+
+	#[test]
+	fn builds_event_envelope() {
+		let body = event_body(
+			WebhookEvent::ArchiveCompleted,
+			"delivery-1",
+			json!({ "changeId": "add-login" }),
+		);
+		let value: Value = serde_json::from_slice(&body).expect("parse body");
+		assert_eq!(value["event"], "archive-completed");
+		assert_eq!(value["id"], "delivery-1");
+		assert_eq!(value["payload"]["changeId"], "add-login");
+	}
+}
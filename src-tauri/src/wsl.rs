@@ -0,0 +1,209 @@
+use std::{
+	path::{Path, PathBuf},
+	process::Command,
+};
+
+const WSL_EXE: &str = "wsl.exe";
+const WSL_HOSTS: [&str; 2] = ["wsl$", "wsl.localhost"];
+// A login shell picks up the PATH that version managers such as nvm set in
+// the profile; `exec "$0" "$@"` then runs the program without re-quoting.
+const LOGIN_EXEC: &str = r#"exec "$0" "$@""#;
+
+/// A Windows path that points inside a WSL distro, such as
+/// `\\wsl$\Ubuntu\home\dev\repo`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WslPath {
+	host: String,
+	pub distro: String,
+	pub path: String,
+}
+
+pub fn parse(path: &Path) -> Option<WslPath> {
+	let text = path.to_string_lossy().replace('/', "\\");
+	let rest = text
+		.strip_prefix(r"\\?\UNC\")
+		.or_else(|| text.strip_prefix(r"\\"))?;
+	let mut parts = rest.split('\\');
+	let host = parts.next()?;
+	if !WSL_HOSTS
+		.iter()
+		.any(|known| known.eq_ignore_ascii_case(host))
+	{
+		return None;
+	}
+	let distro = parts.next().filter(|distro| !distro.is_empty())?;
+	let path = parts
+		.filter(|part| !part.is_empty())
+		.fold(String::new(), |path, part| format!("{path}/{part}"));
+	Some(WslPath {
+		host: host.to_string(),
+		distro: distro.to_string(),
+		path: if path.is_empty() {
+			"/".to_string()
+		} else {
+			path
+		},
+	})
+}
+
+fn drive_path(text: &str) -> Option<String> {
+	let text = text.strip_prefix(r"\\?\").unwrap_or(text);
+	let drive = text.chars().next().filter(char::is_ascii_alphabetic)?;
+	let rest = text.get(1..)?.strip_prefix(':')?;
+	if !rest.is_empty() && !rest.starts_with(['\\', '/']) {
+		return None;
+	}
+	Some(format!(
+		"/mnt/{}{}",
+		drive.to_ascii_lowercase(),
+		rest.replace('\\', "/")
+	))
+}
+
+impl WslPath {
+	/// Rewrites a Windows path argument into the form the distro sees: paths
+	/// inside the same distro lose their UNC prefix and drive paths move
+	/// under `/mnt`. Anything else is passed through untouched.
+	pub fn linux_arg(&self, arg: &str) -> String {
+		match parse(Path::new(arg)) {
+			Some(inner) if inner.distro.eq_ignore_ascii_case(&self.distro) => inner.path,
+			Some(_) => arg.to_string(),
+			None => drive_path(arg).unwrap_or_else(|| arg.to_string()),
+		}
+	}
+
+	/// Maps an absolute path printed inside the distro back to one Windows
+	/// can open, keeping the `wsl$` or `wsl.localhost` form the user chose.
+	pub fn windows_path(&self, linux: &str) -> PathBuf {
+		if let Some((drive, rest)) = linux
+			.strip_prefix("/mnt/")
+			.and_then(|rest| rest.split_once('/').or(Some((rest, ""))))
+			.filter(|(drive, _)| drive.len() == 1)
+		{
+			return PathBuf::from(format!(
+				"{}:\\{}",
+				drive.to_ascii_uppercase(),
+				rest.replace('/', "\\")
+			));
+		}
+		PathBuf::from(format!(
+			r"\\{}\{}{}",
+			self.host,
+			self.distro,
+			linux.replace('/', "\\")
+		))
+	}
+
+	fn args(&self, program: &str, args: &[impl AsRef<str>]) -> Vec<String> {
+		[
+			"-d",
+			&self.distro,
+			"--cd",
+			&self.path,
+			"--exec",
+			"sh",
+			"-lc",
+		]
+		.into_iter()
+		.chain([LOGIN_EXEC, program])
+		.map(str::to_string)
+		.chain(args.iter().map(|arg| self.linux_arg(arg.as_ref())))
+		.collect()
+	}
+}
+
+/// The `wsl.exe` invocation that runs `program` inside the distro owning
+/// `dir`, or `None` when `dir` is an ordinary Windows path.
+pub fn invocation(
+	program: &str,
+	args: &[impl AsRef<str>],
+	dir: Option<&Path>,
+) -> Option<(String, Vec<String>)> {
+	if !cfg!(windows) {
+		return None;
+	}
+	let location = parse(dir?)?;
+	Some((WSL_EXE.to_string(), location.args(program, args)))
+}
+
+/// Spawning Windows binaries against a `\\wsl$` share either fails or crawls
+/// over the 9P bridge, so commands for WSL projects run inside the distro.
+pub fn command(program: &str, args: &[impl AsRef<str>], dir: Option<&Path>) -> Command {
+	if let Some((wsl, wsl_args)) = invocation(program, args, dir) {
+		let mut command = Command::new(wsl);
+		command.args(wsl_args);
+		return command;
+	}
+	let mut command = Command::new(program);
+	command.args(args.iter().map(AsRef::as_ref));
+	if let Some(dir) = dir {
+		command.current_dir(dir);
+	}
+	command
+}
+
+/// Interprets a path printed by a command that ran in `dir`.
+pub fn host_path(dir: &Path, printed: &str) -> PathBuf {
+	match parse(dir) {
+		Some(location) if cfg!(windows) && printed.starts_with('/') => {
+			location.windows_path(printed)
+		}
+		_ => PathBuf::from(printed),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parses_wsl_unc_paths() {
+		let location = parse(Path::new(r"\\wsl$\Ubuntu-22.04\home\dev\repo")).expect("wsl path");
+		assert_eq!(location.distro, "Ubuntu-22.04");
+		assert_eq!(location.path, "/home/dev/repo");
+		assert_eq!(
+			parse(Path::new(r"\\?\UNC\wsl.localhost\Debian\")).map(|location| location.path),
+			Some("/".to_string())
+		);
+		assert_eq!(parse(Path::new(r"\\fileserver\share\repo")), None);
+		assert_eq!(parse(Path::new(r"C:\Users\dev\repo")), None);
+	}
+
+	#[test]
+	fn translates_paths_in_both_directions() {
+		let location = parse(Path::new(r"\\wsl$\Ubuntu\home\dev\repo")).expect("wsl path");
+		assert_eq!(
+			location.linux_arg(r"\\wsl.localhost\Ubuntu\home\dev\repo\openspec"),
+			"/home/dev/repo/openspec"
+		);
+		assert_eq!(
+			location.linux_arg(r"C:\Users\dev\AppData\Local\Temp\specops"),
+			"/mnt/c/Users/dev/AppData/Local/Temp/specops"
+		);
+		assert_eq!(location.linux_arg("--tools"), "--tools");
+		assert_eq!(
+			location.windows_path("/home/dev/repo"),
+			PathBuf::from(r"\\wsl$\Ubuntu\home\dev\repo")
+		);
+		assert_eq!(
+			location.windows_path("/mnt/d/specs"),
+			PathBuf::from(r"D:\specs")
+		);
+		assert_eq!(
+			location.args("git", &["rev-parse", "--show-toplevel"]),
+			[
+				"-d",
+				"Ubuntu",
+				"--cd",
+				"/home/dev/repo",
+				"--exec",
+				"sh",
+				"-lc",
+				LOGIN_EXEC,
+				"git",
+				"rev-parse",
+				"--show-toplevel"
+			]
+		);
+	}
+}
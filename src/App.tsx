@@ -54,7 +54,11 @@ function App() {
 			})
 			.catch(() => {
 				if (isActive) {
-					setOpenSpecCliStatus({ available: false, version: null });
+					setOpenSpecCliStatus({
+						available: false,
+						version: null,
+						mode: "global",
+					});
 				}
 			});
 
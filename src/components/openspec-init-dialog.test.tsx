@@ -49,7 +49,7 @@ describe("OpenSpecInitDialog", () => {
 			<OpenSpecInitDialog
 				open
 				project={baseProject}
-				cliStatus={{ available: false, version: null }}
+				cliStatus={{ available: false, version: null, mode: "global" }}
 				onClose={vi.fn()}
 				onProjectUpdated={vi.fn()}
 				onCliStatusChange={vi.fn()}
@@ -76,7 +76,7 @@ describe("OpenSpecInitDialog", () => {
 			<OpenSpecInitDialog
 				open
 				project={baseProject}
-				cliStatus={{ available: true, version: "1.2.3" }}
+				cliStatus={{ available: true, version: "1.2.3", mode: "global" }}
 				onClose={vi.fn()}
 				onProjectUpdated={vi.fn()}
 				onCliStatusChange={vi.fn()}
@@ -106,20 +106,20 @@ export function OpenSpecInitDialog({
 		let unlisten: (() => void) | null = null;
 		listen<CliOutputEvent>("openspec://cli-output", (event) => {
 			setLogs((previous) => {
-				const line = {
-					stream: event.payload.stream,
-					line: event.payload.line,
-				};
+				const lines = event.payload.lines.map((entry) => ({
+					stream: entry.stream,
+					line: entry.plain,
+				}));
 				if (event.payload.operation === INSTALL_OPERATION) {
 					return {
 						...previous,
-						install: [...previous.install, line],
+						install: [...previous.install, ...lines],
 					};
 				}
 				if (event.payload.operation === INIT_OPERATION) {
 					return {
 						...previous,
-						init: [...previous.init, line],
+						init: [...previous.init, ...lines],
 					};
 				}
 				return previous;
@@ -1,15 +1,76 @@
 import { invoke } from "@tauri-apps/api/core";
 
 import type {
+	AffectedCapabilities,
+	ArchiveResult,
+	ChangeDeltas,
+	CliUpdateCheck,
+	CliUpgrade,
+	CodeMap,
+	CodeTodoScan,
+	CliChangeList,
 	CommandRunOutput,
+	CreatedChange,
+	DetailedTools,
+	ExecutionBackend,
+	HistoryPrivacy,
+	InstructionUpdate,
+	LintDiagnostic,
+	NewChange,
 	OpenSpecCliStatus,
 	OpenSpecInitRequest,
+	OperationLogPage,
+	OperationPreview,
+	OperationPreviewRequest,
+	OperationSummary,
 	PackageManagerName,
 	PackageManagerStatus,
+	QueuedOperation,
+	RepairPlan,
+	RepairReport,
+	RollbackReport,
+	ShowDetails,
+	ShowKind,
+	SpecTreeEntry,
+	SubsystemStatus,
+	TimeRange,
+	ValidationReport,
 } from "@/types/openspec";
 
-export async function getOpenSpecCliStatus(): Promise<OpenSpecCliStatus> {
-	return invoke<OpenSpecCliStatus>("openspec_cli_status");
+export async function getOpenSpecCliStatus(
+	project?: string,
+): Promise<OpenSpecCliStatus> {
+	return invoke<OpenSpecCliStatus>("openspec_cli_status", { project });
+}
+
+export async function checkOpenSpecCliUpdate(
+	force?: boolean,
+): Promise<CliUpdateCheck> {
+	return invoke<CliUpdateCheck>("openspec_cli_update_check", { force });
+}
+
+export async function getOnDemandCli(): Promise<boolean> {
+	return invoke<boolean>("on_demand_cli");
+}
+
+export async function setOnDemandCli(enabled: boolean): Promise<boolean> {
+	return invoke<boolean>("set_on_demand_cli", { enabled });
+}
+
+export async function getExecutionBackend(
+	project: string,
+): Promise<ExecutionBackend> {
+	return invoke<ExecutionBackend>("execution_backend", { project });
+}
+
+export async function setExecutionBackend(
+	project: string,
+	backend: ExecutionBackend,
+): Promise<ExecutionBackend> {
+	return invoke<ExecutionBackend>("set_execution_backend", {
+		project,
+		backend,
+	});
 }
 
 export async function getPackageManagerStatuses(): Promise<
@@ -18,20 +79,255 @@ export async function getPackageManagerStatuses(): Promise<
 	return invoke<PackageManagerStatus[]>("package_manager_statuses");
 }
 
-export async function getOpenSpecTools(): Promise<string[]> {
-	return invoke<string[]>("openspec_tools");
+export async function getOpenSpecTools(project?: string): Promise<string[]> {
+	return invoke<string[]>("openspec_tools", { project });
+}
+
+export async function getOpenSpecToolsDetailed(
+	project?: string,
+): Promise<DetailedTools> {
+	return invoke<DetailedTools>("openspec_tools_detailed", { project });
 }
 
 export async function installOpenSpecCli(
 	packageManager: PackageManagerName,
+	env?: Record<string, string>,
+	project?: string,
 ): Promise<CommandRunOutput> {
 	return invoke<CommandRunOutput>("install_openspec_cli", {
 		packageManager,
+		env,
+		project,
+	});
+}
+
+export async function uninstallOpenSpecCli(
+	packageManager: PackageManagerName,
+	env?: Record<string, string>,
+): Promise<CommandRunOutput> {
+	return invoke<CommandRunOutput>("uninstall_openspec_cli", {
+		packageManager,
+		env,
+	});
+}
+
+export async function upgradeOpenSpecCli(
+	version?: string,
+	packageManager?: PackageManagerName,
+	env?: Record<string, string>,
+): Promise<CliUpgrade> {
+	return invoke<CliUpgrade>("upgrade_openspec_cli", {
+		version,
+		packageManager,
+		env,
 	});
 }
 
+export async function getCommandEnvironment(): Promise<
+	Record<string, string>
+> {
+	return invoke<Record<string, string>>("command_environment");
+}
+
+export async function setCommandEnvironment(
+	env: Record<string, string>,
+): Promise<Record<string, string>> {
+	return invoke<Record<string, string>>("set_command_environment", { env });
+}
+
+export async function previewOperation(
+	request: OperationPreviewRequest,
+): Promise<OperationPreview> {
+	return invoke<OperationPreview>("preview_operation", { request });
+}
+
 export async function runOpenSpecInit(
 	request: OpenSpecInitRequest,
 ): Promise<CommandRunOutput> {
 	return invoke<CommandRunOutput>("openspec_init", { request });
 }
+
+export async function archiveChange(
+	project: string,
+	changeId: string,
+	yes?: boolean,
+	env?: Record<string, string>,
+): Promise<ArchiveResult> {
+	return invoke<ArchiveResult>("openspec_archive", {
+		project,
+		changeId,
+		yes,
+		env,
+	});
+}
+
+export async function updateInstructions(
+	project: string,
+	env?: Record<string, string>,
+): Promise<InstructionUpdate> {
+	return invoke<InstructionUpdate>("openspec_update", { project, env });
+}
+
+export async function rollbackFailedInit(
+	operationId: string,
+): Promise<RollbackReport> {
+	return invoke<RollbackReport>("rollback_failed_init", { operationId });
+}
+
+export async function readOperationLogPage(
+	operationId: string,
+	offset: number,
+	limit?: number,
+): Promise<OperationLogPage> {
+	return invoke<OperationLogPage>("read_operation_log_page", {
+		operationId,
+		offset,
+		limit,
+	});
+}
+
+export async function getHistoryPrivacy(): Promise<HistoryPrivacy> {
+	return invoke<HistoryPrivacy>("history_privacy");
+}
+
+export async function setHistoryPrivacy(
+	privacy: HistoryPrivacy,
+): Promise<HistoryPrivacy> {
+	return invoke<HistoryPrivacy>("set_history_privacy", { privacy });
+}
+
+export async function purgeHistory(range?: TimeRange): Promise<number> {
+	return invoke<number>("purge_history", { range });
+}
+
+export async function cancelOperation(operationId: string): Promise<void> {
+	return invoke<void>("cancel_operation", { operationId });
+}
+
+export async function rebuildSearchIndex(path: string): Promise<void> {
+	return invoke<void>("rebuild_search_index", { path });
+}
+
+export async function specTree(project: string): Promise<SpecTreeEntry[]> {
+	return invoke<SpecTreeEntry[]>("spec_tree", { project });
+}
+
+export async function listOperations(): Promise<OperationSummary[]> {
+	return invoke<OperationSummary[]>("list_operations");
+}
+
+export async function getQueueStatus(
+	project: string,
+): Promise<QueuedOperation[]> {
+	return invoke<QueuedOperation[]>("queue_status", { project });
+}
+
+export async function moveQueuedOperation(
+	operationId: string,
+	position: number,
+): Promise<void> {
+	return invoke<void>("move_queued_operation", { operationId, position });
+}
+
+export async function cancelQueuedOperation(
+	operationId: string,
+): Promise<void> {
+	return invoke<void>("cancel_queued_operation", { operationId });
+}
+
+export async function sendOperationInput(
+	operationId: string,
+	input: string,
+): Promise<void> {
+	return invoke<void>("send_operation_input", { operationId, input });
+}
+
+export async function getSubsystemStatus(): Promise<SubsystemStatus[]> {
+	return invoke<SubsystemStatus[]>("subsystem_status");
+}
+
+export async function listChangesWithCli(
+	project: string,
+): Promise<CliChangeList> {
+	return invoke<CliChangeList>("openspec_list_changes", { project });
+}
+
+export async function showOpenSpecItem(
+	project: string,
+	itemId: string,
+	kind: ShowKind,
+): Promise<ShowDetails> {
+	return invoke<ShowDetails>("openspec_show", { project, itemId, kind });
+}
+
+export async function getChangeDeltas(
+	project: string,
+	changeId: string,
+): Promise<ChangeDeltas> {
+	return invoke<ChangeDeltas>("openspec_change_deltas", { project, changeId });
+}
+
+export async function planRepair(project: string): Promise<RepairPlan> {
+	return invoke<RepairPlan>("repair_plan", { project });
+}
+
+export async function applyRepair(
+	planId: string,
+	selections: string[],
+): Promise<RepairReport> {
+	return invoke<RepairReport>("apply_repair", { planId, selections });
+}
+
+export async function scanCodeTodos(
+	project: string,
+	changeId: string,
+): Promise<CodeTodoScan> {
+	return invoke<CodeTodoScan>("scan_code_todos", { project, changeId });
+}
+
+export async function getCapabilityCodeMap(project: string): Promise<CodeMap> {
+	return invoke<CodeMap>("capability_code_map", { project });
+}
+
+export async function setCapabilityCodeMap(
+	project: string,
+	map: CodeMap,
+): Promise<CodeMap> {
+	return invoke<CodeMap>("set_capability_code_map", { project, map });
+}
+
+export async function getAffectedCapabilities(
+	project: string,
+	diff?: string,
+): Promise<AffectedCapabilities> {
+	return invoke<AffectedCapabilities>("affected_capabilities", {
+		project,
+		diff,
+	});
+}
+
+export async function createChange(
+	project: string,
+	change: NewChange,
+): Promise<CreatedChange> {
+	return invoke<CreatedChange>("openspec_create_change", { project, change });
+}
+
+export async function validateWithCli(
+	project: string,
+	changeId?: string,
+	strict?: boolean,
+): Promise<ValidationReport> {
+	return invoke<ValidationReport>("openspec_validate", {
+		project,
+		changeId,
+		strict,
+	});
+}
+
+export async function validateFile(
+	path: string,
+	content?: string,
+): Promise<LintDiagnostic[]> {
+	return invoke<LintDiagnostic[]>("validate_file", { path, content });
+}
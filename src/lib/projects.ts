@@ -1,9 +1,115 @@
 import { invoke } from "@tauri-apps/api/core";
 
-import type { ProjectDiscoveryResult } from "@/types/projects";
+import type {
+	Deadline,
+	DeadlineReminders,
+	FilePreview,
+	LaunchIntent,
+	OrderingPolicy,
+	ProjectDiscoveryResult,
+	ProjectStatus,
+	RecentProject,
+	TimeEntry,
+	TimeSummary,
+} from "@/types/projects";
 
 export async function discoverProject(
 	path: string,
 ): Promise<ProjectDiscoveryResult> {
 	return invoke<ProjectDiscoveryResult>("discover_project", { path });
 }
+
+export async function invalidateDiscovery(path: string): Promise<void> {
+	return invoke<void>("invalidate_discovery", { path });
+}
+
+export async function listRecentProjects(): Promise<RecentProject[]> {
+	return invoke<RecentProject[]>("list_recent_projects");
+}
+
+export async function removeRecentProject(
+	path: string,
+): Promise<RecentProject[]> {
+	return invoke<RecentProject[]>("remove_recent_project", { path });
+}
+
+export async function syncRemoteProject(project: string): Promise<void> {
+	return invoke<void>("sync_remote_project", { project });
+}
+
+export async function takeLaunchIntent(): Promise<LaunchIntent | null> {
+	return invoke<LaunchIntent | null>("take_launch_intent");
+}
+
+export async function bulkProjectStatus(
+	paths: string[],
+): Promise<ProjectStatus[]> {
+	return invoke<ProjectStatus[]>("bulk_project_status", { paths });
+}
+
+export async function previewFile(
+	project: string,
+	path: string,
+): Promise<FilePreview> {
+	return invoke<FilePreview>("preview_file", { project, path });
+}
+
+export async function startTimer(
+	project: string,
+	changeId: string,
+): Promise<TimeEntry> {
+	return invoke<TimeEntry>("start_timer", { project, changeId });
+}
+
+export async function stopTimer(
+	project: string,
+	changeId: string,
+): Promise<TimeEntry> {
+	return invoke<TimeEntry>("stop_timer", { project, changeId });
+}
+
+export async function getTimeSummary(
+	project: string,
+	changeId?: string,
+): Promise<TimeSummary> {
+	return invoke<TimeSummary>("time_summary", { project, changeId });
+}
+
+export async function setTargetDate(
+	project: string,
+	changeId: string,
+	targetDate?: string,
+): Promise<string | null> {
+	return invoke<string | null>("set_target_date", {
+		project,
+		changeId,
+		targetDate,
+	});
+}
+
+export async function getUpcomingDeadlines(
+	project: string,
+	withinDays?: number,
+): Promise<Deadline[]> {
+	return invoke<Deadline[]>("upcoming_deadlines", { project, withinDays });
+}
+
+export async function getDeadlineReminders(): Promise<DeadlineReminders> {
+	return invoke<DeadlineReminders>("deadline_reminders");
+}
+
+export async function setDeadlineReminders(
+	reminders: DeadlineReminders,
+): Promise<DeadlineReminders> {
+	return invoke<DeadlineReminders>("set_deadline_reminders", { reminders });
+}
+
+export async function getOrderingPolicy(): Promise<OrderingPolicy> {
+	return invoke<OrderingPolicy>("ordering_policy");
+}
+
+export async function setOrderingPolicy(
+	policy: OrderingPolicy,
+): Promise<OrderingPolicy> {
+	return invoke<OrderingPolicy>("set_ordering_policy", { policy });
+}
@@ -17,6 +17,11 @@ describe("projects store", () => {
 			repoName: "specops",
 			repoPath: "/tmp/specops",
 			openspecPresent: true,
+			vcs: "git",
+			vcsReadOnly: false,
+			storage: "local",
+			pollingWatcher: false,
+			remote: null,
 		});
 
 		expect(project).toEqual({
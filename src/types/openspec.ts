@@ -1,8 +1,30 @@
+export type CliMode = "global" | "project" | "onDemand";
+
 export type OpenSpecCliStatus = {
 	available: boolean;
 	version?: string | null;
+	mode: CliMode;
+};
+
+export type ToolDetails = {
+	id: string;
+	name: string;
+	configFiles: string[];
+	docsUrl?: string | null;
+	known: boolean;
+	supported: boolean;
+};
+
+export type DetailedTools = {
+	tools: ToolDetails[];
+	cliReported: boolean;
 };
 
+export type ExecutionBackend =
+	| { kind: "host" }
+	| { kind: "devcontainer"; container?: string | null }
+	| { kind: "image"; image: string };
+
 export type PackageManagerName = "npm" | "bun" | "yarn" | "pnpm";
 
 export type PackageManagerStatus = {
@@ -17,10 +39,35 @@ export type OpenSpecInitRequest = {
 	path: string;
 	toolsMode: OpenSpecToolsMode;
 	tools: string[];
+	env?: Record<string, string>;
+};
+
+export type OperationPreviewRequest =
+	| {
+			operation: "install";
+			packageManager: PackageManagerName;
+			env?: Record<string, string>;
+			project?: string;
+	  }
+	| ({ operation: "init" } & OpenSpecInitRequest);
+
+export type OperationPreview = {
+	operation: "install" | "init";
+	command: string;
+	args: string[];
+	commandLine: string;
+	cwd: string | null;
+	env: Record<string, string>;
+	timeoutSecs: number | null;
+	pty: boolean;
 };
 
 export type CommandRunOutput = {
+	operationId: string;
 	status: number;
+	signal: number | null;
+	termination: string;
+	logLines: number;
 	stdout: string;
 	stderr: string;
 };
@@ -30,8 +77,327 @@ export type CliOutputLine = {
 	line: string;
 };
 
-export type CliOutputEvent = {
-	operation: "install" | "init";
+export type StyledSegment = {
+	text: string;
+	fg?: string;
+	bg?: string;
+	bold?: boolean;
+	dim?: boolean;
+	italic?: boolean;
+	underline?: boolean;
+};
+
+export type CliOutputLine = {
 	stream: "stdout" | "stderr";
 	line: string;
+	plain: string;
+	segments: StyledSegment[];
+};
+
+export type CliOutputEvent = {
+	operation: "install" | "init";
+	operationId: string;
+	sequence: number;
+	lines: CliOutputLine[];
+};
+
+export type CliRawOutputEvent = {
+	operation: "install" | "init";
+	operationId: string;
+	data: string;
+};
+
+export type OperationStartedEvent = {
+	operation: "install" | "init";
+	operationId: string;
+};
+
+export type OperationQueuedEvent = {
+	operation: string;
+	operationId: string;
+	position: number;
+};
+
+export type OperationPhase =
+	| "resolving"
+	| "downloading"
+	| "linking"
+	| "writingToolConfigs";
+
+export type OperationProgressEvent = {
+	operation: string;
+	operationId: string;
+	phase: OperationPhase;
+	percent: number | null;
+};
+
+export type OperationRetryEvent = {
+	operation: string;
+	operationId: string | null;
+	attempt: number;
+	maxAttempts: number;
+	delaySecs: number;
+	reason: string;
+};
+
+export type QueuedOperation = {
+	operationId: string;
+	operation: string;
+	position: number;
+	running: boolean;
+	queuedAt: number;
+};
+
+export type SubsystemStatus = {
+	name: string;
+	healthy: boolean;
+	restarts: number;
+	lastError: string | null;
+	lastFailureAt: number | null;
+};
+
+export type SubsystemErrorEvent = {
+	subsystem: string;
+	message: string;
+	restarts: number;
+	restarting: boolean;
+};
+
+export type OperationStatus =
+	| "running"
+	| "succeeded"
+	| "failed"
+	| "cancelled"
+	| "rolledBack";
+
+export type OperationSummary = {
+	operationId: string;
+	kind: string;
+	commandLine: string;
+	startedAt: number;
+	finishedAt: number | null;
+	status: OperationStatus;
+};
+
+export type OperationFinishedEvent = {
+	operation: "install" | "init";
+	operationId: string;
+	status: OperationStatus;
+};
+
+export type OperationLogPage = {
+	operationId: string;
+	offset: number;
+	totalLines: number;
+	complete: boolean;
+	lines: CliOutputLine[];
+};
+
+export type RollbackReport = {
+	operationId: string;
+	removed: string[];
+	restored: string[];
+};
+
+export type CliChange = {
+	id: string;
+	title: string | null;
+	tasksDone: number;
+	tasksTotal: number;
+	completion: number;
+	complete: boolean;
+};
+
+export type CliChangeList = {
+	format: "json" | "text";
+	changes: CliChange[];
+};
+
+export type ShowKind = "change" | "spec";
+
+export type ShowRequirement = {
+	name: string | null;
+	text: string;
+	scenarios: string[];
+};
+
+export type ShowDelta = {
+	spec: string | null;
+	operation: string;
+	requirement: ShowRequirement;
+};
+
+export type ShowDetails = {
+	id: string;
+	kind: ShowKind;
+	format: "json" | "text";
+	title: string | null;
+	overview: string | null;
+	requirements: ShowRequirement[];
+	deltas: ShowDelta[];
+};
+
+export type DeltaSection = {
+	operation: string;
+	requirements: ShowRequirement[];
+};
+
+export type CapabilityDelta = {
+	capability: string;
+	sections: DeltaSection[];
+};
+
+export type ChangeDeltas = {
+	changeId: string;
+	source: "cli" | "files";
+	capabilities: CapabilityDelta[];
+};
+
+export type RepairKind = "missingSpec" | "orphanedDelta" | "malformedArchive";
+
+export type RepairFix = {
+	id: string;
+	kind: RepairKind;
+	path: string;
+	description: string;
+};
+
+export type RepairPlan = {
+	id: string;
+	project: string;
+	createdAt: number;
+	fixes: RepairFix[];
+};
+
+export type RepairReport = {
+	operationId: string;
+	applied: string[];
+	skipped: string[];
+};
+
+export type CodeTodo = {
+	path: string;
+	line: number;
+	marker: "TODO" | "FIXME";
+	text: string;
+	taskId: string | null;
+};
+
+export type CodeTodoScan = {
+	changeId: string;
+	filesScanned: number;
+	todos: CodeTodo[];
+};
+
+export type CodeMap = {
+	capabilities: Record<string, string[]>;
+};
+
+export type AffectedCapability = {
+	capability: string;
+	files: string[];
+	specTouched: boolean;
+	coveredBy: string[];
+	needsDelta: boolean;
+};
+
+export type AffectedCapabilities = {
+	capabilities: AffectedCapability[];
+	unmapped: string[];
+};
+
+export type NewChange = {
+	changeId: string;
+	title?: string;
+	why?: string;
+	design?: boolean;
+};
+
+export type CreatedChange = {
+	changeId: string;
+	path: string;
+	files: string[];
+};
+
+export type ValidationDiagnostic = {
+	item: string | null;
+	file: string | null;
+	rule: string | null;
+	message: string;
+	severity: "info" | "warning" | "error";
+};
+
+export type LintDiagnostic = {
+	id: string;
+	path: string;
+	line: number;
+	rule: string;
+	severity: "info" | "warning" | "error";
+	message: string;
+	fixable: boolean;
+};
+
+export type LintOutcome = {
+	gate: LintDiagnostic["severity"];
+	passed: boolean;
+	blocking: number;
+	diagnostics: LintDiagnostic[];
+};
+
+export type ValidationReport = {
+	format: "json" | "text";
+	valid: boolean;
+	errors: number;
+	warnings: number;
+	diagnostics: ValidationDiagnostic[];
+};
+
+export type ArchiveResult = CommandRunOutput & {
+	changeId: string;
+	archivedTo: string | null;
+	updatedSpecs: string[];
+	createdSpecs: string[];
+};
+
+export type FileChangeKind = "added" | "modified" | "removed";
+
+export type ToolFileChange = {
+	path: string;
+	change: FileChangeKind;
+	diff: string;
+};
+
+export type InstructionUpdate = CommandRunOutput & {
+	files: ToolFileChange[];
+};
+
+export type CliUpdateCheck = {
+	installedVersion: string | null;
+	latestVersion: string | null;
+	updateAvailable: boolean;
+	checkedAt: number | null;
+	cached: boolean;
+};
+
+export type CliUpgrade = CommandRunOutput & {
+	packageManager: PackageManagerName;
+	previousVersion: string;
+	version: string | null;
+};
+
+export type HistoryPrivacy = {
+	scrubEnvironment: boolean;
+	scrubHomePaths: boolean;
+};
+
+export type TimeRange = {
+	from?: number;
+	to?: number;
+};
+
+export type SpecTreeEntry = {
+	path: string;
+	relativePath: string;
+	title: string | null;
+	requirements: number;
 };
@@ -1,7 +1,26 @@
+export type VcsKind = "git" | "jujutsu" | "sapling";
+
+export type StorageKind = "local" | "network" | "cloudSynced";
+
 export type ProjectDiscoveryResult = {
 	repoPath: string;
 	repoName: string;
 	openspecPresent: boolean;
+	vcs: VcsKind;
+	vcsReadOnly: boolean;
+	storage: StorageKind;
+	pollingWatcher: boolean;
+	remote?: string | null;
+};
+
+export type RecentProject = {
+	path: string;
+	name: string;
+	openspecPresent: boolean;
+	lastOpened: number;
+	tags: string[];
+	group?: string | null;
+	missingSince?: number | null;
 };
 
 export type ProjectDiscoveryError = {
@@ -9,6 +28,14 @@ export type ProjectDiscoveryError = {
 	message: string;
 };
 
+export type LaunchIntent = {
+	path: string;
+	project?: ProjectDiscoveryResult;
+	changeId?: string;
+	file?: string;
+	error?: ProjectDiscoveryError;
+};
+
 export type OpenSpecStatus = "present" | "missing";
 
 export type Project = {
@@ -17,3 +44,72 @@ export type Project = {
 	path: string;
 	openspecStatus: OpenSpecStatus;
 };
+
+export type ProjectStatus = {
+	path: string;
+	openspecPresent: boolean;
+	activeChanges: number;
+	archivedChanges: number;
+	errors: number;
+	warnings: number;
+	valid: boolean;
+	failure?: string | null;
+};
+
+export type MarkdownBlock =
+	| { type: "heading"; level: number; text: string }
+	| { type: "paragraph"; text: string }
+	| {
+			type: "listItem";
+			text: string;
+			ordered: boolean;
+			checked: boolean | null;
+	  }
+	| { type: "code"; language: string | null; text: string }
+	| { type: "quote"; text: string }
+	| { type: "rule" };
+
+export type FilePreview =
+	| { kind: "markdown"; blocks: MarkdownBlock[]; truncated: boolean }
+	| { kind: "code"; language: string; html: string }
+	| { kind: "image"; mime: string; dataUrl: string }
+	| { kind: "binary"; size: number; truncated: boolean };
+
+export type TimeEntry = {
+	project: string;
+	changeId: string;
+	startedAt: number;
+	stoppedAt: number | null;
+};
+
+export type ChangeTime = {
+	changeId: string;
+	totalSecs: number;
+	sessions: number;
+	runningSince: number | null;
+};
+
+export type TimeSummary = {
+	project: string;
+	totalSecs: number;
+	changes: ChangeTime[];
+};
+
+export type Deadline = {
+	changeId: string;
+	state: "draft" | "inProgress" | "complete" | "archived";
+	targetDate: string;
+	daysRemaining: number;
+};
+
+export type DeadlineReminders = {
+	enabled: boolean;
+	leadDays: number;
+};
+
+export type SortMode = "natural" | "lexical";
+
+export type OrderingPolicy = {
+	mode: SortMode;
+	caseSensitive: boolean;
+};